@@ -0,0 +1,419 @@
+//! Derive macros for the `AsyncEncode` and `AsyncDecode` traits of [`tokio-byteorder`].
+//!
+//! This crate is not meant to be used directly; enable the `derive` feature of
+//! `tokio-byteorder` instead, which re-exports these macros next to the traits they implement.
+//! The attributes the macros understand are documented on `tokio_byteorder::AsyncEncode`.
+//!
+//! [`tokio-byteorder`]: https://docs.rs/tokio-byteorder/
+
+#![warn(rust_2018_idioms)]
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, GenericArgument,
+    Ident, LitInt, LitStr, PathArguments, Result, Type,
+};
+
+/// Derives `tokio_byteorder::AsyncDecode` for a struct.
+#[proc_macro_derive(AsyncDecode, attributes(byteorder))]
+pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `tokio_byteorder::AsyncEncode` for a struct.
+#[proc_macro_derive(AsyncEncode, attributes(byteorder))]
+pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encode(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The byte order of a field, as named in an `endian = "..."` attribute.
+#[derive(Clone, Copy)]
+enum Endian {
+    Big,
+    Little,
+    Native,
+}
+
+impl Endian {
+    fn tokens(self) -> TokenStream {
+        match self {
+            Endian::Big => quote!(::tokio_byteorder::BigEndian),
+            Endian::Little => quote!(::tokio_byteorder::LittleEndian),
+            Endian::Native => quote!(::tokio_byteorder::NativeEndian),
+        }
+    }
+}
+
+/// Everything that can be set with `#[byteorder(...)]`.
+#[derive(Default)]
+struct Attrs {
+    endian: Option<Endian>,
+    width: Option<u8>,
+    pad: Option<usize>,
+    len: Option<Ident>,
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> Result<Attrs> {
+    let mut out = Attrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("byteorder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("endian") {
+                let s: LitStr = meta.value()?.parse()?;
+                out.endian = Some(match &*s.value() {
+                    "big" | "network" => Endian::Big,
+                    "little" => Endian::Little,
+                    "native" => Endian::Native,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            s,
+                            "expected \"big\", \"little\", or \"native\"",
+                        ))
+                    }
+                });
+            } else if meta.path.is_ident("width") {
+                let n: LitInt = meta.value()?.parse()?;
+                match n.base10_parse()? {
+                    w @ (24 | 48) => out.width = Some(w),
+                    _ => return Err(Error::new_spanned(n, "expected 24 or 48")),
+                }
+            } else if meta.path.is_ident("pad") {
+                let n: LitInt = meta.value()?.parse()?;
+                out.pad = Some(n.base10_parse()?);
+            } else if meta.path.is_ident("len") {
+                let s: LitStr = meta.value()?.parse()?;
+                match &*s.value() {
+                    "u8" | "u16" | "u32" | "u64" => {
+                        out.len = Some(Ident::new(&s.value(), s.span()))
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(
+                            s,
+                            "expected \"u8\", \"u16\", \"u32\", or \"u64\"",
+                        ))
+                    }
+                }
+            } else {
+                return Err(meta.error("unknown byteorder attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(out)
+}
+
+/// How a single field (or collection element) is encoded.
+enum Kind {
+    /// One of the primitive numbers, named by the suffix of its `read_`/`write_` methods.
+    Primitive(Ident),
+    /// A `[T; N]` of primitive numbers.
+    Array(Ident, TokenStream),
+    /// A `Vec<T>` with a length prefix of the given type.
+    Vec(Ident, Box<Kind>),
+    /// Any other type, which must implement the traits itself.
+    Nested(Type),
+}
+
+fn primitive(ty: &Type) -> Option<Ident> {
+    let path = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+    let ident = path.get_ident()?;
+    match &*ident.to_string() {
+        "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" | "u128" | "i128" | "f32"
+        | "f64" => Some(ident.clone()),
+        _ => None,
+    }
+}
+
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Vec" {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn classify(ty: &Type, attrs: &Attrs) -> Result<Kind> {
+    if let Some(prim) = primitive(ty) {
+        return match (attrs.width, &*prim.to_string()) {
+            (None, _) => Ok(Kind::Primitive(prim)),
+            (Some(24), "u32") | (Some(24), "i32") | (Some(48), "u64") | (Some(48), "i64") => {
+                let name = prim.to_string();
+                Ok(Kind::Primitive(format_ident!(
+                    "{}{}",
+                    &name[..1],
+                    attrs.width.unwrap()
+                )))
+            }
+            (Some(w), _) => Err(Error::new_spanned(
+                ty,
+                format!("a width of {} is not supported for this type", w),
+            )),
+        };
+    }
+    if attrs.width.is_some() {
+        return Err(Error::new_spanned(
+            ty,
+            "`width` is only supported on primitive integer fields",
+        ));
+    }
+    if let Type::Array(arr) = ty {
+        let elem = primitive(&arr.elem).ok_or_else(|| {
+            Error::new_spanned(&arr.elem, "only arrays of primitive numbers are supported")
+        })?;
+        let len = &arr.len;
+        return Ok(Kind::Array(elem, quote!(#len)));
+    }
+    if let Some(elem) = vec_element(ty) {
+        let prefix = attrs.len.clone().ok_or_else(|| {
+            Error::new_spanned(
+                ty,
+                "`Vec` fields need a `#[byteorder(len = \"...\")]` prefix",
+            )
+        })?;
+        let elem = classify(elem, &Attrs::default())?;
+        if let Kind::Vec(..) = elem {
+            return Err(Error::new_spanned(ty, "nested `Vec`s are not supported"));
+        }
+        return Ok(Kind::Vec(prefix, Box::new(elem)));
+    }
+    if attrs.len.is_some() {
+        return Err(Error::new_spanned(
+            ty,
+            "`len` is only supported on `Vec` fields",
+        ));
+    }
+    Ok(Kind::Nested(ty.clone()))
+}
+
+fn read_primitive(prim: &Ident, endian: Endian) -> TokenStream {
+    let method = format_ident!("read_{}", prim);
+    if prim == "u8" || prim == "i8" {
+        quote!(::tokio_byteorder::AsyncReadBytesExt::#method(src).await?)
+    } else {
+        let e = endian.tokens();
+        quote!(::tokio_byteorder::AsyncReadBytesExt::#method::<#e>(src).await?)
+    }
+}
+
+fn write_primitive(prim: &Ident, endian: Endian, value: TokenStream) -> TokenStream {
+    let method = format_ident!("write_{}", prim);
+    if prim == "u8" || prim == "i8" {
+        quote!(::tokio_byteorder::AsyncWriteBytesExt::#method(dst, #value).await?;)
+    } else {
+        let e = endian.tokens();
+        quote!(::tokio_byteorder::AsyncWriteBytesExt::#method::<#e>(dst, #value).await?;)
+    }
+}
+
+fn read_kind(kind: &Kind, endian: Endian) -> TokenStream {
+    match kind {
+        Kind::Primitive(p) => read_primitive(p, endian),
+        Kind::Array(p, len) => {
+            let read = read_primitive(p, endian);
+            quote!({
+                let mut arr = [0 as #p; #len];
+                for x in arr.iter_mut() {
+                    *x = #read;
+                }
+                arr
+            })
+        }
+        Kind::Vec(prefix, elem) => {
+            let read_len = read_primitive(prefix, endian);
+            let read = read_kind(elem, endian);
+            quote!({
+                let n = ::tokio_byteorder::__private::from_len(#read_len)?;
+                let mut v = ::tokio_byteorder::__private::Vec::with_capacity(::core::cmp::min(n, 4096));
+                for _ in 0..n {
+                    v.push(#read);
+                }
+                v
+            })
+        }
+        Kind::Nested(ty) => {
+            quote!(<#ty as ::tokio_byteorder::AsyncDecode>::read_from(src).await?)
+        }
+    }
+}
+
+fn write_kind(kind: &Kind, endian: Endian, place: TokenStream) -> TokenStream {
+    match kind {
+        Kind::Primitive(p) => write_primitive(p, endian, quote!(#place)),
+        Kind::Array(p, _) => {
+            let write = write_primitive(p, endian, quote!(*x));
+            quote!(for x in (#place).iter() { #write })
+        }
+        Kind::Vec(prefix, elem) => {
+            let write_len = write_primitive(
+                prefix,
+                endian,
+                quote!(::tokio_byteorder::__private::len::<#prefix>((#place).len())?),
+            );
+            let write = write_kind(elem, endian, quote!(*x));
+            quote!({
+                #write_len
+                for x in (#place).iter() { #write }
+            })
+        }
+        Kind::Nested(ty) => {
+            quote!(<#ty as ::tokio_byteorder::AsyncEncode>::write_to(&#place, dst).await?;)
+        }
+    }
+}
+
+/// A field of the struct, along with how to encode it.
+struct Field {
+    member: syn::Member,
+    binding: Ident,
+    kind: Kind,
+    endian: Endian,
+    pad: Option<usize>,
+}
+
+fn fields(input: &DeriveInput) -> Result<(Vec<Field>, bool)> {
+    let data = match &input.data {
+        Data::Struct(s) => s,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "only structs can be derived for now",
+            ))
+        }
+    };
+    let container = parse_attrs(&input.attrs)?;
+    if container.width.is_some() || container.pad.is_some() || container.len.is_some() {
+        return Err(Error::new(
+            Span::call_site(),
+            "only `endian` is supported on the struct itself",
+        ));
+    }
+    let default = container.endian.unwrap_or(Endian::Big);
+    let named = matches!(data.fields, Fields::Named(_));
+    let mut out = Vec::new();
+    for (i, f) in data.fields.iter().enumerate() {
+        let attrs = parse_attrs(&f.attrs)?;
+        let member = match &f.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+        out.push(Field {
+            member,
+            binding: format_ident!("__field{}", i),
+            kind: classify(&f.ty, &attrs)?,
+            endian: attrs.endian.unwrap_or(default),
+            pad: attrs.pad,
+        });
+    }
+    Ok((out, named))
+}
+
+fn add_bounds(input: &mut DeriveInput, bound: TokenStream) {
+    let params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for p in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#p: #bound + ::core::marker::Send + ::core::marker::Sync));
+    }
+}
+
+fn expand_decode(mut input: DeriveInput) -> Result<TokenStream> {
+    let (fields, named) = fields(&input)?;
+    add_bounds(&mut input, quote!(::tokio_byteorder::AsyncDecode));
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let reads = fields.iter().map(|f| {
+        let binding = &f.binding;
+        let read = read_kind(&f.kind, f.endian);
+        let pad = f
+            .pad
+            .map(|n| quote!(::tokio_byteorder::__private::skip(src, #n).await?;));
+        quote! {
+            #pad
+            let #binding = #read;
+        }
+    });
+    let members = fields.iter().map(|f| &f.member);
+    let bindings = fields.iter().map(|f| &f.binding);
+    let construct = if named || fields.is_empty() {
+        quote!(#name { #(#members: #bindings),* })
+    } else {
+        quote!(#name ( #(#bindings),* ))
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::tokio_byteorder::AsyncDecode for #name #ty_generics #where_clause {
+            fn read_from<R>(
+                src: &mut R,
+            ) -> impl ::core::future::Future<Output = ::std::io::Result<Self>> + ::core::marker::Send
+            where
+                R: ::tokio_byteorder::__private::AsyncRead + ::core::marker::Unpin + ::core::marker::Send + ?::core::marker::Sized,
+            {
+                async move {
+                    #(#reads)*
+                    ::tokio_byteorder::__private::Ok(#construct)
+                }
+            }
+        }
+    })
+}
+
+fn expand_encode(mut input: DeriveInput) -> Result<TokenStream> {
+    let (fields, _) = fields(&input)?;
+    add_bounds(&mut input, quote!(::tokio_byteorder::AsyncEncode));
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let writes = fields.iter().map(|f| {
+        let member = &f.member;
+        let write = write_kind(&f.kind, f.endian, quote!(self.#member));
+        let pad = f
+            .pad
+            .map(|n| quote!(::tokio_byteorder::__private::pad(dst, #n).await?;));
+        quote! {
+            #pad
+            #write
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::tokio_byteorder::AsyncEncode for #name #ty_generics #where_clause {
+            fn write_to<W>(
+                &self,
+                dst: &mut W,
+            ) -> impl ::core::future::Future<Output = ::std::io::Result<()>> + ::core::marker::Send
+            where
+                W: ::tokio_byteorder::__private::AsyncWrite + ::core::marker::Unpin + ::core::marker::Send + ?::core::marker::Sized,
+            {
+                async move {
+                    #(#writes)*
+                    ::tokio_byteorder::__private::Ok(())
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,374 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+/// How deeply tables and arrays may be nested in a field table that is read.
+const MAX_DEPTH: usize = 32;
+
+/// The fields of an AMQP 0-9-1 field table, in the order they are on the wire.
+///
+/// Read with [`read_amqp_field_table`], and written with [`write_amqp_field_table`].
+///
+/// [`read_amqp_field_table`]: trait.AsyncReadBytesExt.html#method.read_amqp_field_table
+/// [`write_amqp_field_table`]: trait.AsyncWriteBytesExt.html#method.write_amqp_field_table
+pub type AmqpTable = Vec<(String, AmqpValue)>;
+
+/// A typed value in an AMQP 0-9-1 field table or field array.
+///
+/// The type tags are those that RabbitMQ and most other brokers and clients use, which differ
+/// from the 0-9-1 specification for some of the integer types. Each variant notes its tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmqpValue {
+    /// A boolean, `t`.
+    Bool(bool),
+    /// A signed 8-bit integer, `b`.
+    I8(i8),
+    /// An unsigned 8-bit integer, `B`.
+    U8(u8),
+    /// A signed 16-bit integer, `s`.
+    I16(i16),
+    /// An unsigned 16-bit integer, `u`.
+    U16(u16),
+    /// A signed 32-bit integer, `I`.
+    I32(i32),
+    /// An unsigned 32-bit integer, `i`.
+    U32(u32),
+    /// A signed 64-bit integer, `l`.
+    I64(i64),
+    /// A single-precision float, `f`.
+    F32(f32),
+    /// A double-precision float, `d`.
+    F64(f64),
+    /// A decimal, `D`, whose value is `value / 10^scale`.
+    Decimal {
+        /// The number of decimal digits after the point.
+        scale: u8,
+        /// The value without its decimal point.
+        value: u32,
+    },
+    /// A long string, `S`, whose bytes are usually, but not always, UTF-8.
+    LongString(Vec<u8>),
+    /// A field array, `A`.
+    Array(Vec<AmqpValue>),
+    /// A timestamp, `T`, in seconds since the Unix epoch.
+    Timestamp(u64),
+    /// A nested field table, `F`.
+    Table(AmqpTable),
+    /// No value, `V`.
+    Void,
+    /// A byte array, `x`.
+    Bytes(Vec<u8>),
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Takes `n` bytes off the front of `input`.
+fn take<'a>(input: &mut &'a [u8], n: usize) -> io::Result<&'a [u8]> {
+    if input.len() < n {
+        return Err(invalid("amqp field table is cut short"));
+    }
+    let (head, rest) = input.split_at(n);
+    *input = rest;
+    Ok(head)
+}
+
+fn take_long_bytes<'a>(input: &mut &'a [u8]) -> io::Result<&'a [u8]> {
+    let len = BigEndian::read_u32(take(input, 4)?);
+    take(input, len as usize)
+}
+
+fn decode_table(mut input: &[u8], depth: usize) -> io::Result<AmqpTable> {
+    let mut table = Vec::new();
+    while !input.is_empty() {
+        let len = take(&mut input, 1)?[0];
+        let name = take(&mut input, usize::from(len))?;
+        let name = String::from_utf8(name.to_vec())
+            .map_err(|_| invalid("amqp field name is not valid utf-8"))?;
+        let value = decode_value(&mut input, depth)?;
+        table.push((name, value));
+    }
+    Ok(table)
+}
+
+fn decode_value(input: &mut &[u8], depth: usize) -> io::Result<AmqpValue> {
+    let tag = take(input, 1)?[0];
+    Ok(match tag {
+        b't' => AmqpValue::Bool(take(input, 1)?[0] != 0),
+        b'b' => AmqpValue::I8(take(input, 1)?[0] as i8),
+        b'B' => AmqpValue::U8(take(input, 1)?[0]),
+        b's' => AmqpValue::I16(BigEndian::read_i16(take(input, 2)?)),
+        b'u' => AmqpValue::U16(BigEndian::read_u16(take(input, 2)?)),
+        b'I' => AmqpValue::I32(BigEndian::read_i32(take(input, 4)?)),
+        b'i' => AmqpValue::U32(BigEndian::read_u32(take(input, 4)?)),
+        b'l' => AmqpValue::I64(BigEndian::read_i64(take(input, 8)?)),
+        b'f' => AmqpValue::F32(BigEndian::read_f32(take(input, 4)?)),
+        b'd' => AmqpValue::F64(BigEndian::read_f64(take(input, 8)?)),
+        b'D' => {
+            let buf = take(input, 5)?;
+            AmqpValue::Decimal {
+                scale: buf[0],
+                value: BigEndian::read_u32(&buf[1..]),
+            }
+        }
+        b'S' => AmqpValue::LongString(take_long_bytes(input)?.to_vec()),
+        b'A' | b'F' => {
+            if depth == MAX_DEPTH {
+                return Err(invalid("amqp field table is nested too deeply"));
+            }
+            let mut body = take_long_bytes(input)?;
+            if tag == b'F' {
+                AmqpValue::Table(decode_table(body, depth + 1)?)
+            } else {
+                let mut values = Vec::new();
+                while !body.is_empty() {
+                    values.push(decode_value(&mut body, depth + 1)?);
+                }
+                AmqpValue::Array(values)
+            }
+        }
+        b'T' => AmqpValue::Timestamp(BigEndian::read_u64(take(input, 8)?)),
+        b'V' => AmqpValue::Void,
+        b'x' => AmqpValue::Bytes(take_long_bytes(input)?.to_vec()),
+        _ => return Err(invalid("unknown amqp field value type")),
+    })
+}
+
+/// Appends `f`'s output to `out`, prefixed with its length as a big-endian `u32`.
+fn encode_long(
+    out: &mut Vec<u8>,
+    f: impl FnOnce(&mut Vec<u8>) -> Result<(), &'static str>,
+) -> Result<(), &'static str> {
+    let start = out.len();
+    out.extend_from_slice(&[0; 4]);
+    f(out)?;
+    let len = u32::try_from(out.len() - start - 4).map_err(|_| "amqp value is too long")?;
+    BigEndian::write_u32(&mut out[start..], len);
+    Ok(())
+}
+
+fn encode_table(out: &mut Vec<u8>, table: &[(String, AmqpValue)]) -> Result<(), &'static str> {
+    encode_long(out, |out| {
+        for (name, value) in table {
+            let len = u8::try_from(name.len()).map_err(|_| "amqp field name is too long")?;
+            out.push(len);
+            out.extend_from_slice(name.as_bytes());
+            encode_value(out, value)?;
+        }
+        Ok(())
+    })
+}
+
+fn encode_value(out: &mut Vec<u8>, value: &AmqpValue) -> Result<(), &'static str> {
+    match value {
+        AmqpValue::Bool(b) => out.extend_from_slice(&[b't', u8::from(*b)]),
+        AmqpValue::I8(n) => out.extend_from_slice(&[b'b', *n as u8]),
+        AmqpValue::U8(n) => out.extend_from_slice(&[b'B', *n]),
+        AmqpValue::I16(n) => {
+            out.push(b's');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::U16(n) => {
+            out.push(b'u');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::I32(n) => {
+            out.push(b'I');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::U32(n) => {
+            out.push(b'i');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::I64(n) => {
+            out.push(b'l');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::F32(n) => {
+            out.push(b'f');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::F64(n) => {
+            out.push(b'd');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::Decimal { scale, value } => {
+            out.extend_from_slice(&[b'D', *scale]);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+        AmqpValue::LongString(bytes) => {
+            out.push(b'S');
+            encode_long(out, |out| {
+                out.extend_from_slice(bytes);
+                Ok(())
+            })?;
+        }
+        AmqpValue::Array(values) => {
+            out.push(b'A');
+            encode_long(out, |out| {
+                values.iter().try_for_each(|v| encode_value(out, v))
+            })?;
+        }
+        AmqpValue::Timestamp(n) => {
+            out.push(b'T');
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        AmqpValue::Table(table) => {
+            out.push(b'F');
+            encode_table(out, table)?;
+        }
+        AmqpValue::Void => out.push(b'V'),
+        AmqpValue::Bytes(bytes) => {
+            out.push(b'x');
+            encode_long(out, |out| {
+                out.extend_from_slice(bytes);
+                Ok(())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Future returned by
+/// [`read_amqp_short_string`](../trait.AsyncReadBytesExt.html#method.read_amqp_short_string).
+#[derive(Debug)]
+pub struct ReadAmqpShortString<R> {
+    src: R,
+    // the length, once it is in
+    len: Option<u8>,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadAmqpShortString<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadAmqpShortString {
+            src,
+            len: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadAmqpShortString<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<String>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let mut b = [0; 1];
+                ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+                *this.len.insert(b[0])
+            }
+        };
+        ready!(poll_read_to_len(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            usize::from(len)
+        ))?;
+        Poll::Ready(
+            String::from_utf8(core::mem::take(&mut this.buf))
+                .map_err(|_| invalid("amqp short string is not valid utf-8")),
+        )
+    }
+}
+
+/// Future returned by
+/// [`read_amqp_field_table`](../trait.AsyncReadBytesExt.html#method.read_amqp_field_table).
+#[derive(Debug)]
+pub struct ReadAmqpFieldTable<R> {
+    src: R,
+    max_len: usize,
+    prefix: [u8; 4],
+    filled: usize,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadAmqpFieldTable<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadAmqpFieldTable {
+            src,
+            max_len,
+            prefix: [0; 4],
+            filled: 0,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadAmqpFieldTable<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<AmqpTable>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < this.prefix.len() {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.prefix,
+                &mut this.filled
+            ))?;
+        }
+        let len = BigEndian::read_u32(&this.prefix);
+        let len = match usize::try_from(len) {
+            Ok(len) if len <= this.max_len => len,
+            _ => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "amqp field table is longer than the maximum of {} bytes",
+                        this.max_len
+                    ),
+                )));
+            }
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        let buf = core::mem::take(&mut this.buf);
+        Poll::Ready(decode_table(&buf, 0))
+    }
+}
+
+/// Future returned by
+/// [`write_amqp_field_table`](../trait.AsyncWriteBytesExt.html#method.write_amqp_field_table).
+#[derive(Debug)]
+pub struct WriteAmqpFieldTable<W> {
+    dst: W,
+    // the encoded table, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteAmqpFieldTable<W> {
+    pub(crate) fn new(dst: W, table: &[(String, AmqpValue)]) -> Self {
+        let mut buf = Vec::new();
+        let buf = encode_table(&mut buf, table).map(|()| buf);
+        WriteAmqpFieldTable {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteAmqpFieldTable<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
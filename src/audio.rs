@@ -0,0 +1,218 @@
+use crate::codec::__private::skip;
+use crate::*;
+use tokio::io::AsyncReadExt;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Skips chunks of an IFF-style container until one with the given id, and returns its size.
+///
+/// Chunk sizes are read in the byte order `T`, and chunks are padded to an even length.
+async fn find_chunk<R, T>(src: &mut R, id: &[u8; 4]) -> io::Result<u32>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+    T: ByteOrder,
+{
+    loop {
+        let mut chunk = [0; 4];
+        src.read_exact(&mut chunk).await?;
+        let size = AsyncReadBytesExt::read_u32::<T>(src).await?;
+        if &chunk == id {
+            return Ok(size);
+        }
+        skip(src, size as usize + (size & 1) as usize).await?;
+    }
+}
+
+/// Reads the header of an IFF-style container, and checks its magic and form type.
+async fn read_container<R>(src: &mut R, magic: &[u8; 4], forms: &[&[u8; 4]]) -> io::Result<()>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+{
+    let mut header = [0; 12];
+    src.read_exact(&mut header).await?;
+    if &header[..4] != magic || !forms.iter().any(|form| header[8..] == form[..]) {
+        return Err(invalid("unexpected container magic or form type"));
+    }
+    Ok(())
+}
+
+byte_layout! {
+    /// The fields of the `fmt ` chunk of a RIFF WAVE file.
+    ///
+    /// The [`AsyncDecode`] and [`AsyncEncode`] implementations cover the 16 bytes that every
+    /// `fmt ` chunk starts with, and not the chunk header or any extension that follows. Use
+    /// [`read_from_wav`](#method.read_from_wav) to find and read the chunk in a whole file.
+    ///
+    /// [`AsyncDecode`]: trait.AsyncDecode.html
+    /// [`AsyncEncode`]: trait.AsyncEncode.html
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct WavFormat {
+        /// The sample encoding, like 1 for integer PCM, 3 for IEEE754 floats, or `0xfffe`
+        /// when the real encoding is given in the chunk's extension.
+        pub format_tag: u16 as u16<LittleEndian>,
+        /// The number of channels.
+        pub channels: u16 as u16<LittleEndian>,
+        /// The number of frames per second.
+        pub sample_rate: u32 as u32<LittleEndian>,
+        /// The number of bytes per second.
+        pub byte_rate: u32 as u32<LittleEndian>,
+        /// The number of bytes per frame.
+        pub block_align: u16 as u16<LittleEndian>,
+        /// The number of bits in each sample.
+        pub bits_per_sample: u16 as u16<LittleEndian>,
+    }
+}
+
+impl WavFormat {
+    /// Reads the header of a RIFF WAVE file, and then chunks up to and including `fmt `.
+    ///
+    /// Any chunks before `fmt ` are skipped, as is any extension of the `fmt ` chunk itself, so
+    /// the reader is left at the start of the chunk that follows it, which usually is `data`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. It also returns an error of
+    /// the kind [`ErrorKind::InvalidData`] if the stream does not start with a RIFF WAVE header,
+    /// or if its `fmt ` chunk is too short.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    /// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::WavFormat;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut file = &b"RIFF\x24\x00\x00\x00WAVEfmt \x10\x00\x00\x00\
+    ///         \x01\x00\x02\x00\x44\xac\x00\x00\x10\xb1\x02\x00\x04\x00\x10\x00\
+    ///         data\x00\x00\x00\x00"[..];
+    ///     let format = WavFormat::read_from_wav(&mut file).await.unwrap();
+    ///     assert_eq!(format.channels, 2);
+    ///     assert_eq!(format.sample_rate, 44100);
+    ///     assert_eq!(format.bits_per_sample, 16);
+    ///     assert!(file.starts_with(b"data"));
+    /// }
+    /// ```
+    pub async fn read_from_wav<R>(src: &mut R) -> io::Result<Self>
+    where
+        R: io::AsyncRead + Unpin + Send + ?Sized,
+    {
+        read_container(src, b"RIFF", &[b"WAVE"]).await?;
+        let size = find_chunk::<_, LittleEndian>(src, b"fmt ").await?;
+        if size < 16 {
+            return Err(invalid("wav fmt chunk is too short"));
+        }
+        let format = Self::read_from(src).await?;
+        skip(src, (size - 16) as usize + (size & 1) as usize).await?;
+        Ok(format)
+    }
+}
+
+/// Converts an 80 bit IEEE754 extended precision number, as used by AIFF for its sample rate,
+/// to the nearest `f64`.
+fn extended_to_f64(buf: &[u8; 10]) -> f64 {
+    let sign = if buf[0] & 0x80 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from(BigEndian::read_u16(&buf[..2]) & 0x7fff);
+    let mantissa = BigEndian::read_u64(&buf[2..]);
+    if mantissa == 0 {
+        return sign * 0.0;
+    }
+    if exponent == 0x7fff {
+        return if mantissa << 1 == 0 {
+            sign * f64::INFINITY
+        } else {
+            f64::NAN
+        };
+    }
+    // the mantissa has an explicit integer bit, so it is worth `mantissa * 2^-63`
+    let e = exponent - 16383 - 63;
+    // split the scaling so that neither half overflows for values that fit in an f64
+    sign * mantissa as f64 * 2f64.powi(e / 2) * 2f64.powi(e - e / 2)
+}
+
+/// The fields of the `COMM` chunk of an AIFF or AIFF-C file.
+///
+/// The [`AsyncDecode`] implementation covers the 18 bytes that every `COMM` chunk starts with,
+/// and not the chunk header or the compression fields of AIFF-C. Use
+/// [`read_from_aiff`](#method.read_from_aiff) to find and read the chunk in a whole file.
+///
+/// [`AsyncDecode`]: trait.AsyncDecode.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiffCommon {
+    /// The number of channels.
+    pub channels: u16,
+    /// The number of frames in the `SSND` chunk.
+    pub sample_frames: u32,
+    /// The number of bits in each sample.
+    pub sample_size: u16,
+    /// The number of frames per second, decoded from its 80 bit extended precision encoding.
+    pub sample_rate: f64,
+}
+
+impl AsyncDecode for AiffCommon {
+    async fn read_from<R>(src: &mut R) -> io::Result<Self>
+    where
+        R: io::AsyncRead + Unpin + Send + ?Sized,
+    {
+        let channels = AsyncReadBytesExt::read_u16::<BigEndian>(src).await?;
+        let sample_frames = AsyncReadBytesExt::read_u32::<BigEndian>(src).await?;
+        let sample_size = AsyncReadBytesExt::read_u16::<BigEndian>(src).await?;
+        let mut rate = [0; 10];
+        src.read_exact(&mut rate).await?;
+        Ok(AiffCommon {
+            channels,
+            sample_frames,
+            sample_size,
+            sample_rate: extended_to_f64(&rate),
+        })
+    }
+}
+
+impl AiffCommon {
+    /// Reads the header of an AIFF or AIFF-C file, and then chunks up to and including `COMM`.
+    ///
+    /// Any chunks before `COMM` are skipped, as is the rest of the `COMM` chunk itself, so the
+    /// reader is left at the start of the chunk that follows it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. It also returns an error of
+    /// the kind [`ErrorKind::InvalidData`] if the stream does not start with an AIFF or AIFF-C
+    /// header, or if its `COMM` chunk is too short.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    /// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AiffCommon;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut file = &b"FORM\x00\x00\x00\x1eAIFFCOMM\x00\x00\x00\x12\
+    ///         \x00\x01\x00\x00\x00\x00\x00\x18\x40\x0e\xac\x44\x00\x00\x00\x00\x00\x00"[..];
+    ///     let comm = AiffCommon::read_from_aiff(&mut file).await.unwrap();
+    ///     assert_eq!(comm.channels, 1);
+    ///     assert_eq!(comm.sample_size, 24);
+    ///     assert_eq!(comm.sample_rate, 44100.0);
+    /// }
+    /// ```
+    pub async fn read_from_aiff<R>(src: &mut R) -> io::Result<Self>
+    where
+        R: io::AsyncRead + Unpin + Send + ?Sized,
+    {
+        read_container(src, b"FORM", &[b"AIFF", b"AIFC"]).await?;
+        let size = find_chunk::<_, BigEndian>(src, b"COMM").await?;
+        if size < 18 {
+            return Err(invalid("aiff COMM chunk is too short"));
+        }
+        let comm = Self::read_from(src).await?;
+        skip(src, (size - 18) as usize + (size & 1) as usize).await?;
+        Ok(comm)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::varint::{encode_varint, zigzag_decode, zigzag_encode, VarintDecoder};
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) fn decode_int(n: u64) -> io::Result<i32> {
+    i32::try_from(zigzag_decode(n)).map_err(|_| invalid("avro int does not fit in an i32"))
+}
+
+fn to_string(buf: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(buf).map_err(|_| invalid("avro string is not valid utf-8"))
+}
+
+/// Future returned by
+/// [`read_avro_bytes`](../trait.AsyncReadBytesExt.html#method.read_avro_bytes) and
+/// [`read_avro_string`](../trait.AsyncReadBytesExt.html#method.read_avro_string).
+#[derive(Debug)]
+pub struct ReadAvroBytes<R, V> {
+    src: R,
+    decoder: VarintDecoder,
+    max_len: usize,
+    buf: Vec<u8>,
+    // the length of the value, once its prefix is in
+    len: Option<usize>,
+    finish: fn(Vec<u8>) -> io::Result<V>,
+}
+
+impl<R> ReadAvroBytes<R, Vec<u8>> {
+    pub(crate) fn bytes(src: R, max_len: usize) -> Self {
+        Self::new(src, max_len, Ok)
+    }
+}
+
+impl<R> ReadAvroBytes<R, String> {
+    pub(crate) fn string(src: R, max_len: usize) -> Self {
+        Self::new(src, max_len, to_string)
+    }
+}
+
+impl<R, V> ReadAvroBytes<R, V> {
+    fn new(src: R, max_len: usize, finish: fn(Vec<u8>) -> io::Result<V>) -> Self {
+        ReadAvroBytes {
+            src,
+            decoder: VarintDecoder::default(),
+            max_len,
+            buf: Vec::new(),
+            len: None,
+            finish,
+        }
+    }
+}
+
+impl<R, V> Future for ReadAvroBytes<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = zigzag_decode(ready!(this.decoder.poll_read(&mut this.src, cx))?);
+                let len = match usize::try_from(len) {
+                    Ok(len) if len <= this.max_len => len,
+                    Ok(_) => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "avro length is larger than the maximum of {} bytes",
+                                this.max_len
+                            ),
+                        )));
+                    }
+                    Err(_) => return Poll::Ready(Err(invalid("avro length is negative"))),
+                };
+                *this.len.insert(len)
+            }
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready((this.finish)(core::mem::take(&mut this.buf)))
+    }
+}
+
+/// Future returned by
+/// [`write_avro_bytes`](../trait.AsyncWriteBytesExt.html#method.write_avro_bytes) and
+/// [`write_avro_string`](../trait.AsyncWriteBytesExt.html#method.write_avro_string).
+#[derive(Debug)]
+pub struct WriteAvroBytes<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteAvroBytes<W> {
+    pub(crate) fn new(dst: W, data: &[u8]) -> Self {
+        let mut buf = Vec::with_capacity(data.len() + 10);
+        encode_varint(zigzag_encode(data.len() as i64), &mut buf);
+        buf.extend_from_slice(data);
+        WriteAvroBytes {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteAvroBytes<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
@@ -0,0 +1,507 @@
+use crate::*;
+
+/// The order in which the bits of each byte are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The most significant bit of each byte comes first, and the first bit of a multi-bit value
+    /// is its most significant one.
+    ///
+    /// This is how most media codecs and packed protocol headers lay out their fields.
+    MsbFirst,
+    /// The least significant bit of each byte comes first, and the first bit of a multi-bit
+    /// value is its least significant one.
+    ///
+    /// This is how DEFLATE and the formats derived from it lay out their fields.
+    LsbFirst,
+}
+
+/// Returns a mask of the low `n` bits, for `n` up to 8.
+fn mask(n: u32) -> u8 {
+    ((1u16 << n) - 1) as u8
+}
+
+/// A reader for values that are not a whole number of bytes long.
+///
+/// Bytes are pulled from the underlying reader one at a time, and handed out a few bits at a
+/// time, in the [`BitOrder`] the reader was created with. Once the remaining bits of a byte are
+/// of no interest, for example because a field that is a whole number of bytes long follows,
+/// [`align`] skips them.
+///
+/// The reader does not read ahead, so after aligning, the underlying reader is positioned just
+/// past the last byte that was used, and can be taken back with [`into_inner`].
+///
+/// # Examples
+///
+/// Read the version, traffic class, and flow label from the start of an IPv6 header:
+///
+/// ```rust
+/// use tokio_byteorder::BitReader;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = BitReader::msb_first(&[0x60, 0x12, 0x34, 0x56][..]);
+///     assert_eq!(rdr.read_bits(4).await.unwrap(), 6);
+///     assert_eq!(rdr.read_bits(8).await.unwrap(), 0x01);
+///     assert_eq!(rdr.read_bits(20).await.unwrap(), 0x23456);
+/// }
+/// ```
+///
+/// [`BitOrder`]: enum.BitOrder.html
+/// [`align`]: #method.align
+/// [`into_inner`]: #method.into_inner
+#[derive(Debug)]
+pub struct BitReader<R> {
+    inner: R,
+    order: BitOrder,
+    byte: u8,
+    /// How many bits of `byte` have not been handed out yet.
+    left: u32,
+}
+
+impl<R> BitReader<R> {
+    /// Wraps the given reader, using the bits of each byte in the order `order`.
+    pub fn new(inner: R, order: BitOrder) -> Self {
+        BitReader {
+            inner,
+            order,
+            byte: 0,
+            left: 0,
+        }
+    }
+
+    /// Wraps the given reader, using the bits of each byte from the most significant one down.
+    pub fn msb_first(inner: R) -> Self {
+        Self::new(inner, BitOrder::MsbFirst)
+    }
+
+    /// Wraps the given reader, using the bits of each byte from the least significant one up.
+    pub fn lsb_first(inner: R) -> Self {
+        Self::new(inner, BitOrder::LsbFirst)
+    }
+
+    /// Returns the order in which the bits of each byte are used.
+    pub fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns true if no bits of the current byte are left, so that the next read starts at a
+    /// byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.left == 0
+    }
+
+    /// Skips the rest of the current byte, and returns the number of bits that were skipped.
+    ///
+    /// This does nothing if the reader is already at a byte boundary.
+    pub fn align(&mut self) -> u32 {
+        let skipped = self.left;
+        self.left = 0;
+        skipped
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the returned reference skips any bits left in the current byte.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.left = 0;
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying reader.
+    ///
+    /// Any bits left in the current byte are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> BitReader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    /// Reads a single bit.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    pub async fn read_bit(&mut self) -> io::Result<bool> {
+        Ok(self.read_bits(1).await? == 1)
+    }
+
+    /// Reads an `n` bit unsigned integer.
+    ///
+    /// Reading zero bits returns zero without touching the underlying reader.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If the reader runs out of
+    /// bytes halfway through the value, the bits that were read are lost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than 64.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    pub async fn read_bits(&mut self, n: u32) -> io::Result<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at a time");
+        let mut value = 0u64;
+        let mut done = 0;
+        while done < n {
+            if self.left == 0 {
+                self.byte = AsyncReadBytesExt::read_u8(&mut self.inner).await?;
+                self.left = 8;
+            }
+            let k = (n - done).min(self.left);
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let chunk = (self.byte >> (self.left - k)) & mask(k);
+                    value = value << k | u64::from(chunk);
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (self.byte >> (8 - self.left)) & mask(k);
+                    value |= u64::from(chunk) << done;
+                }
+            }
+            self.left -= k;
+            done += k;
+        }
+        Ok(value)
+    }
+
+    /// Reads an integer packed as `packing` describes, and returns the value it stands for.
+    ///
+    /// The integer is `packing.bits` bits long, and is read with [`read_bits`], so the reader
+    /// should be most significant bit first, as GRIB2 and BUFR both are. If `packing` marks
+    /// all-ones integers as missing, as BUFR does, they are returned as NaN.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_bits`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packing.bits` is larger than 64.
+    ///
+    /// [`read_bits`]: #method.read_bits
+    ///
+    /// # Examples
+    ///
+    /// Read two 12 bit temperatures in kelvin, to a tenth of a degree, from GRIB2 data:
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{BitReader, SimplePacking};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let packing = SimplePacking::grib2(12, 2500.0, 0, 1);
+    ///     let mut rdr = BitReader::msb_first(&[0x00, 0x00, 0x0f][..]);
+    ///     assert_eq!(rdr.read_packed(&packing).await.unwrap(), 250.0);
+    ///     assert_eq!(rdr.read_packed(&packing).await.unwrap(), 251.5);
+    /// }
+    /// ```
+    pub async fn read_packed(&mut self, packing: &SimplePacking) -> io::Result<f64> {
+        let x = self.read_bits(packing.bits).await?;
+        Ok(packing.unpack(x))
+    }
+
+    /// Reads `dst.len()` integers packed as `packing` describes, and fills `dst` with the
+    /// values they stand for.
+    ///
+    /// This is [`read_packed`] in a loop, for the data section of a GRIB2 message or a run of
+    /// BUFR elements, which hold many values packed the same way.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_bits`]. If it returns an error, the
+    /// contents of `dst` are unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packing.bits` is larger than 64.
+    ///
+    /// [`read_packed`]: #method.read_packed
+    /// [`read_bits`]: #method.read_bits
+    pub async fn read_packed_into(
+        &mut self,
+        packing: &SimplePacking,
+        dst: &mut [f64],
+    ) -> io::Result<()> {
+        for v in dst {
+            *v = self.read_packed(packing).await?;
+        }
+        Ok(())
+    }
+}
+
+/// How real values are packed into unsigned integers of a few bits, in the simple packing of
+/// GRIB2 (data representation template 5.0) and in BUFR.
+///
+/// A packed integer `x` stands for the value `(reference + x * 2^binary_scale) /
+/// 10^decimal_scale`. Read packed values with [`BitReader::read_packed`].
+///
+/// [`BitReader::read_packed`]: struct.BitReader.html#method.read_packed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimplePacking {
+    /// How many bits each packed integer takes. Zero means every value is the reference value.
+    pub bits: u32,
+    /// The value that a packed zero stands for, before decimal scaling.
+    pub reference: f64,
+    /// The power of two that packed integers are multiplied by.
+    pub binary_scale: i32,
+    /// The power of ten that values are divided by.
+    pub decimal_scale: i32,
+    /// Whether an integer of all ones stands for a missing value, as in BUFR.
+    pub all_ones_missing: bool,
+}
+
+impl SimplePacking {
+    /// Returns the packing described by GRIB2's data representation template 5.0: the number of
+    /// bits, the reference value `R`, the binary scale factor `E`, and the decimal scale factor
+    /// `D`.
+    pub fn grib2(bits: u32, reference: f32, binary_scale: i16, decimal_scale: i16) -> Self {
+        SimplePacking {
+            bits,
+            reference: f64::from(reference),
+            binary_scale: i32::from(binary_scale),
+            decimal_scale: i32::from(decimal_scale),
+            all_ones_missing: false,
+        }
+    }
+
+    /// Returns the packing of a BUFR element, from its data width, reference value, and scale
+    /// in Table B.
+    ///
+    /// A BUFR element's value is `(x + reference) / 10^scale`, and an integer of all ones
+    /// stands for a missing value.
+    pub fn bufr(bits: u32, reference: i32, scale: i32) -> Self {
+        SimplePacking {
+            bits,
+            reference: f64::from(reference),
+            binary_scale: 0,
+            decimal_scale: scale,
+            all_ones_missing: true,
+        }
+    }
+
+    /// Returns whether the packed integer `x` stands for a missing value.
+    pub fn is_missing(&self, x: u64) -> bool {
+        self.all_ones_missing && self.bits > 0 && x == u64::MAX >> (64 - self.bits)
+    }
+
+    /// Returns the value that the packed integer `x` stands for, or NaN if it is missing.
+    pub fn unpack(&self, x: u64) -> f64 {
+        if self.is_missing(x) {
+            return f64::NAN;
+        }
+        let scaled = self.reference + x as f64 * 2f64.powi(self.binary_scale);
+        scaled / 10f64.powi(self.decimal_scale)
+    }
+}
+
+/// A writer for values that are not a whole number of bytes long.
+///
+/// Bits are collected into a byte in the [`BitOrder`] the writer was created with, and each byte
+/// is written to the underlying writer as soon as it is full. A byte that is only partially
+/// filled is held back until more bits arrive, or until [`align`] fills the rest of it with the
+/// [padding bit] and writes it out. Remember to align before taking the underlying writer back,
+/// or the last few bits are lost.
+///
+/// Bytes are written one at a time, so wrapping a [`ByteWriter`] or [`BufWriter`] is
+/// recommended when writing anything but a short header.
+///
+/// # Examples
+///
+/// Write the start of an IPv6 header:
+///
+/// ```rust
+/// use tokio_byteorder::BitWriter;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = BitWriter::msb_first(Vec::new());
+///     wtr.write_bits(6, 4).await.unwrap();
+///     wtr.write_bits(0x01, 8).await.unwrap();
+///     wtr.write_bits(0x23456, 20).await.unwrap();
+///     assert_eq!(wtr.get_ref(), &[0x60, 0x12, 0x34, 0x56]);
+/// }
+/// ```
+///
+/// [`BitOrder`]: enum.BitOrder.html
+/// [`align`]: #method.align
+/// [padding bit]: #method.set_padding
+/// [`ByteWriter`]: struct.ByteWriter.html
+/// [`BufWriter`]: https://docs.rs/tokio/1/tokio/io/struct.BufWriter.html
+#[derive(Debug)]
+pub struct BitWriter<W> {
+    inner: W,
+    order: BitOrder,
+    padding: bool,
+    byte: u8,
+    /// How many bits of `byte` have been filled in.
+    filled: u32,
+}
+
+impl<W> BitWriter<W> {
+    /// Wraps the given writer, filling each byte in the order `order`, and padding with zeros.
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        BitWriter {
+            inner,
+            order,
+            padding: false,
+            byte: 0,
+            filled: 0,
+        }
+    }
+
+    /// Wraps the given writer, filling each byte from the most significant bit down.
+    pub fn msb_first(inner: W) -> Self {
+        Self::new(inner, BitOrder::MsbFirst)
+    }
+
+    /// Wraps the given writer, filling each byte from the least significant bit up.
+    pub fn lsb_first(inner: W) -> Self {
+        Self::new(inner, BitOrder::LsbFirst)
+    }
+
+    /// Returns the order in which the bits of each byte are filled in.
+    pub fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns the bit that [`align`](#method.align) fills the rest of a byte with.
+    pub fn padding(&self) -> bool {
+        self.padding
+    }
+
+    /// Sets the bit that [`align`](#method.align) fills the rest of a byte with.
+    ///
+    /// The default is to pad with zeros. Some formats, like MPEG-2 transport streams and JPEG
+    /// entropy-coded segments, pad with ones instead.
+    pub fn set_padding(&mut self, padding: bool) {
+        self.padding = padding;
+    }
+
+    /// Returns true if no bits are waiting to be written, so that the next write starts at a
+    /// byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the returned reference end up ahead of any bits that are still
+    /// waiting for their byte to fill up.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying writer.
+    ///
+    /// Any bits that are still waiting for their byte to fill up are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> BitWriter<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    async fn emit(&mut self) -> io::Result<()> {
+        AsyncWriteBytesExt::write_u8(&mut self.inner, self.byte).await?;
+        self.byte = 0;
+        self.filled = 0;
+        Ok(())
+    }
+
+    /// Writes a single bit.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.write_bits(u64::from(bit), 1).await
+    }
+
+    /// Writes the low `n` bits of `value`.
+    ///
+    /// Any higher bits of `value` are ignored. Writing zero bits does nothing.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than 64.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_bits(&mut self, value: u64, n: u32) -> io::Result<()> {
+        assert!(n <= 64, "cannot write more than 64 bits at a time");
+        let mut done = 0;
+        while done < n {
+            if self.filled == 8 {
+                // a previous write of this byte failed
+                self.emit().await?;
+            }
+            let k = (n - done).min(8 - self.filled);
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let chunk = (value >> (n - done - k)) as u8 & mask(k);
+                    self.byte |= chunk << (8 - self.filled - k);
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (value >> done) as u8 & mask(k);
+                    self.byte |= chunk << self.filled;
+                }
+            }
+            self.filled += k;
+            done += k;
+            if self.filled == 8 {
+                self.emit().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills the rest of the current byte with the [padding bit] and writes it out, and returns
+    /// the number of padding bits that were used.
+    ///
+    /// This does nothing if the writer is already at a byte boundary.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [padding bit]: #method.set_padding
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn align(&mut self) -> io::Result<u32> {
+        if self.filled == 0 {
+            return Ok(0);
+        }
+        let pad = 8 - self.filled;
+        if self.padding {
+            self.byte |= match self.order {
+                BitOrder::MsbFirst => mask(pad),
+                BitOrder::LsbFirst => !mask(self.filled),
+            };
+        }
+        self.filled = 8;
+        self.emit().await?;
+        Ok(pad)
+    }
+}
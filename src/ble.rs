@@ -0,0 +1,246 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use core::convert::TryFrom;
+use core::fmt;
+use core::task::ready;
+use std::vec::Vec;
+
+/// The Bluetooth Base UUID, `00000000-0000-1000-8000-00805f9b34fb`, which 16 and 32 bit UUIDs
+/// are short forms of.
+const BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5f9b_34fb;
+
+/// A Bluetooth UUID, which identifies a service, characteristic, or other attribute.
+///
+/// The UUIDs that the Bluetooth SIG assigns have 16 or 32 bit short forms, which stand for
+/// the [Bluetooth Base UUID] with the short form in its top 32 bits. On the air, all three
+/// forms are little-endian; read them with [`read_ble_uuid16`] and its siblings.
+///
+/// The value is the UUID as a number, so that `0000180d-0000-1000-8000-00805f9b34fb`, the Heart
+/// Rate service, is `0x0000180d_0000_1000_8000_00805f9b34fb`. It displays in the usual
+/// hyphenated form.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::BluetoothUuid;
+///
+/// let heart_rate = BluetoothUuid::from_u16(0x180d);
+/// assert_eq!(heart_rate.to_string(), "0000180d-0000-1000-8000-00805f9b34fb");
+/// assert_eq!(heart_rate.to_u16(), Some(0x180d));
+/// ```
+///
+/// [Bluetooth Base UUID]: #associatedconstant.BASE
+/// [`read_ble_uuid16`]: trait.AsyncReadBytesExt.html#method.read_ble_uuid16
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BluetoothUuid(pub u128);
+
+impl BluetoothUuid {
+    /// The Bluetooth Base UUID, `00000000-0000-1000-8000-00805f9b34fb`.
+    pub const BASE: BluetoothUuid = BluetoothUuid(BASE_UUID);
+
+    /// Returns the UUID that the 16 bit short form `n` stands for.
+    pub fn from_u16(n: u16) -> Self {
+        Self::from_u32(u32::from(n))
+    }
+
+    /// Returns the UUID that the 32 bit short form `n` stands for.
+    pub fn from_u32(n: u32) -> Self {
+        BluetoothUuid(BASE_UUID | (u128::from(n) << 96))
+    }
+
+    /// Returns the 32 bit short form of this UUID, if it has one.
+    pub fn to_u32(self) -> Option<u32> {
+        if self.0 & !(u128::from(u32::MAX) << 96) == BASE_UUID {
+            Some((self.0 >> 96) as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the 16 bit short form of this UUID, if it has one.
+    pub fn to_u16(self) -> Option<u16> {
+        self.to_u32().and_then(|n| u16::try_from(n).ok())
+    }
+}
+
+impl fmt::Display for BluetoothUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            n >> 96,
+            (n >> 80) & 0xffff,
+            (n >> 64) & 0xffff,
+            (n >> 48) & 0xffff,
+            n & 0xffff_ffff_ffff
+        )
+    }
+}
+
+pub(crate) fn uuid16_decoder() -> FixedDecoder<BluetoothUuid> {
+    FixedDecoder::new(2, |buf| {
+        BluetoothUuid::from_u16(LittleEndian::read_u16(buf))
+    })
+}
+
+pub(crate) fn uuid32_decoder() -> FixedDecoder<BluetoothUuid> {
+    FixedDecoder::new(4, |buf| {
+        BluetoothUuid::from_u32(LittleEndian::read_u32(buf))
+    })
+}
+
+pub(crate) fn uuid128_decoder() -> FixedDecoder<BluetoothUuid> {
+    FixedDecoder::new(16, |buf| BluetoothUuid(LittleEndian::read_u128(buf)))
+}
+
+fn uuid16_encoder(uuid: BluetoothUuid) -> Result<FixedEncoder, &'static str> {
+    let n = uuid
+        .to_u16()
+        .ok_or("bluetooth uuid has no 16 bit short form")?;
+    Ok(FixedEncoder::new(2, |buf| LittleEndian::write_u16(buf, n)))
+}
+
+fn uuid32_encoder(uuid: BluetoothUuid) -> Result<FixedEncoder, &'static str> {
+    let n = uuid
+        .to_u32()
+        .ok_or("bluetooth uuid has no 32 bit short form")?;
+    Ok(FixedEncoder::new(4, |buf| LittleEndian::write_u32(buf, n)))
+}
+
+pub(crate) fn uuid128_encoder(uuid: BluetoothUuid) -> FixedEncoder {
+    FixedEncoder::new(16, |buf| LittleEndian::write_u128(buf, uuid.0))
+}
+
+/// An AD structure from Bluetooth LE advertising or scan response data (Core Specification
+/// Vol 3, Part C, §11).
+///
+/// On the air, it is a length byte that counts the type and the data, then the AD type, then
+/// the data. Read with [`read_ble_ad_structure`], and written with [`write_ble_ad_structure`].
+///
+/// [`read_ble_ad_structure`]: trait.AsyncReadBytesExt.html#method.read_ble_ad_structure
+/// [`write_ble_ad_structure`]: trait.AsyncWriteBytesExt.html#method.write_ble_ad_structure
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BleAdStructure {
+    /// The AD type, which says what the data holds.
+    pub ad_type: u8,
+    /// The data.
+    pub data: Vec<u8>,
+}
+
+impl BleAdStructure {
+    /// The AD type of the flags.
+    pub const FLAGS: u8 = 0x01;
+    /// The AD type of an incomplete list of 16 bit service UUIDs.
+    pub const INCOMPLETE_UUID16_LIST: u8 = 0x02;
+    /// The AD type of a complete list of 16 bit service UUIDs.
+    pub const COMPLETE_UUID16_LIST: u8 = 0x03;
+    /// The AD type of an incomplete list of 32 bit service UUIDs.
+    pub const INCOMPLETE_UUID32_LIST: u8 = 0x04;
+    /// The AD type of a complete list of 32 bit service UUIDs.
+    pub const COMPLETE_UUID32_LIST: u8 = 0x05;
+    /// The AD type of an incomplete list of 128 bit service UUIDs.
+    pub const INCOMPLETE_UUID128_LIST: u8 = 0x06;
+    /// The AD type of a complete list of 128 bit service UUIDs.
+    pub const COMPLETE_UUID128_LIST: u8 = 0x07;
+    /// The AD type of a shortened local name.
+    pub const SHORTENED_LOCAL_NAME: u8 = 0x08;
+    /// The AD type of the complete local name.
+    pub const COMPLETE_LOCAL_NAME: u8 = 0x09;
+    /// The AD type of the transmit power level.
+    pub const TX_POWER_LEVEL: u8 = 0x0a;
+    /// The AD type of service data for a 16 bit service UUID, which leads the data.
+    pub const SERVICE_DATA_UUID16: u8 = 0x16;
+    /// The AD type of manufacturer specific data, led by a 16 bit company identifier.
+    pub const MANUFACTURER_SPECIFIC_DATA: u8 = 0xff;
+}
+
+/// Future returned by
+/// [`read_ble_ad_structure`](../trait.AsyncReadBytesExt.html#method.read_ble_ad_structure).
+#[derive(Debug)]
+pub struct ReadBleAdStructure<R> {
+    src: R,
+    // the length byte, once it is in
+    len: Option<u8>,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadBleAdStructure<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadBleAdStructure {
+            src,
+            len: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadBleAdStructure<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Option<BleAdStructure>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let mut b = [0; 1];
+                ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+                *this.len.insert(b[0])
+            }
+        };
+        if len == 0 {
+            return Poll::Ready(Ok(None));
+        }
+        ready!(poll_read_to_len(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            usize::from(len)
+        ))?;
+        let mut data = core::mem::take(&mut this.buf);
+        let ad_type = data.remove(0);
+        Poll::Ready(Ok(Some(BleAdStructure { ad_type, data })))
+    }
+}
+
+/// Future returned by
+/// [`write_ble_uuid16`](../trait.AsyncWriteBytesExt.html#method.write_ble_uuid16) and
+/// [`write_ble_uuid32`](../trait.AsyncWriteBytesExt.html#method.write_ble_uuid32).
+#[derive(Debug)]
+pub struct WriteBleUuid<W> {
+    dst: W,
+    // the encoded short form, or why it cannot be encoded
+    encoder: Result<FixedEncoder, &'static str>,
+}
+
+impl<W> WriteBleUuid<W> {
+    pub(crate) fn uuid16(dst: W, uuid: BluetoothUuid) -> Self {
+        WriteBleUuid {
+            dst,
+            encoder: uuid16_encoder(uuid),
+        }
+    }
+
+    pub(crate) fn uuid32(dst: W, uuid: BluetoothUuid) -> Self {
+        WriteBleUuid {
+            dst,
+            encoder: uuid32_encoder(uuid),
+        }
+    }
+}
+
+impl<W> Future for WriteBleUuid<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.encoder {
+            Ok(encoder) => poll_write_encoder(Pin::new(&mut this.dst), cx, encoder),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
@@ -0,0 +1,705 @@
+use crate::*;
+use core::task::ready;
+
+/// Future returned by the `peek_` methods of [`AsyncBufReadBytesExt`](../trait.AsyncBufReadBytesExt.html).
+#[derive(Debug)]
+pub struct Peek<R, V> {
+    src: R,
+    len: usize,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<R, V> Peek<R, V> {
+    fn new(src: R, len: usize, decode: fn(&[u8]) -> V) -> Self {
+        Peek { src, len, decode }
+    }
+}
+
+impl<R, V> Future for Peek<R, V>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<Option<V>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let buf = ready!(Pin::new(&mut this.src).poll_fill_buf(cx))?;
+        if buf.len() < this.len {
+            return Poll::Ready(Ok(None));
+        }
+        Poll::Ready(Ok(Some((this.decode)(&buf[..this.len]))))
+    }
+}
+
+/// Future returned by the `buf_read_` methods of
+/// [`AsyncBufReadBytesExt`](../trait.AsyncBufReadBytesExt.html).
+#[derive(Debug)]
+pub struct ReadBuffered<R, V> {
+    buf: [u8; 16],
+    len: u8,
+    read: u8,
+    src: R,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<R, V> ReadBuffered<R, V> {
+    fn new(src: R, len: usize, decode: fn(&[u8]) -> V) -> Self {
+        ReadBuffered {
+            buf: [0; 16],
+            len: len as u8,
+            read: 0,
+            src,
+            decode,
+        }
+    }
+}
+
+impl<R, V> Future for ReadBuffered<R, V>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = this.len as usize;
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let avail = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if this.read == 0 && avail.len() >= len {
+                // the common case: the whole value is already buffered
+                let v = (this.decode)(&avail[..len]);
+                src.consume(len);
+                return Poll::Ready(Ok(v));
+            }
+            if avail.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                )));
+            }
+
+            // the value straddles the end of the buffer, so stitch it together
+            let read = this.read as usize;
+            let n = std::cmp::min(avail.len(), len - read);
+            this.buf[read..read + n].copy_from_slice(&avail[..n]);
+            src.consume(n);
+            this.read += n as u8;
+            if this.read as usize == len {
+                return Poll::Ready(Ok((this.decode)(&this.buf[..len])));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`resync_to`](../trait.AsyncBufReadBytesExt.html#method.resync_to).
+#[derive(Debug)]
+pub struct ResyncTo<'a, R> {
+    src: R,
+    pattern: &'a [u8],
+    // for each prefix of the pattern, the length of its longest proper prefix that is also a
+    // suffix of it, so that a failed partial match can carry on without going back
+    fallback: Vec<usize>,
+    max_scan: usize,
+    // bytes consumed so far, and how many at the end of those match the start of the pattern
+    consumed: usize,
+    matched: usize,
+}
+
+impl<'a, R> ResyncTo<'a, R> {
+    fn new(src: R, pattern: &'a [u8], max_scan: usize) -> Self {
+        let mut fallback = vec![0; pattern.len()];
+        let mut k = 0;
+        for i in 1..pattern.len() {
+            while k > 0 && pattern[i] != pattern[k] {
+                k = fallback[k - 1];
+            }
+            if pattern[i] == pattern[k] {
+                k += 1;
+            }
+            fallback[i] = k;
+        }
+        ResyncTo {
+            src,
+            pattern,
+            fallback,
+            max_scan,
+            consumed: 0,
+            matched: 0,
+        }
+    }
+}
+
+impl<R> Future for ResyncTo<'_, R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<usize>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let pattern = this.pattern;
+        if pattern.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let buf = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if buf.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "reached the end of the stream before the sync pattern",
+                )));
+            }
+
+            // never consume past where the pattern would have to end to be within reach
+            let reach = this
+                .max_scan
+                .saturating_add(pattern.len())
+                .saturating_sub(this.consumed);
+            let buf = &buf[..buf.len().min(reach)];
+            let mut i = 0;
+            while i < buf.len() && this.matched < pattern.len() {
+                if this.matched == 0 {
+                    // skip straight to the next byte that could start the pattern
+                    match buf[i..].iter().position(|&b| b == pattern[0]) {
+                        Some(n) => i += n,
+                        None => {
+                            i = buf.len();
+                            break;
+                        }
+                    }
+                }
+                let b = buf[i];
+                while this.matched > 0 && pattern[this.matched] != b {
+                    this.matched = this.fallback[this.matched - 1];
+                }
+                if pattern[this.matched] == b {
+                    this.matched += 1;
+                }
+                i += 1;
+            }
+            src.consume(i);
+            this.consumed += i;
+
+            let skipped = this.consumed - this.matched;
+            if this.matched == pattern.len() {
+                return Poll::Ready(Ok(skipped));
+            }
+            if skipped > this.max_scan {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("sync pattern not found within {} bytes", this.max_scan),
+                )));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`resync_to_byte`](../trait.AsyncBufReadBytesExt.html#method.resync_to_byte).
+#[derive(Debug)]
+pub struct ResyncToByte<R> {
+    src: R,
+    sync: u8,
+    max_scan: usize,
+    consumed: usize,
+}
+
+impl<R> ResyncToByte<R> {
+    fn new(src: R, sync: u8, max_scan: usize) -> Self {
+        ResyncToByte {
+            src,
+            sync,
+            max_scan,
+            consumed: 0,
+        }
+    }
+}
+
+impl<R> Future for ResyncToByte<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<usize>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let sync = this.sync;
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let buf = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if buf.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "reached the end of the stream before the sync byte",
+                )));
+            }
+
+            // never consume past where the sync byte would have to be to be within reach
+            let reach = this
+                .max_scan
+                .saturating_add(1)
+                .saturating_sub(this.consumed);
+            let buf = &buf[..buf.len().min(reach)];
+            if let Some(n) = buf.iter().position(|&b| b == sync) {
+                // the sync byte itself stays in the buffer
+                src.consume(n);
+                this.consumed += n;
+                return Poll::Ready(Ok(this.consumed));
+            }
+            let n = buf.len();
+            src.consume(n);
+            this.consumed += n;
+            if this.consumed > this.max_scan {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("sync byte not found within {} bytes", this.max_scan),
+                )));
+            }
+        }
+    }
+}
+
+macro_rules! buf_read_impl {
+    ($(($name:ident, $ty:ty, $bytes:expr, $decode:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Reads ", $desc, " straight out of the reader's buffer.")]
+            ///
+            /// See [`buf_read_u32`](#method.buf_read_u32) for details.
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self) -> ReadBuffered<&mut Self, $ty>
+            where
+                Self: Unpin,
+            {
+                ReadBuffered::new(self, $bytes, T::$decode)
+            }
+        )*
+    };
+}
+
+macro_rules! peek_impl {
+    ($(($name:ident, $ty:ty, $bytes:expr, $decode:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Peeks at ", $desc, " in the reader's buffer without consuming it.")]
+            ///
+            /// See [`peek_u32`](#method.peek_u32) for details.
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self) -> Peek<&mut Self, $ty>
+            where
+                Self: Unpin,
+            {
+                Peek::new(self, $bytes, T::$decode)
+            }
+        )*
+    };
+}
+
+/// Extends [`AsyncBufRead`] with methods for inspecting numbers before consuming them.
+///
+/// All of the methods here decode directly from the reader's internal buffer, and leave that
+/// buffer untouched. This makes them convenient for tag-dispatch parsers, which need to look at
+/// the next opcode before deciding which branch to take.
+///
+/// Since [`AsyncBufRead`] has no way to ask for more bytes without consuming the ones already
+/// buffered, a value can only be peeked if it is wholly contained in the current buffer. The
+/// peek methods therefore resolve to `None` if the buffer holds fewer bytes than the value
+/// needs, which is always the case at the end of the stream.
+///
+/// The `buf_read_*` methods are the consuming counterparts of the peek methods. They return
+/// the same values as the corresponding methods on [`AsyncReadBytesExt`], but decode directly
+/// out of the reader's buffer and consume the value in one step, rather than copying the
+/// value into the future piecemeal. This is considerably faster for buffered sources such as
+/// a socket wrapped in a [`BufReader`]. Unlike the peek methods, they handle values that
+/// straddle the end of the buffer.
+///
+/// [`AsyncBufRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+pub trait AsyncBufReadBytesExt: io::AsyncBufRead {
+    /// Peeks at the next byte without consuming it.
+    ///
+    /// Resolves to `None` at the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// Dispatch on an opcode without consuming it:
+    ///
+    /// ```rust
+    /// use tokio::io::BufReader;
+    /// use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = BufReader::new(&[0x01, 0x2a][..]);
+    ///     assert_eq!(Some(0x01), rdr.peek_u8().await.unwrap());
+    ///     assert_eq!(0x01, rdr.read_u8().await.unwrap());
+    ///     assert_eq!(0x2a, rdr.read_u8().await.unwrap());
+    ///     assert_eq!(None, rdr.peek_u8().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn peek_u8(&mut self) -> Peek<&mut Self, u8>
+    where
+        Self: Unpin,
+    {
+        Peek::new(self, 1, |buf| buf[0])
+    }
+
+    /// Peeks at the next byte as a signed 8 bit integer without consuming it.
+    ///
+    /// See [`peek_u8`](#method.peek_u8) for details.
+    #[inline]
+    fn peek_i8(&mut self) -> Peek<&mut Self, i8>
+    where
+        Self: Unpin,
+    {
+        Peek::new(self, 1, |buf| buf[0] as i8)
+    }
+
+    /// Peeks at an unsigned 32 bit integer in the reader's buffer without consuming it.
+    ///
+    /// Resolves to `None` if fewer than four bytes are buffered.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// Check for a magic number before handing the stream to a parser:
+    ///
+    /// ```rust
+    /// use tokio::io::BufReader;
+    /// use tokio_byteorder::{AsyncBufReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = BufReader::new(&b"\x89PNG\r\n\x1a\n"[..]);
+    ///     let magic = rdr.peek_u32::<BigEndian>().await.unwrap();
+    ///     assert_eq!(Some(0x89504e47), magic);
+    /// }
+    /// ```
+    #[inline]
+    fn peek_u32<T: ByteOrder>(&mut self) -> Peek<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        Peek::new(self, 4, T::read_u32)
+    }
+
+    peek_impl! {
+        (peek_u16, u16, 2, read_u16, "an unsigned 16 bit integer"),
+        (peek_i16, i16, 2, read_i16, "a signed 16 bit integer"),
+        (peek_u24, u32, 3, read_u24, "an unsigned 24 bit integer"),
+        (peek_i24, i32, 3, read_i24, "a signed 24 bit integer"),
+        (peek_i32, i32, 4, read_i32, "a signed 32 bit integer"),
+        (peek_u48, u64, 6, read_u48, "an unsigned 48 bit integer"),
+        (peek_i48, i64, 6, read_i48, "a signed 48 bit integer"),
+        (peek_u64, u64, 8, read_u64, "an unsigned 64 bit integer"),
+        (peek_i64, i64, 8, read_i64, "a signed 64 bit integer"),
+        (peek_u128, u128, 16, read_u128, "an unsigned 128 bit integer"),
+        (peek_i128, i128, 16, read_i128, "a signed 128 bit integer"),
+        (peek_f32, f32, 4, read_f32, "a IEEE754 single-precision floating point number"),
+        (peek_f64, f64, 8, read_f64, "a IEEE754 double-precision floating point number"),
+    }
+
+    /// Reads an unsigned 8 bit integer straight out of the reader's buffer.
+    ///
+    /// See [`buf_read_u32`](#method.buf_read_u32) for details.
+    #[inline]
+    fn buf_read_u8(&mut self) -> ReadBuffered<&mut Self, u8>
+    where
+        Self: Unpin,
+    {
+        ReadBuffered::new(self, 1, |buf| buf[0])
+    }
+
+    /// Reads a signed 8 bit integer straight out of the reader's buffer.
+    ///
+    /// See [`buf_read_u32`](#method.buf_read_u32) for details.
+    #[inline]
+    fn buf_read_i8(&mut self) -> ReadBuffered<&mut Self, i8>
+    where
+        Self: Unpin,
+    {
+        ReadBuffered::new(self, 1, |buf| buf[0] as i8)
+    }
+
+    /// Reads an unsigned 32 bit integer straight out of the reader's buffer.
+    ///
+    /// This is equivalent to [`AsyncReadBytesExt::read_u32`], but faster for buffered readers.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`AsyncReadBytesExt::read_u32`]: trait.AsyncReadBytesExt.html#method.read_u32
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read unsigned 32 bit big-endian integers from a `BufReader`:
+    ///
+    /// ```rust
+    /// use tokio::io::BufReader;
+    /// use tokio_byteorder::{AsyncBufReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = BufReader::new(&[0x00, 0x00, 0x01, 0x0b][..]);
+    ///     assert_eq!(267, rdr.buf_read_u32::<BigEndian>().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn buf_read_u32<T: ByteOrder>(&mut self) -> ReadBuffered<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        ReadBuffered::new(self, 4, T::read_u32)
+    }
+
+    buf_read_impl! {
+        (buf_read_u16, u16, 2, read_u16, "an unsigned 16 bit integer"),
+        (buf_read_i16, i16, 2, read_i16, "a signed 16 bit integer"),
+        (buf_read_u24, u32, 3, read_u24, "an unsigned 24 bit integer"),
+        (buf_read_i24, i32, 3, read_i24, "a signed 24 bit integer"),
+        (buf_read_i32, i32, 4, read_i32, "a signed 32 bit integer"),
+        (buf_read_u48, u64, 6, read_u48, "an unsigned 48 bit integer"),
+        (buf_read_i48, i64, 6, read_i48, "a signed 48 bit integer"),
+        (buf_read_u64, u64, 8, read_u64, "an unsigned 64 bit integer"),
+        (buf_read_i64, i64, 8, read_i64, "a signed 64 bit integer"),
+        (buf_read_u128, u128, 16, read_u128, "an unsigned 128 bit integer"),
+        (buf_read_i128, i128, 16, read_i128, "a signed 128 bit integer"),
+        (buf_read_f32, f32, 4, read_f32, "a IEEE754 single-precision floating point number"),
+        (buf_read_f64, f64, 8, read_f64, "a IEEE754 double-precision floating point number"),
+    }
+
+    /// Discards bytes up to and including the next occurrence of `pattern`, and returns how
+    /// many bytes were discarded before it.
+    ///
+    /// This is how a stream of framed records, such as the output of a serial sensor, gets
+    /// back in step after corruption. The search runs over the reader's buffer, a whole buffer
+    /// at a time, and handles a pattern that straddles two buffers. Once this resolves, the next
+    /// byte read is the one right after the pattern. When the sync marker is a single byte that
+    /// belongs to the record it starts, as in an MPEG transport stream, use
+    /// [`resync_to_byte`](#method.resync_to_byte) instead, which leaves it to be read.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// In addition, an error of the kind `UnexpectedEof` is returned if the stream ends before
+    /// the pattern is found, and an error of the kind `InvalidData` is returned if the pattern
+    /// does not start within the first `max_scan` bytes. Either way, the bytes that were
+    /// searched are consumed.
+    ///
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio::io::BufReader;
+    /// use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = BufReader::new(&b"\x12\xfa\xfa\xf3\x20\x00\x2a"[..]);
+    ///     let skipped = rdr.resync_to(b"\xfa\xf3\x20", 1024).await.unwrap();
+    ///     assert_eq!(skipped, 2);
+    ///     assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 42);
+    /// }
+    /// ```
+    #[inline]
+    fn resync_to<'a>(&'a mut self, pattern: &'a [u8], max_scan: usize) -> ResyncTo<'a, &'a mut Self>
+    where
+        Self: Unpin,
+    {
+        ResyncTo::new(self, pattern, max_scan)
+    }
+
+    /// Discards bytes up to the next occurrence of `sync`, and returns how many bytes were
+    /// discarded.
+    ///
+    /// Unlike [`resync_to`](#method.resync_to), the sync byte is left in the buffer, so once
+    /// this resolves, the next byte read is the sync byte itself. That suits framings where the
+    /// sync byte is the first byte of the record, such as the `0x47` that starts every MPEG
+    /// transport stream packet: the whole packet can then be read as usual.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// In addition, an error of the kind `UnexpectedEof` is returned if the stream ends before
+    /// the sync byte is found, and an error of the kind `InvalidData` is returned if it is not
+    /// within the first `max_scan + 1` bytes. Either way, the bytes that were searched are
+    /// consumed.
+    ///
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio::io::BufReader;
+    /// use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // the tail of a damaged packet, then the header of a packet on PID 0x0100
+    ///     let mut rdr = BufReader::new(&b"\xff\xff\x47\x41\x00\x10"[..]);
+    ///     let skipped = rdr.resync_to_byte(0x47, 188).await.unwrap();
+    ///     assert_eq!(skipped, 2);
+    ///     let header = rdr.read_u32::<BigEndian>().await.unwrap();
+    ///     assert_eq!(header >> 24, 0x47);
+    ///     assert_eq!((header >> 8) & 0x1fff, 0x0100);
+    /// }
+    /// ```
+    #[inline]
+    fn resync_to_byte(&mut self, sync: u8, max_scan: usize) -> ResyncToByte<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ResyncToByte::new(self, sync, max_scan)
+    }
+
+    /// Reads a frame encoded with Consistent Overhead Byte Stuffing, up to and including the
+    /// `0x00` delimiter that ends it, and returns the decoded bytes.
+    ///
+    /// COBS replaces every zero in a frame, so that a zero only ever marks the end of one. This
+    /// makes it a common framing for byte streams that carry packets, like serial links and
+    /// the TCP bridges in front of them, since a reader that loses its place only has to skip
+    /// to the next zero. Delimiters with no frame between them are skipped. The frame is decoded
+    /// a whole buffer at a time, so wrap unbuffered readers in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// In addition, an error of the kind `UnexpectedEof` is returned if the stream ends before
+    /// the delimiter, and an error of the kind `InvalidData` is returned if the frame decodes
+    /// to more than `max_len` bytes or a delimiter turns up where the encoding promised data.
+    /// In the latter cases, the rest of the frame is discarded, so the next read starts at the
+    /// next frame.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncBufReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x03\x11\x22\x02\x33\x00\x01\x00"[..];
+    ///     assert_eq!(rdr.read_cobs_frame(1500).await.unwrap(), b"\x11\x22\x00\x33");
+    ///     assert_eq!(rdr.read_cobs_frame(1500).await.unwrap(), b"");
+    /// }
+    /// ```
+    #[inline]
+    fn read_cobs_frame(&mut self, max_len: usize) -> ReadCobsFrame<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadCobsFrame::new(self, max_len)
+    }
+
+    /// Reads a SLIP packet (RFC 1055), up to and including the `END` byte that ends it, and
+    /// returns the unescaped bytes.
+    ///
+    /// SLIP ends each packet with an `END` byte (`0xc0`), and escapes any `END` or `ESC` byte
+    /// (`0xdb`) within it. Embedded gateways use it to carry sensor packets over serial lines
+    /// and the TCP connections that tunnel them. An `END` with no packet before it is skipped,
+    /// as senders commonly start every packet with one to flush out line noise; this means an
+    /// empty packet is never returned. The packet is decoded a whole buffer at a time, so wrap
+    /// unbuffered readers in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// In addition, an error of the kind `UnexpectedEof` is returned if the stream ends before
+    /// the `END` byte, and an error of the kind `InvalidData` is returned if the packet is
+    /// longer than `max_len` bytes or holds an `ESC` that is not followed by `ESC_END` or
+    /// `ESC_ESC`. In the latter cases, the rest of the packet is discarded, so the next read
+    /// starts at the next packet.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncBufReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\xc0\x01\xdb\xdc\x02\xc0\xc0\xdb\xdd\xc0"[..];
+    ///     assert_eq!(rdr.read_slip_frame(1006).await.unwrap(), b"\x01\xc0\x02");
+    ///     assert_eq!(rdr.read_slip_frame(1006).await.unwrap(), b"\xdb");
+    /// }
+    /// ```
+    #[inline]
+    fn read_slip_frame(&mut self, max_len: usize) -> ReadSlipFrame<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadSlipFrame::new(self, max_len)
+    }
+
+    /// Reads a string of the PostgreSQL frontend/backend protocol: UTF-8 bytes ended by a NUL,
+    /// which is consumed but not returned.
+    ///
+    /// The string is searched for a whole buffer at a time, so wrap unbuffered readers in a
+    /// [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`AsyncBufRead::poll_fill_buf`].
+    ///
+    /// In addition, an error of the kind `UnexpectedEof` is returned if the stream ends before
+    /// the NUL, and an error of the kind `InvalidData` is returned if the string is longer than
+    /// `max_len` bytes or is not valid UTF-8. A string that is too long is left partly read.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`AsyncBufRead::poll_fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html#tymethod.poll_fill_buf
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncBufReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"user\0postgres\0\0"[..];
+    ///     assert_eq!(rdr.read_pg_string(64).await.unwrap(), "user");
+    ///     assert_eq!(rdr.read_pg_string(64).await.unwrap(), "postgres");
+    ///     assert_eq!(rdr.read_pg_string(64).await.unwrap(), "");
+    /// }
+    /// ```
+    #[inline]
+    fn read_pg_string(&mut self, max_len: usize) -> ReadPgString<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadPgString::new(self, max_len)
+    }
+}
+
+/// All types that implement `AsyncBufRead` get methods defined in `AsyncBufReadBytesExt`
+/// for free.
+impl<R: io::AsyncBufRead + ?Sized> AsyncBufReadBytesExt for R {}
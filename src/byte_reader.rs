@@ -0,0 +1,363 @@
+use crate::*;
+use core::task::ready;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+// every fixed-size value must fit in the buffer so that it can be peeked
+const MIN_CAPACITY: usize = 16;
+
+/// A buffered reader with all of this crate's decoding methods built in.
+///
+/// `ByteReader` owns the underlying reader along with an internal buffer, and decodes values
+/// straight out of that buffer. It is a batteries-included alternative to stacking a
+/// [`BufReader`] and importing the extension traits: all the fixed-width read methods are
+/// available as inherent methods, and it additionally tracks the stream [`position`] and can
+/// [peek] at values of any width.
+///
+/// When one of its read methods fails, the error carries a [`DecodeError`] that records the
+/// type that was being read and the offset it was read at. A value that is cut short by the end
+/// of the stream is not consumed, so its bytes can still be read some other way.
+///
+/// `ByteReader` implements [`AsyncRead`] and [`AsyncBufRead`], so the methods of
+/// [`AsyncReadBytesExt`] remain available for everything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, ByteReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = ByteReader::new(&[0x01, 0x00, 0x00, 0x01, 0x0b][..]);
+///     assert_eq!(Some(1), rdr.peek_u8().await.unwrap());
+///     assert_eq!(1, rdr.read_u8().await.unwrap());
+///     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+///     assert_eq!(5, rdr.position());
+/// }
+/// ```
+///
+/// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+/// [`AsyncBufRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`position`]: #method.position
+/// [peek]: #method.peek_u32
+/// [`DecodeError`]: struct.DecodeError.html
+#[derive(Debug)]
+pub struct ByteReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    // the unconsumed bytes are `buf[pos..filled]`
+    pos: usize,
+    filled: usize,
+    position: u64,
+}
+
+impl<R> ByteReader<R> {
+    /// Wraps the given reader with a buffer of the default capacity (currently 8 KiB).
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Wraps the given reader with a buffer of (at least) the given capacity.
+    ///
+    /// The capacity is rounded up to 16 bytes so that any fixed-width value can be peeked.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        ByteReader {
+            inner,
+            buf: vec![0; std::cmp::max(capacity, MIN_CAPACITY)].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed from this reader so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the capacity of the internal buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the bytes that are currently buffered but not yet consumed.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader skips past any buffered bytes.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ByteReader`, returning the underlying reader.
+    ///
+    /// Any buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::AsyncRead + Unpin> ByteReader<R> {
+    /// Ensures that at least `n` unconsumed bytes are buffered, reading more if necessary.
+    ///
+    /// Resolves to `false` if the stream ends first.
+    pub(crate) fn poll_fill_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        n: usize,
+    ) -> Poll<io::Result<bool>> {
+        debug_assert!(n <= self.buf.len());
+        if self.filled - self.pos >= n {
+            return Poll::Ready(Ok(true));
+        }
+        if self.buf.len() - self.pos < n {
+            self.buf.copy_within(self.pos..self.filled, 0);
+            self.filled -= self.pos;
+            self.pos = 0;
+        }
+        while self.filled - self.pos < n {
+            let mut rbuf = io::ReadBuf::new(&mut self.buf[self.filled..]);
+            match ready!(Pin::new(&mut self.inner).poll_read(cx, &mut rbuf)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            }
+            let read = rbuf.filled().len();
+            if read == 0 {
+                return Poll::Ready(Ok(false));
+            }
+            self.filled += read;
+        }
+        Poll::Ready(Ok(true))
+    }
+
+    /// Reads an unsigned 8 bit integer.
+    ///
+    /// See [`AsyncReadBytesExt::read_u8`](trait.AsyncReadBytesExt.html#method.read_u8).
+    #[inline]
+    pub fn read_u8(&mut self) -> ReadTracked<'_, R, u8> {
+        ReadTracked::new(self, "u8", 1, |buf| buf[0])
+    }
+
+    /// Reads a signed 8 bit integer.
+    ///
+    /// See [`AsyncReadBytesExt::read_i8`](trait.AsyncReadBytesExt.html#method.read_i8).
+    #[inline]
+    pub fn read_i8(&mut self) -> ReadTracked<'_, R, i8> {
+        ReadTracked::new(self, "i8", 1, |buf| buf[0] as i8)
+    }
+
+    /// Peeks at the next byte without consuming it.
+    ///
+    /// Resolves to `None` at the end of the stream.
+    #[inline]
+    pub fn peek_u8(&mut self) -> PeekExact<'_, R, u8> {
+        PeekExact::new(self, 1, |buf| buf[0])
+    }
+
+    /// Peeks at the next byte as a signed 8 bit integer without consuming it.
+    ///
+    /// Resolves to `None` at the end of the stream.
+    #[inline]
+    pub fn peek_i8(&mut self) -> PeekExact<'_, R, i8> {
+        PeekExact::new(self, 1, |buf| buf[0] as i8)
+    }
+}
+
+macro_rules! byte_reader_impl {
+    ($(($read:ident, $peek:ident, $what:literal, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        impl<R: io::AsyncRead + Unpin> ByteReader<R> {
+            $(
+                #[doc = concat!("Reads ", $desc, ".")]
+                ///
+                #[doc = concat!("See [`AsyncReadBytesExt::", stringify!($read), "`](trait.AsyncReadBytesExt.html#method.", stringify!($read), ").")]
+                #[inline]
+                pub fn $read<T: ByteOrder>(&mut self) -> ReadTracked<'_, R, $ty> {
+                    let read = ReadTracked::new(self, $what, $bytes, T::$read);
+                    #[cfg(feature = "tracing")]
+                    let read = read.endian(crate::trace::endian::<T>());
+                    read
+                }
+
+                #[doc = concat!("Peeks at ", $desc, " without consuming it.")]
+                ///
+                /// Unlike the peek methods of [`AsyncBufReadBytesExt`], this reads more from the
+                /// underlying reader if too few bytes are buffered, and so only resolves to `None`
+                /// if the stream ends before the value is complete.
+                ///
+                /// [`AsyncBufReadBytesExt`]: trait.AsyncBufReadBytesExt.html
+                #[inline]
+                pub fn $peek<T: ByteOrder>(&mut self) -> PeekExact<'_, R, $ty> {
+                    PeekExact::new(self, $bytes, T::$read)
+                }
+            )*
+        }
+    };
+}
+
+byte_reader_impl! {
+    (read_u16, peek_u16, "u16", u16, 2, "an unsigned 16 bit integer"),
+    (read_i16, peek_i16, "i16", i16, 2, "a signed 16 bit integer"),
+    (read_u24, peek_u24, "u24", u32, 3, "an unsigned 24 bit integer"),
+    (read_i24, peek_i24, "i24", i32, 3, "a signed 24 bit integer"),
+    (read_u32, peek_u32, "u32", u32, 4, "an unsigned 32 bit integer"),
+    (read_i32, peek_i32, "i32", i32, 4, "a signed 32 bit integer"),
+    (read_u48, peek_u48, "u48", u64, 6, "an unsigned 48 bit integer"),
+    (read_i48, peek_i48, "i48", i64, 6, "a signed 48 bit integer"),
+    (read_u64, peek_u64, "u64", u64, 8, "an unsigned 64 bit integer"),
+    (read_i64, peek_i64, "i64", i64, 8, "a signed 64 bit integer"),
+    (read_u128, peek_u128, "u128", u128, 16, "an unsigned 128 bit integer"),
+    (read_i128, peek_i128, "i128", i128, 16, "a signed 128 bit integer"),
+    (read_f32, peek_f32, "f32", f32, 4, "a IEEE754 single-precision floating point number"),
+    (read_f64, peek_f64, "f64", f64, 8, "a IEEE754 double-precision floating point number"),
+}
+
+impl<R: io::AsyncRead + Unpin> io::AsyncRead for ByteReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        // large reads with nothing buffered go straight to the underlying reader
+        if this.pos == this.filled && buf.remaining() >= this.buf.len() {
+            let before = buf.filled().len();
+            ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            this.position += (buf.filled().len() - before) as u64;
+            return Poll::Ready(Ok(()));
+        }
+        let avail = ready!(io::AsyncBufRead::poll_fill_buf(Pin::new(&mut *this), cx))?;
+        let n = std::cmp::min(avail.len(), buf.remaining());
+        buf.put_slice(&avail[..n]);
+        io::AsyncBufRead::consume(Pin::new(this), n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: io::AsyncRead + Unpin> io::AsyncBufRead for ByteReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.pos == this.filled {
+            let mut rbuf = io::ReadBuf::new(&mut this.buf[..]);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut rbuf))?;
+            this.filled = rbuf.filled().len();
+            this.pos = 0;
+        }
+        Poll::Ready(Ok(&this.buf[this.pos..this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = std::cmp::min(amt, this.filled - this.pos);
+        this.pos += amt;
+        this.position += amt as u64;
+    }
+}
+
+/// Future returned by the `peek_` methods of [`ByteReader`](../struct.ByteReader.html).
+#[derive(Debug)]
+pub struct PeekExact<'a, R, V> {
+    src: &'a mut ByteReader<R>,
+    len: usize,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<'a, R, V> PeekExact<'a, R, V> {
+    fn new(src: &'a mut ByteReader<R>, len: usize, decode: fn(&[u8]) -> V) -> Self {
+        PeekExact { src, len, decode }
+    }
+}
+
+impl<R, V> Future for PeekExact<'_, R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Option<V>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if !ready!(this.src.poll_fill_to(cx, this.len))? {
+            return Poll::Ready(Ok(None));
+        }
+        Poll::Ready(Ok(Some((this.decode)(&this.src.buffer()[..this.len]))))
+    }
+}
+
+/// Future returned by the `read_` methods of [`ByteReader`](../struct.ByteReader.html).
+///
+/// Its errors carry a [`DecodeError`](../struct.DecodeError.html).
+#[derive(Debug)]
+pub struct ReadTracked<'a, R, V> {
+    src: &'a mut ByteReader<R>,
+    what: &'static str,
+    #[cfg(feature = "tracing")]
+    endian: Option<&'static str>,
+    len: usize,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<'a, R, V> ReadTracked<'a, R, V> {
+    fn new(
+        src: &'a mut ByteReader<R>,
+        what: &'static str,
+        len: usize,
+        decode: fn(&[u8]) -> V,
+    ) -> Self {
+        ReadTracked {
+            src,
+            what,
+            #[cfg(feature = "tracing")]
+            endian: None,
+            len,
+            decode,
+        }
+    }
+
+    /// Records the byte order the value is read in, for tracing.
+    #[cfg(feature = "tracing")]
+    fn endian(mut self, endian: &'static str) -> Self {
+        self.endian = Some(endian);
+        self
+    }
+}
+
+impl<R, V> Future for ReadTracked<'_, R, V>
+where
+    R: io::AsyncRead + Unpin,
+    V: core::fmt::Debug,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let err = match ready!(this.src.poll_fill_to(cx, this.len)) {
+            Ok(true) => {
+                let v = (this.decode)(&this.src.buffer()[..this.len]);
+                #[cfg(feature = "tracing")]
+                crate::trace::read(
+                    this.what,
+                    this.endian,
+                    &v,
+                    &this.src.buffer()[..this.len],
+                    Some(this.src.position()),
+                );
+                io::AsyncBufRead::consume(Pin::new(&mut *this.src), this.len);
+                return Poll::Ready(Ok(v));
+            }
+            Ok(false) => {
+                io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer")
+            }
+            Err(e) => e,
+        };
+        let available = this.src.buffer().len();
+        let ctx = DecodeError::new(this.what, this.src.position(), available, err);
+        Poll::Ready(Err(ctx.into()))
+    }
+}
@@ -0,0 +1,373 @@
+use crate::*;
+use bytes::{Buf, BytesMut};
+use core::task::ready;
+
+const DEFAULT_WATERMARK: usize = 8 * 1024;
+
+/// A buffered writer with all of this crate's encoding methods built in.
+///
+/// `ByteWriter` owns the underlying writer along with an internal buffer. Encoded values are
+/// appended to the buffer, which is only written out once it grows past its [watermark], or
+/// when the writer is explicitly [flushed]. Writing many small numbers therefore does not result
+/// in one tiny write to the underlying writer per number, without having to remember to stack a
+/// [`BufWriter`]. A writer created with [`unbounded`] has no watermark, and holds on to
+/// everything until it is flushed, which is useful when a whole message should go out in a
+/// single write. The other ways of deciding when to write out are described by [`FlushPolicy`].
+///
+/// Since buffered bytes only reach the underlying writer on flush, you must call [`flush`] (or
+/// [`shutdown`]) before dropping a `ByteWriter`, or the tail of the output is lost.
+///
+/// `ByteWriter` implements [`AsyncWrite`], so the methods of [`AsyncWriteBytesExt`] remain
+/// available for everything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, ByteWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = ByteWriter::new(Vec::new());
+///     wtr.write_u8(1).await.unwrap();
+///     wtr.write_u32::<BigEndian>(267).await.unwrap();
+///     assert!(wtr.get_ref().is_empty());
+///     wtr.flush().await.unwrap();
+///     assert_eq!(wtr.get_ref(), b"\x01\x00\x00\x01\x0b");
+/// }
+/// ```
+///
+/// [watermark]: #method.watermark
+/// [`unbounded`]: #method.unbounded
+/// [`FlushPolicy`]: enum.FlushPolicy.html
+/// [flushed]: #method.flush
+/// [`flush`]: #method.flush
+/// [`shutdown`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWriteExt.html#method.shutdown
+/// [`BufWriter`]: https://docs.rs/tokio/1/tokio/io/struct.BufWriter.html
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`AsyncWriteBytesExt`]: trait.AsyncWriteBytesExt.html
+#[derive(Debug)]
+pub struct ByteWriter<W> {
+    inner: W,
+    buf: BytesMut,
+    policy: FlushPolicy,
+}
+
+/// When a buffering writer pushes the bytes it holds on toward the wire.
+///
+/// This trades latency against the number of writes and flushes that reach the underlying
+/// writer. A [`ByteWriter`] writes its own buffer out to the writer it wraps, while a
+/// [`FramedU32Sink`], which does not buffer, flushes the writer it wraps. In every case, an
+/// explicit flush pushes out everything right away.
+///
+/// [`ByteWriter`]: struct.ByteWriter.html
+/// [`FramedU32Sink`]: struct.FramedU32Sink.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlushPolicy {
+    /// Only on explicit flushes.
+    Manual,
+    /// Whenever at least this many bytes are held back.
+    Watermark(usize),
+    /// At the end of every frame.
+    ///
+    /// For a `ByteWriter`, that is whenever [`end_frame`] is called, which writes out and
+    /// flushes everything that is buffered. For a `FramedU32Sink`, every frame sent is one.
+    ///
+    /// [`end_frame`]: struct.ByteWriter.html#method.end_frame
+    Frame,
+    /// After every value written, which is also flushed.
+    ///
+    /// Bytes that a `ByteWriter` is given through its [`AsyncWrite`] implementation go straight
+    /// to the underlying writer, but it is up to the caller to flush them.
+    ///
+    /// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+    Immediate,
+}
+
+impl<W> ByteWriter<W> {
+    /// Wraps the given writer with a buffer that is written out past the default watermark
+    /// (currently 8 KiB).
+    pub fn new(inner: W) -> Self {
+        Self::with_watermark(DEFAULT_WATERMARK, inner)
+    }
+
+    /// Wraps the given writer with a buffer that is written out whenever it holds at least
+    /// `watermark` bytes.
+    pub fn with_watermark(watermark: usize, inner: W) -> Self {
+        Self::with_policy(FlushPolicy::Watermark(watermark), inner)
+    }
+
+    /// Wraps the given writer with a buffer that is only written out when explicitly flushed.
+    pub fn unbounded(inner: W) -> Self {
+        Self::with_policy(FlushPolicy::Manual, inner)
+    }
+
+    /// Wraps the given writer with a buffer that is written out according to `policy`.
+    pub fn with_policy(policy: FlushPolicy, inner: W) -> Self {
+        let buf = match policy {
+            FlushPolicy::Watermark(watermark) => BytesMut::with_capacity(watermark),
+            _ => BytesMut::new(),
+        };
+        ByteWriter { inner, buf, policy }
+    }
+
+    /// Returns the number of buffered bytes past which the buffer is written out, if any.
+    ///
+    /// This is `None` unless the flush policy is [`FlushPolicy::Watermark`].
+    ///
+    /// [`FlushPolicy::Watermark`]: enum.FlushPolicy.html#variant.Watermark
+    pub fn watermark(&self) -> Option<usize> {
+        match self.policy {
+            FlushPolicy::Watermark(watermark) => Some(watermark),
+            _ => None,
+        }
+    }
+
+    /// Changes the watermark.
+    ///
+    /// The new watermark takes effect with the next write; `None` disables automatic write-out.
+    pub fn set_watermark(&mut self, watermark: Option<usize>) {
+        self.policy = watermark.map_or(FlushPolicy::Manual, FlushPolicy::Watermark);
+    }
+
+    /// Returns when the buffer is written out.
+    pub fn policy(&self) -> FlushPolicy {
+        self.policy
+    }
+
+    /// Changes when the buffer is written out.
+    ///
+    /// The new policy takes effect with the next write.
+    pub fn set_policy(&mut self, policy: FlushPolicy) {
+        self.policy = policy;
+    }
+
+    fn past_watermark(&self, extra: usize) -> bool {
+        match self.policy {
+            FlushPolicy::Watermark(watermark) => self.buf.len() + extra >= watermark,
+            FlushPolicy::Immediate => true,
+            FlushPolicy::Manual | FlushPolicy::Frame => false,
+        }
+    }
+
+    /// Returns the bytes that are buffered but not yet written out.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..]
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer skips ahead of any buffered bytes.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ByteWriter`, returning the underlying writer.
+    ///
+    /// Any buffered bytes are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::AsyncWrite + Unpin> ByteWriter<W> {
+    /// Writes all buffered bytes out to the underlying writer, without flushing it.
+    pub(crate) fn poll_write_out(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.buf.is_empty() {
+            match ready!(Pin::new(&mut self.inner).poll_write(cx, &self.buf[..])) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Poll::Ready(Err(e)),
+                Ok(0) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    )));
+                }
+                Ok(n) => self.buf.advance(n),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Writes out all buffered bytes and flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::flush`].
+    ///
+    /// [`Write::flush`]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
+    pub async fn flush(&mut self) -> io::Result<()> {
+        tokio::io::AsyncWriteExt::flush(self).await
+    }
+
+    /// Marks the end of a frame.
+    ///
+    /// With [`FlushPolicy::Frame`], this writes out all buffered bytes and flushes the
+    /// underlying writer, just like [`flush`]. With any other policy it does nothing, so code
+    /// that marks where its frames end works with whichever policy its caller picked.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::flush`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{BigEndian, ByteWriter, FlushPolicy};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = ByteWriter::with_policy(FlushPolicy::Frame, Vec::new());
+    ///     wtr.write_u16::<BigEndian>(2).await.unwrap();
+    ///     wtr.write_u16::<BigEndian>(7).await.unwrap();
+    ///     assert!(wtr.get_ref().is_empty());
+    ///     wtr.end_frame().await.unwrap();
+    ///     assert_eq!(wtr.get_ref(), b"\x00\x02\x00\x07");
+    /// }
+    /// ```
+    ///
+    /// [`FlushPolicy::Frame`]: enum.FlushPolicy.html#variant.Frame
+    /// [`flush`]: #method.flush
+    /// [`Write::flush`]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
+    pub async fn end_frame(&mut self) -> io::Result<()> {
+        if self.policy == FlushPolicy::Frame {
+            self.flush().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes an unsigned 8 bit integer.
+    ///
+    /// See [`AsyncWriteBytesExt::write_u8`](trait.AsyncWriteBytesExt.html#method.write_u8).
+    #[inline]
+    pub fn write_u8(&mut self, n: u8) -> WriteBuffered<'_, W> {
+        WriteBuffered::new(self, &[n])
+    }
+
+    /// Writes a signed 8 bit integer.
+    ///
+    /// See [`AsyncWriteBytesExt::write_i8`](trait.AsyncWriteBytesExt.html#method.write_i8).
+    #[inline]
+    pub fn write_i8(&mut self, n: i8) -> WriteBuffered<'_, W> {
+        WriteBuffered::new(self, &[n as u8])
+    }
+}
+
+macro_rules! byte_writer_impl {
+    ($(($name:ident, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        impl<W: io::AsyncWrite + Unpin> ByteWriter<W> {
+            $(
+                #[doc = concat!("Writes ", $desc, ".")]
+                ///
+                #[doc = concat!("See [`AsyncWriteBytesExt::", stringify!($name), "`](trait.AsyncWriteBytesExt.html#method.", stringify!($name), ").")]
+                #[inline]
+                pub fn $name<T: ByteOrder>(&mut self, n: $ty) -> WriteBuffered<'_, W> {
+                    let mut buf = [0; $bytes];
+                    T::$name(&mut buf, n);
+                    WriteBuffered::new(self, &buf)
+                }
+            )*
+        }
+    };
+}
+
+byte_writer_impl! {
+    (write_u16, u16, 2, "an unsigned 16 bit integer"),
+    (write_i16, i16, 2, "a signed 16 bit integer"),
+    (write_u24, u32, 3, "an unsigned 24 bit integer"),
+    (write_i24, i32, 3, "a signed 24 bit integer"),
+    (write_u32, u32, 4, "an unsigned 32 bit integer"),
+    (write_i32, i32, 4, "a signed 32 bit integer"),
+    (write_u48, u64, 6, "an unsigned 48 bit integer"),
+    (write_i48, i64, 6, "a signed 48 bit integer"),
+    (write_u64, u64, 8, "an unsigned 64 bit integer"),
+    (write_i64, i64, 8, "a signed 64 bit integer"),
+    (write_u128, u128, 16, "an unsigned 128 bit integer"),
+    (write_i128, i128, 16, "a signed 128 bit integer"),
+    (write_f32, f32, 4, "a IEEE754 single-precision floating point number"),
+    (write_f64, f64, 8, "a IEEE754 double-precision floating point number"),
+}
+
+impl<W: io::AsyncWrite + Unpin> io::AsyncWrite for ByteWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !this.past_watermark(buf.len()) {
+            this.buf.extend_from_slice(buf);
+            return Poll::Ready(Ok(buf.len()));
+        }
+        ready!(this.poll_write_out(cx))?;
+        if this.past_watermark(buf.len()) {
+            // no point in copying large writes into the buffer
+            Pin::new(&mut this.inner).poll_write(cx, buf)
+        } else {
+            this.buf.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_out(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_out(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// Future returned by the `write_` methods of [`ByteWriter`](../struct.ByteWriter.html).
+#[derive(Debug)]
+pub struct WriteBuffered<'a, W> {
+    dst: &'a mut ByteWriter<W>,
+    value: [u8; 16],
+    len: u8,
+    buffered: bool,
+}
+
+impl<'a, W> WriteBuffered<'a, W> {
+    fn new(dst: &'a mut ByteWriter<W>, bytes: &[u8]) -> Self {
+        let mut value = [0; 16];
+        value[..bytes.len()].copy_from_slice(bytes);
+        WriteBuffered {
+            dst,
+            value,
+            len: bytes.len() as u8,
+            buffered: false,
+        }
+    }
+}
+
+impl<W> Future for WriteBuffered<'_, W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if !this.buffered {
+            this.dst
+                .buf
+                .extend_from_slice(&this.value[..this.len as usize]);
+            this.buffered = true;
+        }
+        if this.dst.past_watermark(0) {
+            ready!(this.dst.poll_write_out(cx))?;
+        }
+        if this.dst.policy == FlushPolicy::Immediate {
+            ready!(Pin::new(&mut this.dst.inner).poll_flush(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,129 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::task::ready;
+
+/// The major type of a CBOR data item, from the top three bits of its initial byte
+/// (RFC 8949 §3.1).
+///
+/// What the argument that follows it means depends on the major type, as noted for each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MajorType {
+    /// An unsigned integer; the argument is its value.
+    Unsigned,
+    /// A negative integer; the argument is one less than its absolute value, so that the value
+    /// is `-1 - argument`.
+    Negative,
+    /// A byte string; the argument is its length in bytes.
+    Bytes,
+    /// A UTF-8 text string; the argument is its length in bytes.
+    Text,
+    /// An array; the argument is the number of items in it.
+    Array,
+    /// A map; the argument is the number of key/value pairs in it.
+    Map,
+    /// A tagged item; the argument is the tag number, and the item it applies to follows.
+    Tag,
+    /// A simple value or a float; the argument is the simple value, or the bits of a half,
+    /// single, or double precision float if it took two, four, or eight bytes.
+    Simple,
+}
+
+impl MajorType {
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            0 => MajorType::Unsigned,
+            1 => MajorType::Negative,
+            2 => MajorType::Bytes,
+            3 => MajorType::Text,
+            4 => MajorType::Array,
+            5 => MajorType::Map,
+            6 => MajorType::Tag,
+            _ => MajorType::Simple,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Returns an encoder for the shortest head that holds `argument`.
+pub(crate) fn head_encoder(major: MajorType, argument: u64) -> FixedEncoder {
+    let (info, len) = match argument {
+        0..=23 => (argument as u8, 0),
+        24..=0xff => (24, 1),
+        0x100..=0xffff => (25, 2),
+        0x1_0000..=0xffff_ffff => (26, 4),
+        _ => (27, 8),
+    };
+    FixedEncoder::new(1 + len, |buf| {
+        buf[0] = major.bits() << 5 | info;
+        buf[1..].copy_from_slice(&argument.to_be_bytes()[8 - len..]);
+    })
+}
+
+/// Future returned by
+/// [`read_cbor_head`](../trait.AsyncReadBytesExt.html#method.read_cbor_head).
+#[derive(Debug)]
+pub struct ReadCborHead<R> {
+    src: R,
+    buf: [u8; 9],
+    filled: usize,
+}
+
+impl<R> ReadCborHead<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadCborHead {
+            src,
+            buf: [0; 9],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadCborHead<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<(MajorType, u64)>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled == 0 {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf[..1],
+                &mut this.filled
+            ))?;
+        }
+        let major = MajorType::from_bits(this.buf[0] >> 5);
+        let len = match this.buf[0] & 0x1f {
+            info @ 0..=23 => return Poll::Ready(Ok((major, u64::from(info)))),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            31 => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "indefinite-length cbor items are not supported",
+                )));
+            }
+            _ => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "reserved cbor additional information",
+                )));
+            }
+        };
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..1 + len],
+            &mut this.filled
+        ))?;
+        let mut argument = [0; 8];
+        argument[8 - len..].copy_from_slice(&this.buf[1..1 + len]);
+        Poll::Ready(Ok((major, u64::from_be_bytes(argument))))
+    }
+}
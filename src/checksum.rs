@@ -0,0 +1,391 @@
+use crate::{poll_read_exact, ByteOrder, Primitive};
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use std::vec::Vec;
+use tokio::io;
+
+/// A running checksum over a sequence of bytes.
+///
+/// This is implemented by [`Crc16`], [`Crc32`], [`Crc64`], [`Adler32`], and [`InetChecksum`],
+/// and can be implemented for other algorithms to use them with [`CrcReader`] and
+/// [`CrcWriter`].
+///
+/// [`Crc16`]: struct.Crc16.html
+/// [`Crc32`]: struct.Crc32.html
+/// [`Crc64`]: struct.Crc64.html
+/// [`Adler32`]: struct.Adler32.html
+/// [`InetChecksum`]: struct.InetChecksum.html
+/// [`CrcReader`]: struct.CrcReader.html
+/// [`CrcWriter`]: struct.CrcWriter.html
+pub trait Checksum {
+    /// The type of the computed checksum.
+    type Output: Copy + PartialEq + fmt::Debug;
+
+    /// Feeds `bytes` into the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all the bytes fed in since creation or the last reset.
+    fn value(&self) -> Self::Output;
+
+    /// Resets the checksum to its initial state.
+    fn reset(&mut self);
+}
+
+macro_rules! crc {
+    ($(#[$outer:meta])* $name:ident, $ty:ty, $(($ctor:ident, $alg:ident, $desc:literal)),*) => {
+        $(#[$outer])*
+        #[derive(Clone)]
+        pub struct $name {
+            crc: &'static crc::Crc<$ty>,
+            digest: crc::Digest<'static, $ty>,
+        }
+
+        impl $name {
+            /// Creates a checksum using the given CRC algorithm.
+            pub fn new(crc: &'static crc::Crc<$ty>) -> Self {
+                $name {
+                    crc,
+                    digest: crc.digest(),
+                }
+            }
+
+            $(
+                #[doc = concat!("Creates a checksum using ", $desc, ".")]
+                pub fn $ctor() -> Self {
+                    static CRC: crc::Crc<$ty> = crc::Crc::<$ty>::new(&crc::$alg);
+                    Self::new(&CRC)
+                }
+            )*
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &self.value())
+                    .finish()
+            }
+        }
+
+        impl Checksum for $name {
+            type Output = $ty;
+
+            fn update(&mut self, bytes: &[u8]) {
+                self.digest.update(bytes);
+            }
+
+            fn value(&self) -> $ty {
+                self.digest.clone().finalize()
+            }
+
+            fn reset(&mut self) {
+                self.digest = self.crc.digest();
+            }
+        }
+    };
+}
+
+crc!(
+    /// A 16 bit cyclic redundancy check.
+    Crc16,
+    u16,
+    (xmodem, CRC_16_XMODEM, "CRC-16/XMODEM"),
+    (modbus, CRC_16_MODBUS, "CRC-16/MODBUS"),
+    (ibm_sdlc, CRC_16_IBM_SDLC, "CRC-16/IBM-SDLC (X.25)")
+);
+
+crc!(
+    /// A 32 bit cyclic redundancy check.
+    Crc32,
+    u32,
+    (iso_hdlc, CRC_32_ISO_HDLC, "CRC-32/ISO-HDLC, as used by PNG, gzip, and Ethernet"),
+    (iscsi, CRC_32_ISCSI, "CRC-32/ISCSI (CRC-32C)")
+);
+
+crc!(
+    /// A 64 bit cyclic redundancy check.
+    Crc64,
+    u64,
+    (xz, CRC_64_XZ, "CRC-64/XZ")
+);
+
+/// The Adler-32 checksum, as used by zlib.
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Creates a new Adler-32 checksum.
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        const MOD: u32 = 65521;
+        // the largest number of bytes that can be summed before `b` may overflow a u32
+        const NMAX: usize = 5552;
+        for chunk in bytes.chunks(NMAX) {
+            for &byte in chunk {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+
+    fn value(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// A reader that computes a checksum over every byte read through it.
+///
+/// The checksum covers exactly the bytes that were handed out to the caller, so it can be
+/// checked at a frame boundary against a trailer. Bytes that should not be covered, like the
+/// trailer itself, can be read through [`get_mut`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, Crc32, CrcReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let frame = b"\x00\x00\x01\x0b\xaf\x8d\x37\xd5";
+///     let mut rdr = CrcReader::new(&frame[..], Crc32::iso_hdlc());
+///     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+///     let expected = rdr.get_mut().read_u32::<BigEndian>().await.unwrap();
+///     rdr.verify(expected).unwrap();
+/// }
+/// ```
+///
+/// [`get_mut`]: #method.get_mut
+#[derive(Debug)]
+pub struct CrcReader<R, C> {
+    inner: R,
+    checksum: C,
+}
+
+/// A writer that computes a checksum over every byte written through it.
+///
+/// Only bytes that the underlying writer has accepted are covered.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, Crc32, CrcWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = CrcWriter::new(Vec::new(), Crc32::iso_hdlc());
+///     wtr.write_u32::<BigEndian>(267).await.unwrap();
+///     let crc = wtr.checksum();
+///     wtr.get_mut().write_u32::<BigEndian>(crc).await.unwrap();
+///     assert_eq!(wtr.into_inner(), b"\x00\x00\x01\x0b\xaf\x8d\x37\xd5");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CrcWriter<W, C> {
+    inner: W,
+    checksum: C,
+}
+
+fn verify<T: PartialEq + fmt::Debug>(actual: T, expected: T) -> io::Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "checksum mismatch: expected {:?}, computed {:?}",
+                expected, actual
+            ),
+        ))
+    }
+}
+
+macro_rules! crc_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, C: Checksum> $name<$inner, C> {
+            /// Wraps the given stream, computing `checksum` over the bytes that pass through.
+            pub fn new(inner: $inner, checksum: C) -> Self {
+                $name { inner, checksum }
+            }
+
+            /// Returns the checksum of the bytes seen since creation or the last reset.
+            pub fn checksum(&self) -> C::Output {
+                self.checksum.value()
+            }
+
+            /// Checks that the current checksum equals `expected`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error of kind `InvalidData` if the checksums differ.
+            pub fn verify(&self, expected: C::Output) -> io::Result<()> {
+                verify(self.checksum(), expected)
+            }
+
+            /// Resets the checksum, typically at the start of a new frame.
+            pub fn reset(&mut self) {
+                self.checksum.reset();
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            ///
+            /// Bytes transferred directly through the returned reference are not checksummed.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+        }
+    };
+}
+
+crc_common!(CrcReader, R);
+crc_common!(CrcWriter, W);
+
+impl<R, C> io::AsyncRead for CrcReader<R, C>
+where
+    R: io::AsyncRead + Unpin,
+    C: Checksum + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let res = Pin::new(&mut this.inner).poll_read(cx, buf);
+        this.checksum.update(&buf.filled()[before..]);
+        res
+    }
+}
+
+impl<W, C> io::AsyncWrite for CrcWriter<W, C>
+where
+    W: io::AsyncWrite + Unpin,
+    C: Checksum + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = res {
+            this.checksum.update(&buf[..n]);
+        }
+        res
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Future returned by
+/// [`read_checked_header`](../trait.AsyncReadBytesExt.html#method.read_checked_header).
+pub struct ReadCheckedHeader<R, C, T, F> {
+    src: R,
+    checksum: C,
+    // the covered bytes, followed by the checksum
+    buf: Vec<u8>,
+    len: usize,
+    filled: usize,
+    parse: Option<F>,
+    order: PhantomData<fn() -> T>,
+}
+
+impl<R, C, T, F> ReadCheckedHeader<R, C, T, F>
+where
+    C: Checksum,
+    C::Output: Primitive,
+    T: ByteOrder,
+{
+    pub(crate) fn new(src: R, len: usize, checksum: C, parse: F) -> Self {
+        let checksum_len = C::Output::decoder::<T>().remaining();
+        ReadCheckedHeader {
+            src,
+            checksum,
+            buf: vec![0; len + checksum_len],
+            len,
+            filled: 0,
+            parse: Some(parse),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<R: fmt::Debug, C, T, F> fmt::Debug for ReadCheckedHeader<R, C, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadCheckedHeader")
+            .field("src", &self.src)
+            .field("len", &self.len)
+            .field("filled", &self.filled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, C, T, F, V> Future for ReadCheckedHeader<R, C, T, F>
+where
+    R: io::AsyncRead + Unpin,
+    C: Checksum + Unpin,
+    C::Output: Primitive,
+    T: ByteOrder,
+    F: FnOnce(&[u8]) -> V + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            &mut this.filled
+        ))?;
+        let (header, mut trailer) = this.buf.split_at(this.len);
+        let expected = C::Output::decoder::<T>()
+            .push_bytes(&mut trailer)
+            .expect("the buffer holds the whole checksum");
+        this.checksum.reset();
+        this.checksum.update(header);
+        verify(this.checksum.value(), expected)?;
+        let parse = this.parse.take().expect("polled after completion");
+        Poll::Ready(Ok(parse(header)))
+    }
+}
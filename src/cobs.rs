@@ -0,0 +1,188 @@
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+/// Encodes `data` with Consistent Overhead Byte Stuffing, followed by the `0x00` delimiter.
+fn encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 254 + 2);
+    // index of the code byte of the block being written, and that block's code so far
+    let mut at = 0;
+    let mut code = 1u8;
+    out.push(0);
+    for &b in data {
+        // a full block only gets a successor if there is more data to put in one
+        if code == 0xff {
+            out[at] = code;
+            at = out.len();
+            out.push(0);
+            code = 1;
+        }
+        if b == 0 {
+            out[at] = code;
+            at = out.len();
+            out.push(0);
+            code = 1;
+        } else {
+            out.push(b);
+            code += 1;
+        }
+    }
+    out[at] = code;
+    out.push(0);
+    out
+}
+
+/// Adds decoded bytes to `frame`, unless the frame has already gone bad.
+fn push(frame: &mut Vec<u8>, max_len: usize, error: &mut Option<&'static str>, data: &[u8]) {
+    if error.is_some() {
+        return;
+    }
+    if frame.len() + data.len() > max_len {
+        *error = Some("cobs frame exceeds maximum length");
+        *frame = Vec::new();
+    } else {
+        frame.extend_from_slice(data);
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Future returned by
+/// [`read_cobs_frame`](../trait.AsyncBufReadBytesExt.html#method.read_cobs_frame).
+#[derive(Debug)]
+pub struct ReadCobsFrame<R> {
+    src: R,
+    max_len: usize,
+    frame: Vec<u8>,
+    // data bytes left in the current block, or `None` while waiting for a code byte
+    left: Option<u8>,
+    // whether a code byte has been read, which tells an empty frame from a bare delimiter
+    started: bool,
+    // whether the current block ends in a zero, which is only written if another block follows
+    zero: bool,
+    // the error to return once the rest of a bad frame has been discarded
+    error: Option<&'static str>,
+}
+
+impl<R> ReadCobsFrame<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadCobsFrame {
+            src,
+            max_len,
+            frame: Vec::new(),
+            left: None,
+            started: false,
+            zero: false,
+            error: None,
+        }
+    }
+}
+
+impl<R> Future for ReadCobsFrame<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<Vec<u8>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let buf = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if buf.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "reached the end of the stream before the end of the cobs frame",
+                )));
+            }
+            let mut i = 0;
+            let mut done = false;
+            while i < buf.len() {
+                match this.left {
+                    None => {
+                        let code = buf[i];
+                        i += 1;
+                        if code == 0 {
+                            if this.started {
+                                done = true;
+                                break;
+                            }
+                            // a bare delimiter; there is no frame here to return
+                            continue;
+                        }
+                        if this.zero {
+                            push(&mut this.frame, this.max_len, &mut this.error, &[0]);
+                        }
+                        this.started = true;
+                        this.zero = code != 0xff;
+                        if code > 1 {
+                            this.left = Some(code - 1);
+                        }
+                    }
+                    Some(left) => {
+                        let run = &buf[i..buf.len().min(i + left as usize)];
+                        let n = run.iter().position(|&b| b == 0).unwrap_or(run.len());
+                        push(&mut this.frame, this.max_len, &mut this.error, &run[..n]);
+                        i += n;
+                        if n < run.len() {
+                            // the delimiter came early; this is where the next frame starts
+                            i += 1;
+                            this.error
+                                .get_or_insert("cobs frame ended in the middle of a block");
+                            done = true;
+                            break;
+                        }
+                        let left = left - n as u8;
+                        this.left = if left == 0 { None } else { Some(left) };
+                    }
+                }
+            }
+            src.consume(i);
+
+            if done {
+                this.left = None;
+                this.started = false;
+                this.zero = false;
+                if let Some(msg) = this.error.take() {
+                    this.frame.clear();
+                    return Poll::Ready(Err(invalid(msg)));
+                }
+                return Poll::Ready(Ok(core::mem::take(&mut this.frame)));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`write_cobs_frame`](../trait.AsyncWriteBytesExt.html#method.write_cobs_frame).
+#[derive(Debug)]
+pub struct WriteCobsFrame<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteCobsFrame<W> {
+    pub(crate) fn new(dst: W, data: &[u8]) -> Self {
+        WriteCobsFrame {
+            dst,
+            buf: encode(data),
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteCobsFrame<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
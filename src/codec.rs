@@ -0,0 +1,160 @@
+use crate::*;
+
+/// A type that can be decoded from an [`AsyncRead`].
+///
+/// This is usually derived with `#[derive(AsyncDecode)]` (with the `derive` feature), which
+/// decodes each field in declaration order, or declared along with the struct through
+/// [`byte_layout!`]. See [`AsyncEncode`] for the attributes the derive
+/// macros understand.
+///
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+/// [`AsyncEncode`]: trait.AsyncEncode.html
+/// [`byte_layout!`]: macro.byte_layout.html
+pub trait AsyncDecode: Sized {
+    /// Decodes a value from `src`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`], and the derived
+    /// implementations do not return any others.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    fn read_from<R>(src: &mut R) -> impl Future<Output = io::Result<Self>> + Send
+    where
+        R: io::AsyncRead + Unpin + Send + ?Sized;
+}
+
+/// A type that can be encoded to an [`AsyncWrite`].
+///
+/// This is usually derived with `#[derive(AsyncEncode)]` (with the `derive` feature), which
+/// encodes each field in declaration order. Fields whose type is one of the primitive numbers
+/// are encoded with the matching `write_` method, and fields of any other type through that
+/// type's own `AsyncEncode` or `AsyncDecode` implementation.
+///
+/// Both derive macros understand the same `#[byteorder(...)]` attributes:
+///
+///  - `#[byteorder(endian = "big")]` (or `"little"` or `"native"`) on the struct sets the byte
+///    order of all its fields, and on a field sets the byte order of just that field. The
+///    default is big-endian.
+///  - `#[byteorder(width = 24)]` on a `u32` or `i32` field encodes it as a 24 bit integer, and
+///    `#[byteorder(width = 48)]` does the same for 48 bit integers in `u64` and `i64` fields.
+///  - `#[byteorder(pad = 3)]` on a field inserts that many zero bytes before it; they are
+///    skipped when decoding.
+///  - `#[byteorder(len = "u16")]` on a `Vec` field prefixes its elements with their count,
+///    encoded as the given unsigned integer type. `Vec` fields must have this attribute.
+///
+/// Fixed-size arrays of primitive numbers are encoded element by element, with no prefix.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use tokio_byteorder::{AsyncDecode, AsyncEncode};
+///
+/// #[derive(Debug, PartialEq, AsyncEncode, AsyncDecode)]
+/// #[byteorder(endian = "little")]
+/// struct Header {
+///     magic: [u8; 4],
+///     #[byteorder(width = 24)]
+///     len: u32,
+///     #[byteorder(endian = "big", pad = 1)]
+///     flags: u16,
+///     #[byteorder(len = "u8")]
+///     names: Vec<u16>,
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let header = Header { magic: *b"DEMO", len: 7, flags: 1, names: vec![2, 3] };
+/// let mut buf = Vec::new();
+/// header.write_to(&mut buf).await.unwrap();
+/// assert_eq!(buf, b"DEMO\x07\x00\x00\x00\x00\x01\x02\x02\x00\x03\x00");
+/// assert_eq!(Header::read_from(&mut &buf[..]).await.unwrap(), header);
+/// # });
+/// # }
+/// ```
+///
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+pub trait AsyncEncode {
+    /// Encodes this value to `dst`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`]. The derived implementations
+    /// additionally return an error of kind `InvalidInput` if a collection has more elements
+    /// than its length prefix can count.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    fn write_to<W>(&self, dst: &mut W) -> impl Future<Output = io::Result<()>> + Send
+    where
+        W: io::AsyncWrite + Unpin + Send + ?Sized;
+}
+
+/// Helpers for the code generated by the derive macros and `byte_layout!`. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::coop::{yield_now, Budget};
+    use crate::*;
+    use core::convert::TryInto;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    pub use crate::layout::wire;
+    pub use core::convert::TryFrom;
+    pub use core::result::Result::{Err, Ok};
+    pub use std::vec::Vec;
+    pub use tokio::io::{AsyncRead, AsyncWrite};
+
+    pub async fn skip<R>(src: &mut R, n: usize) -> io::Result<()>
+    where
+        R: io::AsyncRead + Unpin + ?Sized,
+    {
+        let mut buf = [0; 16];
+        let mut left = n;
+        let mut budget = Budget::new();
+        while left > 0 {
+            let chunk = core::cmp::min(left, buf.len());
+            src.read_exact(&mut buf[..chunk]).await?;
+            left -= chunk;
+            if left > 0 && budget.charge(chunk) {
+                yield_now().await;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn pad<W>(dst: &mut W, n: usize) -> io::Result<()>
+    where
+        W: io::AsyncWrite + Unpin + ?Sized,
+    {
+        let zeros = [0; 16];
+        let mut left = n;
+        let mut budget = Budget::new();
+        while left > 0 {
+            let chunk = core::cmp::min(left, zeros.len());
+            dst.write_all(&zeros[..chunk]).await?;
+            left -= chunk;
+            if left > 0 && budget.charge(chunk) {
+                yield_now().await;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn len<T: TryFrom<usize>>(len: usize) -> io::Result<T> {
+        T::try_from(len).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "collection is too long for its length prefix",
+            )
+        })
+    }
+
+    pub fn from_len<T: TryInto<usize>>(len: T) -> io::Result<usize> {
+        len.try_into().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "length prefix does not fit in a usize",
+            )
+        })
+    }
+}
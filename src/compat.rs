@@ -0,0 +1,254 @@
+//! Plumbing shared by the modules that provide extension traits for other I/O trait families.
+//!
+//! Every decoder and encoder future in this crate is written once, against tokio 1.x's I/O
+//! traits. Another I/O trait family (a "backend") plugs in by naming itself with a marker type
+//! `B` and implementing [`AsyncByteSource<B>`] and [`AsyncByteSink<B>`] for its streams;
+//! [`Compat`] then turns any such stream into a tokio stream, and [`compat_ext!`] defines
+//! extension traits whose methods return the shared futures wrapped around it. Nothing about
+//! decoding or encoding is ever implemented per backend.
+
+use crate::*;
+
+/// The read half of a backend: pulls bytes from a stream into a [`io::ReadBuf`].
+pub trait AsyncByteSource<B> {
+    /// Attempts to read into `buf`, like [`io::AsyncRead::poll_read`].
+    fn poll_read_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>>;
+}
+
+/// The write half of a backend: pushes bytes into a stream.
+pub trait AsyncByteSink<B> {
+    /// Attempts to write from `buf`, like [`io::AsyncWrite::poll_write`].
+    fn poll_write_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempts to flush the stream, like [`io::AsyncWrite::poll_flush`].
+    fn poll_flush_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempts to shut the stream down, like [`io::AsyncWrite::poll_shutdown`].
+    fn poll_close_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+/// Adapts a stream from backend `B` to tokio's I/O traits.
+///
+/// This is what lets the futures returned by the backend modules' traits be shared with the
+/// rest of the crate.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Compat<S, B>(S, PhantomData<fn() -> B>);
+
+impl<S, B> Compat<S, B> {
+    pub(crate) fn new(stream: S) -> Self {
+        Compat(stream, PhantomData)
+    }
+}
+
+impl<S, B> io::AsyncRead for Compat<S, B>
+where
+    S: AsyncByteSource<B> + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_read_bytes(cx, buf)
+    }
+}
+
+impl<S, B> io::AsyncWrite for Compat<S, B>
+where
+    S: AsyncByteSink<B> + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write_bytes(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush_bytes(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_close_bytes(cx)
+    }
+}
+
+macro_rules! compat_read {
+    ($backend:ty; $(($name:ident, $fut:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Reads ", $desc, " from the underlying reader.")]
+            ///
+            #[doc = concat!("See [`AsyncReadBytesExt::", stringify!($name), "`](../trait.AsyncReadBytesExt.html#method.", stringify!($name), ").")]
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self) -> future::$fut<'_, Self, T>
+            where
+                Self: Unpin,
+            {
+                $fut::new(Compat::new(self))
+            }
+        )*
+    };
+}
+
+macro_rules! compat_write {
+    ($backend:ty; $(($name:ident, $ty:ty, $fut:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Writes ", $desc, " to the underlying writer.")]
+            ///
+            #[doc = concat!("See [`AsyncWriteBytesExt::", stringify!($name), "`](../trait.AsyncWriteBytesExt.html#method.", stringify!($name), ").")]
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self, n: $ty) -> future::$fut<'_, Self>
+            where
+                Self: Unpin,
+            {
+                $fut::new::<T>(Compat::new(self), n)
+            }
+        )*
+    };
+}
+
+macro_rules! compat_futures {
+    ($backend:ident; read { $($read:ident),* $(,)? } write { $($write:ident),* $(,)? }) => {
+        $(
+            #[doc = concat!("The [`", stringify!($read), "`](../../future/struct.", stringify!($read), ".html) future for this module's streams.")]
+            pub type $read<'a, S, T> = crate::future::$read<Compat<&'a mut S, super::$backend>, T>;
+        )*
+        $(
+            #[doc = concat!("The [`", stringify!($write), "`](../../future/struct.", stringify!($write), ".html) future for this module's streams.")]
+            pub type $write<'a, S> = crate::future::$write<Compat<&'a mut S, super::$backend>>;
+        )*
+    };
+}
+
+/// Defines `AsyncReadBytesExt` and `AsyncWriteBytesExt` for the given read and write traits of
+/// `$backend`, along with a `future` module naming the futures they return.
+macro_rules! compat_ext {
+    ($backend:ident, $read:path, $write:path, $read_name:literal, $write_name:literal, $docs:literal) => {
+        /// The futures returned by this module's traits.
+        ///
+        /// These are the crate's [own futures](../../future/index.html) wrapped around an
+        /// adapter for this module's streams, and have the same `Unpin`, `Send` and `Debug`
+        /// properties as those.
+        pub mod future {
+            use crate::compat::Compat;
+
+            compat_futures! {
+                $backend;
+                read { ReadU16, ReadI16, ReadU24, ReadI24, ReadU32, ReadI32, ReadU48, ReadI48,
+                       ReadU64, ReadI64, ReadU128, ReadI128, ReadF32, ReadF64 }
+                write { WriteU8, WriteI8, WriteU16, WriteI16, WriteU24, WriteI24, WriteU32,
+                        WriteI32, WriteU48, WriteI48, WriteU64, WriteI64, WriteU128, WriteI128,
+                        WriteF32, WriteF64 }
+            }
+
+            /// The [`ReadU8`](../../future/struct.ReadU8.html) future for this module's streams.
+            pub type ReadU8<'a, S> = crate::future::ReadU8<Compat<&'a mut S, super::$backend>>;
+            /// The [`ReadI8`](../../future/struct.ReadI8.html) future for this module's streams.
+            pub type ReadI8<'a, S> = crate::future::ReadI8<Compat<&'a mut S, super::$backend>>;
+        }
+
+        #[doc = concat!("Extends [`", $read_name, "`](", $docs, "/trait.AsyncRead.html) with methods for reading numbers.")]
+        pub trait AsyncReadBytesExt: $read {
+            /// Reads an unsigned 8 bit integer from the underlying reader.
+            ///
+            /// See [`AsyncReadBytesExt::read_u8`](../trait.AsyncReadBytesExt.html#method.read_u8).
+            #[inline]
+            fn read_u8(&mut self) -> future::ReadU8<'_, Self>
+            where
+                Self: Unpin,
+            {
+                ReadU8::new(Compat::new(self))
+            }
+
+            /// Reads a signed 8 bit integer from the underlying reader.
+            ///
+            /// See [`AsyncReadBytesExt::read_i8`](../trait.AsyncReadBytesExt.html#method.read_i8).
+            #[inline]
+            fn read_i8(&mut self) -> future::ReadI8<'_, Self>
+            where
+                Self: Unpin,
+            {
+                ReadI8::new(Compat::new(self))
+            }
+
+            compat_read! {
+                $backend;
+                (read_u16, ReadU16, "an unsigned 16 bit integer"),
+                (read_i16, ReadI16, "a signed 16 bit integer"),
+                (read_u24, ReadU24, "an unsigned 24 bit integer"),
+                (read_i24, ReadI24, "a signed 24 bit integer"),
+                (read_u32, ReadU32, "an unsigned 32 bit integer"),
+                (read_i32, ReadI32, "a signed 32 bit integer"),
+                (read_u48, ReadU48, "an unsigned 48 bit integer"),
+                (read_i48, ReadI48, "a signed 48 bit integer"),
+                (read_u64, ReadU64, "an unsigned 64 bit integer"),
+                (read_i64, ReadI64, "a signed 64 bit integer"),
+                (read_u128, ReadU128, "an unsigned 128 bit integer"),
+                (read_i128, ReadI128, "a signed 128 bit integer"),
+                (read_f32, ReadF32, "a IEEE754 single-precision floating point number"),
+                (read_f64, ReadF64, "a IEEE754 double-precision floating point number"),
+            }
+        }
+
+        #[doc = concat!("All types that implement `", $read_name, "` get methods defined in `AsyncReadBytesExt`")]
+        /// for free.
+        impl<R: $read + ?Sized> AsyncReadBytesExt for R {}
+
+        #[doc = concat!("Extends [`", $write_name, "`](", $docs, "/trait.AsyncWrite.html) with methods for writing numbers.")]
+        pub trait AsyncWriteBytesExt: $write {
+            /// Writes an unsigned 8 bit integer to the underlying writer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_u8`](../trait.AsyncWriteBytesExt.html#method.write_u8).
+            #[inline]
+            fn write_u8(&mut self, n: u8) -> future::WriteU8<'_, Self>
+            where
+                Self: Unpin,
+            {
+                WriteU8::new(Compat::new(self), n)
+            }
+
+            /// Writes a signed 8 bit integer to the underlying writer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_i8`](../trait.AsyncWriteBytesExt.html#method.write_i8).
+            #[inline]
+            fn write_i8(&mut self, n: i8) -> future::WriteI8<'_, Self>
+            where
+                Self: Unpin,
+            {
+                WriteI8::new(Compat::new(self), n)
+            }
+
+            compat_write! {
+                $backend;
+                (write_u16, u16, WriteU16, "an unsigned 16 bit integer"),
+                (write_i16, i16, WriteI16, "a signed 16 bit integer"),
+                (write_u24, u32, WriteU24, "an unsigned 24 bit integer"),
+                (write_i24, i32, WriteI24, "a signed 24 bit integer"),
+                (write_u32, u32, WriteU32, "an unsigned 32 bit integer"),
+                (write_i32, i32, WriteI32, "a signed 32 bit integer"),
+                (write_u48, u64, WriteU48, "an unsigned 48 bit integer"),
+                (write_i48, i64, WriteI48, "a signed 48 bit integer"),
+                (write_u64, u64, WriteU64, "an unsigned 64 bit integer"),
+                (write_i64, i64, WriteI64, "a signed 64 bit integer"),
+                (write_u128, u128, WriteU128, "an unsigned 128 bit integer"),
+                (write_i128, i128, WriteI128, "a signed 128 bit integer"),
+                (write_f32, f32, WriteF32, "a IEEE754 single-precision floating point number"),
+                (write_f64, f64, WriteF64, "a IEEE754 double-precision floating point number"),
+            }
+        }
+
+        #[doc = concat!("All types that implement `", $write_name, "` get methods defined in `AsyncWriteBytesExt`")]
+        /// for free.
+        impl<W: $write + ?Sized> AsyncWriteBytesExt for W {}
+    };
+}
@@ -0,0 +1,64 @@
+//! Cooperative yielding for operations that move a lot of bytes.
+//!
+//! Data that is already buffered, whether in a `BufReader`, an in-memory slice, or a `Vec`
+//! being written to, never makes the stream return `Pending`. A large read or write from such a
+//! stream would therefore run to completion in a single poll, and starve every other task on
+//! the same runtime thread for as long as it takes. The bulk operations in this crate instead
+//! give up their turn every [`YIELD_AFTER`] bytes, after waking themselves so that they are
+//! polled again right away.
+
+use crate::*;
+
+/// How many bytes a bulk operation may move before it yields.
+pub(crate) const YIELD_AFTER: usize = 64 * 1024;
+
+/// The number of bytes an operation may still move before it yields.
+#[derive(Debug)]
+pub(crate) struct Budget {
+    left: usize,
+}
+
+impl Budget {
+    pub(crate) fn new() -> Self {
+        Budget { left: YIELD_AFTER }
+    }
+
+    /// Charges `n` bytes to the budget, and returns true if it is used up.
+    ///
+    /// The budget is refilled once it is used up, so that the operation can continue after it
+    /// has yielded.
+    pub(crate) fn charge(&mut self, n: usize) -> bool {
+        if n < self.left {
+            self.left -= n;
+            return false;
+        }
+        self.left = YIELD_AFTER;
+        true
+    }
+
+    /// Charges `n` bytes to the budget, and yields if it is used up.
+    ///
+    /// The caller must have recorded its progress before calling this, since it returns
+    /// `Pending` to yield.
+    pub(crate) fn poll_charge(&mut self, cx: &mut Context<'_>, n: usize) -> Poll<()> {
+        if self.charge(n) {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(())
+    }
+}
+
+/// Yields once to the executor.
+pub(crate) async fn yield_now() {
+    let mut yielded = false;
+    core::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
@@ -0,0 +1,168 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use tokio::io;
+
+/// A reader that keeps track of how many bytes have been read through it.
+///
+/// This is useful for stream parsers that cannot seek, but still want to report the offset at
+/// which something went wrong, or check that a chunk ended exactly where its header said it
+/// would.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, CountingReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = CountingReader::new(&[0x00, 0x01, 0x0b, 0x02][..]);
+///     rdr.read_u24::<BigEndian>().await.unwrap();
+///     assert_eq!(rdr.position(), 3);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    pos: u64,
+}
+
+/// A writer that keeps track of how many bytes have been written through it.
+///
+/// Only bytes that the underlying writer has accepted are counted.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, CountingWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = CountingWriter::new(Vec::new());
+///     wtr.write_u48::<BigEndian>(541).await.unwrap();
+///     assert_eq!(wtr.position(), 6);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    pos: u64,
+}
+
+macro_rules! counting_common {
+    ($name:ident, $inner:ident, $verb:literal) => {
+        impl<$inner> $name<$inner> {
+            /// Wraps the given stream, starting the count at zero.
+            pub fn new(inner: $inner) -> Self {
+                Self::with_position(inner, 0)
+            }
+
+            /// Wraps the given stream, starting the count at `pos`.
+            ///
+            /// This is handy when some of the stream has already been consumed before the
+            /// wrapper was put in place.
+            pub fn with_position(inner: $inner, pos: u64) -> Self {
+                $name { inner, pos }
+            }
+
+            #[doc = concat!("Returns the number of bytes ", $verb, " so far.")]
+            pub fn position(&self) -> u64 {
+                self.pos
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            ///
+            /// Bytes transferred directly through the returned reference are not counted.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+        }
+    };
+}
+
+counting_common!(CountingReader, R, "read");
+counting_common!(CountingWriter, W, "written");
+
+impl<R> io::AsyncRead for CountingReader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let res = Pin::new(&mut this.inner).poll_read(cx, buf);
+        this.pos += (buf.filled().len() - before) as u64;
+        res
+    }
+}
+
+impl<R> io::AsyncBufRead for CountingReader<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(&mut self.get_mut().inner).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.pos += amt as u64;
+        Pin::new(&mut this.inner).consume(amt)
+    }
+}
+
+impl<W> io::AsyncWrite for CountingWriter<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = res {
+            this.pos += n as u64;
+        }
+        res
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(n)) = res {
+            this.pos += n as u64;
+        }
+        res
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,156 @@
+use crate::sans_io::FixedDecoder;
+use crate::*;
+
+/// A resumable decoder for a single fixed-width value.
+///
+/// The futures returned by [`AsyncReadBytesExt`] keep the bytes they have read so far inside
+/// themselves, so dropping one halfway through a value (for example because another branch of
+/// a `tokio::select!` completed first) silently discards those bytes and desynchronizes the
+/// stream. A `Decoder` instead keeps its progress in itself: the future returned by
+/// [`read`](#method.read) only borrows it, so it can be dropped and recreated any number of times
+/// without losing data. Once a value is complete, the decoder is reset and can be reused for the
+/// next one.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tokio::io::AsyncWriteExt;
+/// use tokio_byteorder::{BigEndian, Decoder};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (mut tx, mut rx) = tokio::io::duplex(64);
+///     let mut decoder = Decoder::u32::<BigEndian>();
+///
+///     tx.write_all(&[0x00, 0x00]).await.unwrap();
+///     tokio::select! {
+///         _ = decoder.read(&mut rx) => unreachable!(),
+///         _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+///     }
+///     assert_eq!(decoder.buffered(), [0x00, 0x00]);
+///
+///     tx.write_all(&[0x01, 0x0b]).await.unwrap();
+///     assert_eq!(267, decoder.read(&mut rx).await.unwrap());
+///     assert!(decoder.buffered().is_empty());
+/// }
+/// ```
+///
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+#[derive(Debug, Clone)]
+pub struct Decoder<V> {
+    inner: FixedDecoder<V>,
+}
+
+impl<V> Decoder<V> {
+    /// Returns the bytes of the current value that have been read so far.
+    pub fn buffered(&self) -> &[u8] {
+        self.inner.buffered()
+    }
+
+    /// Discards any partially read value.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Continues decoding the current value from `src`.
+    ///
+    /// Returns `Poll::Ready` with the value once all of its bytes have been read, after which
+    /// the decoder starts over with the next value.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. The bytes read before the
+    /// error are kept, and are available through [`buffered`](#method.buffered).
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    pub fn poll_decode<R>(&mut self, src: &mut R, cx: &mut Context<'_>) -> Poll<io::Result<V>>
+    where
+        R: io::AsyncRead + Unpin + ?Sized,
+    {
+        poll_decode(Pin::new(src), cx, &mut self.inner)
+    }
+
+    /// Continues decoding the current value from `src`.
+    ///
+    /// The returned future is cancel safe: dropping it before it completes does not lose any
+    /// bytes, and the next call picks up where it left off.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`poll_decode`](#method.poll_decode).
+    pub fn read<'a, R>(&'a mut self, src: &'a mut R) -> Decode<'a, R, V>
+    where
+        R: io::AsyncRead + Unpin + ?Sized,
+    {
+        Decode { decoder: self, src }
+    }
+}
+
+impl<V> From<FixedDecoder<V>> for Decoder<V> {
+    fn from(inner: FixedDecoder<V>) -> Self {
+        Decoder { inner }
+    }
+}
+
+impl Decoder<u8> {
+    /// Creates a decoder for unsigned 8 bit integers.
+    pub fn u8() -> Self {
+        FixedDecoder::u8().into()
+    }
+}
+
+impl Decoder<i8> {
+    /// Creates a decoder for signed 8 bit integers.
+    pub fn i8() -> Self {
+        FixedDecoder::i8().into()
+    }
+}
+
+macro_rules! decoder_impl {
+    ($(($name:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        $(
+            impl Decoder<$ty> {
+                #[doc = concat!("Creates a decoder for ", $desc, "s in the byte order `T`.")]
+                pub fn $name<T: ByteOrder>() -> Self {
+                    FixedDecoder::$name::<T>().into()
+                }
+            }
+        )*
+    };
+}
+
+decoder_impl! {
+    (u16, u16, "unsigned 16 bit integer"),
+    (i16, i16, "signed 16 bit integer"),
+    (u24, u32, "unsigned 24 bit integer"),
+    (i24, i32, "signed 24 bit integer"),
+    (u32, u32, "unsigned 32 bit integer"),
+    (i32, i32, "signed 32 bit integer"),
+    (u48, u64, "unsigned 48 bit integer"),
+    (i48, i64, "signed 48 bit integer"),
+    (u64, u64, "unsigned 64 bit integer"),
+    (i64, i64, "signed 64 bit integer"),
+    (u128, u128, "unsigned 128 bit integer"),
+    (i128, i128, "signed 128 bit integer"),
+    (f32, f32, "IEEE754 single-precision floating point number"),
+    (f64, f64, "IEEE754 double-precision floating point number"),
+}
+
+/// Future returned by [`Decoder::read`](../struct.Decoder.html#method.read).
+#[derive(Debug)]
+pub struct Decode<'a, R: ?Sized, V> {
+    decoder: &'a mut Decoder<V>,
+    src: &'a mut R,
+}
+
+impl<R, V> Future for Decode<'_, R, V>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.decoder.poll_decode(this.src, cx)
+    }
+}
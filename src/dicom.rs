@@ -0,0 +1,239 @@
+use crate::endian::RuntimeCodec;
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+/// The length that marks a sequence or item as running until its delimitation item.
+const UNDEFINED_LENGTH: u32 = 0xffff_ffff;
+
+/// Whether the value representation of each DICOM data element is written in its header.
+///
+/// Which one a data set uses, along with its byte order, is set by its transfer syntax:
+/// Implicit VR Little Endian is the only implicit one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DicomVrMode {
+    /// Each header holds a two-letter VR, and the width of the length that follows depends on
+    /// it.
+    Explicit,
+    /// Headers hold no VR, which must be looked up in the data dictionary, and every length is
+    /// 32 bits.
+    Implicit,
+}
+
+/// The header of a DICOM data element (PS3.5 §7.1).
+///
+/// Read with [`read_dicom_element_header`], and written with [`write_dicom_element_header`].
+/// The value, which is `length` bytes long, follows it.
+///
+/// [`read_dicom_element_header`]: trait.AsyncReadBytesExt.html#method.read_dicom_element_header
+/// [`write_dicom_element_header`]: trait.AsyncWriteBytesExt.html#method.write_dicom_element_header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DicomElementHeader {
+    /// The group number of the tag.
+    pub group: u16,
+    /// The element number of the tag.
+    pub element: u16,
+    /// The value representation, such as `*b"PN"`, or `None` if the header holds none.
+    ///
+    /// This is always `None` with implicit VRs, and for the item and delimitation tags in
+    /// group `0xfffe`, which never carry a VR.
+    pub vr: Option<[u8; 2]>,
+    /// The length of the value in bytes, or `None` if it is undefined, and the value runs until
+    /// a delimitation item.
+    pub length: Option<u32>,
+}
+
+impl DicomElementHeader {
+    /// Returns whether this is an item, item delimitation, or sequence delimitation tag, in
+    /// group `0xfffe`.
+    pub fn is_item_tag(&self) -> bool {
+        self.group == 0xfffe
+    }
+}
+
+/// Returns whether an explicit VR is followed by two reserved bytes and a 32 bit length,
+/// rather than a 16 bit one.
+///
+/// Every VR added to the standard since the short ones has taken the long form, so VRs that
+/// are not known to be short are taken to be long.
+fn has_long_length(vr: [u8; 2]) -> bool {
+    !matches!(
+        &vr,
+        b"AE"
+            | b"AS"
+            | b"AT"
+            | b"CS"
+            | b"DA"
+            | b"DS"
+            | b"DT"
+            | b"FD"
+            | b"FL"
+            | b"IS"
+            | b"LO"
+            | b"LT"
+            | b"PN"
+            | b"SH"
+            | b"SL"
+            | b"SS"
+            | b"ST"
+            | b"TM"
+            | b"UI"
+            | b"UL"
+            | b"US"
+    )
+}
+
+fn length(n: u32) -> Option<u32> {
+    if n == UNDEFINED_LENGTH {
+        None
+    } else {
+        Some(n)
+    }
+}
+
+/// Encodes a header, or returns why it cannot be encoded.
+fn encode_header(
+    header: &DicomElementHeader,
+    endian: RuntimeEndian,
+    mode: DicomVrMode,
+) -> Result<Vec<u8>, &'static str> {
+    let length = header.length.unwrap_or(UNDEFINED_LENGTH);
+    let vr = match mode {
+        DicomVrMode::Explicit if !header.is_item_tag() => Some(
+            header
+                .vr
+                .ok_or("dicom element header has no vr in explicit vr mode")?,
+        ),
+        _ => None,
+    };
+    let long = vr.map_or(true, has_long_length);
+    if !long && length > u32::from(u16::MAX) {
+        return Err("dicom element length does not fit in the 16 bit length of its vr");
+    }
+    let mut buf = vec![0; if vr.is_some() && long { 12 } else { 8 }];
+    u16::write(endian, &mut buf[..2], header.group);
+    u16::write(endian, &mut buf[2..4], header.element);
+    match vr {
+        None => u32::write(endian, &mut buf[4..8], length),
+        Some(vr) if long => {
+            buf[4..6].copy_from_slice(&vr);
+            u32::write(endian, &mut buf[8..12], length);
+        }
+        Some(vr) => {
+            buf[4..6].copy_from_slice(&vr);
+            u16::write(endian, &mut buf[6..8], length as u16);
+        }
+    }
+    Ok(buf)
+}
+
+/// Future returned by
+/// [`read_dicom_element_header`](../trait.AsyncReadBytesExt.html#method.read_dicom_element_header).
+#[derive(Debug)]
+pub struct ReadDicomElementHeader<R> {
+    src: R,
+    endian: RuntimeEndian,
+    mode: DicomVrMode,
+    buf: [u8; 12],
+    filled: usize,
+}
+
+impl<R> ReadDicomElementHeader<R> {
+    pub(crate) fn new(src: R, endian: RuntimeEndian, mode: DicomVrMode) -> Self {
+        ReadDicomElementHeader {
+            src,
+            endian,
+            mode,
+            buf: [0; 12],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadDicomElementHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<DicomElementHeader>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let endian = this.endian;
+        if this.filled < 8 {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf[..8],
+                &mut this.filled
+            ))?;
+        }
+        let mut header = DicomElementHeader {
+            group: u16::read(endian, &this.buf[..2]),
+            element: u16::read(endian, &this.buf[2..4]),
+            vr: None,
+            length: None,
+        };
+        if this.mode == DicomVrMode::Implicit || header.is_item_tag() {
+            header.length = length(u32::read(endian, &this.buf[4..8]));
+            return Poll::Ready(Ok(header));
+        }
+        let vr = [this.buf[4], this.buf[5]];
+        if !vr.iter().all(u8::is_ascii_uppercase) {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "dicom value representation is not two uppercase letters",
+            )));
+        }
+        header.vr = Some(vr);
+        if has_long_length(vr) {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf,
+                &mut this.filled
+            ))?;
+            header.length = length(u32::read(endian, &this.buf[8..12]));
+        } else {
+            header.length = Some(u32::from(u16::read(endian, &this.buf[6..8])));
+        }
+        Poll::Ready(Ok(header))
+    }
+}
+
+/// Future returned by
+/// [`write_dicom_element_header`](../trait.AsyncWriteBytesExt.html#method.write_dicom_element_header).
+#[derive(Debug)]
+pub struct WriteDicomElementHeader<W> {
+    dst: W,
+    // the encoded header, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteDicomElementHeader<W> {
+    pub(crate) fn new(
+        dst: W,
+        header: &DicomElementHeader,
+        endian: RuntimeEndian,
+        mode: DicomVrMode,
+    ) -> Self {
+        WriteDicomElementHeader {
+            dst,
+            buf: encode_header(header, endian, mode),
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteDicomElementHeader<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use tokio::io;
+
+/// The longest label a DNS name may contain (RFC 1035 §2.3.4).
+const MAX_LABEL: usize = 63;
+
+/// Future returned by
+/// [`read_dns_name`](../trait.AsyncReadBytesExt.html#method.read_dns_name).
+#[derive(Debug)]
+pub struct ReadDnsName<R> {
+    src: R,
+    max_len: usize,
+    // number of wire bytes consumed so far, including length octets
+    consumed: usize,
+    name: Vec<u8>,
+    label: [u8; MAX_LABEL],
+    // `None` while waiting for the next length octet
+    label_len: Option<u8>,
+    filled: usize,
+}
+
+impl<R> ReadDnsName<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadDnsName {
+            src,
+            max_len,
+            consumed: 0,
+            name: Vec::new(),
+            label: [0; MAX_LABEL],
+            label_len: None,
+            filled: 0,
+        }
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<R> Future for ReadDnsName<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<String>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match this.label_len {
+                None => {
+                    let mut b = [0; 1];
+                    ready!(crate::poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+
+                    let len = b[0];
+                    if len & 0xc0 == 0xc0 {
+                        return Poll::Ready(Err(invalid("dns name compression is not supported")));
+                    } else if len & 0xc0 != 0 {
+                        return Poll::Ready(Err(invalid("unknown dns label type")));
+                    }
+
+                    this.consumed += 1 + len as usize;
+                    if this.consumed > this.max_len {
+                        return Poll::Ready(Err(invalid("dns name exceeds maximum length")));
+                    }
+
+                    if len == 0 {
+                        let name = std::mem::take(&mut this.name);
+                        let mut name = String::from_utf8(name)
+                            .map_err(|_| invalid("dns name is not valid utf-8"))?;
+                        if name.is_empty() {
+                            name.push('.');
+                        }
+                        return Poll::Ready(Ok(name));
+                    }
+
+                    this.label_len = Some(len);
+                    this.filled = 0;
+                }
+                Some(len) => {
+                    ready!(crate::poll_read_exact(
+                        &mut this.src,
+                        cx,
+                        &mut this.label[..len as usize],
+                        &mut this.filled,
+                    ))?;
+
+                    if !this.name.is_empty() {
+                        this.name.push(b'.');
+                    }
+                    this.name.extend_from_slice(&this.label[..len as usize]);
+                    this.label_len = None;
+                }
+            }
+        }
+    }
+}
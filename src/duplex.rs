@@ -0,0 +1,155 @@
+use crate::*;
+
+/// A reader and a writer, combined into a single handle that can be both read from and written
+/// to.
+///
+/// `Duplex` implements [`AsyncRead`] through its reader and [`AsyncWrite`] through its writer,
+/// so the methods of [`AsyncReadBytesExt`] and [`AsyncWriteBytesExt`] can all be called on it.
+/// This suits request/response handlers, which want to pass around one value that they decode
+/// requests from and encode responses to. The two sides can be anything, such as the halves of
+/// a [`TcpStream::into_split`] or standard input and output, and [`split`] creates one from a
+/// single stream.
+///
+/// [`halves_mut`] borrows the two sides separately, so that a read and a write can be in flight
+/// at the same time.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, Duplex};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (client, server) = tokio::io::duplex(64);
+///     let mut server = Duplex::split(server);
+///     let mut client = Duplex::split(client);
+///
+///     client.write_u32::<BigEndian>(20).await.unwrap();
+///     let n = server.read_u32::<BigEndian>().await.unwrap();
+///     server.write_u32::<BigEndian>(n + 1).await.unwrap();
+///     assert_eq!(client.read_u32::<BigEndian>().await.unwrap(), 21);
+/// }
+/// ```
+///
+/// [`split`]: #method.split
+/// [`halves_mut`]: #method.halves_mut
+/// [`TcpStream::into_split`]: https://docs.rs/tokio/1/tokio/net/struct.TcpStream.html#method.into_split
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`AsyncWriteBytesExt`]: trait.AsyncWriteBytesExt.html
+#[derive(Debug)]
+pub struct Duplex<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R, W> Duplex<R, W> {
+    /// Combines the given reader and writer.
+    pub fn new(reader: R, writer: W) -> Self {
+        Duplex { reader, writer }
+    }
+
+    /// Gets a reference to the reader.
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Gets mutable references to the reader and the writer at the same time.
+    pub fn halves_mut(&mut self) -> (&mut R, &mut W) {
+        (&mut self.reader, &mut self.writer)
+    }
+
+    /// Unwraps this `Duplex`, returning the reader and the writer.
+    pub fn into_parts(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<S> Duplex<io::ReadHalf<S>, io::WriteHalf<S>>
+where
+    S: io::AsyncRead + io::AsyncWrite,
+{
+    /// Splits `stream` with [`tokio::io::split`], and combines the halves.
+    ///
+    /// [`tokio::io::split`]: https://docs.rs/tokio/1/tokio/io/fn.split.html
+    pub fn split(stream: S) -> Self {
+        let (reader, writer) = io::split(stream);
+        Duplex { reader, writer }
+    }
+
+    /// Puts the halves back together, returning the original stream.
+    pub fn unsplit(self) -> S
+    where
+        S: Unpin,
+    {
+        self.reader.unsplit(self.writer)
+    }
+}
+
+impl<R, W> io::AsyncRead for Duplex<R, W>
+where
+    R: io::AsyncRead + Unpin,
+    W: Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().reader).poll_read(cx, buf)
+    }
+}
+
+impl<R, W> io::AsyncBufRead for Duplex<R, W>
+where
+    R: io::AsyncBufRead + Unpin,
+    W: Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(&mut self.get_mut().reader).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut self.get_mut().reader).consume(amt)
+    }
+}
+
+impl<R, W> io::AsyncWrite for Duplex<R, W>
+where
+    R: Unpin,
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().writer).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().writer).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.writer.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().writer).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().writer).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::*;
+
+/// An owned, boxed future that can be sent across threads.
+///
+/// This is the return type of every method on [`AsyncReadBytesDyn`] and [`AsyncWriteBytesDyn`].
+///
+/// [`AsyncReadBytesDyn`]: trait.AsyncReadBytesDyn.html
+/// [`AsyncWriteBytesDyn`]: trait.AsyncWriteBytesDyn.html
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+macro_rules! dyn_traits {
+    (
+        read { $(($read:ident, $read_rt:ident, $rty:ty, $rdesc:literal)),* $(,)? }
+        write { $(($write:ident, $write_rt:ident, $wty:ty, $wdesc:literal)),* $(,)? }
+    ) => {
+        /// An object-safe counterpart to [`AsyncReadBytesExt`].
+        ///
+        /// The methods on [`AsyncReadBytesExt`] are generic over the byte order and return
+        /// concrete future types that mention `Self`, so they cannot be called through a trait
+        /// object or named in another trait's signature. The methods here instead take the byte
+        /// order as a [`RuntimeEndian`] value and return a [`BoxFuture`], at the cost of one
+        /// allocation per call.
+        ///
+        /// This trait is implemented for every [`AsyncRead`] that is also `Send` and `Unpin`,
+        /// including `dyn AsyncRead + Send + Unpin` itself.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio::io::AsyncRead;
+        /// use tokio_byteorder::{AsyncReadBytesDyn, RuntimeEndian};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr: Box<dyn AsyncRead + Send + Unpin> = Box::new(Cursor::new(vec![1, 0, 0, 1, 11]));
+        ///     let rdr: &mut dyn AsyncReadBytesDyn = &mut rdr;
+        ///     assert_eq!(1, rdr.read_u8_boxed().await.unwrap());
+        ///     assert_eq!(267, rdr.read_u32_boxed(RuntimeEndian::Big).await.unwrap());
+        /// }
+        /// ```
+        ///
+        /// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+        /// [`RuntimeEndian`]: enum.RuntimeEndian.html
+        /// [`BoxFuture`]: type.BoxFuture.html
+        /// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+        pub trait AsyncReadBytesDyn: Send {
+            /// Reads an unsigned 8 bit integer from the underlying reader.
+            ///
+            /// See [`AsyncReadBytesExt::read_u8`](trait.AsyncReadBytesExt.html#method.read_u8).
+            fn read_u8_boxed(&mut self) -> BoxFuture<'_, io::Result<u8>>;
+
+            /// Reads a signed 8 bit integer from the underlying reader.
+            ///
+            /// See [`AsyncReadBytesExt::read_i8`](trait.AsyncReadBytesExt.html#method.read_i8).
+            fn read_i8_boxed(&mut self) -> BoxFuture<'_, io::Result<i8>>;
+
+            $(
+                #[doc = concat!("Reads ", $rdesc, " from the underlying reader in the given byte order.")]
+                ///
+                #[doc = concat!("See [`AsyncReadBytesExt::", stringify!($read_rt), "`](trait.AsyncReadBytesExt.html#method.", stringify!($read_rt), ").")]
+                fn $read(&mut self, endian: RuntimeEndian) -> BoxFuture<'_, io::Result<$rty>>;
+            )*
+        }
+
+        impl<R> AsyncReadBytesDyn for R
+        where
+            R: io::AsyncRead + Send + Unpin + ?Sized,
+        {
+            fn read_u8_boxed(&mut self) -> BoxFuture<'_, io::Result<u8>> {
+                Box::pin(self.read_u8())
+            }
+
+            fn read_i8_boxed(&mut self) -> BoxFuture<'_, io::Result<i8>> {
+                Box::pin(self.read_i8())
+            }
+
+            $(
+                fn $read(&mut self, endian: RuntimeEndian) -> BoxFuture<'_, io::Result<$rty>> {
+                    Box::pin(self.$read_rt(endian))
+                }
+            )*
+        }
+
+        /// An object-safe counterpart to [`AsyncWriteBytesExt`].
+        ///
+        /// See [`AsyncReadBytesDyn`] for why this exists. This trait is implemented for every
+        /// [`AsyncWrite`] that is also `Send` and `Unpin`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use tokio::io::AsyncWrite;
+        /// use tokio_byteorder::{AsyncWriteBytesDyn, RuntimeEndian};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr: Vec<u8> = Vec::new();
+        ///     let w: &mut dyn AsyncWriteBytesDyn = &mut wtr;
+        ///     w.write_u16_boxed(RuntimeEndian::Little, 517).await.unwrap();
+        ///     w.write_i8_boxed(-1).await.unwrap();
+        ///     assert_eq!(wtr, [5, 2, 0xff]);
+        /// }
+        /// ```
+        ///
+        /// [`AsyncWriteBytesExt`]: trait.AsyncWriteBytesExt.html
+        /// [`AsyncReadBytesDyn`]: trait.AsyncReadBytesDyn.html
+        /// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+        pub trait AsyncWriteBytesDyn: Send {
+            /// Writes an unsigned 8 bit integer to the underlying writer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_u8`](trait.AsyncWriteBytesExt.html#method.write_u8).
+            fn write_u8_boxed(&mut self, n: u8) -> BoxFuture<'_, io::Result<()>>;
+
+            /// Writes a signed 8 bit integer to the underlying writer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_i8`](trait.AsyncWriteBytesExt.html#method.write_i8).
+            fn write_i8_boxed(&mut self, n: i8) -> BoxFuture<'_, io::Result<()>>;
+
+            $(
+                #[doc = concat!("Writes ", $wdesc, " to the underlying writer in the given byte order.")]
+                ///
+                #[doc = concat!("See [`AsyncWriteBytesExt::", stringify!($write_rt), "`](trait.AsyncWriteBytesExt.html#method.", stringify!($write_rt), ").")]
+                fn $write(&mut self, endian: RuntimeEndian, n: $wty) -> BoxFuture<'_, io::Result<()>>;
+            )*
+        }
+
+        impl<W> AsyncWriteBytesDyn for W
+        where
+            W: io::AsyncWrite + Send + Unpin + ?Sized,
+        {
+            fn write_u8_boxed(&mut self, n: u8) -> BoxFuture<'_, io::Result<()>> {
+                Box::pin(self.write_u8(n))
+            }
+
+            fn write_i8_boxed(&mut self, n: i8) -> BoxFuture<'_, io::Result<()>> {
+                Box::pin(self.write_i8(n))
+            }
+
+            $(
+                fn $write(&mut self, endian: RuntimeEndian, n: $wty) -> BoxFuture<'_, io::Result<()>> {
+                    Box::pin(self.$write_rt(endian, n))
+                }
+            )*
+        }
+    };
+}
+
+dyn_traits! {
+    read {
+        (read_u16_boxed, read_u16_rt, u16, "an unsigned 16 bit integer"),
+        (read_i16_boxed, read_i16_rt, i16, "a signed 16 bit integer"),
+        (read_u24_boxed, read_u24_rt, u32, "an unsigned 24 bit integer"),
+        (read_i24_boxed, read_i24_rt, i32, "a signed 24 bit integer"),
+        (read_u32_boxed, read_u32_rt, u32, "an unsigned 32 bit integer"),
+        (read_i32_boxed, read_i32_rt, i32, "a signed 32 bit integer"),
+        (read_u48_boxed, read_u48_rt, u64, "an unsigned 48 bit integer"),
+        (read_i48_boxed, read_i48_rt, i64, "a signed 48 bit integer"),
+        (read_u64_boxed, read_u64_rt, u64, "an unsigned 64 bit integer"),
+        (read_i64_boxed, read_i64_rt, i64, "a signed 64 bit integer"),
+        (read_u128_boxed, read_u128_rt, u128, "an unsigned 128 bit integer"),
+        (read_i128_boxed, read_i128_rt, i128, "a signed 128 bit integer"),
+        (read_f32_boxed, read_f32_rt, f32, "a IEEE754 single-precision floating point number"),
+        (read_f64_boxed, read_f64_rt, f64, "a IEEE754 double-precision floating point number"),
+    }
+    write {
+        (write_u16_boxed, write_u16_rt, u16, "an unsigned 16 bit integer"),
+        (write_i16_boxed, write_i16_rt, i16, "a signed 16 bit integer"),
+        (write_u24_boxed, write_u24_rt, u32, "an unsigned 24 bit integer"),
+        (write_i24_boxed, write_i24_rt, i32, "a signed 24 bit integer"),
+        (write_u32_boxed, write_u32_rt, u32, "an unsigned 32 bit integer"),
+        (write_i32_boxed, write_i32_rt, i32, "a signed 32 bit integer"),
+        (write_u48_boxed, write_u48_rt, u64, "an unsigned 48 bit integer"),
+        (write_i48_boxed, write_i48_rt, i64, "a signed 48 bit integer"),
+        (write_u64_boxed, write_u64_rt, u64, "an unsigned 64 bit integer"),
+        (write_i64_boxed, write_i64_rt, i64, "a signed 64 bit integer"),
+        (write_u128_boxed, write_u128_rt, u128, "an unsigned 128 bit integer"),
+        (write_i128_boxed, write_i128_rt, i128, "a signed 128 bit integer"),
+        (write_f32_boxed, write_f32_rt, f32, "a IEEE754 single-precision floating point number"),
+        (write_f64_boxed, write_f64_rt, f64, "a IEEE754 double-precision floating point number"),
+    }
+}
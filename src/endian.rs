@@ -0,0 +1,255 @@
+use crate::future::WriteAndFlush;
+use crate::sans_io::FixedEncoder;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use tokio::io;
+
+/// A byte order that is only known at runtime.
+///
+/// Unlike [`BigEndian`] and [`LittleEndian`], which select a byte order at compile time through
+/// a type parameter, this is a plain value. It is typically obtained by sniffing a file header,
+/// for example with [`detect_endianness_elf`] or [`detect_endianness_tiff`], and can then be
+/// passed to the `_rt` variants of the methods on [`AsyncReadBytesExt`] and
+/// [`AsyncWriteBytesExt`].
+///
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`AsyncWriteBytesExt`]: trait.AsyncWriteBytesExt.html
+/// [`BigEndian`]: enum.BigEndian.html
+/// [`LittleEndian`]: enum.LittleEndian.html
+/// [`detect_endianness_elf`]: trait.AsyncReadBytesExt.html#method.detect_endianness_elf
+/// [`detect_endianness_tiff`]: trait.AsyncReadBytesExt.html#method.detect_endianness_tiff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuntimeEndian {
+    /// Most significant byte first.
+    Big,
+    /// Least significant byte first.
+    Little,
+    /// The byte order of the target platform.
+    Native,
+}
+
+macro_rules! dispatch {
+    ($endian:expr, $method:ident($($arg:expr),*)) => {
+        match $endian {
+            RuntimeEndian::Big => BigEndian::$method($($arg),*),
+            RuntimeEndian::Little => LittleEndian::$method($($arg),*),
+            RuntimeEndian::Native => NativeEndian::$method($($arg),*),
+        }
+    };
+}
+
+/// A value that can be encoded in a byte order chosen at runtime.
+pub(crate) trait RuntimeCodec: Sized {
+    fn read(endian: RuntimeEndian, buf: &[u8]) -> Self;
+    fn write(endian: RuntimeEndian, buf: &mut [u8], v: Self);
+}
+
+macro_rules! runtime_codec {
+    ($ty:ty, $read:ident, $write:ident) => {
+        impl RuntimeCodec for $ty {
+            fn read(endian: RuntimeEndian, buf: &[u8]) -> Self {
+                dispatch!(endian, $read(buf))
+            }
+            fn write(endian: RuntimeEndian, buf: &mut [u8], v: Self) {
+                dispatch!(endian, $write(buf, v))
+            }
+        }
+    };
+}
+
+runtime_codec!(u16, read_u16, write_u16);
+runtime_codec!(i16, read_i16, write_i16);
+runtime_codec!(u32, read_u32, write_u32);
+runtime_codec!(i32, read_i32, write_i32);
+runtime_codec!(u64, read_u64, write_u64);
+runtime_codec!(i64, read_i64, write_i64);
+runtime_codec!(u128, read_u128, write_u128);
+runtime_codec!(i128, read_i128, write_i128);
+runtime_codec!(f32, read_f32, write_f32);
+runtime_codec!(f64, read_f64, write_f64);
+
+/// Unsigned integers whose width on the wire is not their in-memory width, like 24 or 48 bits.
+pub(crate) fn read_uint(endian: RuntimeEndian, buf: &[u8]) -> u64 {
+    dispatch!(endian, read_uint(buf, buf.len()))
+}
+
+pub(crate) fn read_int(endian: RuntimeEndian, buf: &[u8]) -> i64 {
+    dispatch!(endian, read_int(buf, buf.len()))
+}
+
+pub(crate) fn write_uint(endian: RuntimeEndian, buf: &mut [u8], v: u64) {
+    let nbytes = buf.len();
+    dispatch!(endian, write_uint(buf, v, nbytes))
+}
+
+pub(crate) fn write_int(endian: RuntimeEndian, buf: &mut [u8], v: i64) {
+    let nbytes = buf.len();
+    dispatch!(endian, write_int(buf, v, nbytes))
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) fn elf(ident: &[u8]) -> io::Result<RuntimeEndian> {
+    if &ident[..4] != b"\x7fELF" {
+        return Err(invalid("missing ELF magic"));
+    }
+    match ident[4] {
+        1 | 2 => {}
+        _ => return Err(invalid("unknown ELF class")),
+    }
+    match ident[5] {
+        1 => Ok(RuntimeEndian::Little),
+        2 => Ok(RuntimeEndian::Big),
+        _ => Err(invalid("unknown ELF data encoding")),
+    }
+}
+
+pub(crate) fn tiff(bom: &[u8]) -> io::Result<RuntimeEndian> {
+    match bom {
+        b"II" => Ok(RuntimeEndian::Little),
+        b"MM" => Ok(RuntimeEndian::Big),
+        _ => Err(invalid("unknown TIFF byte order mark")),
+    }
+}
+
+/// Future returned by the `detect_endianness_` methods of
+/// [`AsyncReadBytesExt`](../trait.AsyncReadBytesExt.html).
+#[derive(Debug)]
+pub struct DetectEndianness<R> {
+    buf: [u8; 6],
+    len: usize,
+    read: usize,
+    src: R,
+    detect: fn(&[u8]) -> io::Result<RuntimeEndian>,
+}
+
+impl<R> DetectEndianness<R> {
+    pub(crate) fn new(src: R, len: usize, detect: fn(&[u8]) -> io::Result<RuntimeEndian>) -> Self {
+        DetectEndianness {
+            buf: [0; 6],
+            len,
+            read: 0,
+            src,
+            detect,
+        }
+    }
+}
+
+impl<R> Future for DetectEndianness<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<RuntimeEndian>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(crate::poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..this.len],
+            &mut this.read
+        ))?;
+        Poll::Ready((this.detect)(&this.buf[..this.len]))
+    }
+}
+
+/// Future returned by the `_rt` read methods of
+/// [`AsyncReadBytesExt`](../trait.AsyncReadBytesExt.html).
+#[derive(Debug)]
+pub struct ReadRuntime<R, V> {
+    buf: [u8; 16],
+    len: u8,
+    read: usize,
+    src: R,
+    endian: RuntimeEndian,
+    decode: fn(RuntimeEndian, &[u8]) -> V,
+}
+
+impl<R, V> ReadRuntime<R, V> {
+    pub(crate) fn new(
+        src: R,
+        endian: RuntimeEndian,
+        len: usize,
+        decode: fn(RuntimeEndian, &[u8]) -> V,
+    ) -> Self {
+        ReadRuntime {
+            buf: [0; 16],
+            len: len as u8,
+            read: 0,
+            src,
+            endian,
+            decode,
+        }
+    }
+}
+
+impl<R, V> Future for ReadRuntime<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let buf = &mut this.buf[..this.len as usize];
+        ready!(crate::poll_read_exact(
+            &mut this.src,
+            cx,
+            buf,
+            &mut this.read
+        ))?;
+        Poll::Ready(Ok((this.decode)(this.endian, buf)))
+    }
+}
+
+/// Future returned by the `_rt` write methods of
+/// [`AsyncWriteBytesExt`](../trait.AsyncWriteBytesExt.html).
+#[derive(Debug)]
+pub struct WriteRuntime<W> {
+    buf: [u8; 16],
+    len: u8,
+    written: usize,
+    dst: W,
+}
+
+impl<W> WriteRuntime<W> {
+    pub(crate) fn new<V>(
+        dst: W,
+        endian: RuntimeEndian,
+        len: usize,
+        v: V,
+        encode: fn(RuntimeEndian, &mut [u8], V),
+    ) -> Self {
+        let mut buf = [0; 16];
+        encode(endian, &mut buf[..len], v);
+        WriteRuntime {
+            buf,
+            len: len as u8,
+            written: 0,
+            dst,
+        }
+    }
+
+    /// Flushes the writer once the value has been written.
+    ///
+    /// See [`WriteAndFlush`](struct.WriteAndFlush.html).
+    pub fn and_flush(self) -> WriteAndFlush<W> {
+        let rest = &self.buf[self.written..self.len as usize];
+        let encoder = FixedEncoder::new(rest.len(), |buf| buf.copy_from_slice(rest));
+        WriteAndFlush::new(self.dst, encoder)
+    }
+}
+
+impl<W> Future for WriteRuntime<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let buf = &this.buf[..this.len as usize];
+        crate::poll_write_all(&mut this.dst, cx, buf, &mut this.written)
+    }
+}
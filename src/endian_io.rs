@@ -0,0 +1,277 @@
+use crate::*;
+use byteorder::{BigEndian, LittleEndian, NativeEndian, NetworkEndian};
+
+/// A reader that decodes every number in a single, fixed byte order.
+///
+/// This wraps an [`AsyncRead`] and exposes the methods of [`AsyncReadBytesExt`] without a byte
+/// order type parameter, so that long decoders need not repeat it at every call site. The
+/// wrapper itself also implements [`AsyncRead`], so any other method remains available.
+///
+/// Usually this is named through one of its aliases, such as [`BigEndianReader`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use tokio_byteorder::BigEndianReader;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = BigEndianReader::new(Cursor::new(vec![2, 5, 3, 0]));
+///     assert_eq!(517, rdr.read_u16().await.unwrap());
+///     assert_eq!(768, rdr.read_u16().await.unwrap());
+/// }
+/// ```
+///
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`BigEndianReader`]: type.BigEndianReader.html
+#[derive(Debug)]
+pub struct EndianReader<R, T> {
+    inner: R,
+    bo: PhantomData<fn() -> T>,
+}
+
+/// A reader that decodes numbers in big-endian order.
+pub type BigEndianReader<R> = EndianReader<R, BigEndian>;
+/// A reader that decodes numbers in little-endian order.
+pub type LittleEndianReader<R> = EndianReader<R, LittleEndian>;
+/// A reader that decodes numbers in the byte order of the target platform.
+pub type NativeEndianReader<R> = EndianReader<R, NativeEndian>;
+/// A reader that decodes numbers in network byte order (big-endian).
+///
+/// This is the closest equivalent to having the byte order type parameter default to
+/// [`NetworkEndian`]: protocol code can wrap its stream once and then simply call
+/// `read_u32().await`. Rust does not allow defaults for type parameters on methods.
+///
+/// [`NetworkEndian`]: type.NetworkEndian.html
+pub type NetworkEndianReader<R> = EndianReader<R, NetworkEndian>;
+
+/// A writer that encodes every number in a single, fixed byte order.
+///
+/// This is the writing counterpart of [`EndianReader`], and wraps an [`AsyncWrite`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::LittleEndianWriter;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = LittleEndianWriter::new(Vec::new());
+///     wtr.write_u16(517).await.unwrap();
+///     wtr.write_u16(768).await.unwrap();
+///     assert_eq!(wtr.into_inner(), vec![5, 2, 0, 3]);
+/// }
+/// ```
+///
+/// [`EndianReader`]: struct.EndianReader.html
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+#[derive(Debug)]
+pub struct EndianWriter<W, T> {
+    inner: W,
+    bo: PhantomData<fn() -> T>,
+}
+
+/// A writer that encodes numbers in big-endian order.
+pub type BigEndianWriter<W> = EndianWriter<W, BigEndian>;
+/// A writer that encodes numbers in little-endian order.
+pub type LittleEndianWriter<W> = EndianWriter<W, LittleEndian>;
+/// A writer that encodes numbers in the byte order of the target platform.
+pub type NativeEndianWriter<W> = EndianWriter<W, NativeEndian>;
+/// A writer that encodes numbers in network byte order (big-endian).
+///
+/// See [`NetworkEndianReader`] for why this exists.
+///
+/// [`NetworkEndianReader`]: type.NetworkEndianReader.html
+pub type NetworkEndianWriter<W> = EndianWriter<W, NetworkEndian>;
+
+macro_rules! wrapper_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, T> $name<$inner, T> {
+            /// Wraps the given stream.
+            pub fn new(inner: $inner) -> Self {
+                $name {
+                    inner,
+                    bo: PhantomData,
+                }
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+        }
+    };
+}
+
+wrapper_common!(EndianReader, R);
+wrapper_common!(EndianWriter, W);
+
+macro_rules! bound_read {
+    ($(($name:ident, $fut:ident, $desc:literal)),*) => {
+        impl<R, T> EndianReader<R, T>
+        where
+            R: io::AsyncRead + Unpin,
+            T: ByteOrder,
+        {
+            /// Reads an unsigned 8 bit integer.
+            ///
+            /// See [`AsyncReadBytesExt::read_u8`](trait.AsyncReadBytesExt.html#method.read_u8).
+            #[inline]
+            pub fn read_u8(&mut self) -> ReadU8<&mut R> {
+                self.inner.read_u8()
+            }
+
+            /// Reads a signed 8 bit integer.
+            ///
+            /// See [`AsyncReadBytesExt::read_i8`](trait.AsyncReadBytesExt.html#method.read_i8).
+            #[inline]
+            pub fn read_i8(&mut self) -> ReadI8<&mut R> {
+                self.inner.read_i8()
+            }
+
+            $(
+                #[doc = concat!("Reads ", $desc, " in this reader's byte order.")]
+                ///
+                #[doc = concat!("See [`AsyncReadBytesExt::", stringify!($name), "`](trait.AsyncReadBytesExt.html#method.", stringify!($name), ").")]
+                #[inline]
+                pub fn $name(&mut self) -> $fut<&mut R, T> {
+                    self.inner.$name::<T>()
+                }
+            )*
+        }
+    };
+}
+
+bound_read!(
+    (read_u16, ReadU16, "an unsigned 16 bit integer"),
+    (read_i16, ReadI16, "a signed 16 bit integer"),
+    (read_u24, ReadU24, "an unsigned 24 bit integer"),
+    (read_i24, ReadI24, "a signed 24 bit integer"),
+    (read_u32, ReadU32, "an unsigned 32 bit integer"),
+    (read_i32, ReadI32, "a signed 32 bit integer"),
+    (read_u48, ReadU48, "an unsigned 48 bit integer"),
+    (read_i48, ReadI48, "a signed 48 bit integer"),
+    (read_u64, ReadU64, "an unsigned 64 bit integer"),
+    (read_i64, ReadI64, "a signed 64 bit integer"),
+    (read_u128, ReadU128, "an unsigned 128 bit integer"),
+    (read_i128, ReadI128, "a signed 128 bit integer"),
+    (
+        read_f32,
+        ReadF32,
+        "a IEEE754 single-precision floating point number"
+    ),
+    (
+        read_f64,
+        ReadF64,
+        "a IEEE754 double-precision floating point number"
+    )
+);
+
+macro_rules! bound_write {
+    ($(($name:ident, $ty:ty, $fut:ident, $desc:literal)),*) => {
+        impl<W, T> EndianWriter<W, T>
+        where
+            W: io::AsyncWrite + Unpin,
+            T: ByteOrder,
+        {
+            /// Writes an unsigned 8 bit integer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_u8`](trait.AsyncWriteBytesExt.html#method.write_u8).
+            #[inline]
+            pub fn write_u8(&mut self, n: u8) -> WriteU8<&mut W> {
+                self.inner.write_u8(n)
+            }
+
+            /// Writes a signed 8 bit integer.
+            ///
+            /// See [`AsyncWriteBytesExt::write_i8`](trait.AsyncWriteBytesExt.html#method.write_i8).
+            #[inline]
+            pub fn write_i8(&mut self, n: i8) -> WriteI8<&mut W> {
+                self.inner.write_i8(n)
+            }
+
+            $(
+                #[doc = concat!("Writes ", $desc, " in this writer's byte order.")]
+                ///
+                #[doc = concat!("See [`AsyncWriteBytesExt::", stringify!($name), "`](trait.AsyncWriteBytesExt.html#method.", stringify!($name), ").")]
+                #[inline]
+                pub fn $name(&mut self, n: $ty) -> $fut<&mut W> {
+                    self.inner.$name::<T>(n)
+                }
+            )*
+        }
+    };
+}
+
+bound_write!(
+    (write_u16, u16, WriteU16, "an unsigned 16 bit integer"),
+    (write_i16, i16, WriteI16, "a signed 16 bit integer"),
+    (write_u24, u32, WriteU24, "an unsigned 24 bit integer"),
+    (write_i24, i32, WriteI24, "a signed 24 bit integer"),
+    (write_u32, u32, WriteU32, "an unsigned 32 bit integer"),
+    (write_i32, i32, WriteI32, "a signed 32 bit integer"),
+    (write_u48, u64, WriteU48, "an unsigned 48 bit integer"),
+    (write_i48, i64, WriteI48, "a signed 48 bit integer"),
+    (write_u64, u64, WriteU64, "an unsigned 64 bit integer"),
+    (write_i64, i64, WriteI64, "a signed 64 bit integer"),
+    (write_u128, u128, WriteU128, "an unsigned 128 bit integer"),
+    (write_i128, i128, WriteI128, "a signed 128 bit integer"),
+    (
+        write_f32,
+        f32,
+        WriteF32,
+        "a IEEE754 single-precision floating point number"
+    ),
+    (
+        write_f64,
+        f64,
+        WriteF64,
+        "a IEEE754 double-precision floating point number"
+    )
+);
+
+impl<R, T> io::AsyncRead for EndianReader<R, T>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl<W, T> io::AsyncWrite for EndianWriter<W, T>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
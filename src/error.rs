@@ -0,0 +1,168 @@
+use crate::*;
+use core::fmt;
+
+/// Where and what a failed read was decoding.
+///
+/// The read methods of [`ByteReader`] report their failures as an [`io::Error`] of the usual
+/// kind that wraps one of these, so that an error from deep inside a long header can be traced
+/// back to the value it came from. It can be recovered with [`DecodeError::from_io`]; code that
+/// does not care can keep treating the error as any other `io::Error`.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, ByteReader, DecodeError};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = ByteReader::new(&[0x00, 0x01, 0x02, 0x03, 0x04][..]);
+///     rdr.read_u16::<BigEndian>().await.unwrap();
+///     let err = rdr.read_u32::<BigEndian>().await.unwrap_err();
+///     assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+///
+///     let ctx = DecodeError::from_io(&err).unwrap();
+///     assert_eq!(ctx.what(), "u32");
+///     assert_eq!(ctx.offset(), 2);
+///     assert_eq!(ctx.available(), 3);
+/// }
+/// ```
+///
+/// [`ByteReader`]: struct.ByteReader.html
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`DecodeError::from_io`]: #method.from_io
+#[derive(Debug)]
+pub struct DecodeError {
+    what: &'static str,
+    offset: u64,
+    available: usize,
+    source: io::Error,
+}
+
+impl DecodeError {
+    pub(crate) fn new(
+        what: &'static str,
+        offset: u64,
+        available: usize,
+        source: io::Error,
+    ) -> Self {
+        DecodeError {
+            what,
+            offset,
+            available,
+            source,
+        }
+    }
+
+    /// Returns the context attached to `err`, if it has any.
+    pub fn from_io(err: &io::Error) -> Option<&Self> {
+        err.get_ref()?.downcast_ref()
+    }
+
+    /// Returns the name of the type that was being decoded, like `"u16"` or `"i24"`.
+    pub fn what(&self) -> &'static str {
+        self.what
+    }
+
+    /// Returns the offset in the stream at which the value starts.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns how many bytes of the value were available before the failure.
+    ///
+    /// These bytes are not consumed, and remain in the reader's buffer.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Returns the kind of the underlying error.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.source.kind()
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to decode {} at offset {} ({} bytes available): {}",
+            self.what, self.offset, self.available, self.source
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<DecodeError> for io::Error {
+    fn from(e: DecodeError) -> Self {
+        io::Error::new(e.kind(), e)
+    }
+}
+
+/// A [`Limit`] that was read past, or not read to its end.
+///
+/// [`Limit`] reports both as an [`io::Error`] of the kind `InvalidData` that wraps one of these,
+/// which can be recovered with [`LimitError::from_io`].
+///
+/// [`Limit`]: struct.Limit.html
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`LimitError::from_io`]: #method.from_io
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitError {
+    limit: u64,
+    remaining: u64,
+}
+
+impl LimitError {
+    pub(crate) fn new(limit: u64, remaining: u64) -> Self {
+        LimitError { limit, remaining }
+    }
+
+    /// Returns the limit error attached to `err`, if it has one.
+    pub fn from_io(err: &io::Error) -> Option<&Self> {
+        err.get_ref()?.downcast_ref()
+    }
+
+    /// Returns the number of bytes that the reader was limited to.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns the number of bytes that were left unread, which is zero if the limit was read
+    /// past.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns true if the error is an attempt to read past the limit, rather than bytes left
+    /// unread.
+    pub fn is_overrun(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_overrun() {
+            write!(f, "read past the end of a {} byte limit", self.limit)
+        } else {
+            write!(
+                f,
+                "{} of a {} byte limit left unread",
+                self.remaining, self.limit
+            )
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+impl From<LimitError> for io::Error {
+    fn from(e: LimitError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
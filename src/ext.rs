@@ -0,0 +1,6518 @@
+use crate::coop::Budget;
+use crate::endian::{self, RuntimeCodec};
+use crate::prefixed::SpareCapacity;
+use crate::*;
+use core::convert::TryFrom;
+use core::mem::MaybeUninit;
+
+/// Reads into `buf[*filled..]` until it is full, tracking progress in `filled` so that the call
+/// can be resumed after returning `Poll::Pending`.
+///
+/// Like [`std::io::Read::read_exact`], reads that fail with `ErrorKind::Interrupted` are retried.
+/// Large reads yield every so often; see [`crate::coop`].
+pub(crate) fn poll_read_exact<R>(
+    src: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+    filled: &mut usize,
+) -> Poll<io::Result<()>>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+{
+    let mut budget = Budget::new();
+    while *filled < buf.len() {
+        let mut rbuf = io::ReadBuf::new(&mut buf[*filled..]);
+        let n = match Pin::new(&mut *src).poll_read(cx, &mut rbuf) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) if rbuf.filled().is_empty() => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                )));
+            }
+            Poll::Ready(Ok(())) => rbuf.filled().len(),
+        };
+        *filled += n;
+        if *filled < buf.len() {
+            core::task::ready!(budget.poll_charge(cx, n));
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Reads into the spare capacity of `buf` until it holds `len` bytes.
+///
+/// Unlike [`poll_read_exact`], this does not need the memory to be zeroed first, which is
+/// measurable for large buffers. All progress is kept in `buf`, so the call can be resumed
+/// after returning `Poll::Pending`. Large reads yield every so often; see [`crate::coop`].
+pub(crate) fn poll_read_to_len<R, B>(
+    src: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut B,
+    len: usize,
+) -> Poll<io::Result<()>>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+    B: SpareCapacity + ?Sized,
+{
+    if buf.len() < len {
+        buf.reserve(len - buf.len());
+    }
+    let mut budget = Budget::new();
+    while buf.len() < len {
+        let want = len - buf.len();
+        let spare = &mut buf.spare_capacity_mut()[..want];
+        let start = spare.as_ptr();
+        let mut rbuf = io::ReadBuf::uninit(spare);
+        let n = match Pin::new(&mut *src).poll_read(cx, &mut rbuf) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) if rbuf.filled().is_empty() => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                )));
+            }
+            Poll::Ready(Ok(())) => rbuf.filled().len(),
+        };
+        // the reader could have swapped in a different buffer, in which case the filled bytes
+        // are not the ones in `buf`
+        assert_eq!(
+            start as *const u8,
+            rbuf.filled().as_ptr(),
+            "AsyncRead::poll_read replaced the buffer it was given"
+        );
+        // SAFETY: `ReadBuf` guarantees that its first `n` bytes are initialized, and these are
+        // the first `n` bytes of the spare capacity of `buf`, as checked above.
+        unsafe { buf.grow(n) };
+        if buf.len() < len {
+            core::task::ready!(budget.poll_charge(cx, n));
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Feeds `decoder` from `src` until it produces a value.
+///
+/// All progress is kept in `decoder`, so the call can be resumed after returning
+/// `Poll::Pending`. The bytes are read into an uninitialized stack buffer; readers that need
+/// initialized memory zero only what they ask for through [`io::ReadBuf::initialize_unfilled_to`].
+pub(crate) fn poll_decode<R, V>(
+    mut src: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    decoder: &mut sans_io::FixedDecoder<V>,
+) -> Poll<io::Result<V>>
+where
+    R: io::AsyncRead + ?Sized,
+{
+    loop {
+        let mut buf = [MaybeUninit::uninit(); 16];
+        let mut rbuf = io::ReadBuf::uninit(&mut buf[..decoder.remaining()]);
+        match src.as_mut().poll_read(cx, &mut rbuf) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) if rbuf.filled().is_empty() => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                )));
+            }
+            Poll::Ready(Ok(())) => {
+                if let Some(v) = decoder.push_bytes(&mut rbuf.filled()) {
+                    return Poll::Ready(Ok(v));
+                }
+            }
+        }
+    }
+}
+
+/// Writes `buf[*written..]` until all of it has been accepted, tracking progress in `written` so
+/// that the call can be resumed after returning `Poll::Pending`.
+///
+/// Large writes yield every so often; see [`crate::coop`].
+pub(crate) fn poll_write_all<W>(
+    dst: &mut W,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+    written: &mut usize,
+) -> Poll<io::Result<()>>
+where
+    W: io::AsyncWrite + Unpin + ?Sized,
+{
+    let mut budget = Budget::new();
+    while *written < buf.len() {
+        let n = match Pin::new(&mut *dst).poll_write(cx, &buf[*written..]) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Ready(Ok(0)) => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            Poll::Ready(Ok(n)) => n,
+        };
+        *written += n;
+        if *written < buf.len() {
+            core::task::ready!(budget.poll_charge(cx, n));
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Writes out everything `encoder` has pending, keeping all progress in `encoder` so that the
+/// call can be resumed after returning `Poll::Pending`.
+///
+/// Like [`std::io::Write::write_all`], a write that accepts no bytes fails with
+/// `ErrorKind::WriteZero` rather than being retried.
+pub(crate) fn poll_write_encoder<W>(
+    mut dst: Pin<&mut W>,
+    cx: &mut Context<'_>,
+    encoder: &mut sans_io::FixedEncoder,
+) -> Poll<io::Result<()>>
+where
+    W: io::AsyncWrite + ?Sized,
+{
+    while !encoder.is_done() {
+        match dst.as_mut().poll_write(cx, encoder.pending()) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Ready(Ok(0)) => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            Poll::Ready(Ok(n)) => encoder.advance(n),
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+macro_rules! read_impl {
+    (
+        $(#[$outer:meta])*
+        fn $name:ident(&mut self) -> $($fut:tt)*
+    ) => {
+        $(#[$outer])*
+        #[inline]
+        fn $name<T: ByteOrder>(&mut self) -> $($fut)*<&mut Self, T> where Self: Unpin {
+            $($fut)*::new(self)
+        }
+    }
+}
+
+macro_rules! read_variants {
+    ($(($name:ident, $be:ident, $le:ident, $ne:ident, $fut:ident, $desc:literal)),* $(,)?) => {
+        $(
+            read_variants!(@one $name, $be, $fut, BigEndian, $desc, "big-endian");
+            read_variants!(@one $name, $le, $fut, LittleEndian, $desc, "little-endian");
+            read_variants!(@one $name, $ne, $fut, NativeEndian, $desc, "native-endian");
+        )*
+    };
+    (@one $name:ident, $variant:ident, $fut:ident, $bo:ident, $desc:literal, $order:literal) => {
+        #[doc = concat!("Reads ", $desc, " in ", $order, " order from the underlying reader.")]
+        ///
+        #[doc = concat!("This is equivalent to [`", stringify!($name), "::<", stringify!($bo), ">`](#method.", stringify!($name), ").")]
+        #[inline]
+        fn $variant(&mut self) -> $fut<&mut Self, $bo>
+        where
+            Self: Unpin,
+        {
+            $fut::new(self)
+        }
+    };
+}
+
+macro_rules! read_owned {
+    ($(($name:ident, $decoder:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Reads ", $desc, " from the underlying reader, which is taken by value.")]
+            ///
+            /// See [`read_u8_owned`](#method.read_u8_owned).
+            #[inline]
+            fn $name<T: ByteOrder>(self) -> ReadOwned<Self, $ty>
+            where
+                Self: Sized + Unpin,
+            {
+                ReadOwned::new(self, sans_io::FixedDecoder::$decoder::<T>())
+            }
+        )*
+    };
+}
+
+/// Extends [`AsyncRead`] with methods for reading numbers.
+///
+/// Most of the methods defined here have an unconstrained type parameter that
+/// must be explicitly instantiated. Typically, it is instantiated with either
+/// the [`BigEndian`] or [`LittleEndian`] types defined in this crate.
+///
+/// The methods require `Self: Unpin`. To use them with a reader that is not `Unpin`, pin it
+/// first (for example with [`std::pin::pin!`]) and call them on the resulting `Pin<&mut _>`.
+///
+/// A value does not have to arrive in a single read: its bytes are collected across as many
+/// reads as it takes. In particular, a value can straddle the seam of a [`chain`], as when the
+/// bytes left over from parsing a handshake are put back in front of the live connection.
+///
+/// [`std::pin::pin!`]: https://doc.rust-lang.org/std/pin/macro.pin.html
+/// [`chain`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncReadExt.html#method.chain
+///
+/// # Examples
+///
+/// Read unsigned 16 bit big-endian integers from a [`Read`]:
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = Cursor::new(vec![2, 5, 3, 0]);
+///     assert_eq!(517, rdr.read_u16::<BigEndian>().await.unwrap());
+///     assert_eq!(768, rdr.read_u16::<BigEndian>().await.unwrap());
+/// }
+/// ```
+///
+/// Read a value that is split between leftover bytes and the rest of the stream:
+///
+/// ```rust
+/// use tokio::io::AsyncReadExt;
+/// use tokio_byteorder::{AsyncReadBytesExt, LittleEndian};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let leftover = &[0x0b, 0x01][..];
+///     let socket = &[0x00, 0x00][..];
+///     let mut rdr = leftover.chain(socket);
+///     assert_eq!(267, AsyncReadBytesExt::read_u32::<LittleEndian>(&mut rdr).await.unwrap());
+/// }
+/// ```
+///
+/// [`BigEndian`]: enum.BigEndian.html
+/// [`LittleEndian`]: enum.LittleEndian.html
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+pub trait AsyncReadBytesExt: io::AsyncRead {
+    /// Reads an unsigned 8 bit integer from the underlying reader.
+    ///
+    /// Note that since this reads a single byte, no byte order conversions
+    /// are used. It is included for completeness.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read unsigned 8 bit integers from a `Read`:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(vec![2, 5]);
+    ///     assert_eq!(2, rdr.read_u8().await.unwrap());
+    ///     assert_eq!(5, rdr.read_u8().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_u8(&mut self) -> ReadU8<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadU8::new(self)
+    }
+
+    /// Reads a signed 8 bit integer from the underlying reader.
+    ///
+    /// Note that since this reads a single byte, no byte order conversions
+    /// are used. It is included for completeness.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read signed 8 bit integers from a `Read`:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(vec![0x02, 0xfb]);
+    ///     assert_eq!(2, rdr.read_i8().await.unwrap());
+    ///     assert_eq!(-5, rdr.read_i8().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_i8(&mut self) -> ReadI8<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadI8::new(self)
+    }
+
+    read_impl! {
+        /// Reads an unsigned 16 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read unsigned 16 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![2, 5, 3, 0]);
+        ///     assert_eq!(517, rdr.read_u16::<BigEndian>().await.unwrap());
+        ///     assert_eq!(768, rdr.read_u16::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u16(&mut self) -> ReadU16
+    }
+
+    read_impl! {
+        /// Reads a signed 16 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read signed 16 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x00, 0xc1, 0xff, 0x7c]);
+        ///     assert_eq!(193, rdr.read_i16::<BigEndian>().await.unwrap());
+        ///     assert_eq!(-132, rdr.read_i16::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i16(&mut self) -> ReadI16
+    }
+
+    read_impl! {
+        /// Reads an unsigned 24 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read unsigned 24 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x00, 0x01, 0x0b]);
+        ///     assert_eq!(267, rdr.read_u24::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u24(&mut self) -> ReadU24
+    }
+
+    read_impl! {
+        /// Reads a signed 24 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read signed 24 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0xff, 0x7a, 0x33]);
+        ///     assert_eq!(-34253, rdr.read_i24::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i24(&mut self) -> ReadI24
+    }
+
+    read_impl! {
+        /// Reads an unsigned 32 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read unsigned 32 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x00, 0x00, 0x01, 0x0b]);
+        ///     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u32(&mut self) -> ReadU32
+    }
+
+    read_impl! {
+        /// Reads a signed 32 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read signed 32 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0xff, 0xff, 0x7a, 0x33]);
+        ///     assert_eq!(-34253, rdr.read_i32::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i32(&mut self) -> ReadI32
+    }
+
+    read_impl! {
+        /// Reads an unsigned 48 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read unsigned 48 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0xb6, 0x71, 0x6b, 0xdc, 0x2b, 0x31]);
+        ///     assert_eq!(200598257150769, rdr.read_u48::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u48(&mut self) -> ReadU48
+    }
+
+    read_impl! {
+        /// Reads a signed 48 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read signed 48 bit big-endian integers from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x9d, 0x71, 0xab, 0xe7, 0x97, 0x8f]);
+        ///     assert_eq!(-108363435763825, rdr.read_i48::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i48(&mut self) -> ReadI48
+    }
+
+    read_impl! {
+        /// Reads an unsigned 64 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read an unsigned 64 bit big-endian integer from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x00, 0x03, 0x43, 0x95, 0x4d, 0x60, 0x86, 0x83]);
+        ///     assert_eq!(918733457491587, rdr.read_u64::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u64(&mut self) -> ReadU64
+    }
+
+    read_impl! {
+        /// Reads a signed 64 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read a signed 64 bit big-endian integer from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x80, 0, 0, 0, 0, 0, 0, 0]);
+        ///     assert_eq!(i64::min_value(), rdr.read_i64::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i64(&mut self) -> ReadI64
+    }
+
+    read_impl! {
+        /// Reads an unsigned 128 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read an unsigned 128 bit big-endian integer from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![
+        ///         0x00, 0x03, 0x43, 0x95, 0x4d, 0x60, 0x86, 0x83,
+        ///         0x00, 0x03, 0x43, 0x95, 0x4d, 0x60, 0x86, 0x83
+        ///     ]);
+        ///     assert_eq!(16947640962301618749969007319746179, rdr.read_u128::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_u128(&mut self) -> ReadU128
+    }
+
+    read_impl! {
+        /// Reads a signed 128 bit integer from the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read a signed 128 bit big-endian integer from a `Read`:
+        ///
+        /// ```rust
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        ///     assert_eq!(i128::min_value(), rdr.read_i128::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_i128(&mut self) -> ReadI128
+    }
+
+    // TODO: read_*int
+
+    read_impl! {
+        /// Reads a IEEE754 single-precision (4 bytes) floating point number from
+        /// the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read a big-endian single-precision floating point number from a `Read`:
+        ///
+        /// ```rust
+        /// use std::f32;
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![
+        ///         0x40, 0x49, 0x0f, 0xdb,
+        ///     ]);
+        ///     assert_eq!(f32::consts::PI, rdr.read_f32::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+        fn read_f32(&mut self) -> ReadF32
+    }
+
+    read_impl! {
+        /// Reads a IEEE754 double-precision (8 bytes) floating point number from
+        /// the underlying reader.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Read::read_exact`].
+        ///
+        /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+        ///
+        /// # Examples
+        ///
+        /// Read a big-endian double-precision floating point number from a `Read`:
+        ///
+        /// ```rust
+        /// use std::f64;
+        /// use std::io::Cursor;
+        /// use tokio_byteorder::{BigEndian, AsyncReadBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut rdr = Cursor::new(vec![
+        ///         0x40, 0x09, 0x21, 0xfb, 0x54, 0x44, 0x2d, 0x18,
+        ///     ]);
+        ///     assert_eq!(f64::consts::PI, rdr.read_f64::<BigEndian>().await.unwrap());
+        /// }
+        /// ```
+    fn read_f64(&mut self) -> ReadF64
+    }
+
+    read_variants!(
+        (
+            read_u16,
+            read_u16_be,
+            read_u16_le,
+            read_u16_ne,
+            ReadU16,
+            "an unsigned 16 bit integer"
+        ),
+        (
+            read_i16,
+            read_i16_be,
+            read_i16_le,
+            read_i16_ne,
+            ReadI16,
+            "a signed 16 bit integer"
+        ),
+        (
+            read_u24,
+            read_u24_be,
+            read_u24_le,
+            read_u24_ne,
+            ReadU24,
+            "an unsigned 24 bit integer"
+        ),
+        (
+            read_i24,
+            read_i24_be,
+            read_i24_le,
+            read_i24_ne,
+            ReadI24,
+            "a signed 24 bit integer"
+        ),
+        (
+            read_u32,
+            read_u32_be,
+            read_u32_le,
+            read_u32_ne,
+            ReadU32,
+            "an unsigned 32 bit integer"
+        ),
+        (
+            read_i32,
+            read_i32_be,
+            read_i32_le,
+            read_i32_ne,
+            ReadI32,
+            "a signed 32 bit integer"
+        ),
+        (
+            read_u48,
+            read_u48_be,
+            read_u48_le,
+            read_u48_ne,
+            ReadU48,
+            "an unsigned 48 bit integer"
+        ),
+        (
+            read_i48,
+            read_i48_be,
+            read_i48_le,
+            read_i48_ne,
+            ReadI48,
+            "a signed 48 bit integer"
+        ),
+        (
+            read_u64,
+            read_u64_be,
+            read_u64_le,
+            read_u64_ne,
+            ReadU64,
+            "an unsigned 64 bit integer"
+        ),
+        (
+            read_i64,
+            read_i64_be,
+            read_i64_le,
+            read_i64_ne,
+            ReadI64,
+            "a signed 64 bit integer"
+        ),
+        (
+            read_u128,
+            read_u128_be,
+            read_u128_le,
+            read_u128_ne,
+            ReadU128,
+            "an unsigned 128 bit integer"
+        ),
+        (
+            read_i128,
+            read_i128_be,
+            read_i128_le,
+            read_i128_ne,
+            ReadI128,
+            "a signed 128 bit integer"
+        ),
+        (
+            read_f32,
+            read_f32_be,
+            read_f32_le,
+            read_f32_ne,
+            ReadF32,
+            "a IEEE754 single-precision floating point number"
+        ),
+        (
+            read_f64,
+            read_f64_be,
+            read_f64_le,
+            read_f64_ne,
+            ReadF64,
+            "a IEEE754 double-precision floating point number"
+        ),
+    );
+
+    /// Reads a number of any [`Primitive`] type from the underlying reader.
+    ///
+    /// This is equivalent to the `read_` method for `V`, but lets code that is generic over the
+    /// type of number it reads pick that method through a type parameter.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Primitive`]: trait.Primitive.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::{AsyncReadBytesExt, LittleEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(vec![0x0b, 0x01, 0x2a]);
+    ///     assert_eq!(267, rdr.read_primitive::<u16, LittleEndian>().await.unwrap());
+    ///     assert_eq!(42, rdr.read_primitive::<i8, LittleEndian>().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_primitive<V: Primitive, T: ByteOrder>(&mut self) -> ReadPrimitive<&mut Self, V>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, V::decoder::<T>())
+    }
+
+    /// Reads several [`Primitive`] values, all in the byte order `T`, with as few reads from
+    /// the underlying reader as possible.
+    ///
+    /// Reading each field of a header with its own `read_` method costs one read per field,
+    /// which adds up against an unbuffered socket. This method instead works out the combined
+    /// length of the fields up front and fills it in one go, only reading again if the reader
+    /// hands out fewer bytes than were asked for. Tuples of up to twelve fields are supported.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If it fails, none of the
+    /// values are returned, even if the bytes for some of them had been read.
+    ///
+    /// [`Primitive`]: trait.Primitive.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x01, 0x01, 0x0b, 0xff, 0xff, 0xff, 0xfe][..];
+    ///     let (version, len, id) = rdr
+    ///         .read_tuple::<(u8, u16, i32), BigEndian>()
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!((version, len, id), (1, 267, -2));
+    /// }
+    /// ```
+    #[inline]
+    fn read_tuple<V: PrimitiveTuple, T: ByteOrder>(&mut self) -> ReadTuple<&mut Self, V>
+    where
+        Self: Unpin,
+    {
+        ReadTuple::new::<T>(self)
+    }
+
+    /// Reads a IEEE754 single-precision (4 bytes) floating point number from the underlying
+    /// reader, replacing any NaN with the canonical one.
+    ///
+    /// This is otherwise identical to [`read_f32`](#method.read_f32). Every NaN, whatever its
+    /// sign and payload, comes out as [`CANONICAL_NAN_F32`], so values read this way can be
+    /// hashed or compared bit for bit. Deterministic simulations, lockstep networking, and
+    /// replicated state machines rely on this.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`CANONICAL_NAN_F32`]: sans_io/constant.CANONICAL_NAN_F32.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(vec![0xff, 0xc0, 0x12, 0x34]);
+    ///     let n = rdr.read_f32_canonical::<BigEndian>().await.unwrap();
+    ///     assert_eq!(n.to_bits(), 0x7fc0_0000);
+    /// }
+    /// ```
+    #[inline]
+    fn read_f32_canonical<T: ByteOrder>(&mut self) -> ReadPrimitive<&mut Self, f32>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, sans_io::FixedDecoder::f32_canonical::<T>())
+    }
+
+    /// Reads a IEEE754 double-precision (8 bytes) floating point number from the underlying
+    /// reader, replacing any NaN with the canonical one.
+    ///
+    /// This is otherwise identical to [`read_f64`](#method.read_f64). Every NaN comes out as
+    /// [`CANONICAL_NAN_F64`]; see [`read_f32_canonical`](#method.read_f32_canonical).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`CANONICAL_NAN_F64`]: sans_io/constant.CANONICAL_NAN_F64.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_f64_canonical<T: ByteOrder>(&mut self) -> ReadPrimitive<&mut Self, f64>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, sans_io::FixedDecoder::f64_canonical::<T>())
+    }
+
+    /// Reads a flags field of the wire type `V` into the [bitflags] type `F`.
+    ///
+    /// The field is read like [`read_primitive`](#method.read_primitive) would read a `V`, and
+    /// then converted to the bits of `F`, which may be wider. Bits that are set in the field but
+    /// not defined by `F` are handled according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. With
+    /// [`UnknownBits::Error`], it also returns an error of the kind [`ErrorKind::InvalidData`]
+    /// if the field has unknown bits set.
+    ///
+    /// [bitflags]: https://docs.rs/bitflags/2/bitflags/
+    /// [`UnknownBits::Error`]: enum.UnknownBits.html#variant.Error
+    /// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bitflags::bitflags;
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, UnknownBits};
+    ///
+    /// bitflags! {
+    ///     #[derive(Debug, PartialEq)]
+    ///     struct Perms: u32 {
+    ///         const READ = 0b001;
+    ///         const WRITE = 0b010;
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x00, 0x03, 0x00, 0x05][..];
+    ///     let perms = rdr.read_flags::<Perms, u16, BigEndian>(UnknownBits::Error).await;
+    ///     assert_eq!(perms.unwrap(), Perms::READ | Perms::WRITE);
+    ///     let perms = rdr.read_flags::<Perms, u16, BigEndian>(UnknownBits::Error).await;
+    ///     assert!(perms.is_err());
+    /// }
+    /// ```
+    #[cfg(feature = "bitflags")]
+    #[inline]
+    fn read_flags<F, V, T>(&mut self, policy: UnknownBits) -> ReadFlags<&mut Self, F, V>
+    where
+        Self: Unpin,
+        F: bitflags::Flags,
+        F::Bits: From<V>,
+        V: Primitive,
+        T: ByteOrder,
+    {
+        ReadFlags::new(ReadPrimitive::new(self, V::decoder::<T>()), policy)
+    }
+
+    // TODO: read_*_into
+
+    /// Reads a DNS domain name encoded as a sequence of length-prefixed
+    /// labels (RFC 1035 §3.1) and returns it in dotted form.
+    ///
+    /// The labels are joined with `.` and no trailing dot is included, except
+    /// for the root name, which is returned as `"."`. `max_len` bounds the
+    /// number of bytes the encoded name may occupy on the wire, including
+    /// the length octets and the terminating zero-length label. RFC 1035
+    /// limits names to 255 bytes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of kind `InvalidData` is returned if the name
+    /// is longer than `max_len`, if it contains a compression pointer (which
+    /// cannot be followed on a stream), if it uses a reserved label type, or
+    /// if it is not valid UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read a DNS name from a `Read`:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(b"\x03www\x07example\x03com\x00".to_vec());
+    ///     assert_eq!("www.example.com", rdr.read_dns_name(255).await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_dns_name(&mut self, max_len: usize) -> ReadDnsName<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadDnsName::new(self, max_len)
+    }
+
+    /// Reads `n` packed 4-bit values, two to a byte, and returns them one per byte.
+    ///
+    /// `order` says which half of each byte comes first; see [`Nibbles`]. If `n` is odd, the
+    /// byte holding the last nibble is consumed in full, and its other half is dropped. This
+    /// is how BCD-encoded phone numbers and other digit strings are commonly stored.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Nibbles`]: struct.Nibbles.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read a five-digit number stored as swapped BCD, padded with `0xf`:
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BitOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x21, 0x43, 0xf5][..];
+    ///     let digits = rdr.read_nibbles(5, BitOrder::LsbFirst).await.unwrap();
+    ///     assert_eq!(digits, [1, 2, 3, 4, 5]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_nibbles(&mut self, n: usize, order: BitOrder) -> ReadNibbles<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadNibbles::new(self, n, order)
+    }
+
+    /// Reads packed 24 bit signed integers, three bytes each, until `dst` is full.
+    ///
+    /// Each value is sign-extended into an `i32`, and then scaled according to `scaling`. All
+    /// of the bytes are read before any of them are decoded, which is much faster than calling
+    /// [`read_i24`](#method.read_i24) once per value when there are many of them, as with
+    /// 24 bit audio.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If it fails, the contents
+    /// of `dst` are left as they were.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, I24Scaling, LittleEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x01, 0x00, 0x00, 0xff, 0xff, 0xff][..];
+    ///     let mut samples = [0; 2];
+    ///     rdr.read_i24_into_unpacked::<LittleEndian>(&mut samples, I24Scaling::LeftJustify)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(samples, [0x100, -0x100]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_i24_into_unpacked<'a, T: ByteOrder>(
+        &'a mut self,
+        dst: &'a mut [i32],
+        scaling: I24Scaling,
+    ) -> ReadI24Into<'a, Self, T>
+    where
+        Self: Unpin,
+    {
+        ReadI24Into::new(self, dst, scaling)
+    }
+
+    /// Reads a block of group varint encoded integers until `dst` is full.
+    ///
+    /// Group varint stores each value in as few little-endian bytes as it fits in, one to
+    /// four, and groups the values in fours behind a tag byte that holds the length, less
+    /// one, of each of them in two bits, the first value's in the highest two. Search engines
+    /// store posting lists this way, since a whole group can be decoded without a branch per
+    /// byte. If `dst.len()` is not a multiple of four, the last group holds only the values
+    /// that are left, and the unused bits of its tag are zero.
+    ///
+    /// Each tag is read on its own before the group it describes, so wrap unbuffered readers
+    /// in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If it fails, the contents
+    /// of `dst` are left as they were.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0b00_00_01_10, 1, 15, 0xff, 0x01, 0xff, 0xff, 0x01][..];
+    ///     let mut ids = [0; 4];
+    ///     rdr.read_group_varint_u32(&mut ids).await.unwrap();
+    ///     assert_eq!(ids, [1, 15, 511, 131071]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_group_varint_u32<'a>(&'a mut self, dst: &'a mut [u32]) -> ReadVarintBlock<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadVarintBlock::group(self, dst)
+    }
+
+    /// Reads a block of Stream VByte encoded integers until `dst` is full.
+    ///
+    /// Stream VByte stores each value in as few little-endian bytes as it fits in, one to
+    /// four, like [group varint](#method.read_group_varint_u32) does, but puts all of the tag
+    /// bytes in front of all of the data. Each tag holds the lengths, less one, of four values
+    /// in two bits each, the first value's in the lowest two. The unused bits of the last tag
+    /// are zero.
+    ///
+    /// The block is read with two reads at most: one for the tags, and one for the data.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If it fails, the contents
+    /// of `dst` are left as they were.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0b00_00_01_00, 0b11, 7, 0x00, 0x01, 9, 3, 0x78, 0x56, 0x34, 0x12][..];
+    ///     let mut counts = [0; 5];
+    ///     rdr.read_stream_vbyte_u32(&mut counts).await.unwrap();
+    ///     assert_eq!(counts, [7, 0x100, 9, 3, 0x12345678]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_stream_vbyte_u32<'a>(&'a mut self, dst: &'a mut [u32]) -> ReadVarintBlock<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadVarintBlock::stream_vbyte(self, dst)
+    }
+
+    /// Reads an unsigned LEB128 varint.
+    ///
+    /// Each byte holds seven bits of the value, the least significant first, and has its top
+    /// bit set if another byte follows. This is the varint of protocol buffers, WebAssembly,
+    /// and DWARF, among others. The bytes are read one at a time, so wrap unbuffered readers
+    /// in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in a `u64`.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xe5, 0x8e, 0x26][..];
+    ///     assert_eq!(rdr.read_varint_u64().await.unwrap(), 624485);
+    /// }
+    /// ```
+    #[inline]
+    fn read_varint_u64(&mut self) -> ReadVarint<&mut Self, u64>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, Ok)
+    }
+
+    /// Reads a zigzag-encoded signed integer stored in an unsigned LEB128 varint.
+    ///
+    /// This is how protocol buffers store `sint64`s, and how Avro and Thrift's compact
+    /// protocol store all of their integers; see [`zigzag_decode`] for how the sign is
+    /// folded in.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// [`zigzag_decode`]: fn.zigzag_decode.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x03, 0xcf, 0x0f][..];
+    ///     assert_eq!(rdr.read_zigzag_varint().await.unwrap(), -2);
+    ///     assert_eq!(rdr.read_zigzag_varint().await.unwrap(), -1000);
+    /// }
+    /// ```
+    #[inline]
+    fn read_zigzag_varint(&mut self) -> ReadVarint<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, |n| Ok(zigzag_decode(n)))
+    }
+
+    /// Reads `count` delta-encoded integers, each stored as the zigzag-encoded difference from
+    /// the one before it in an unsigned LEB128 varint.
+    ///
+    /// The first value is stored as its difference from zero. Zigzag encoding maps small
+    /// differences of either sign to small varints, so a slowly changing sequence, like a run
+    /// of timestamps or of sorted IDs, takes a byte or two per value. The differences are
+    /// added with wrapping arithmetic, so a sequence of `u64`s cast to `i64` decodes as well.
+    ///
+    /// The bytes are read one at a time, so wrap unbuffered readers in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xd0, 0x0f, 0x02, 0x04, 0x01][..];
+    ///     let timestamps = rdr.read_delta_varint_sequence(4).await.unwrap();
+    ///     assert_eq!(timestamps, [1000, 1001, 1003, 1002]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_delta_varint_sequence(&mut self, count: usize) -> ReadDeltaVarints<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadDeltaVarints::new(self, count)
+    }
+
+    /// Reads the head of a CBOR data item: its major type, and the argument that follows it.
+    ///
+    /// The head is the initial byte, whose top three bits are the major type and whose low
+    /// five bits either hold an argument below 24 or say that the argument takes the next
+    /// one, two, four, or eight bytes, in big-endian order (RFC 8949 §3). This is all it takes
+    /// to walk small CBOR fragments embedded in another protocol; see [`MajorType`] for what
+    /// the argument means. The content of strings, and the items of arrays, maps, and tags,
+    /// are left to be read next.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the initial byte marks
+    /// an indefinite-length item or a break, or uses additional information that RFC 8949
+    /// reserves. The initial byte is consumed either way.
+    ///
+    /// [`MajorType`]: enum.MajorType.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, MajorType};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // [1000, "a"]
+    ///     let mut rdr = &[0x82, 0x19, 0x03, 0xe8, 0x61, b'a'][..];
+    ///     assert_eq!(rdr.read_cbor_head().await.unwrap(), (MajorType::Array, 2));
+    ///     assert_eq!(rdr.read_cbor_head().await.unwrap(), (MajorType::Unsigned, 1000));
+    ///     assert_eq!(rdr.read_cbor_head().await.unwrap(), (MajorType::Text, 1));
+    ///     assert_eq!(rdr, b"a");
+    /// }
+    /// ```
+    #[inline]
+    fn read_cbor_head(&mut self) -> ReadCborHead<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadCborHead::new(self)
+    }
+
+    /// Reads a MessagePack integer, in any of its formats, as an `i64`.
+    ///
+    /// MessagePack stores integers in a positive or negative fixint, which fits the value
+    /// into the marker byte itself, or behind a marker that gives the width and signedness of
+    /// the big-endian value after it. This reads any of them, which is all it takes to pick
+    /// out msgpack scalars embedded in another format.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the marker is not that
+    /// of an integer, or if the value is a `uint 64` above `i64::MAX`. The marker is consumed
+    /// either way.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x2a, 0xe0, 0xcd, 0x01, 0x00, 0xd0, 0x80][..];
+    ///     assert_eq!(rdr.read_msgpack_int().await.unwrap(), 42);
+    ///     assert_eq!(rdr.read_msgpack_int().await.unwrap(), -32);
+    ///     assert_eq!(rdr.read_msgpack_int().await.unwrap(), 256);
+    ///     assert_eq!(rdr.read_msgpack_int().await.unwrap(), -128);
+    /// }
+    /// ```
+    #[inline]
+    fn read_msgpack_int(&mut self) -> ReadMsgpack<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadMsgpack::int(self)
+    }
+
+    /// Reads a MessagePack `float 32` or `float 64` as an `f64`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the marker is not that
+    /// of a float. The marker is consumed either way.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xca, 0x3f, 0xc0, 0x00, 0x00][..];
+    ///     assert_eq!(rdr.read_msgpack_f64().await.unwrap(), 1.5);
+    /// }
+    /// ```
+    #[inline]
+    fn read_msgpack_f64(&mut self) -> ReadMsgpack<&mut Self, f64>
+    where
+        Self: Unpin,
+    {
+        ReadMsgpack::float(self)
+    }
+
+    /// Reads an Avro `int`: a zigzag-encoded varint that must fit in an `i32`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in an `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x7f, 0x80, 0x01][..];
+    ///     assert_eq!(rdr.read_avro_int().await.unwrap(), -64);
+    ///     assert_eq!(rdr.read_avro_int().await.unwrap(), 64);
+    /// }
+    /// ```
+    #[inline]
+    fn read_avro_int(&mut self) -> ReadVarint<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, crate::avro::decode_int)
+    }
+
+    /// Reads an Avro `long`: a zigzag-encoded varint.
+    ///
+    /// This is the same as [`read_zigzag_varint`](#method.read_zigzag_varint), under the name
+    /// the Avro specification gives it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    #[inline]
+    fn read_avro_long(&mut self) -> ReadVarint<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        self.read_zigzag_varint()
+    }
+
+    /// Reads an Avro `float`: a little-endian IEEE754 single-precision number.
+    ///
+    /// This is the same as [`read_f32_le`](#method.read_f32_le).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_avro_float(&mut self) -> ReadF32<&mut Self, LittleEndian>
+    where
+        Self: Unpin,
+    {
+        ReadF32::new(self)
+    }
+
+    /// Reads an Avro `double`: a little-endian IEEE754 double-precision number.
+    ///
+    /// This is the same as [`read_f64_le`](#method.read_f64_le).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_avro_double(&mut self) -> ReadF64<&mut Self, LittleEndian>
+    where
+        Self: Unpin,
+    {
+        ReadF64::new(self)
+    }
+
+    /// Reads Avro `bytes`: an Avro `long` length, followed by that many bytes.
+    ///
+    /// Avro's `fixed` type has no length in front of it, and can be read with
+    /// [`read_exact`](https://docs.rs/tokio/1/tokio/io/trait.AsyncReadExt.html#method.read_exact).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64)
+    /// and [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the length is negative
+    /// or greater than `max_len`, before anything else is read.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x06, 0x66, 0x6f, 0x6f][..];
+    ///     assert_eq!(rdr.read_avro_bytes(1024).await.unwrap(), b"foo");
+    /// }
+    /// ```
+    #[inline]
+    fn read_avro_bytes(&mut self, max_len: usize) -> ReadAvroBytes<&mut Self, Vec<u8>>
+    where
+        Self: Unpin,
+    {
+        ReadAvroBytes::bytes(self, max_len)
+    }
+
+    /// Reads an Avro `string`: an Avro `long` length, followed by that many bytes of UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_avro_bytes`](#method.read_avro_bytes).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is not valid
+    /// UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x06, 0x66, 0x6f, 0x6f][..];
+    ///     assert_eq!(rdr.read_avro_string(1024).await.unwrap(), "foo");
+    /// }
+    /// ```
+    #[inline]
+    fn read_avro_string(&mut self, max_len: usize) -> ReadAvroBytes<&mut Self, String>
+    where
+        Self: Unpin,
+    {
+        ReadAvroBytes::string(self, max_len)
+    }
+
+    /// Reads a Thrift compact protocol `i16`: a zigzag-encoded varint that must fit in an
+    /// `i16`.
+    ///
+    /// The compact protocol's `i64` is read with
+    /// [`read_zigzag_varint`](#method.read_zigzag_varint), and all three are written with
+    /// [`write_zigzag_varint`](trait.AsyncWriteBytesExt.html#method.write_zigzag_varint).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in an `i16`.
+    #[inline]
+    fn read_thrift_i16(&mut self) -> ReadVarint<&mut Self, i16>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, crate::thrift::decode_i16)
+    }
+
+    /// Reads a Thrift compact protocol `i32`: a zigzag-encoded varint that must fit in an
+    /// `i32`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in an `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xd0, 0x0f][..];
+    ///     assert_eq!(rdr.read_thrift_i32().await.unwrap(), 1000);
+    /// }
+    /// ```
+    #[inline]
+    fn read_thrift_i32(&mut self) -> ReadVarint<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, crate::thrift::decode_i32)
+    }
+
+    /// Reads the header of a field of a struct encoded with Thrift's compact protocol, or
+    /// `None` if it is the stop byte that ends the struct.
+    ///
+    /// Field ids are usually stored as the difference from `last_id`, the id of the field
+    /// before, in the top four bits of the byte that holds the type. When the difference
+    /// does not fit there, the byte holds only the type, and the id follows as a
+    /// zigzag-encoded varint. Pass 0 as `last_id` for the first field of a struct.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the header has no type
+    /// but is not a stop byte, or if the field id does not fit in an `i16`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, ThriftFieldHeader};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // field 1, an i32 of 21; then field 300, a true boolean; then stop
+    ///     let mut rdr = &[0x15, 0x2a, 0x01, 0xd8, 0x04, 0x00][..];
+    ///     let header = rdr.read_thrift_field_header(0).await.unwrap().unwrap();
+    ///     assert_eq!(header, ThriftFieldHeader { id: 1, field_type: 5 });
+    ///     assert_eq!(rdr.read_thrift_i32().await.unwrap(), 21);
+    ///     let header = rdr.read_thrift_field_header(1).await.unwrap().unwrap();
+    ///     assert_eq!(header, ThriftFieldHeader { id: 300, field_type: 1 });
+    ///     assert_eq!(rdr.read_thrift_field_header(300).await.unwrap(), None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_thrift_field_header(&mut self, last_id: i16) -> ReadThriftFieldHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadThriftFieldHeader::new(self, last_id)
+    }
+
+    /// Reads the header of an object in a git packfile, and returns its type and its size.
+    ///
+    /// The type is in bits 4 to 6 of the first byte: 1 to 4 for a commit, tree, blob, or tag,
+    /// 6 for an `OFS_DELTA`, and 7 for a `REF_DELTA`. The low four bits of the first byte are
+    /// the low bits of the size, and every byte after it adds seven more bits, least
+    /// significant first, for as long as the top bit of the byte before is set. The size is
+    /// that of the object once inflated.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the type is 0 or 5, which
+    /// git does not use, or if the size overflows a `u64`.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a blob of 300 bytes
+    ///     let mut rdr = &[0xbc, 0x12][..];
+    ///     assert_eq!(rdr.read_git_object_header().await.unwrap(), (3, 300));
+    /// }
+    /// ```
+    #[inline]
+    fn read_git_object_header(&mut self) -> ReadGitObjectHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadGitObjectHeader::new(self)
+    }
+
+    /// Reads the offset that follows the header of an `OFS_DELTA` object in a git packfile:
+    /// how far back from that header the delta's base object starts.
+    ///
+    /// Unlike a LEB128 varint, the most significant seven bits come first, and every byte
+    /// after the first adds one before the offset so far is shifted, so that no two encodings
+    /// have the same value.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the offset overflows a
+    /// `u64`.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x7f, 0x80, 0x00, 0x81, 0x2c][..];
+    ///     assert_eq!(rdr.read_git_ofs_delta_offset().await.unwrap(), 127);
+    ///     assert_eq!(rdr.read_git_ofs_delta_offset().await.unwrap(), 128);
+    ///     assert_eq!(rdr.read_git_ofs_delta_offset().await.unwrap(), 300);
+    /// }
+    /// ```
+    #[inline]
+    fn read_git_ofs_delta_offset(&mut self) -> ReadGitOfsDeltaOffset<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadGitOfsDeltaOffset::new(self)
+    }
+
+    /// Reads an unsigned integer in the UTF-8 like coding that FLAC frame headers use for
+    /// their frame and sample numbers.
+    ///
+    /// The number of leading one bits in the first byte is the number of bytes in the number
+    /// (none for a single byte), and the bits after the zero that ends them are the most
+    /// significant bits of the value. Every byte after it is of the form `10xxxxxx`, and adds
+    /// six more bits. This extends UTF-8 to up to seven bytes, which hold 36 bits. Encodings
+    /// that are longer than they need to be are accepted.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the first byte is of the
+    /// form `10xxxxxx` or is `0xff`, or if a byte after it is not of the form `10xxxxxx`.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x7f, 0xc2, 0x80, 0xe2, 0x82, 0xac][..];
+    ///     assert_eq!(rdr.read_utf8_coded_u64().await.unwrap(), 0x7f);
+    ///     assert_eq!(rdr.read_utf8_coded_u64().await.unwrap(), 0x80);
+    ///     assert_eq!(rdr.read_utf8_coded_u64().await.unwrap(), 0x20ac);
+    /// }
+    /// ```
+    #[inline]
+    fn read_utf8_coded_u64(&mut self) -> ReadUtf8Coded<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadUtf8Coded::new(self)
+    }
+
+    /// Reads a fixed-size header that ends with a checksum over the bytes before it, and parses
+    /// those bytes only once the checksum matches.
+    ///
+    /// The header is `len` bytes that `checksum` covers, followed by the checksum itself in the
+    /// byte order `T`. All of it is read before `checksum` is reset and computed over the
+    /// first `len` bytes; if that matches the stored checksum, `parse` is called with them and
+    /// its result returned. A header that was damaged in transit is thus never parsed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the checksums differ.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, ByteOrder, Crc16};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a message type and a length, then their CRC-16/XMODEM
+    ///     let mut rdr = &[0x01, 0x00, 0x10, 0x25, 0x01][..];
+    ///     let (kind, len) = rdr
+    ///         .read_checked_header::<_, BigEndian, _, _>(3, Crc16::xmodem(), |header| {
+    ///             (header[0], BigEndian::read_u16(&header[1..]))
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!((kind, len), (1, 16));
+    /// }
+    /// ```
+    #[cfg(feature = "crc")]
+    #[inline]
+    fn read_checked_header<C, T, F, V>(
+        &mut self,
+        len: usize,
+        checksum: C,
+        parse: F,
+    ) -> ReadCheckedHeader<&mut Self, C, T, F>
+    where
+        Self: Unpin,
+        C: Checksum,
+        C::Output: Primitive,
+        T: ByteOrder,
+        F: FnOnce(&[u8]) -> V,
+    {
+        ReadCheckedHeader::new(self, len, checksum, parse)
+    }
+
+    /// Reads a MAVLink 1 or 2 packet, and checks its checksum.
+    ///
+    /// The packet must start at the next byte, with the magic byte of either version. Its
+    /// checksum is seeded with the `CRC_EXTRA` byte of its message, which `crc_extra` returns
+    /// given the message id; see [`mavlink_crc`]. A signed MAVLink 2 packet is read along with
+    /// its signature, but the signature is not verified.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the packet does not start
+    /// with a magic byte, if it has incompatibility flags other than the one for signing, if
+    /// `crc_extra` returns `None` for its message id, or if the checksums differ.
+    ///
+    /// [`mavlink_crc`]: fn.mavlink_crc.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, MavlinkVersion};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a MAVLink 2 HEARTBEAT, whose CRC_EXTRA is 50
+    ///     let mut rdr = &[
+    ///         0xfd, 0x09, 0x00, 0x00, 0x07, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///         0x02, 0x03, 0x51, 0x04, 0x03, 0xa4, 0xac,
+    ///     ][..];
+    ///     let frame = rdr
+    ///         .read_mavlink_frame(|msgid| if msgid == 0 { Some(50) } else { None })
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(frame.version, MavlinkVersion::V2);
+    ///     assert_eq!((frame.seq, frame.sysid, frame.compid), (7, 1, 1));
+    ///     assert_eq!(frame.payload[4], 2);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mavlink_frame<F>(&mut self, crc_extra: F) -> ReadMavlinkFrame<&mut Self, F>
+    where
+        Self: Unpin,
+        F: FnMut(u32) -> Option<u8>,
+    {
+        ReadMavlinkFrame::new(self, crc_extra)
+    }
+
+    /// Reads the 20-byte header of a STUN message.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the top two bits of the
+    /// message type are set, if the magic cookie is not [`STUN_MAGIC_COOKIE`], or if the length
+    /// is not a multiple of 4.
+    ///
+    /// [`STUN_MAGIC_COOKIE`]: constant.STUN_MAGIC_COOKIE.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a binding request with no attributes
+    ///     let mut rdr = &[
+    ///         0x00, 0x01, 0x00, 0x00, 0x21, 0x12, 0xa4, 0x42, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12,
+    ///     ][..];
+    ///     let header = rdr.read_stun_header().await.unwrap();
+    ///     assert_eq!(header.message_type, 0x0001);
+    ///     assert_eq!(header.length, 0);
+    ///     assert_eq!(header.transaction_id, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_stun_header(&mut self) -> ReadStunHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadStunHeader::new(self)
+    }
+
+    /// Reads an attribute of a STUN message: its type, the length of its value as a big-endian
+    /// `u16`, and its value, followed by zeros that pad it to a multiple of four bytes.
+    ///
+    /// The padding is read and discarded. Values longer than `max_len` bytes are rejected
+    /// before any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value is longer than
+    /// `max_len`.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a SOFTWARE attribute, then a FINGERPRINT
+    ///     let mut rdr = &[
+    ///         0x80, 0x22, 0x00, 0x03, b'a', b'b', b'c', 0x00,
+    ///         0x80, 0x28, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef,
+    ///     ][..];
+    ///     let attr = rdr.read_stun_attribute(1024).await.unwrap();
+    ///     assert_eq!((attr.attr_type, &attr.value[..]), (0x8022, &b"abc"[..]));
+    ///     let attr = rdr.read_stun_attribute(1024).await.unwrap();
+    ///     assert_eq!(attr.attr_type, 0x8028);
+    /// }
+    /// ```
+    #[inline]
+    fn read_stun_attribute(&mut self, max_len: usize) -> ReadStunAttribute<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadStunAttribute::new(self, max_len)
+    }
+
+    /// Reads the fixed header of an RTP packet, along with the list of contributing sources
+    /// that follows it.
+    ///
+    /// The first two bytes pack the version, the padding and extension flags, the number of
+    /// contributing sources, the marker bit, and the payload type; the sequence number,
+    /// timestamp, and sources follow in network byte order. A header extension, if the header
+    /// has one, is left unread. To parse a packet that is already in memory, use
+    /// [`RtpHeader::from_slice`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the version is not 2.
+    ///
+    /// [`RtpHeader::from_slice`]: struct.RtpHeader.html#method.from_slice
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // one contributing source
+    ///     let mut rdr = &[
+    ///         0x81, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x00, 0x01,
+    ///         0x00, 0x00, 0x00, 0x02,
+    ///     ][..];
+    ///     let header = rdr.read_rtp_header().await.unwrap();
+    ///     assert_eq!(header.sequence_number, 1);
+    ///     assert_eq!(header.timestamp, 160);
+    ///     assert_eq!(header.csrcs, [2]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_rtp_header(&mut self) -> ReadRtpHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadRtpHeader::new(self)
+    }
+
+    /// Reads the header of a binary frame interleaved in an RTSP connection (RFC 2326 §10.12),
+    /// and returns its channel and the length of the data that follows it.
+    ///
+    /// This is how RTP and RTCP packets are carried when they are tunneled over the RTSP TCP
+    /// connection: a `$`, a one byte channel identifier, and the length of the packet as a
+    /// big-endian `u16`. The channels are the ones negotiated by the `interleaved` parameter of
+    /// the `Transport` header, usually an even one for RTP and the odd one after it for RTCP.
+    ///
+    /// Since RTSP responses and requests share the connection, callers usually peek at the next
+    /// byte with [`fill_buf`] to tell a frame, which starts with `$`, from a message.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the header does not start
+    /// with `$`.
+    ///
+    /// [`fill_buf`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufReadExt.html#method.fill_buf
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[b'$', 0x01, 0x00, 0x1c][..];
+    ///     let (channel, len) = rdr.read_rtsp_interleaved_header().await.unwrap();
+    ///     assert_eq!(channel, 1);
+    ///     assert_eq!(len, 28);
+    /// }
+    /// ```
+    #[inline]
+    fn read_rtsp_interleaved_header(&mut self) -> ReadRtspInterleavedHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadRtspInterleavedHeader::new(self)
+    }
+
+    /// Reads the header of a 9P2000 message: its size, its type, and its tag, all
+    /// little-endian.
+    ///
+    /// The size counts the whole message, these seven bytes of header included.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a Tversion with no tag
+    ///     let mut rdr = &[0x13, 0x00, 0x00, 0x00, 100, 0xff, 0xff][..];
+    ///     assert_eq!(rdr.read_9p_header().await.unwrap(), (19, 100, 0xffff));
+    /// }
+    /// ```
+    #[inline]
+    fn read_9p_header(&mut self) -> ReadTuple<&mut Self, (u32, u8, u16)>
+    where
+        Self: Unpin,
+    {
+        ReadTuple::new::<LittleEndian>(self)
+    }
+
+    /// Reads a 9P2000 string: its length in bytes as a little-endian `u16`, followed by that
+    /// many bytes of UTF-8.
+    ///
+    /// Strings longer than `max_len` bytes are rejected before any memory is allocated for
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is longer than
+    /// `max_len`, or is not valid UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x06\x009P2000"[..];
+    ///     assert_eq!(rdr.read_9p_string(8192).await.unwrap(), "9P2000");
+    /// }
+    /// ```
+    #[inline]
+    fn read_9p_string(&mut self, max_len: usize) -> Read9pString<&mut Self>
+    where
+        Self: Unpin,
+    {
+        Read9pString::new(self, max_len)
+    }
+
+    /// Reads a 9P2000 [`Qid`]: its type, then its version and path, little-endian.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Qid`]: struct.Qid.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, Qid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x80, 1, 0, 0, 0, 0x2a, 0, 0, 0, 0, 0, 0, 0][..];
+    ///     let qid = rdr.read_9p_qid().await.unwrap();
+    ///     assert_eq!(qid, Qid { qid_type: 0x80, version: 1, path: 42 });
+    /// }
+    /// ```
+    #[inline]
+    fn read_9p_qid(&mut self) -> ReadPrimitive<&mut Self, Qid>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, crate::ninep::qid_decoder())
+    }
+
+    /// Reads the header of a message of the PostgreSQL frontend/backend protocol: its type
+    /// byte and its big-endian `i32` length, and returns the type and the length of the body
+    /// that follows.
+    ///
+    /// The length on the wire counts itself but not the type byte, so four is taken off it.
+    /// The fields of the body are big-endian, and can be read with methods like
+    /// [`read_i16`](#method.read_i16) and [`read_i32`](#method.read_i32). The startup message
+    /// that opens a connection has no type byte, and so is not read by this method.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the length is less
+    /// than 4.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // ReadyForQuery, idle
+    ///     let mut rdr = &[b'Z', 0x00, 0x00, 0x00, 0x05, b'I'][..];
+    ///     assert_eq!(rdr.read_pg_message_header().await.unwrap(), (b'Z', 1));
+    ///     assert_eq!(rdr.read_u8().await.unwrap(), b'I');
+    /// }
+    /// ```
+    #[inline]
+    fn read_pg_message_header(&mut self) -> ReadPgMessageHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadPgMessageHeader::new(self)
+    }
+
+    /// Reads the header of a MySQL client/server protocol packet: the length of its payload
+    /// as a little-endian 24-bit integer, and its sequence id.
+    ///
+    /// A payload of `0xffffff` bytes is continued by the packet after it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a COM_PING
+    ///     let mut rdr = &[0x01, 0x00, 0x00, 0x00, 0x0e][..];
+    ///     assert_eq!(rdr.read_mysql_packet_header().await.unwrap(), (1, 0));
+    ///     assert_eq!(rdr.read_u8().await.unwrap(), 0x0e);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mysql_packet_header(&mut self) -> ReadPrimitive<&mut Self, (u32, u8)>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, crate::mysql::packet_header_decoder())
+    }
+
+    /// Reads a MySQL length-encoded integer.
+    ///
+    /// A first byte below `0xfb` is the value itself; `0xfc`, `0xfd`, and `0xfe` are followed
+    /// by the value as a little-endian integer of two, three, and eight bytes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the first byte is
+    /// `0xfb`, which stands for `NULL` in a result row, or `0xff`, which starts an error
+    /// packet instead.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xfa, 0xfc, 0xfb, 0x00, 0xfd, 0x00, 0x00, 0x01][..];
+    ///     assert_eq!(rdr.read_mysql_lenenc_int().await.unwrap(), 250);
+    ///     assert_eq!(rdr.read_mysql_lenenc_int().await.unwrap(), 251);
+    ///     assert_eq!(rdr.read_mysql_lenenc_int().await.unwrap(), 1 << 16);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mysql_lenenc_int(&mut self) -> ReadMysqlLenencInt<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadMysqlLenencInt::new(self)
+    }
+
+    /// Reads a MySQL length-encoded string: its length as a length-encoded integer, followed
+    /// by that many bytes, or `None` if it is `NULL`.
+    ///
+    /// The bytes are returned as they are, since their character set depends on the
+    /// connection. Strings longer than `max_len` bytes are rejected before any memory is
+    /// allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_mysql_lenenc_int`](#method.read_mysql_lenenc_int), except that a `NULL` is not an
+    /// error.
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is longer than
+    /// `max_len` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // a text result row of `'abc', NULL`
+    ///     let mut rdr = &b"\x03abc\xfb"[..];
+    ///     assert_eq!(rdr.read_mysql_lenenc_str(1024).await.unwrap().unwrap(), b"abc");
+    ///     assert_eq!(rdr.read_mysql_lenenc_str(1024).await.unwrap(), None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mysql_lenenc_str(&mut self, max_len: usize) -> ReadMysqlLenencStr<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadMysqlLenencStr::new(self, max_len)
+    }
+
+    /// Reads an AMQP 0-9-1 short string: its length as a `u8`, followed by that many bytes of
+    /// UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is not valid
+    /// UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x05amq.direct"[..];
+    ///     assert_eq!(rdr.read_amqp_short_string().await.unwrap(), "amq.d");
+    /// }
+    /// ```
+    #[inline]
+    fn read_amqp_short_string(&mut self) -> ReadAmqpShortString<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadAmqpShortString::new(self)
+    }
+
+    /// Reads an AMQP 0-9-1 long string: its length as a big-endian `u32`, followed by that
+    /// many bytes.
+    ///
+    /// The bytes are returned as they are, since long strings often carry binary data such as
+    /// message bodies or SASL responses. Strings longer than `max_len` bytes are rejected
+    /// before any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_length_prefixed_bytes`](#method.read_length_prefixed_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x00\x00\x00\x09\x00guest\x00pw"[..];
+    ///     let response = rdr.read_amqp_long_string(1024).await.unwrap();
+    ///     assert_eq!(response, &b"\x00guest\x00pw"[..]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_amqp_long_string(&mut self, max_len: usize) -> ReadLengthPrefixedBytes<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        self.read_length_prefixed_bytes::<u32, BigEndian>(max_len)
+    }
+
+    /// Reads an AMQP 0-9-1 field table: its length in bytes as a big-endian `u32`, followed by
+    /// its fields, each a short string name, a type tag, and a value.
+    ///
+    /// The whole table is read before it is decoded, so tables longer than `max_len` bytes are
+    /// rejected before any memory is allocated for them. Nested tables and arrays are decoded
+    /// too, up to 32 levels deep. See [`AmqpValue`] for the type tags that are understood.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the table is longer than
+    /// `max_len` bytes, if it holds a value of an unknown type, a field name that is not valid
+    /// UTF-8, or a value that runs past its end, or if it is nested too deeply.
+    ///
+    /// [`AmqpValue`]: enum.AmqpValue.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AmqpValue, AsyncReadBytesExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x00\x00\x00\x0d\x07durablet\x01\x01xV"[..];
+    ///     let table = rdr.read_amqp_field_table(4096).await.unwrap();
+    ///     assert_eq!(
+    ///         table,
+    ///         [
+    ///             ("durable".to_string(), AmqpValue::Bool(true)),
+    ///             ("x".to_string(), AmqpValue::Void),
+    ///         ]
+    ///     );
+    /// }
+    /// ```
+    #[inline]
+    fn read_amqp_field_table(&mut self, max_len: usize) -> ReadAmqpFieldTable<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadAmqpFieldTable::new(self, max_len)
+    }
+
+    /// Reads a Kafka `VARINT`: a zigzag-encoded varint that must fit in an `i32`.
+    ///
+    /// Kafka's fixed-size `INT16`, `INT32`, and `INT64` are big-endian, and are read with
+    /// [`read_i16`](#method.read_i16) and its siblings.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in an `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x01, 0xd0, 0x0f][..];
+    ///     assert_eq!(rdr.read_kafka_varint().await.unwrap(), -1);
+    ///     assert_eq!(rdr.read_kafka_varint().await.unwrap(), 1000);
+    /// }
+    /// ```
+    #[inline]
+    fn read_kafka_varint(&mut self) -> ReadVarint<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, crate::kafka::decode_varint)
+    }
+
+    /// Reads a Kafka `VARLONG`: a zigzag-encoded varint.
+    ///
+    /// This is the same as [`read_zigzag_varint`](#method.read_zigzag_varint), under the name
+    /// the Kafka protocol gives it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    #[inline]
+    fn read_kafka_varlong(&mut self) -> ReadVarint<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        self.read_zigzag_varint()
+    }
+
+    /// Reads a Kafka `UNSIGNED_VARINT`: an unsigned LEB128 varint that must fit in a `u32`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_varint_u64`](#method.read_varint_u64).
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value does not fit
+    /// in a `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xac, 0x02][..];
+    ///     assert_eq!(rdr.read_kafka_unsigned_varint().await.unwrap(), 300);
+    /// }
+    /// ```
+    #[inline]
+    fn read_kafka_unsigned_varint(&mut self) -> ReadVarint<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        ReadVarint::new(self, crate::kafka::decode_unsigned_varint)
+    }
+
+    /// Reads a Kafka `NULLABLE_STRING`: its length as a big-endian `i16`, followed by that
+    /// many bytes of UTF-8, or `None` if the length is -1.
+    ///
+    /// A non-nullable `STRING` has the same encoding, and is never `None`. Strings longer than
+    /// `max_len` bytes are rejected before any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the length is negative
+    /// but not -1, or greater than `max_len`, or if the string is not valid UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x00\x06client\xff\xff"[..];
+    ///     let client_id = rdr.read_kafka_nullable_string(1024).await.unwrap();
+    ///     assert_eq!(client_id.as_deref(), Some("client"));
+    ///     assert_eq!(rdr.read_kafka_nullable_string(1024).await.unwrap(), None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_kafka_nullable_string(&mut self, max_len: usize) -> ReadKafkaBytes<&mut Self, String>
+    where
+        Self: Unpin,
+    {
+        ReadKafkaBytes::nullable_string(self, max_len)
+    }
+
+    /// Reads a Kafka `COMPACT_NULLABLE_STRING`: its length plus one as an `UNSIGNED_VARINT`,
+    /// followed by that many bytes of UTF-8, or `None` if the varint is 0.
+    ///
+    /// A non-nullable `COMPACT_STRING` has the same encoding, and is never `None`. Strings
+    /// longer than `max_len` bytes are rejected before any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_kafka_unsigned_varint`](#method.read_kafka_unsigned_varint) and
+    /// [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is longer than
+    /// `max_len` bytes, or is not valid UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x07orders\x00"[..];
+    ///     let topic = rdr.read_kafka_compact_string(1024).await.unwrap();
+    ///     assert_eq!(topic.as_deref(), Some("orders"));
+    ///     assert_eq!(rdr.read_kafka_compact_string(1024).await.unwrap(), None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_kafka_compact_string(&mut self, max_len: usize) -> ReadKafkaBytes<&mut Self, String>
+    where
+        Self: Unpin,
+    {
+        ReadKafkaBytes::compact_string(self, max_len)
+    }
+
+    /// Reads Kafka `COMPACT_NULLABLE_BYTES`: their length plus one as an `UNSIGNED_VARINT`,
+    /// followed by that many bytes, or `None` if the varint is 0.
+    ///
+    /// Non-nullable `COMPACT_BYTES` have the same encoding, and are never `None`. Values longer
+    /// than `max_len` bytes are rejected before any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_kafka_unsigned_varint`](#method.read_kafka_unsigned_varint) and
+    /// [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the value is longer than
+    /// `max_len` bytes.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x01\x04abc"[..];
+    ///     assert_eq!(rdr.read_kafka_compact_bytes(1024).await.unwrap().unwrap(), b"");
+    ///     assert_eq!(rdr.read_kafka_compact_bytes(1024).await.unwrap().unwrap(), b"abc");
+    /// }
+    /// ```
+    #[inline]
+    fn read_kafka_compact_bytes(&mut self, max_len: usize) -> ReadKafkaBytes<&mut Self, Vec<u8>>
+    where
+        Self: Unpin,
+    {
+        ReadKafkaBytes::compact_bytes(self, max_len)
+    }
+
+    /// Reads the type of an NBT tag.
+    ///
+    /// The payloads of the numeric tags are big-endian, and are read with
+    /// [`read_i8`](#method.read_i8), [`read_i16`](#method.read_i16), and their siblings.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the type is not one of
+    /// those in [`NbtTag`].
+    ///
+    /// [`NbtTag`]: enum.NbtTag.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, NbtTag};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x03\x00\x05level\x00\x00\x00\x2a"[..];
+    ///     assert_eq!(rdr.read_nbt_tag().await.unwrap(), NbtTag::Int);
+    ///     assert_eq!(rdr.read_nbt_string(256).await.unwrap(), "level");
+    ///     assert_eq!(rdr.read_i32::<BigEndian>().await.unwrap(), 42);
+    /// }
+    /// ```
+    #[inline]
+    fn read_nbt_tag(&mut self) -> ReadNbtTag<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadNbtTag::new(self)
+    }
+
+    /// Reads an NBT string: its length in bytes as a big-endian `u16`, followed by that many
+    /// bytes of Java's modified UTF-8.
+    ///
+    /// Modified UTF-8 writes NUL as the two bytes `0xc0 0x80`, and characters outside the
+    /// Basic Multilingual Plane as the three-byte encodings of their two UTF-16 surrogates.
+    /// Plain UTF-8 is accepted too. Strings longer than `max_len` bytes are rejected before
+    /// any memory is allocated for them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the string is longer than
+    /// `max_len` bytes, or is not valid modified UTF-8.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x00\x04a\xc0\x80b"[..];
+    ///     assert_eq!(rdr.read_nbt_string(256).await.unwrap(), "a\0b");
+    /// }
+    /// ```
+    #[inline]
+    fn read_nbt_string(&mut self, max_len: usize) -> ReadNbtString<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadNbtString::new(self, max_len)
+    }
+
+    /// Reads the payload of an NBT byte array: its length as a big-endian `i32`, followed by
+    /// that many `i8`s.
+    ///
+    /// Arrays longer than `max_len` elements are rejected before any memory is allocated for
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the length is negative or
+    /// greater than `max_len`.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x00, 0x00, 0x00, 0x02, 0x01, 0xff][..];
+    ///     assert_eq!(rdr.read_nbt_byte_array(4096).await.unwrap(), [1, -1]);
+    /// }
+    /// ```
+    #[inline]
+    fn read_nbt_byte_array(&mut self, max_len: usize) -> ReadNbtArray<&mut Self, i8>
+    where
+        Self: Unpin,
+    {
+        ReadNbtArray::new(self, max_len)
+    }
+
+    /// Reads the payload of an NBT int array: its length as a big-endian `i32`, followed by
+    /// that many big-endian `i32`s.
+    ///
+    /// Arrays longer than `max_len` elements are rejected before any memory is allocated for
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_nbt_byte_array`](#method.read_nbt_byte_array).
+    #[inline]
+    fn read_nbt_int_array(&mut self, max_len: usize) -> ReadNbtArray<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadNbtArray::new(self, max_len)
+    }
+
+    /// Reads the payload of an NBT long array: its length as a big-endian `i32`, followed by
+    /// that many big-endian `i64`s.
+    ///
+    /// Arrays longer than `max_len` elements are rejected before any memory is allocated for
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_nbt_byte_array`](#method.read_nbt_byte_array).
+    #[inline]
+    fn read_nbt_long_array(&mut self, max_len: usize) -> ReadNbtArray<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadNbtArray::new(self, max_len)
+    }
+
+    /// Reads a Minecraft protocol VarInt: an `i32` in two's complement, stored as an unsigned
+    /// LEB128 varint of at most five bytes.
+    ///
+    /// Negative values are not zigzag encoded, so they always take all five bytes. This reads
+    /// VarInts the way the vanilla server does: encodings that are longer than they need to
+    /// be are accepted, as long as they fit in five bytes, and bits in the fifth byte that do
+    /// not fit in an `i32` are dropped. The bytes are read one at a time, so wrap unbuffered
+    /// readers in a [`BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the fifth byte has its
+    /// continuation bit set.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio/1/tokio/io/struct.BufReader.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0xdd, 0xc7, 0x01, 0xff, 0xff, 0xff, 0xff, 0x0f][..];
+    ///     assert_eq!(rdr.read_minecraft_varint().await.unwrap(), 25565);
+    ///     assert_eq!(rdr.read_minecraft_varint().await.unwrap(), -1);
+    /// }
+    /// ```
+    #[inline]
+    fn read_minecraft_varint(&mut self) -> ReadMinecraftVarint<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadMinecraftVarint::varint(self)
+    }
+
+    /// Reads a Minecraft protocol VarLong: an `i64` in two's complement, stored as an unsigned
+    /// LEB128 varint of at most ten bytes.
+    ///
+    /// As with [`read_minecraft_varint`](#method.read_minecraft_varint), over-long encodings
+    /// are accepted as long as they fit in ten bytes, and bits that do not fit in an `i64` are
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the tenth byte has its
+    /// continuation bit set.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_minecraft_varlong(&mut self) -> ReadMinecraftVarint<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadMinecraftVarint::varlong(self)
+    }
+
+    /// Reads the header of a DICOM data element: its tag, its value representation if `mode`
+    /// is explicit, and the length of its value.
+    ///
+    /// With explicit VRs, the length is 16 bits for most VRs, and follows two reserved bytes
+    /// and is 32 bits for `OB`, `OW`, `SQ`, `UN`, `UT`, and the other VRs whose values may be
+    /// long. Item and delimitation tags, in group `0xfffe`, carry no VR, and always have a 32
+    /// bit length. `endian` is the byte order of the transfer syntax.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if an explicit VR is not
+    /// two uppercase ASCII letters.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, DicomVrMode, RuntimeEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // Patient's Name, then Pixel Data of undefined length, in Explicit VR Little Endian
+    ///     let mut rdr = &b"\x10\x00\x10\x00PN\x08\x00Doe^John\xe0\x7f\x10\x00OB\x00\x00\xff\xff\xff\xff"[..];
+    ///     let endian = RuntimeEndian::Little;
+    ///
+    ///     let header = rdr
+    ///         .read_dicom_element_header(endian, DicomVrMode::Explicit)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!((header.group, header.element), (0x0010, 0x0010));
+    ///     assert_eq!(header.vr, Some(*b"PN"));
+    ///     assert_eq!(header.length, Some(8));
+    ///     rdr = &rdr[8..];
+    ///
+    ///     let header = rdr
+    ///         .read_dicom_element_header(endian, DicomVrMode::Explicit)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!((header.group, header.element), (0x7fe0, 0x0010));
+    ///     assert_eq!(header.length, None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_dicom_element_header(
+        &mut self,
+        endian: RuntimeEndian,
+        mode: DicomVrMode,
+    ) -> ReadDicomElementHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadDicomElementHeader::new(self, endian, mode)
+    }
+
+    /// Reads a 33 bit PTS or DTS from an MPEG-2 PES header, and returns the four bit prefix in
+    /// front of it along with the timestamp, in 90 kHz ticks.
+    ///
+    /// The timestamp is split across five bytes, with a marker bit after each of its three
+    /// parts. The prefix is `0b0010` for a PTS on its own, and `0b0011` and `0b0001` for a PTS
+    /// and the DTS that follows it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if any of the three marker
+    /// bits is not set.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x21, 0x00, 0x37, 0x77, 0x41][..];
+    ///     let (prefix, pts) = rdr.read_mpeg_pes_timestamp().await.unwrap();
+    ///     assert_eq!(prefix, 0b0010);
+    ///     assert_eq!(pts, 10 * 90_000);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mpeg_pes_timestamp(&mut self) -> ReadMpegTs<&mut Self, (u8, u64)>
+    where
+        Self: Unpin,
+    {
+        ReadMpegTs::pes_timestamp(self)
+    }
+
+    /// Reads a 42 bit PCR from an MPEG-2 transport stream adaptation field, and returns it in
+    /// 27 MHz ticks.
+    ///
+    /// The PCR is six bytes long: a 33 bit base in 90 kHz ticks, six reserved bits, and a nine
+    /// bit extension that counts the 27 MHz ticks in between. The same layout is used for the
+    /// OPCR. The value returned is `base * 300 + extension`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of the kind `InvalidData` is returned if the extension is 300 or
+    /// more.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x00, 0x00, 0xaf, 0xc8, 0x7e, 0x96][..];
+    ///     assert_eq!(rdr.read_mpeg_pcr().await.unwrap(), 27_000_000 + 150);
+    /// }
+    /// ```
+    #[inline]
+    fn read_mpeg_pcr(&mut self) -> ReadMpegTs<&mut Self, u64>
+    where
+        Self: Unpin,
+    {
+        ReadMpegTs::pcr(self)
+    }
+
+    /// Reads a 16 bit Bluetooth UUID in little-endian order, and returns the full UUID that it
+    /// stands for.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BluetoothUuid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x0d, 0x18][..];
+    ///     let uuid = rdr.read_ble_uuid16().await.unwrap();
+    ///     assert_eq!(uuid, BluetoothUuid::from_u16(0x180d));
+    /// }
+    /// ```
+    #[inline]
+    fn read_ble_uuid16(&mut self) -> ReadPrimitive<&mut Self, BluetoothUuid>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, crate::ble::uuid16_decoder())
+    }
+
+    /// Reads a 32 bit Bluetooth UUID in little-endian order, and returns the full UUID that it
+    /// stands for.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BluetoothUuid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[0x78, 0x56, 0x34, 0x12][..];
+    ///     let uuid = rdr.read_ble_uuid32().await.unwrap();
+    ///     assert_eq!(uuid, BluetoothUuid::from_u32(0x1234_5678));
+    /// }
+    /// ```
+    #[inline]
+    fn read_ble_uuid32(&mut self) -> ReadPrimitive<&mut Self, BluetoothUuid>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, crate::ble::uuid32_decoder())
+    }
+
+    /// Reads a 128 bit Bluetooth UUID in little-endian order, which is how it goes on the air.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &[
+    ///         0x9e, 0xca, 0xdc, 0x24, 0x0e, 0xe5, 0xa9, 0xe0,
+    ///         0x93, 0xf3, 0xa3, 0xb5, 0x01, 0x00, 0x40, 0x6e,
+    ///     ][..];
+    ///     let uuid = rdr.read_ble_uuid128().await.unwrap();
+    ///     assert_eq!(uuid.to_string(), "6e400001-b5a3-f393-e0a9-e50e24dcca9e");
+    /// }
+    /// ```
+    #[inline]
+    fn read_ble_uuid128(&mut self) -> ReadPrimitive<&mut Self, BluetoothUuid>
+    where
+        Self: Unpin,
+    {
+        ReadPrimitive::new(self, crate::ble::uuid128_decoder())
+    }
+
+    /// Reads an AD structure from Bluetooth LE advertising or scan response data: a length
+    /// byte, then that many bytes holding the AD type and the data.
+    ///
+    /// A length of zero, which marks the end of the significant part of the data, or the zero
+    /// padding after it, gives `None`. Only the length byte is read in that case.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BleAdStructure};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x02\x01\x06\x05\x09beep\x00"[..];
+    ///     let flags = rdr.read_ble_ad_structure().await.unwrap().unwrap();
+    ///     assert_eq!(flags.ad_type, BleAdStructure::FLAGS);
+    ///     assert_eq!(flags.data, [0x06]);
+    ///     let name = rdr.read_ble_ad_structure().await.unwrap().unwrap();
+    ///     assert_eq!(name.ad_type, BleAdStructure::COMPLETE_LOCAL_NAME);
+    ///     assert_eq!(name.data, b"beep");
+    ///     assert_eq!(rdr.read_ble_ad_structure().await.unwrap(), None);
+    /// }
+    /// ```
+    #[inline]
+    fn read_ble_ad_structure(&mut self) -> ReadBleAdStructure<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadBleAdStructure::new(self)
+    }
+
+    /// Turns this reader into a [`Stream`] of frames that are each prefixed with their length
+    /// as a `u32` in the byte order `T`.
+    ///
+    /// This is the classic length-prefixed framing, as written by
+    /// [`into_framed_u32_sink`](trait.AsyncWriteBytesExt.html#method.into_framed_u32_sink).
+    /// Frames longer than `max_frame` bytes are rejected before any memory is allocated for
+    /// them; see [`FramedU32`] for the details.
+    ///
+    /// [`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html
+    /// [`FramedU32`]: struct.FramedU32.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use futures_lite::StreamExt;
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rdr = &b"\x00\x00\x00\x02hi\x00\x00\x00\x00"[..];
+    ///     let mut frames = rdr.into_framed_u32::<BigEndian>(1024);
+    ///     assert_eq!(frames.next().await.unwrap().unwrap(), "hi");
+    ///     assert_eq!(frames.next().await.unwrap().unwrap(), "");
+    ///     assert!(frames.next().await.is_none());
+    /// }
+    /// ```
+    #[cfg(feature = "framed")]
+    #[inline]
+    fn into_framed_u32<T: ByteOrder>(self, max_frame: usize) -> FramedU32<Self, T>
+    where
+        Self: Sized + Unpin,
+    {
+        FramedU32::new(self, max_frame)
+    }
+
+    /// Wraps the reader so that exactly `n` more bytes can be read from it.
+    ///
+    /// Reading past the limit fails with an error, as does [`expect_exhausted`] if the limit
+    /// was not reached. See [`Limit`].
+    ///
+    /// [`Limit`]: struct.Limit.html
+    /// [`expect_exhausted`]: struct.Limit.html#method.expect_exhausted
+    #[inline]
+    fn limit(self, n: u64) -> Limit<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        Limit::new(self, n)
+    }
+
+    /// Reads a length prefix of type `P` in the byte order `T`, followed by that many bytes,
+    /// into `buf`.
+    ///
+    /// `buf` is cleared first, but keeps its allocation, so reading every message of a
+    /// connection into the same buffer only allocates when a message is larger than any
+    /// before it. The bytes are read straight into the spare capacity of `buf` without being
+    /// zeroed first. See [`ScratchBuffer`] for the buffer types that can be used.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`]. If it fails, `buf` holds
+    /// the part of the value that was read, if any.
+    ///
+    /// In addition, an error of kind `InvalidData` is returned, before anything is read into
+    /// `buf`, if the length prefix is larger than `max_len` or is negative.
+    ///
+    /// [`ScratchBuffer`]: trait.ScratchBuffer.html
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x00\x05hello\x00\x02hi"[..];
+    ///     let mut buf = Vec::new();
+    ///     rdr.read_length_prefixed_into::<u16, BigEndian>(&mut buf, 1024)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(buf, b"hello");
+    ///     rdr.read_length_prefixed_into::<u16, BigEndian>(&mut buf, 1024)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(buf, b"hi");
+    /// }
+    /// ```
+    #[inline]
+    fn read_length_prefixed_into<'a, P, T>(
+        &'a mut self,
+        buf: &'a mut dyn ScratchBuffer,
+        max_len: usize,
+    ) -> ReadLengthPrefixedInto<'a, Self, P>
+    where
+        P: Primitive,
+        T: ByteOrder,
+        usize: TryFrom<P>,
+        Self: Unpin,
+    {
+        ReadLengthPrefixedInto::new(self, buf, P::decoder::<T>(), max_len)
+    }
+
+    /// Reads a length prefix of type `P` in the byte order `T`, followed by that many bytes,
+    /// and returns those bytes as [`Bytes`].
+    ///
+    /// The bytes are read straight into the memory that the returned `Bytes` refers to, so they
+    /// can be handed on to the rest of the [`bytes`] ecosystem without being copied again.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of kind `InvalidData` is returned, before any memory is allocated
+    /// for the value, if the length prefix is larger than `max_len` or is negative.
+    ///
+    /// [`Bytes`]: https://docs.rs/bytes/1/bytes/struct.Bytes.html
+    /// [`bytes`]: https://docs.rs/bytes/1/bytes/
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncReadBytesExt, LittleEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = &b"\x05\x00\x00\x00hello"[..];
+    ///     let payload = rdr
+    ///         .read_length_prefixed_bytes::<u32, LittleEndian>(1024)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(payload, "hello");
+    /// }
+    /// ```
+    #[inline]
+    fn read_length_prefixed_bytes<P, T>(
+        &mut self,
+        max_len: usize,
+    ) -> ReadLengthPrefixedBytes<&mut Self, P>
+    where
+        P: Primitive,
+        T: ByteOrder,
+        usize: TryFrom<P>,
+        Self: Unpin,
+    {
+        ReadLengthPrefixedBytes::new(self, P::decoder::<T>(), max_len)
+    }
+
+    /// Reads the start of an ELF identification header and returns the byte order it declares.
+    ///
+    /// This consumes exactly the first six bytes of `e_ident`: the `\x7fELF` magic, the file
+    /// class (`EI_CLASS`), and the data encoding (`EI_DATA`). The reader is left positioned at
+    /// `EI_VERSION`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of kind `InvalidData` is returned if the magic is missing, or if the
+    /// class or data encoding is not one defined by the ELF specification.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Detect the byte order of a 64-bit little-endian ELF file:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::{AsyncReadBytesExt, RuntimeEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(b"\x7fELF\x02\x01\x01".to_vec());
+    ///     assert_eq!(RuntimeEndian::Little, rdr.detect_endianness_elf().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn detect_endianness_elf(&mut self) -> DetectEndianness<&mut Self>
+    where
+        Self: Unpin,
+    {
+        DetectEndianness::new(self, 6, endian::elf)
+    }
+
+    /// Reads a TIFF byte order mark and returns the byte order it declares.
+    ///
+    /// This consumes exactly two bytes: `II` for little-endian or `MM` for big-endian. The
+    /// reader is left positioned at the magic number that follows.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// In addition, an error of kind `InvalidData` is returned if the two bytes are not a valid
+    /// byte order mark.
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Detect the byte order of a big-endian TIFF file:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::{AsyncReadBytesExt, RuntimeEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(b"MM\x00\x2a".to_vec());
+    ///     assert_eq!(RuntimeEndian::Big, rdr.detect_endianness_tiff().await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn detect_endianness_tiff(&mut self) -> DetectEndianness<&mut Self>
+    where
+        Self: Unpin,
+    {
+        DetectEndianness::new(self, 2, endian::tiff)
+    }
+
+    /// Reads an unsigned 16 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u16`](#method.read_u16).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u16_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u16>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 2, RuntimeCodec::read)
+    }
+
+    /// Reads a signed 16 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i16`](#method.read_i16).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i16_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i16>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 2, RuntimeCodec::read)
+    }
+
+    /// Reads an unsigned 24 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u24`](#method.read_u24).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u24_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 3, |e, b| endian::read_uint(e, b) as u32)
+    }
+
+    /// Reads a signed 24 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i24`](#method.read_i24).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i24_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 3, |e, b| endian::read_int(e, b) as i32)
+    }
+
+    /// Reads an unsigned 32 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u32`](#method.read_u32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// Read an unsigned 32 bit integer whose byte order is decided by a TIFF header:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::AsyncReadBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut rdr = Cursor::new(b"II\x0b\x01\x00\x00".to_vec());
+    ///     let endian = rdr.detect_endianness_tiff().await.unwrap();
+    ///     assert_eq!(267, rdr.read_u32_rt(endian).await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_u32_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u32>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 4, RuntimeCodec::read)
+    }
+
+    /// Reads a signed 32 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i32`](#method.read_i32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i32_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i32>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 4, RuntimeCodec::read)
+    }
+
+    /// Reads an unsigned 48 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u48`](#method.read_u48).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u48_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u64>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 6, endian::read_uint)
+    }
+
+    /// Reads a signed 48 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i48`](#method.read_i48).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i48_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 6, endian::read_int)
+    }
+
+    /// Reads an unsigned 64 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u64`](#method.read_u64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u64_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u64>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 8, RuntimeCodec::read)
+    }
+
+    /// Reads a signed 64 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i64`](#method.read_i64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i64_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i64>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 8, RuntimeCodec::read)
+    }
+
+    /// Reads an unsigned 128 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_u128`](#method.read_u128).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u128_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, u128>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 16, RuntimeCodec::read)
+    }
+
+    /// Reads a signed 128 bit integer from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_i128`](#method.read_i128).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_i128_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, i128>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 16, RuntimeCodec::read)
+    }
+
+    /// Reads a IEEE754 single-precision (4 bytes) floating point number from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_f32`](#method.read_f32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_f32_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, f32>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 4, RuntimeCodec::read)
+    }
+
+    /// Reads a IEEE754 double-precision (8 bytes) floating point number from the underlying reader in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`read_f64`](#method.read_f64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_f64_rt(&mut self, endian: RuntimeEndian) -> ReadRuntime<&mut Self, f64>
+    where
+        Self: Unpin,
+    {
+        ReadRuntime::new(self, endian, 8, RuntimeCodec::read)
+    }
+
+    /// Reads an unsigned 8 bit integer from the underlying reader, which is taken by value.
+    ///
+    /// The returned future resolves to the reader along with the result, and does not borrow
+    /// anything, so it can be spawned or stored without tying it to the reader's owner. The
+    /// other `_owned` methods work the same way.
+    ///
+    /// # Errors
+    ///
+    /// The result has the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rdr = Cursor::new(vec![0x2a, 0x00, 0x00, 0x01, 0x0b]);
+    ///     let (rdr, n) = tokio::spawn(rdr.read_u8_owned()).await.unwrap();
+    ///     assert_eq!(42, n.unwrap());
+    ///     let (_, n) = rdr.read_u32_owned::<BigEndian>().await;
+    ///     assert_eq!(267, n.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    fn read_u8_owned(self) -> ReadOwned<Self, u8>
+    where
+        Self: Sized + Unpin,
+    {
+        ReadOwned::new(self, sans_io::FixedDecoder::u8())
+    }
+
+    /// Reads a signed 8 bit integer from the underlying reader, which is taken by value.
+    ///
+    /// See [`read_u8_owned`](#method.read_u8_owned).
+    #[inline]
+    fn read_i8_owned(self) -> ReadOwned<Self, i8>
+    where
+        Self: Sized + Unpin,
+    {
+        ReadOwned::new(self, sans_io::FixedDecoder::i8())
+    }
+
+    read_owned! {
+        (read_u16_owned, u16, u16, "an unsigned 16 bit integer"),
+        (read_i16_owned, i16, i16, "a signed 16 bit integer"),
+        (read_u24_owned, u24, u32, "an unsigned 24 bit integer"),
+        (read_i24_owned, i24, i32, "a signed 24 bit integer"),
+        (read_u32_owned, u32, u32, "an unsigned 32 bit integer"),
+        (read_i32_owned, i32, i32, "a signed 32 bit integer"),
+        (read_u48_owned, u48, u64, "an unsigned 48 bit integer"),
+        (read_i48_owned, i48, i64, "a signed 48 bit integer"),
+        (read_u64_owned, u64, u64, "an unsigned 64 bit integer"),
+        (read_i64_owned, i64, i64, "a signed 64 bit integer"),
+        (read_u128_owned, u128, u128, "an unsigned 128 bit integer"),
+        (read_i128_owned, i128, i128, "a signed 128 bit integer"),
+        (read_f32_owned, f32, f32, "a IEEE754 single-precision floating point number"),
+        (read_f64_owned, f64, f64, "a IEEE754 double-precision floating point number"),
+    }
+}
+
+/// All types that implement `AsyncRead` get methods defined in `AsyncReadBytesExt`
+/// for free.
+impl<R: io::AsyncRead + ?Sized> AsyncReadBytesExt for R {}
+
+macro_rules! write_impl {
+    (
+        $(#[$outer:meta])*
+        fn $name:ident(&mut self, n: $ty:ty) -> $($fut:tt)*
+    ) => {
+        $(#[$outer])*
+        #[inline]
+        fn $name<T: ByteOrder>(&mut self, n: $ty) -> $($fut)*<&mut Self> where Self: Unpin {
+            $($fut)*::new::<T>(self, n)
+        }
+    }
+}
+
+macro_rules! write_variants {
+    ($(($name:ident, $be:ident, $le:ident, $ne:ident, $ty:ty, $fut:ident, $desc:literal)),* $(,)?) => {
+        $(
+            write_variants!(@one $name, $be, $ty, $fut, BigEndian, $desc, "big-endian");
+            write_variants!(@one $name, $le, $ty, $fut, LittleEndian, $desc, "little-endian");
+            write_variants!(@one $name, $ne, $ty, $fut, NativeEndian, $desc, "native-endian");
+        )*
+    };
+    (@one $name:ident, $variant:ident, $ty:ty, $fut:ident, $bo:ident, $desc:literal, $order:literal) => {
+        #[doc = concat!("Writes ", $desc, " in ", $order, " order to the underlying writer.")]
+        ///
+        #[doc = concat!("This is equivalent to [`", stringify!($name), "::<", stringify!($bo), ">`](#method.", stringify!($name), ").")]
+        #[inline]
+        fn $variant(&mut self, n: $ty) -> $fut<&mut Self>
+        where
+            Self: Unpin,
+        {
+            $fut::new::<$bo>(self, n)
+        }
+    };
+}
+
+macro_rules! write_owned {
+    ($(($name:ident, $encoder:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Writes ", $desc, " to the underlying writer, which is taken by value.")]
+            ///
+            /// See [`write_u8_owned`](#method.write_u8_owned).
+            #[inline]
+            fn $name<T: ByteOrder>(self, n: $ty) -> WriteOwned<Self>
+            where
+                Self: Sized + Unpin,
+            {
+                WriteOwned::new(self, sans_io::FixedEncoder::$encoder::<T>(n))
+            }
+        )*
+    };
+}
+
+/// Extends [`AsyncWrite`] with methods for writing numbers.
+///
+/// Most of the methods defined here have an unconstrained type parameter that
+/// must be explicitly instantiated. Typically, it is instantiated with either
+/// the [`BigEndian`] or [`LittleEndian`] types defined in this crate.
+///
+/// The methods require `Self: Unpin`. To use them with a writer that is not `Unpin`, pin it
+/// first (for example with [`std::pin::pin!`]) and call them on the resulting `Pin<&mut _>`.
+///
+/// None of the methods flush the writer. The futures that write a single number have an
+/// `and_flush` method that does so once the number is written, as in
+/// `wtr.write_u32::<BigEndian>(n).and_flush().await`; see [`WriteAndFlush`].
+///
+/// [`std::pin::pin!`]: https://doc.rust-lang.org/std/pin/macro.pin.html
+/// [`WriteAndFlush`]: future/struct.WriteAndFlush.html
+///
+/// # Examples
+///
+/// Write unsigned 16 bit big-endian integers to a [`Write`]:
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = vec![];
+///     wtr.write_u16::<BigEndian>(517).await.unwrap();
+///     wtr.write_u16::<BigEndian>(768).await.unwrap();
+///     assert_eq!(wtr, vec![2, 5, 3, 0]);
+/// }
+/// ```
+///
+/// [`BigEndian`]: enum.BigEndian.html
+/// [`LittleEndian`]: enum.LittleEndian.html
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+pub trait AsyncWriteBytesExt: io::AsyncWrite {
+    /// Writes an unsigned 8 bit integer to the underlying writer.
+    ///
+    /// Note that since this writes a single byte, no byte order conversions
+    /// are used. It is included for completeness.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// Write unsigned 8 bit integers to a `Write`:
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_u8(2).await.unwrap();
+    ///     wtr.write_u8(5).await.unwrap();
+    ///     assert_eq!(wtr, b"\x02\x05");
+    /// }
+    /// ```
+    #[inline]
+    fn write_u8(&mut self, n: u8) -> WriteU8<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteU8::new(self, n)
+    }
+
+    /// Writes a signed 8 bit integer to the underlying writer.
+    ///
+    /// Note that since this writes a single byte, no byte order conversions
+    /// are used. It is included for completeness.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// Write signed 8 bit integers to a `Write`:
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_i8(2).await.unwrap();
+    ///     wtr.write_i8(-5).await.unwrap();
+    ///     assert_eq!(wtr, b"\x02\xfb");
+    /// }
+    /// ```
+    #[inline]
+    fn write_i8(&mut self, n: i8) -> WriteI8<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteI8::new(self, n)
+    }
+
+    write_impl! {
+        /// Writes an unsigned 16 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write unsigned 16 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_u16::<BigEndian>(517).await.unwrap();
+        ///     wtr.write_u16::<BigEndian>(768).await.unwrap();
+        ///     assert_eq!(wtr, b"\x02\x05\x03\x00");
+        /// }
+        /// ```
+        fn write_u16(&mut self, n: u16) -> WriteU16
+    }
+
+    write_impl! {
+        /// Writes a signed 16 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write signed 16 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_i16::<BigEndian>(193).await.unwrap();
+        ///     wtr.write_i16::<BigEndian>(-132).await.unwrap();
+        ///     assert_eq!(wtr, b"\x00\xc1\xff\x7c");
+        /// }
+        /// ```
+        fn write_i16(&mut self, n: i16) -> WriteI16
+    }
+
+    write_impl! {
+        /// Writes an unsigned 24 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write unsigned 24 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_u24::<BigEndian>(267).await.unwrap();
+        ///     wtr.write_u24::<BigEndian>(120111).await.unwrap();
+        ///     assert_eq!(wtr, b"\x00\x01\x0b\x01\xd5\x2f");
+        /// }
+        /// ```
+        fn write_u24(&mut self, n: u32) -> WriteU24
+    }
+
+    write_impl! {
+        /// Writes a signed 24 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write signed 24 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_i24::<BigEndian>(-34253).await.unwrap();
+        ///     wtr.write_i24::<BigEndian>(120111).await.unwrap();
+        ///     assert_eq!(wtr, b"\xff\x7a\x33\x01\xd5\x2f");
+        /// }
+        /// ```
+        fn write_i24(&mut self, n: i32) -> WriteI24
+    }
+
+    write_impl! {
+        /// Writes an unsigned 32 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write unsigned 32 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_u32::<BigEndian>(267).await.unwrap();
+        ///     wtr.write_u32::<BigEndian>(1205419366).await.unwrap();
+        ///     assert_eq!(wtr, b"\x00\x00\x01\x0b\x47\xd9\x3d\x66");
+        /// }
+        /// ```
+        fn write_u32(&mut self, n: u32) -> WriteU32
+    }
+
+    write_impl! {
+        /// Writes a signed 32 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write signed 32 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_i32::<BigEndian>(-34253).await.unwrap();
+        ///     wtr.write_i32::<BigEndian>(1205419366).await.unwrap();
+        ///     assert_eq!(wtr, b"\xff\xff\x7a\x33\x47\xd9\x3d\x66");
+        /// }
+        /// ```
+        fn write_i32(&mut self, n: i32) -> WriteI32
+    }
+
+    write_impl! {
+        /// Writes an unsigned 48 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write unsigned 48 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_u48::<BigEndian>(52360336390828).await.unwrap();
+        ///     wtr.write_u48::<BigEndian>(541).await.unwrap();
+        ///     assert_eq!(wtr, b"\x2f\x9f\x17\x40\x3a\xac\x00\x00\x00\x00\x02\x1d");
+        /// }
+        /// ```
+        fn write_u48(&mut self, n: u64) -> WriteU48
+    }
+
+    write_impl! {
+        /// Writes a signed 48 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write signed 48 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_i48::<BigEndian>(-108363435763825).await.unwrap();
+        ///     wtr.write_i48::<BigEndian>(77).await.unwrap();
+        ///     assert_eq!(wtr, b"\x9d\x71\xab\xe7\x97\x8f\x00\x00\x00\x00\x00\x4d");
+        /// }
+        /// ```
+        fn write_i48(&mut self, n: i64) -> WriteI48
+    }
+
+    write_impl! {
+        /// Writes an unsigned 64 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write unsigned 64 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_u64::<BigEndian>(918733457491587).await.unwrap();
+        ///     wtr.write_u64::<BigEndian>(143).await.unwrap();
+        ///     assert_eq!(wtr, b"\x00\x03\x43\x95\x4d\x60\x86\x83\x00\x00\x00\x00\x00\x00\x00\x8f");
+        /// }
+        /// ```
+        fn write_u64(&mut self, n: u64) -> WriteU64
+    }
+
+    write_impl! {
+        /// Writes a signed 64 bit integer to the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write signed 64 bit big-endian integers to a `Write`:
+        ///
+        /// ```rust
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_i64::<BigEndian>(i64::min_value()).await.unwrap();
+        ///     wtr.write_i64::<BigEndian>(i64::max_value()).await.unwrap();
+        ///     assert_eq!(wtr, b"\x80\x00\x00\x00\x00\x00\x00\x00\x7f\xff\xff\xff\xff\xff\xff\xff");
+        /// }
+        /// ```
+        fn write_i64(&mut self, n: i64) -> WriteI64
+    }
+
+    write_impl! {
+        /// Writes an unsigned 128 bit integer to the underlying writer.
+        fn write_u128(&mut self, n: u128) -> WriteU128
+    }
+
+    write_impl! {
+        /// Writes a signed 128 bit integer to the underlying writer.
+        fn write_i128(&mut self, n: i128) -> WriteI128
+    }
+
+    // TODO: write_*int
+
+    write_impl! {
+        /// Writes a IEEE754 single-precision (4 bytes) floating point number to
+        /// the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write a big-endian single-precision floating point number to a `Write`:
+        ///
+        /// ```rust
+        /// use std::f32;
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_f32::<BigEndian>(f32::consts::PI).await.unwrap();
+        ///     assert_eq!(wtr, b"\x40\x49\x0f\xdb");
+        /// }
+        /// ```
+        fn write_f32(&mut self, n: f32) -> WriteF32
+    }
+
+    write_impl! {
+        /// Writes a IEEE754 double-precision (8 bytes) floating point number to
+        /// the underlying writer.
+        ///
+        /// # Errors
+        ///
+        /// This method returns the same errors as [`Write::write_all`].
+        ///
+        /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+        ///
+        /// # Examples
+        ///
+        /// Write a big-endian double-precision floating point number to a `Write`:
+        ///
+        /// ```rust
+        /// use std::f64;
+        /// use tokio_byteorder::{BigEndian, AsyncWriteBytesExt};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut wtr = Vec::new();
+        ///     wtr.write_f64::<BigEndian>(f64::consts::PI).await.unwrap();
+        ///     assert_eq!(wtr, b"\x40\x09\x21\xfb\x54\x44\x2d\x18");
+        /// }
+        /// ```
+        fn write_f64(&mut self, n: f64) -> WriteF64
+    }
+
+    write_variants!(
+        (
+            write_u16,
+            write_u16_be,
+            write_u16_le,
+            write_u16_ne,
+            u16,
+            WriteU16,
+            "an unsigned 16 bit integer"
+        ),
+        (
+            write_i16,
+            write_i16_be,
+            write_i16_le,
+            write_i16_ne,
+            i16,
+            WriteI16,
+            "a signed 16 bit integer"
+        ),
+        (
+            write_u24,
+            write_u24_be,
+            write_u24_le,
+            write_u24_ne,
+            u32,
+            WriteU24,
+            "an unsigned 24 bit integer"
+        ),
+        (
+            write_i24,
+            write_i24_be,
+            write_i24_le,
+            write_i24_ne,
+            i32,
+            WriteI24,
+            "a signed 24 bit integer"
+        ),
+        (
+            write_u32,
+            write_u32_be,
+            write_u32_le,
+            write_u32_ne,
+            u32,
+            WriteU32,
+            "an unsigned 32 bit integer"
+        ),
+        (
+            write_i32,
+            write_i32_be,
+            write_i32_le,
+            write_i32_ne,
+            i32,
+            WriteI32,
+            "a signed 32 bit integer"
+        ),
+        (
+            write_u48,
+            write_u48_be,
+            write_u48_le,
+            write_u48_ne,
+            u64,
+            WriteU48,
+            "an unsigned 48 bit integer"
+        ),
+        (
+            write_i48,
+            write_i48_be,
+            write_i48_le,
+            write_i48_ne,
+            i64,
+            WriteI48,
+            "a signed 48 bit integer"
+        ),
+        (
+            write_u64,
+            write_u64_be,
+            write_u64_le,
+            write_u64_ne,
+            u64,
+            WriteU64,
+            "an unsigned 64 bit integer"
+        ),
+        (
+            write_i64,
+            write_i64_be,
+            write_i64_le,
+            write_i64_ne,
+            i64,
+            WriteI64,
+            "a signed 64 bit integer"
+        ),
+        (
+            write_u128,
+            write_u128_be,
+            write_u128_le,
+            write_u128_ne,
+            u128,
+            WriteU128,
+            "an unsigned 128 bit integer"
+        ),
+        (
+            write_i128,
+            write_i128_be,
+            write_i128_le,
+            write_i128_ne,
+            i128,
+            WriteI128,
+            "a signed 128 bit integer"
+        ),
+        (
+            write_f32,
+            write_f32_be,
+            write_f32_le,
+            write_f32_ne,
+            f32,
+            WriteF32,
+            "a IEEE754 single-precision floating point number"
+        ),
+        (
+            write_f64,
+            write_f64_be,
+            write_f64_le,
+            write_f64_ne,
+            f64,
+            WriteF64,
+            "a IEEE754 double-precision floating point number"
+        ),
+    );
+
+    /// Writes a number of any [`Primitive`] type to the underlying writer.
+    ///
+    /// This is equivalent to the `write_` method for `V`, but lets code that is generic over
+    /// the type of number it writes pick that method through a type parameter.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Primitive`]: trait.Primitive.html
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_primitive::<u32, BigEndian>(267).await.unwrap();
+    ///     wtr.write_primitive::<f32, BigEndian>(1.0).await.unwrap();
+    ///     assert_eq!(wtr, b"\x00\x00\x01\x0b\x3f\x80\x00\x00");
+    /// }
+    /// ```
+    #[inline]
+    fn write_primitive<V: Primitive, T: ByteOrder>(&mut self, n: V) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, n.encoder::<T>())
+    }
+
+    /// Writes a IEEE754 single-precision (4 bytes) floating point number to the underlying
+    /// writer, replacing any NaN with the canonical one.
+    ///
+    /// This is otherwise identical to [`write_f32`](#method.write_f32). Every NaN, whatever its
+    /// sign and payload, is written as [`CANONICAL_NAN_F32`], so the same values always produce
+    /// the same bytes, regardless of the platform or the operations that computed them.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`CANONICAL_NAN_F32`]: sans_io/constant.CANONICAL_NAN_F32.html
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_f32_canonical::<BigEndian>(-f32::NAN).await.unwrap();
+    ///     wtr.write_f32_canonical::<BigEndian>(1.0).await.unwrap();
+    ///     assert_eq!(wtr, b"\x7f\xc0\x00\x00\x3f\x80\x00\x00");
+    /// }
+    /// ```
+    #[inline]
+    fn write_f32_canonical<T: ByteOrder>(&mut self, n: f32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, sans_io::FixedEncoder::f32_canonical::<T>(n))
+    }
+
+    /// Writes a IEEE754 double-precision (8 bytes) floating point number to the underlying
+    /// writer, replacing any NaN with the canonical one.
+    ///
+    /// This is otherwise identical to [`write_f64`](#method.write_f64). Every NaN is written as
+    /// [`CANONICAL_NAN_F64`]; see [`write_f32_canonical`](#method.write_f32_canonical).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`CANONICAL_NAN_F64`]: sans_io/constant.CANONICAL_NAN_F64.html
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_f64_canonical<T: ByteOrder>(&mut self, n: f64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, sans_io::FixedEncoder::f64_canonical::<T>(n))
+    }
+
+    /// Writes each value of `src` as a packed 24 bit signed integer, three bytes each.
+    ///
+    /// Each value is first scaled according to `scaling`. The values are all encoded up front,
+    /// and then written out together, which is much faster than calling
+    /// [`write_i24`](#method.write_i24) once per value when there are many of them, as with
+    /// 24 bit audio.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, I24Scaling};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_i24_from::<BigEndian>(&[1, -1], I24Scaling::SignExtend)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(wtr, b"\x00\x00\x01\xff\xff\xff");
+    /// }
+    /// ```
+    #[inline]
+    fn write_i24_from<T: ByteOrder>(
+        &mut self,
+        src: &[i32],
+        scaling: I24Scaling,
+    ) -> WriteI24From<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteI24From::new::<T>(self, src, scaling)
+    }
+
+    /// Writes `src` as a block of group varint encoded integers.
+    ///
+    /// This is the format that
+    /// [`read_group_varint_u32`](trait.AsyncReadBytesExt.html#method.read_group_varint_u32)
+    /// reads. The values are all encoded up front, and then written out together.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_group_varint_u32(&[1, 15, 511, 131071]).await.unwrap();
+    ///     assert_eq!(wtr, [0b00_00_01_10, 1, 15, 0xff, 0x01, 0xff, 0xff, 0x01]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_group_varint_u32(&mut self, src: &[u32]) -> WriteVarintBlock<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteVarintBlock::group(self, src)
+    }
+
+    /// Writes `src` as a block of Stream VByte encoded integers.
+    ///
+    /// This is the format that
+    /// [`read_stream_vbyte_u32`](trait.AsyncReadBytesExt.html#method.read_stream_vbyte_u32)
+    /// reads. The values are all encoded up front, and then written out together.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_stream_vbyte_u32(&[7, 0x100, 9]).await.unwrap();
+    ///     assert_eq!(wtr, [0b00_00_01_00, 7, 0x00, 0x01, 9]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_stream_vbyte_u32(&mut self, src: &[u32]) -> WriteVarintBlock<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteVarintBlock::stream_vbyte(self, src)
+    }
+
+    /// Writes an unsigned LEB128 varint.
+    ///
+    /// This is the format that [`read_varint_u64`](trait.AsyncReadBytesExt.html#method.read_varint_u64)
+    /// reads. It takes one byte for every seven bits of `n`, and at least one.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_varint_u64(624485).await.unwrap();
+    ///     assert_eq!(wtr, [0xe5, 0x8e, 0x26]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_varint_u64(&mut self, n: u64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::varint::varint_encoder(n))
+    }
+
+    /// Writes a zigzag-encoded signed integer in an unsigned LEB128 varint.
+    ///
+    /// This is the format that
+    /// [`read_zigzag_varint`](trait.AsyncReadBytesExt.html#method.read_zigzag_varint) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_zigzag_varint(-1000).await.unwrap();
+    ///     assert_eq!(wtr, [0xcf, 0x0f]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_zigzag_varint(&mut self, n: i64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::varint::varint_encoder(zigzag_encode(n)))
+    }
+
+    /// Writes `src` as delta-encoded integers, each stored as the zigzag-encoded difference
+    /// from the one before it in an unsigned LEB128 varint.
+    ///
+    /// This is the format that
+    /// [`read_delta_varint_sequence`](trait.AsyncReadBytesExt.html#method.read_delta_varint_sequence)
+    /// reads. The values are all encoded up front, and then written out together. The count is
+    /// not written.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_delta_varint_sequence(&[1000, 1001, 1003, 1002])
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(wtr, [0xd0, 0x0f, 0x02, 0x04, 0x01]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_delta_varint_sequence(&mut self, src: &[i64]) -> WriteVarints<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteVarints::delta(self, src)
+    }
+
+    /// Writes the head of a CBOR data item: its major type, and the argument that follows it.
+    ///
+    /// This is what [`read_cbor_head`](trait.AsyncReadBytesExt.html#method.read_cbor_head)
+    /// reads. The argument is written in the fewest bytes that hold it, as RFC 8949's
+    /// preferred serialization asks for. That makes this unsuitable for floats, whose width is
+    /// part of their meaning; write their initial byte and bits directly instead.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, MajorType};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_cbor_head(MajorType::Array, 2).await.unwrap();
+    ///     wtr.write_cbor_head(MajorType::Unsigned, 1000).await.unwrap();
+    ///     assert_eq!(wtr, [0x82, 0x19, 0x03, 0xe8]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_cbor_head(&mut self, major: MajorType, argument: u64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::cbor::head_encoder(major, argument))
+    }
+
+    /// Writes a MessagePack integer in the smallest format that holds it.
+    ///
+    /// Non-negative values use a positive fixint or an unsigned format, and negative values a
+    /// negative fixint or a signed format, as most msgpack encoders do. This is what
+    /// [`read_msgpack_int`](trait.AsyncReadBytesExt.html#method.read_msgpack_int) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_msgpack_int(42).await.unwrap();
+    ///     wtr.write_msgpack_int(256).await.unwrap();
+    ///     wtr.write_msgpack_int(-128).await.unwrap();
+    ///     assert_eq!(wtr, [0x2a, 0xcd, 0x01, 0x00, 0xd0, 0x80]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_msgpack_int(&mut self, n: i64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::msgpack::int_encoder(n))
+    }
+
+    /// Writes a MessagePack `float 32`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_msgpack_f32(1.5).await.unwrap();
+    ///     assert_eq!(wtr, [0xca, 0x3f, 0xc0, 0x00, 0x00]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_msgpack_f32(&mut self, n: f32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::msgpack::f32_encoder(n))
+    }
+
+    /// Writes a MessagePack `float 64`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_msgpack_f64(1.5).await.unwrap();
+    ///     assert_eq!(wtr, [0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_msgpack_f64(&mut self, n: f64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::msgpack::f64_encoder(n))
+    }
+
+    /// Writes an Avro `long` or `int`: a zigzag-encoded varint.
+    ///
+    /// Avro encodes `int`s and `long`s the same way. This is the same as
+    /// [`write_zigzag_varint`](#method.write_zigzag_varint), under the name the Avro
+    /// specification gives it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_avro_long(&mut self, n: i64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_zigzag_varint(n)
+    }
+
+    /// Writes an Avro `float`: a little-endian IEEE754 single-precision number.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_avro_float(&mut self, n: f32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, sans_io::FixedEncoder::f32::<LittleEndian>(n))
+    }
+
+    /// Writes an Avro `double`: a little-endian IEEE754 double-precision number.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_avro_double(&mut self, n: f64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, sans_io::FixedEncoder::f64::<LittleEndian>(n))
+    }
+
+    /// Writes Avro `bytes`: the length of `data` as an Avro `long`, followed by `data`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_avro_bytes(b"foo").await.unwrap();
+    ///     assert_eq!(wtr, [0x06, 0x66, 0x6f, 0x6f]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_avro_bytes(&mut self, data: &[u8]) -> WriteAvroBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteAvroBytes::new(self, data)
+    }
+
+    /// Writes an Avro `string`: the length of `s` as an Avro `long`, followed by `s`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_avro_string(&mut self, s: &str) -> WriteAvroBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteAvroBytes::new(self, s.as_bytes())
+    }
+
+    /// Writes the header of a field of a struct encoded with Thrift's compact protocol.
+    ///
+    /// `last_id` is the id of the field before, or 0 for the first field of a struct. The id
+    /// is stored as the difference from it when that is between 1 and 15, and in full after
+    /// the type otherwise. The stop byte that ends a struct is a single zero, which
+    /// [`write_u8`](#method.write_u8) writes. This is what
+    /// [`read_thrift_field_header`](trait.AsyncReadBytesExt.html#method.read_thrift_field_header)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if the
+    /// type of the field is not between 1 and 15.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, ThriftFieldHeader};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     let header = ThriftFieldHeader { id: 1, field_type: 5 };
+    ///     wtr.write_thrift_field_header(0, header).await.unwrap();
+    ///     wtr.write_zigzag_varint(21).await.unwrap();
+    ///     let header = ThriftFieldHeader { id: 300, field_type: 1 };
+    ///     wtr.write_thrift_field_header(1, header).await.unwrap();
+    ///     wtr.write_u8(0).await.unwrap();
+    ///     assert_eq!(wtr, [0x15, 0x2a, 0x01, 0xd8, 0x04, 0x00]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_thrift_field_header(
+        &mut self,
+        last_id: i16,
+        header: ThriftFieldHeader,
+    ) -> WriteThriftFieldHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteThriftFieldHeader::new(self, last_id, header)
+    }
+
+    /// Writes the header of an object in a git packfile, given its type and its size once
+    /// inflated.
+    ///
+    /// This is what
+    /// [`read_git_object_header`](trait.AsyncReadBytesExt.html#method.read_git_object_header)
+    /// reads; see there for the format.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if `kind`
+    /// is not one of the object types git uses: 1 to 4, 6, or 7.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_git_object_header(3, 300).await.unwrap();
+    ///     assert_eq!(wtr, [0xbc, 0x12]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_git_object_header(&mut self, kind: u8, size: u64) -> WriteGitObjectHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteGitObjectHeader::new(self, kind, size)
+    }
+
+    /// Writes the offset back to the base object of an `OFS_DELTA` object in a git packfile.
+    ///
+    /// This is what
+    /// [`read_git_ofs_delta_offset`](trait.AsyncReadBytesExt.html#method.read_git_ofs_delta_offset)
+    /// reads; see there for the format.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_git_ofs_delta_offset(128).await.unwrap();
+    ///     wtr.write_git_ofs_delta_offset(300).await.unwrap();
+    ///     assert_eq!(wtr, [0x80, 0x00, 0x81, 0x2c]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_git_ofs_delta_offset(&mut self, offset: u64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::git::ofs_delta_encoder(offset))
+    }
+
+    /// Writes an unsigned integer in the UTF-8 like coding that FLAC frame headers use for
+    /// their frame and sample numbers, in the fewest bytes that hold it.
+    ///
+    /// This is what
+    /// [`read_utf8_coded_u64`](trait.AsyncReadBytesExt.html#method.read_utf8_coded_u64)
+    /// reads; see there for the format.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if `n`
+    /// does not fit in 36 bits.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_utf8_coded_u64(0x7f).await.unwrap();
+    ///     wtr.write_utf8_coded_u64(0x20ac).await.unwrap();
+    ///     assert_eq!(wtr, [0x7f, 0xe2, 0x82, 0xac]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_utf8_coded_u64(&mut self, n: u64) -> WriteUtf8Coded<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteUtf8Coded::new(self, n)
+    }
+
+    /// Writes a MAVLink 1 or 2 packet, with a checksum seeded with `crc_extra`, the
+    /// `CRC_EXTRA` byte of its message.
+    ///
+    /// This is what
+    /// [`read_mavlink_frame`](trait.AsyncReadBytesExt.html#method.read_mavlink_frame) reads.
+    /// The payload is written as it is; MAVLink 2 senders are expected to drop the zero bytes
+    /// at its end first.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the payload is longer than 255 bytes, or if the message id does not fit the version. The
+    /// same goes for a MAVLink 1 packet with flags or a signature, and for a MAVLink 2 packet
+    /// with a signature but without the flag for it, or the other way around.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, MavlinkFrame, MavlinkVersion};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let frame = MavlinkFrame {
+    ///         version: MavlinkVersion::V2,
+    ///         incompat_flags: 0,
+    ///         compat_flags: 0,
+    ///         seq: 7,
+    ///         sysid: 1,
+    ///         compid: 1,
+    ///         msgid: 0,
+    ///         payload: vec![0, 0, 0, 0, 2, 3, 0x51, 4, 3],
+    ///         signature: None,
+    ///     };
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mavlink_frame(&frame, 50).await.unwrap();
+    ///     assert_eq!(wtr[..10], [0xfd, 0x09, 0x00, 0x00, 0x07, 0x01, 0x01, 0x00, 0x00, 0x00]);
+    ///     assert_eq!(wtr.len(), 10 + 9 + 2);
+    /// }
+    /// ```
+    #[inline]
+    fn write_mavlink_frame(
+        &mut self,
+        frame: &MavlinkFrame,
+        crc_extra: u8,
+    ) -> WriteMavlinkFrame<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteMavlinkFrame::new(self, frame, crc_extra)
+    }
+
+    /// Writes the 20-byte header of a STUN message, with the magic cookie.
+    ///
+    /// This is what
+    /// [`read_stun_header`](trait.AsyncReadBytesExt.html#method.read_stun_header) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if the top
+    /// two bits of the message type are set.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, StunHeader};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let header = StunHeader {
+    ///         message_type: 0x0001,
+    ///         length: 0,
+    ///         transaction_id: [0; 12],
+    ///     };
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_stun_header(&header).await.unwrap();
+    ///     assert_eq!(wtr[..8], [0x00, 0x01, 0x00, 0x00, 0x21, 0x12, 0xa4, 0x42]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_stun_header(&mut self, header: &StunHeader) -> WriteStun<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteStun::header(self, header)
+    }
+
+    /// Writes an attribute of a STUN message, padding its value with zeros to a multiple of
+    /// four bytes.
+    ///
+    /// This is what
+    /// [`read_stun_attribute`](trait.AsyncReadBytesExt.html#method.read_stun_attribute) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `value` is longer than 65535 bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_stun_attribute(0x8022, b"abc").await.unwrap();
+    ///     assert_eq!(wtr, [0x80, 0x22, 0x00, 0x03, b'a', b'b', b'c', 0x00]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_stun_attribute(&mut self, attr_type: u16, value: &[u8]) -> WriteStun<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteStun::attribute(self, attr_type, value)
+    }
+
+    /// Writes the header of a 9P2000 message: its size, its type, and its tag, all
+    /// little-endian.
+    ///
+    /// This is what
+    /// [`read_9p_header`](trait.AsyncReadBytesExt.html#method.read_9p_header) reads. The size
+    /// counts the whole message, these seven bytes of header included.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_9p_header(19, 100, 0xffff).await.unwrap();
+    ///     assert_eq!(wtr, [0x13, 0x00, 0x00, 0x00, 100, 0xff, 0xff]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_9p_header(&mut self, size: u32, msg_type: u8, tag: u16) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::ninep::header_encoder(size, msg_type, tag))
+    }
+
+    /// Writes a 9P2000 string: its length in bytes as a little-endian `u16`, followed by its
+    /// bytes.
+    ///
+    /// This is what
+    /// [`read_9p_string`](trait.AsyncReadBytesExt.html#method.read_9p_string) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string is longer than 65535 bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_9p_string("9P2000").await.unwrap();
+    ///     assert_eq!(wtr, b"\x06\x009P2000");
+    /// }
+    /// ```
+    #[inline]
+    fn write_9p_string<'a>(&'a mut self, s: &'a str) -> WriteLengthPrefixed<&'a mut Self, &'a [u8]>
+    where
+        Self: Unpin,
+    {
+        WriteLengthPrefixed::new::<u16, LittleEndian>(self, s.as_bytes())
+    }
+
+    /// Writes a 9P2000 [`Qid`]: its type, then its version and path, little-endian.
+    ///
+    /// This is what [`read_9p_qid`](trait.AsyncReadBytesExt.html#method.read_9p_qid) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Qid`]: struct.Qid.html
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, Qid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     let qid = Qid { qid_type: 0x80, version: 1, path: 42 };
+    ///     wtr.write_9p_qid(&qid).await.unwrap();
+    ///     assert_eq!(wtr, [0x80, 1, 0, 0, 0, 0x2a, 0, 0, 0, 0, 0, 0, 0]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_9p_qid(&mut self, qid: &Qid) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::ninep::qid_encoder(qid))
+    }
+
+    /// Writes the header of a message of the PostgreSQL frontend/backend protocol: its type
+    /// byte, and the length of a body of `body_len` bytes as a big-endian `i32` that counts
+    /// itself.
+    ///
+    /// This is what
+    /// [`read_pg_message_header`](trait.AsyncReadBytesExt.html#method.read_pg_message_header)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `body_len + 4` does not fit in an `i32`.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_pg_message_header(b'Z', 1).await.unwrap();
+    ///     wtr.write_u8(b'I').await.unwrap();
+    ///     assert_eq!(wtr, [b'Z', 0x00, 0x00, 0x00, 0x05, b'I']);
+    /// }
+    /// ```
+    #[inline]
+    fn write_pg_message_header(&mut self, msg_type: u8, body_len: usize) -> WritePg<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePg::message_header(self, msg_type, body_len)
+    }
+
+    /// Writes a string of the PostgreSQL frontend/backend protocol: its bytes, followed by a
+    /// NUL.
+    ///
+    /// This is what
+    /// [`read_pg_string`](trait.AsyncBufReadBytesExt.html#method.read_pg_string) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string holds a NUL.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_pg_string("user").await.unwrap();
+    ///     assert_eq!(wtr, b"user\0");
+    /// }
+    /// ```
+    #[inline]
+    fn write_pg_string(&mut self, s: &str) -> WritePg<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePg::string(self, s)
+    }
+
+    /// Writes the header of a MySQL client/server protocol packet: the length of its payload
+    /// as a little-endian 24-bit integer, and its sequence id.
+    ///
+    /// This is what
+    /// [`read_mysql_packet_header`](trait.AsyncReadBytesExt.html#method.read_mysql_packet_header)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `len` does not fit in 24 bits.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mysql_packet_header(1, 0).await.unwrap();
+    ///     wtr.write_u8(0x0e).await.unwrap();
+    ///     assert_eq!(wtr, [0x01, 0x00, 0x00, 0x00, 0x0e]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_mysql_packet_header(&mut self, len: u32, seq: u8) -> WriteMysql<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteMysql::packet_header(self, len, seq)
+    }
+
+    /// Writes a MySQL length-encoded integer, in the fewest bytes that hold it.
+    ///
+    /// This is what
+    /// [`read_mysql_lenenc_int`](trait.AsyncReadBytesExt.html#method.read_mysql_lenenc_int)
+    /// reads. A `NULL` in a result row is the single byte `0xfb`, which
+    /// [`write_u8`](#method.write_u8) writes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mysql_lenenc_int(250).await.unwrap();
+    ///     wtr.write_mysql_lenenc_int(251).await.unwrap();
+    ///     assert_eq!(wtr, [0xfa, 0xfc, 0xfb, 0x00]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_mysql_lenenc_int(&mut self, n: u64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::mysql::lenenc_int_encoder(n))
+    }
+
+    /// Writes a MySQL length-encoded string: its length as a length-encoded integer, followed
+    /// by its bytes.
+    ///
+    /// This is what
+    /// [`read_mysql_lenenc_str`](trait.AsyncReadBytesExt.html#method.read_mysql_lenenc_str)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mysql_lenenc_str(b"abc").await.unwrap();
+    ///     assert_eq!(wtr, b"\x03abc");
+    /// }
+    /// ```
+    #[inline]
+    fn write_mysql_lenenc_str(&mut self, data: &[u8]) -> WriteMysql<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteMysql::lenenc_str(self, data)
+    }
+
+    /// Writes an AMQP 0-9-1 short string: its length as a `u8`, followed by its bytes.
+    ///
+    /// This is what
+    /// [`read_amqp_short_string`](trait.AsyncReadBytesExt.html#method.read_amqp_short_string)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string is longer than 255 bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_amqp_short_string("amq.direct").await.unwrap();
+    ///     assert_eq!(wtr, b"\x0aamq.direct");
+    /// }
+    /// ```
+    #[inline]
+    fn write_amqp_short_string<'a>(
+        &'a mut self,
+        s: &'a str,
+    ) -> WriteLengthPrefixed<&'a mut Self, &'a [u8]>
+    where
+        Self: Unpin,
+    {
+        WriteLengthPrefixed::new::<u8, BigEndian>(self, s.as_bytes())
+    }
+
+    /// Writes an AMQP 0-9-1 long string: its length as a big-endian `u32`, followed by its
+    /// bytes.
+    ///
+    /// This is what
+    /// [`read_amqp_long_string`](trait.AsyncReadBytesExt.html#method.read_amqp_long_string)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string is longer than `u32::MAX` bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_amqp_long_string(b"\x00guest\x00pw").await.unwrap();
+    ///     assert_eq!(wtr, b"\x00\x00\x00\x09\x00guest\x00pw");
+    /// }
+    /// ```
+    #[inline]
+    fn write_amqp_long_string<'a>(
+        &'a mut self,
+        data: &'a [u8],
+    ) -> WriteLengthPrefixed<&'a mut Self, &'a [u8]>
+    where
+        Self: Unpin,
+    {
+        WriteLengthPrefixed::new::<u32, BigEndian>(self, data)
+    }
+
+    /// Writes an AMQP 0-9-1 field table: its length in bytes as a big-endian `u32`, followed
+    /// by its fields.
+    ///
+    /// The table, including any tables and arrays nested in it, is encoded in full before
+    /// anything is written. This is what
+    /// [`read_amqp_field_table`](trait.AsyncReadBytesExt.html#method.read_amqp_field_table)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if a
+    /// field name is longer than 255 bytes, or if the table or a value in it is longer than
+    /// `u32::MAX` bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AmqpValue, AsyncWriteBytesExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let table = vec![
+    ///         ("durable".to_string(), AmqpValue::Bool(true)),
+    ///         ("x".to_string(), AmqpValue::Void),
+    ///     ];
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_amqp_field_table(&table).await.unwrap();
+    ///     assert_eq!(wtr, b"\x00\x00\x00\x0d\x07durablet\x01\x01xV");
+    /// }
+    /// ```
+    #[inline]
+    fn write_amqp_field_table(
+        &mut self,
+        table: &[(String, AmqpValue)],
+    ) -> WriteAmqpFieldTable<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteAmqpFieldTable::new(self, table)
+    }
+
+    /// Writes a Kafka `VARINT`: a zigzag-encoded varint.
+    ///
+    /// This is what [`read_kafka_varint`](trait.AsyncReadBytesExt.html#method.read_kafka_varint)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_kafka_varint(1000).await.unwrap();
+    ///     assert_eq!(wtr, [0xd0, 0x0f]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_kafka_varint(&mut self, n: i32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_zigzag_varint(i64::from(n))
+    }
+
+    /// Writes a Kafka `VARLONG`: a zigzag-encoded varint.
+    ///
+    /// This is the same as [`write_zigzag_varint`](#method.write_zigzag_varint), under the
+    /// name the Kafka protocol gives it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_kafka_varlong(&mut self, n: i64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_zigzag_varint(n)
+    }
+
+    /// Writes a Kafka `UNSIGNED_VARINT`: an unsigned LEB128 varint.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_kafka_unsigned_varint(&mut self, n: u32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_varint_u64(u64::from(n))
+    }
+
+    /// Writes a Kafka `NULLABLE_STRING`: its length as a big-endian `i16`, followed by its
+    /// bytes, or a length of -1 if it is `None`.
+    ///
+    /// This is what
+    /// [`read_kafka_nullable_string`](trait.AsyncReadBytesExt.html#method.read_kafka_nullable_string)
+    /// reads, and writes a non-nullable `STRING` when given `Some`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string is longer than 32767 bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_kafka_nullable_string(Some("client")).await.unwrap();
+    ///     wtr.write_kafka_nullable_string(None).await.unwrap();
+    ///     assert_eq!(wtr, b"\x00\x06client\xff\xff");
+    /// }
+    /// ```
+    #[inline]
+    fn write_kafka_nullable_string(&mut self, s: Option<&str>) -> WriteKafkaBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteKafkaBytes::nullable_string(self, s)
+    }
+
+    /// Writes a Kafka `COMPACT_NULLABLE_STRING`: its length plus one as an `UNSIGNED_VARINT`,
+    /// followed by its bytes, or a varint of 0 if it is `None`.
+    ///
+    /// This is what
+    /// [`read_kafka_compact_string`](trait.AsyncReadBytesExt.html#method.read_kafka_compact_string)
+    /// reads, and writes a non-nullable `COMPACT_STRING` when given `Some`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string's length plus one does not fit in a `u32`.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_kafka_compact_string(Some("orders")).await.unwrap();
+    ///     wtr.write_kafka_compact_string(None).await.unwrap();
+    ///     assert_eq!(wtr, b"\x07orders\x00");
+    /// }
+    /// ```
+    #[inline]
+    fn write_kafka_compact_string(&mut self, s: Option<&str>) -> WriteKafkaBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteKafkaBytes::compact(self, s.map(str::as_bytes))
+    }
+
+    /// Writes Kafka `COMPACT_NULLABLE_BYTES`: their length plus one as an `UNSIGNED_VARINT`,
+    /// followed by the bytes, or a varint of 0 if they are `None`.
+    ///
+    /// This is what
+    /// [`read_kafka_compact_bytes`](trait.AsyncReadBytesExt.html#method.read_kafka_compact_bytes)
+    /// reads, and writes non-nullable `COMPACT_BYTES` when given `Some`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`write_kafka_compact_string`](#method.write_kafka_compact_string).
+    #[inline]
+    fn write_kafka_compact_bytes(&mut self, data: Option<&[u8]>) -> WriteKafkaBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteKafkaBytes::compact(self, data)
+    }
+
+    /// Writes the type of an NBT tag.
+    ///
+    /// This is what [`read_nbt_tag`](trait.AsyncReadBytesExt.html#method.read_nbt_tag) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, NbtTag};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_nbt_tag(NbtTag::Int).await.unwrap();
+    ///     wtr.write_nbt_string("level").await.unwrap();
+    ///     wtr.write_i32::<BigEndian>(42).await.unwrap();
+    ///     assert_eq!(wtr, b"\x03\x00\x05level\x00\x00\x00\x2a");
+    /// }
+    /// ```
+    #[inline]
+    fn write_nbt_tag(&mut self, tag: NbtTag) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, sans_io::FixedEncoder::u8(tag as u8))
+    }
+
+    /// Writes an NBT string: its length in bytes as a big-endian `u16`, followed by its bytes
+    /// in Java's modified UTF-8.
+    ///
+    /// This is what [`read_nbt_string`](trait.AsyncReadBytesExt.html#method.read_nbt_string)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the string is longer than 65535 bytes once encoded.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_nbt_string("a\0b").await.unwrap();
+    ///     assert_eq!(wtr, b"\x00\x04a\xc0\x80b");
+    /// }
+    /// ```
+    #[inline]
+    fn write_nbt_string(&mut self, s: &str) -> WriteNbt<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteNbt::string(self, s)
+    }
+
+    /// Writes the payload of an NBT byte array: its length as a big-endian `i32`, followed by
+    /// its elements.
+    ///
+    /// This is what
+    /// [`read_nbt_byte_array`](trait.AsyncReadBytesExt.html#method.read_nbt_byte_array) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the array has more than `i32::MAX` elements.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_nbt_byte_array(&mut self, values: &[i8]) -> WriteNbt<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteNbt::array(self, values)
+    }
+
+    /// Writes the payload of an NBT int array: its length as a big-endian `i32`, followed by
+    /// its elements, big-endian.
+    ///
+    /// This is what
+    /// [`read_nbt_int_array`](trait.AsyncReadBytesExt.html#method.read_nbt_int_array) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`write_nbt_byte_array`](#method.write_nbt_byte_array).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_nbt_int_array(&[1, -1]).await.unwrap();
+    ///     assert_eq!(wtr, [0, 0, 0, 2, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_nbt_int_array(&mut self, values: &[i32]) -> WriteNbt<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteNbt::array(self, values)
+    }
+
+    /// Writes the payload of an NBT long array: its length as a big-endian `i32`, followed by
+    /// its elements, big-endian.
+    ///
+    /// This is what
+    /// [`read_nbt_long_array`](trait.AsyncReadBytesExt.html#method.read_nbt_long_array) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`write_nbt_byte_array`](#method.write_nbt_byte_array).
+    #[inline]
+    fn write_nbt_long_array(&mut self, values: &[i64]) -> WriteNbt<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteNbt::array(self, values)
+    }
+
+    /// Writes a Minecraft protocol VarInt: an `i32` in two's complement, stored as an unsigned
+    /// LEB128 varint.
+    ///
+    /// The shortest encoding is always written, so negative values take five bytes. This is
+    /// what
+    /// [`read_minecraft_varint`](trait.AsyncReadBytesExt.html#method.read_minecraft_varint)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_minecraft_varint(25565).await.unwrap();
+    ///     wtr.write_minecraft_varint(-1).await.unwrap();
+    ///     assert_eq!(wtr, [0xdd, 0xc7, 0x01, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_minecraft_varint(&mut self, n: i32) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_varint_u64(u64::from(n as u32))
+    }
+
+    /// Writes a Minecraft protocol VarLong: an `i64` in two's complement, stored as an
+    /// unsigned LEB128 varint.
+    ///
+    /// The shortest encoding is always written, so negative values take ten bytes. This is
+    /// what
+    /// [`read_minecraft_varlong`](trait.AsyncReadBytesExt.html#method.read_minecraft_varlong)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_minecraft_varlong(&mut self, n: i64) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        self.write_varint_u64(n as u64)
+    }
+
+    /// Writes the header of a DICOM data element: its tag, its value representation if `mode`
+    /// is explicit, and the length of its value.
+    ///
+    /// A `length` of `None` is written as the undefined length. This is what
+    /// [`read_dicom_element_header`](trait.AsyncReadBytesExt.html#method.read_dicom_element_header)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `mode` is explicit and `header.vr` is `None`, unless the tag is in group `0xfffe`, or if
+    /// the length does not fit in the 16 bit length field of the VR. The VR is ignored in
+    /// implicit mode.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, DicomElementHeader, DicomVrMode, RuntimeEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let header = DicomElementHeader {
+    ///         group: 0x0010,
+    ///         element: 0x0010,
+    ///         vr: Some(*b"PN"),
+    ///         length: Some(8),
+    ///     };
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_dicom_element_header(&header, RuntimeEndian::Little, DicomVrMode::Explicit)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(wtr, b"\x10\x00\x10\x00PN\x08\x00");
+    /// }
+    /// ```
+    #[inline]
+    fn write_dicom_element_header(
+        &mut self,
+        header: &DicomElementHeader,
+        endian: RuntimeEndian,
+        mode: DicomVrMode,
+    ) -> WriteDicomElementHeader<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteDicomElementHeader::new(self, header, endian, mode)
+    }
+
+    /// Writes a 33 bit PTS or DTS, in 90 kHz ticks, in the five byte layout of an MPEG-2 PES
+    /// header, with the four bit `prefix` in front of it and its marker bits set.
+    ///
+    /// This is what
+    /// [`read_mpeg_pes_timestamp`](trait.AsyncReadBytesExt.html#method.read_mpeg_pes_timestamp)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `prefix` does not fit in four bits, or `ts` does not fit in 33 bits.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mpeg_pes_timestamp(0b0010, 10 * 90_000).await.unwrap();
+    ///     assert_eq!(wtr, [0x21, 0x00, 0x37, 0x77, 0x41]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_mpeg_pes_timestamp(&mut self, prefix: u8, ts: u64) -> WriteMpegTs<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteMpegTs::pes_timestamp(self, prefix, ts)
+    }
+
+    /// Writes a PCR, in 27 MHz ticks, in the six byte layout of an MPEG-2 transport stream
+    /// adaptation field, with its reserved bits set.
+    ///
+    /// This is what [`read_mpeg_pcr`](trait.AsyncReadBytesExt.html#method.read_mpeg_pcr) reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if `pcr`
+    /// is larger than the largest PCR, `(2^33 - 1) * 300 + 299`.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_mpeg_pcr(27_000_000 + 150).await.unwrap();
+    ///     assert_eq!(wtr, [0x00, 0x00, 0xaf, 0xc8, 0x7e, 0x96]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_mpeg_pcr(&mut self, pcr: u64) -> WriteMpegTs<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteMpegTs::pcr(self, pcr)
+    }
+
+    /// Writes the 16 bit short form of a Bluetooth UUID in little-endian order.
+    ///
+    /// This is what [`read_ble_uuid16`](trait.AsyncReadBytesExt.html#method.read_ble_uuid16)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if `uuid`
+    /// has no 16 bit short form.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BluetoothUuid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_ble_uuid16(BluetoothUuid::from_u16(0x180d)).await.unwrap();
+    ///     assert_eq!(wtr, [0x0d, 0x18]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_ble_uuid16(&mut self, uuid: BluetoothUuid) -> WriteBleUuid<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteBleUuid::uuid16(self, uuid)
+    }
+
+    /// Writes the 32 bit short form of a Bluetooth UUID in little-endian order.
+    ///
+    /// This is what [`read_ble_uuid32`](trait.AsyncReadBytesExt.html#method.read_ble_uuid32)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if `uuid`
+    /// has no 32 bit short form.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BluetoothUuid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_ble_uuid32(BluetoothUuid::from_u16(0x180d)).await.unwrap();
+    ///     assert_eq!(wtr, [0x0d, 0x18, 0x00, 0x00]);
+    /// }
+    /// ```
+    #[inline]
+    fn write_ble_uuid32(&mut self, uuid: BluetoothUuid) -> WriteBleUuid<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteBleUuid::uuid32(self, uuid)
+    }
+
+    /// Writes a 128 bit Bluetooth UUID in little-endian order.
+    ///
+    /// This is what [`read_ble_uuid128`](trait.AsyncReadBytesExt.html#method.read_ble_uuid128)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BluetoothUuid};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_ble_uuid128(BluetoothUuid::BASE).await.unwrap();
+    ///     assert_eq!(
+    ///         wtr,
+    ///         [
+    ///             0xfb, 0x34, 0x9b, 0x5f, 0x80, 0x00, 0x00, 0x80,
+    ///             0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///         ]
+    ///     );
+    /// }
+    /// ```
+    #[inline]
+    fn write_ble_uuid128(&mut self, uuid: BluetoothUuid) -> WritePrimitive<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WritePrimitive::new(self, crate::ble::uuid128_encoder(uuid))
+    }
+
+    /// Writes an AD structure for Bluetooth LE advertising or scan response data: a length
+    /// byte that counts the AD type and the data, then the AD type, then the data.
+    ///
+    /// This is what
+    /// [`read_ble_ad_structure`](trait.AsyncReadBytesExt.html#method.read_ble_ad_structure)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// `data` is longer than 254 bytes.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BleAdStructure};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_ble_ad_structure(BleAdStructure::FLAGS, &[0x06]).await.unwrap();
+    ///     wtr.write_ble_ad_structure(BleAdStructure::COMPLETE_LOCAL_NAME, b"beep").await.unwrap();
+    ///     assert_eq!(wtr, b"\x02\x01\x06\x05\x09beep");
+    /// }
+    /// ```
+    #[inline]
+    fn write_ble_ad_structure(
+        &mut self,
+        ad_type: u8,
+        data: &[u8],
+    ) -> WriteLengthPrefixed<&mut Self, bytes::Bytes>
+    where
+        Self: Unpin,
+    {
+        let mut buf = Vec::with_capacity(1 + data.len());
+        buf.push(ad_type);
+        buf.extend_from_slice(data);
+        WriteLengthPrefixed::new::<u8, LittleEndian>(self, bytes::Bytes::from(buf))
+    }
+
+    /// Turns this writer into a [`Sink`] of frames that are each written prefixed with their
+    /// length as a `u32` in the byte order `T`.
+    ///
+    /// This writes what [`into_framed_u32`](trait.AsyncReadBytesExt.html#method.into_framed_u32)
+    /// reads. Frames longer than `max_frame` bytes are rejected; see [`FramedU32Sink`] for the
+    /// details.
+    ///
+    /// [`Sink`]: https://docs.rs/futures-sink/0.3/futures_sink/trait.Sink.html
+    /// [`FramedU32Sink`]: struct.FramedU32Sink.html
+    #[cfg(feature = "framed")]
+    #[inline]
+    fn into_framed_u32_sink<T: ByteOrder>(self, max_frame: usize) -> FramedU32Sink<Self, T>
+    where
+        Self: Sized + Unpin,
+    {
+        FramedU32Sink::new(self, max_frame)
+    }
+
+    /// Writes the number of bytes remaining in `buf` as a length prefix of type `P` in the byte
+    /// order `T`, followed by those bytes.
+    ///
+    /// `buf` can be any [`Buf`], such as [`Bytes`] or a chain of them, and its chunks are
+    /// written to the underlying writer as they are, without being copied into one buffer
+    /// first. This writes what
+    /// [`read_length_prefixed_bytes`](trait.AsyncReadBytesExt.html#method.read_length_prefixed_bytes)
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of kind `InvalidInput` is returned, and nothing is written, if
+    /// the length does not fit in a `P`.
+    ///
+    /// [`Buf`]: https://docs.rs/bytes/1/bytes/trait.Buf.html
+    /// [`Bytes`]: https://docs.rs/bytes/1/bytes/struct.Bytes.html
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::{Buf, Bytes};
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let payload = Bytes::from_static(b"hello, ").chain(Bytes::from_static(b"world"));
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_length_prefixed::<u16, BigEndian, _>(payload)
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(wtr, b"\x00\x0chello, world");
+    /// }
+    /// ```
+    #[inline]
+    fn write_length_prefixed<P, T, B>(&mut self, buf: B) -> WriteLengthPrefixed<&mut Self, B>
+    where
+        P: Primitive + TryFrom<usize>,
+        T: ByteOrder,
+        B: bytes::Buf + Unpin,
+        Self: Unpin,
+    {
+        WriteLengthPrefixed::new::<P, T>(self, buf)
+    }
+
+    /// Writes `data` as a frame encoded with Consistent Overhead Byte Stuffing, followed by the
+    /// `0x00` delimiter that ends it.
+    ///
+    /// The frame is encoded up front and written with as few writes as the writer allows. It
+    /// is what [`read_cobs_frame`](trait.AsyncBufReadBytesExt.html#method.read_cobs_frame)
+    /// reads, and takes at most one byte for every 254 bytes of `data`, plus two, more than
+    /// `data` itself.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_cobs_frame(b"\x11\x22\x00\x33").await.unwrap();
+    ///     assert_eq!(wtr, b"\x03\x11\x22\x02\x33\x00");
+    /// }
+    /// ```
+    #[inline]
+    fn write_cobs_frame(&mut self, data: &[u8]) -> WriteCobsFrame<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteCobsFrame::new(self, data)
+    }
+
+    /// Writes `data` as a SLIP packet (RFC 1055), with an `END` byte on either side.
+    ///
+    /// Any `END` or `ESC` byte in `data` is escaped. The leading `END` flushes out any line
+    /// noise the receiver may have gathered, as RFC 1055 suggests. The packet is encoded up
+    /// front and written with as few writes as the writer allows. It is what
+    /// [`read_slip_frame`](trait.AsyncBufReadBytesExt.html#method.read_slip_frame) reads,
+    /// which skips an empty packet.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::AsyncWriteBytesExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_slip_frame(b"\x01\xc0\x02").await.unwrap();
+    ///     assert_eq!(wtr, b"\xc0\x01\xdb\xdc\x02\xc0");
+    /// }
+    /// ```
+    #[inline]
+    fn write_slip_frame(&mut self, data: &[u8]) -> WriteSlipFrame<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteSlipFrame::new(self, data)
+    }
+
+    /// Writes an unsigned 16 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u16`](#method.write_u16).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_u16_rt(&mut self, endian: RuntimeEndian, n: u16) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 2, n, RuntimeCodec::write)
+    }
+
+    /// Writes a signed 16 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i16`](#method.write_i16).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i16_rt(&mut self, endian: RuntimeEndian, n: i16) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 2, n, RuntimeCodec::write)
+    }
+
+    /// Writes an unsigned 24 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u24`](#method.write_u24).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_u24_rt(&mut self, endian: RuntimeEndian, n: u32) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 3, n, |e, b, n| {
+            endian::write_uint(e, b, u64::from(n))
+        })
+    }
+
+    /// Writes a signed 24 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i24`](#method.write_i24).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i24_rt(&mut self, endian: RuntimeEndian, n: i32) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 3, n, |e, b, n| {
+            endian::write_int(e, b, i64::from(n))
+        })
+    }
+
+    /// Writes an unsigned 32 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u32`](#method.write_u32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// Write an unsigned 32 bit integer in a byte order chosen at runtime:
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, RuntimeEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = Vec::new();
+    ///     wtr.write_u32_rt(RuntimeEndian::Little, 267).await.unwrap();
+    ///     wtr.write_u32_rt(RuntimeEndian::Big, 267).await.unwrap();
+    ///     assert_eq!(wtr, b"\x0b\x01\x00\x00\x00\x00\x01\x0b");
+    /// }
+    /// ```
+    #[inline]
+    fn write_u32_rt(&mut self, endian: RuntimeEndian, n: u32) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 4, n, RuntimeCodec::write)
+    }
+
+    /// Writes a signed 32 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i32`](#method.write_i32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i32_rt(&mut self, endian: RuntimeEndian, n: i32) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 4, n, RuntimeCodec::write)
+    }
+
+    /// Writes an unsigned 48 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u48`](#method.write_u48).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_u48_rt(&mut self, endian: RuntimeEndian, n: u64) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 6, n, endian::write_uint)
+    }
+
+    /// Writes a signed 48 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i48`](#method.write_i48).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i48_rt(&mut self, endian: RuntimeEndian, n: i64) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 6, n, endian::write_int)
+    }
+
+    /// Writes an unsigned 64 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u64`](#method.write_u64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_u64_rt(&mut self, endian: RuntimeEndian, n: u64) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 8, n, RuntimeCodec::write)
+    }
+
+    /// Writes a signed 64 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i64`](#method.write_i64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i64_rt(&mut self, endian: RuntimeEndian, n: i64) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 8, n, RuntimeCodec::write)
+    }
+
+    /// Writes an unsigned 128 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_u128`](#method.write_u128).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_u128_rt(&mut self, endian: RuntimeEndian, n: u128) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 16, n, RuntimeCodec::write)
+    }
+
+    /// Writes a signed 128 bit integer to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_i128`](#method.write_i128).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_i128_rt(&mut self, endian: RuntimeEndian, n: i128) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 16, n, RuntimeCodec::write)
+    }
+
+    /// Writes a IEEE754 single-precision (4 bytes) floating point number to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_f32`](#method.write_f32).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_f32_rt(&mut self, endian: RuntimeEndian, n: f32) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 4, n, RuntimeCodec::write)
+    }
+
+    /// Writes a IEEE754 double-precision (8 bytes) floating point number to the underlying writer in a byte order chosen at
+    /// runtime.
+    ///
+    /// This is otherwise identical to [`write_f64`](#method.write_f64).
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn write_f64_rt(&mut self, endian: RuntimeEndian, n: f64) -> WriteRuntime<&mut Self>
+    where
+        Self: Unpin,
+    {
+        WriteRuntime::new(self, endian, 8, n, RuntimeCodec::write)
+    }
+
+    /// Writes an unsigned 8 bit integer to the underlying writer, which is taken by value.
+    ///
+    /// The returned future resolves to the writer along with the result, and does not borrow
+    /// anything, so it can be spawned or stored without tying it to the writer's owner. The
+    /// other `_owned` methods work the same way.
+    ///
+    /// # Errors
+    ///
+    /// The result has the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (wtr, res) = tokio::spawn(Vec::new().write_u8_owned(42)).await.unwrap();
+    ///     res.unwrap();
+    ///     let (wtr, res) = wtr.write_u32_owned::<BigEndian>(267).await;
+    ///     res.unwrap();
+    ///     assert_eq!(wtr, b"\x2a\x00\x00\x01\x0b");
+    /// }
+    /// ```
+    #[inline]
+    fn write_u8_owned(self, n: u8) -> WriteOwned<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        WriteOwned::new(self, sans_io::FixedEncoder::u8(n))
+    }
+
+    /// Writes a signed 8 bit integer to the underlying writer, which is taken by value.
+    ///
+    /// See [`write_u8_owned`](#method.write_u8_owned).
+    #[inline]
+    fn write_i8_owned(self, n: i8) -> WriteOwned<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        WriteOwned::new(self, sans_io::FixedEncoder::i8(n))
+    }
+
+    write_owned! {
+        (write_u16_owned, u16, u16, "an unsigned 16 bit integer"),
+        (write_i16_owned, i16, i16, "a signed 16 bit integer"),
+        (write_u24_owned, u24, u32, "an unsigned 24 bit integer"),
+        (write_i24_owned, i24, i32, "a signed 24 bit integer"),
+        (write_u32_owned, u32, u32, "an unsigned 32 bit integer"),
+        (write_i32_owned, i32, i32, "a signed 32 bit integer"),
+        (write_u48_owned, u48, u64, "an unsigned 48 bit integer"),
+        (write_i48_owned, i48, i64, "a signed 48 bit integer"),
+        (write_u64_owned, u64, u64, "an unsigned 64 bit integer"),
+        (write_i64_owned, i64, i64, "a signed 64 bit integer"),
+        (write_u128_owned, u128, u128, "an unsigned 128 bit integer"),
+        (write_i128_owned, i128, i128, "a signed 128 bit integer"),
+        (write_f32_owned, f32, f32, "a IEEE754 single-precision floating point number"),
+        (write_f64_owned, f64, f64, "a IEEE754 double-precision floating point number"),
+    }
+}
+
+/// All types that implement `Write` get methods defined in `WriteBytesExt`
+/// for free.
+impl<W: io::AsyncWrite + ?Sized> AsyncWriteBytesExt for W {}
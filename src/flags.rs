@@ -0,0 +1,64 @@
+use crate::*;
+use bitflags::Flags;
+use pin_project_lite::pin_project;
+
+/// What to do with bits that a flags field has set, but that its type does not define.
+///
+/// Passed to [`read_flags`](trait.AsyncReadBytesExt.html#method.read_flags).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnknownBits {
+    /// Drop the unknown bits.
+    ///
+    /// This suits fields where a newer peer may set flags that older code can safely ignore.
+    Truncate,
+    /// Keep the unknown bits in the value, so that they survive being written back out.
+    Retain,
+    /// Fail the read with an error of the kind [`ErrorKind::InvalidData`].
+    ///
+    /// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    Error,
+}
+
+pin_project! {
+    /// Future returned by [`read_flags`](../trait.AsyncReadBytesExt.html#method.read_flags).
+    #[derive(Debug)]
+    pub struct ReadFlags<R, F, V> {
+        #[pin]
+        inner: ReadPrimitive<R, V>,
+        policy: UnknownBits,
+        flags: PhantomData<fn() -> F>,
+    }
+}
+
+impl<R, F, V> ReadFlags<R, F, V> {
+    pub(crate) fn new(inner: ReadPrimitive<R, V>, policy: UnknownBits) -> Self {
+        ReadFlags {
+            inner,
+            policy,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<R, F, V> Future for ReadFlags<R, F, V>
+where
+    R: io::AsyncRead,
+    F: Flags,
+    F::Bits: From<V>,
+{
+    type Output = io::Result<F>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let bits = F::Bits::from(core::task::ready!(this.inner.poll(cx))?);
+        Poll::Ready(match this.policy {
+            UnknownBits::Truncate => Ok(F::from_bits_truncate(bits)),
+            UnknownBits::Retain => Ok(F::from_bits_retain(bits)),
+            UnknownBits::Error => F::from_bits(bits).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "flags field has unknown bits set",
+                )
+            }),
+        })
+    }
+}
@@ -0,0 +1,214 @@
+use crate::*;
+use bytes::BytesMut;
+use core::convert::TryFrom;
+use tokio::io::AsyncWriteExt;
+
+/// A writer that collects each frame in memory, and writes it out behind a length prefix once
+/// the frame is complete.
+///
+/// Everything written to a `FrameWriter`, through [`AsyncWriteBytesExt`] or any other use of its
+/// [`AsyncWrite`] implementation, becomes part of the current frame. Room for a prefix of type
+/// `P` is left at the start of the frame, and when the frame is [finished], its length is
+/// filled in there in the byte order `T` and the frame is handed to the underlying writer in a
+/// single write. A frame can also be [discarded], in which case nothing of it is ever written.
+/// This saves writing every message twice, once to find its length and once for real.
+///
+/// [`write_frame`] wraps the two up: it finishes the frame if the closure it is given succeeds,
+/// and discards it if the closure fails.
+///
+/// Flushing a `FrameWriter` flushes the underlying writer, but does not write out the current
+/// frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, FrameWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = FrameWriter::<_, u16, BigEndian>::new(Vec::new());
+///     wtr.write_u8(1).await.unwrap();
+///     wtr.write_u32::<BigEndian>(267).await.unwrap();
+///     assert!(wtr.get_ref().is_empty());
+///     wtr.finish().await.unwrap();
+///     assert_eq!(wtr.get_ref(), b"\x00\x05\x01\x00\x00\x01\x0b");
+/// }
+/// ```
+///
+/// [finished]: #method.finish
+/// [discarded]: #method.discard
+/// [`write_frame`]: #method.write_frame
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`AsyncWriteBytesExt`]: trait.AsyncWriteBytesExt.html
+#[derive(Debug)]
+pub struct FrameWriter<W, P, T> {
+    dst: W,
+    // the frame, behind room for its prefix
+    buf: BytesMut,
+    prefix: usize,
+    bo: PhantomData<fn() -> (P, T)>,
+}
+
+impl<W, P: Primitive, T: ByteOrder> FrameWriter<W, P, T> {
+    /// Wraps the given writer, and starts the first frame.
+    pub fn new(dst: W) -> Self {
+        let prefix = P::decoder::<T>().remaining();
+        let mut buf = BytesMut::new();
+        buf.resize(prefix, 0);
+        FrameWriter {
+            dst,
+            buf,
+            prefix,
+            bo: PhantomData,
+        }
+    }
+}
+
+impl<W, P, T> FrameWriter<W, P, T> {
+    /// Returns the number of bytes in the current frame, not counting its prefix.
+    pub fn frame_len(&self) -> usize {
+        self.buf.len() - self.prefix
+    }
+
+    /// Returns the bytes in the current frame, not counting its prefix.
+    pub fn frame(&self) -> &[u8] {
+        &self.buf[self.prefix..]
+    }
+
+    /// Throws away the current frame, and starts a new one.
+    pub fn discard(&mut self) {
+        self.buf.truncate(self.prefix);
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer puts the bytes in between frames.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.dst
+    }
+
+    /// Unwraps this `FrameWriter`, returning the underlying writer.
+    ///
+    /// The current frame is lost.
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W, P, T> FrameWriter<W, P, T>
+where
+    W: io::AsyncWrite + Unpin,
+    P: Primitive + TryFrom<usize>,
+    T: ByteOrder,
+{
+    /// Fills in the length prefix of the current frame, writes the frame to the underlying
+    /// writer, and starts a new one.
+    ///
+    /// The underlying writer is not flushed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`]. If this future is dropped
+    /// before it completes, part of the frame may have been written.
+    ///
+    /// In addition, an error of the kind `InvalidInput` is returned, and nothing is written, if
+    /// the length of the frame does not fit in a `P`.
+    ///
+    /// Either way, the frame is discarded.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn finish(&mut self) -> io::Result<()> {
+        let res = match P::try_from(self.frame_len()) {
+            Ok(len) => {
+                len.encoder::<T>().pull_bytes(&mut self.buf[..self.prefix]);
+                self.dst.write_all(&self.buf).await
+            }
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame does not fit in the length prefix",
+            )),
+        };
+        self.discard();
+        res
+    }
+
+    /// Runs `f` to write the current frame, and then finishes it, or discards it if `f` fails.
+    ///
+    /// Since the future that `f` returns borrows the writer, it has to be boxed, as with
+    /// `Box::pin(async move { ... })`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the error that `f` returns, if any, and otherwise the errors of
+    /// [`finish`](#method.finish).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::{AsyncWriteBytesExt, FrameWriter, LittleEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = FrameWriter::<_, u32, LittleEndian>::new(Vec::new());
+    ///     wtr.write_frame(|frame| {
+    ///         Box::pin(async move { frame.write_u16::<LittleEndian>(7).await })
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    ///
+    ///     let err = wtr
+    ///         .write_frame(|frame| {
+    ///             Box::pin(async move {
+    ///                 frame.write_u16::<LittleEndian>(8).await?;
+    ///                 Err::<(), _>(std::io::Error::other("out of values"))
+    ///             })
+    ///         })
+    ///         .await
+    ///         .unwrap_err();
+    ///     assert_eq!(err.to_string(), "out of values");
+    ///     assert_eq!(wtr.get_ref(), b"\x02\x00\x00\x00\x07\x00");
+    /// }
+    /// ```
+    pub async fn write_frame<F, V>(&mut self, f: F) -> io::Result<V>
+    where
+        F: for<'a> FnOnce(&'a mut Self) -> BoxFuture<'a, io::Result<V>>,
+    {
+        match f(self).await {
+            Ok(v) => {
+                self.finish().await?;
+                Ok(v)
+            }
+            Err(e) => {
+                self.discard();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<W, P, T> io::AsyncWrite for FrameWriter<W, P, T>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().buf.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().dst).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().dst).poll_shutdown(cx)
+    }
+}
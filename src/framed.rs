@@ -0,0 +1,285 @@
+use crate::coop::Budget;
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use bytes::{Buf, Bytes, BytesMut};
+use core::convert::TryFrom;
+use core::task::ready;
+use futures_core::Stream;
+use futures_sink::Sink;
+
+fn too_long(kind: io::ErrorKind, max_frame: usize) -> io::Error {
+    io::Error::new(
+        kind,
+        format!("frame is longer than the maximum of {} bytes", max_frame),
+    )
+}
+
+/// A stream of frames that are each prefixed with their length as a `u32` in the byte order `T`.
+///
+/// Returned by [`into_framed_u32`](trait.AsyncReadBytesExt.html#method.into_framed_u32), and
+/// read back what a [`FramedU32Sink`](struct.FramedU32Sink.html) writes.
+///
+/// The stream ends when the underlying reader reaches end-of-file right where a new frame would
+/// start. End-of-file anywhere else is an error of the kind [`ErrorKind::UnexpectedEof`], and a
+/// frame that declares a length larger than the maximum is an error of the kind
+/// [`ErrorKind::InvalidData`], which is returned before the frame is read.
+///
+/// The frames are read into a single buffer that is split up as they are handed out. Once the
+/// frames from an earlier read have all been dropped, their memory is reused for the next ones,
+/// so a stream whose frames are processed one at a time does not allocate for every frame.
+///
+/// [`ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+/// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+#[derive(Debug)]
+pub struct FramedU32<R, T> {
+    src: R,
+    max_frame: usize,
+    header: FixedDecoder<u32>,
+    // holds the frame being read, if any, and reuses the memory of frames that were dropped
+    buf: BytesMut,
+    // the length of the frame being read, once its header is in
+    len: Option<usize>,
+    bo: PhantomData<fn() -> T>,
+}
+
+impl<R, T: ByteOrder> FramedU32<R, T> {
+    pub(crate) fn new(src: R, max_frame: usize) -> Self {
+        FramedU32 {
+            src,
+            max_frame,
+            header: FixedDecoder::u32::<T>(),
+            buf: BytesMut::new(),
+            len: None,
+            bo: PhantomData,
+        }
+    }
+}
+
+impl<R, T> FramedU32<R, T> {
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.src
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the returned reference while a frame is partially read corrupts
+    /// the stream.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.src
+    }
+
+    /// Unwraps this value, returning the underlying reader.
+    ///
+    /// Any partially read frame is lost.
+    pub fn into_inner(self) -> R {
+        self.src
+    }
+}
+
+impl<R, T> Stream for FramedU32<R, T>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = match ready!(poll_decode(Pin::new(&mut this.src), cx, &mut this.header)) {
+                    Ok(len) => len as usize,
+                    Err(e)
+                        if e.kind() == io::ErrorKind::UnexpectedEof
+                            && this.header.buffered().is_empty() =>
+                    {
+                        return Poll::Ready(None);
+                    }
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                };
+                if len > this.max_frame {
+                    return Poll::Ready(Some(Err(too_long(
+                        io::ErrorKind::InvalidData,
+                        this.max_frame,
+                    ))));
+                }
+                *this.len.insert(len)
+            }
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        this.len = None;
+        Poll::Ready(Some(Ok(this.buf.split().freeze())))
+    }
+}
+
+/// A sink that writes each frame prefixed with its length as a `u32` in the byte order `T`.
+///
+/// Returned by
+/// [`into_framed_u32_sink`](trait.AsyncWriteBytesExt.html#method.into_framed_u32_sink). The
+/// frames are written without being copied, one at a time: [`poll_ready`] only returns once the
+/// previous frame has been handed to the underlying writer.
+///
+/// Sending a frame longer than the maximum fails with an error of the kind
+/// [`ErrorKind::InvalidInput`], and nothing is written.
+///
+/// The underlying writer is only flushed when the sink is, unless a different
+/// [`FlushPolicy`] is [set]. When the sink wraps a buffering writer such as a [`ByteWriter`],
+/// that decides how many frames go out in each write.
+///
+/// [`FlushPolicy`]: enum.FlushPolicy.html
+/// [set]: #method.set_flush_policy
+/// [`ByteWriter`]: struct.ByteWriter.html
+/// [`poll_ready`]: https://docs.rs/futures-sink/0.3/futures_sink/trait.Sink.html#tymethod.poll_ready
+/// [`ErrorKind::InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput
+#[derive(Debug)]
+pub struct FramedU32Sink<W, T> {
+    dst: W,
+    max_frame: usize,
+    header: FixedEncoder,
+    frame: Bytes,
+    policy: FlushPolicy,
+    // bytes handed to the underlying writer since it was last flushed
+    unflushed: usize,
+    bo: PhantomData<fn() -> T>,
+}
+
+impl<W, T> FramedU32Sink<W, T> {
+    pub(crate) fn new(dst: W, max_frame: usize) -> Self {
+        FramedU32Sink {
+            dst,
+            max_frame,
+            header: FixedEncoder::new(0, |_| {}),
+            frame: Bytes::new(),
+            policy: FlushPolicy::Manual,
+            unflushed: 0,
+            bo: PhantomData,
+        }
+    }
+
+    /// Returns when the underlying writer is flushed.
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.policy
+    }
+
+    /// Changes when the underlying writer is flushed.
+    ///
+    /// With [`FlushPolicy::Frame`] or [`FlushPolicy::Immediate`], it is flushed once each frame
+    /// has been written, before the sink is ready for the next one. With
+    /// [`FlushPolicy::Watermark`], it is flushed once the frames written since the last flush,
+    /// including their length prefixes, add up to at least that many bytes.
+    ///
+    /// [`FlushPolicy::Frame`]: enum.FlushPolicy.html#variant.Frame
+    /// [`FlushPolicy::Immediate`]: enum.FlushPolicy.html#variant.Immediate
+    /// [`FlushPolicy::Watermark`]: enum.FlushPolicy.html#variant.Watermark
+    pub fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.policy = policy;
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the returned reference while a frame is partially written corrupts
+    /// the stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.dst
+    }
+
+    /// Unwraps this value, returning the underlying writer.
+    ///
+    /// Any frame that has not been written out yet is lost.
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W, T> FramedU32Sink<W, T>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write_frame(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(poll_write_encoder(
+            Pin::new(&mut self.dst),
+            cx,
+            &mut self.header
+        ))?;
+        let mut budget = Budget::new();
+        while !self.frame.is_empty() {
+            match ready!(Pin::new(&mut self.dst).poll_write(cx, &self.frame)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Poll::Ready(Err(e)),
+                Ok(0) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    )));
+                }
+                Ok(n) => {
+                    self.frame.advance(n);
+                    if !self.frame.is_empty() {
+                        ready!(budget.poll_charge(cx, n));
+                    }
+                }
+            }
+        }
+        let flush = match self.policy {
+            FlushPolicy::Manual => false,
+            FlushPolicy::Watermark(watermark) => self.unflushed >= watermark,
+            FlushPolicy::Frame | FlushPolicy::Immediate => true,
+        };
+        if flush && self.unflushed > 0 {
+            ready!(self.poll_flush_dst(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush_dst(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(Pin::new(&mut self.dst).poll_flush(cx))?;
+        self.unflushed = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, T> Sink<Bytes> for FramedU32Sink<W, T>
+where
+    W: io::AsyncWrite + Unpin,
+    T: ByteOrder,
+{
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_write_frame(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Bytes) -> io::Result<()> {
+        let this = self.get_mut();
+        assert!(
+            this.header.is_done() && this.frame.is_empty(),
+            "start_send called before poll_ready"
+        );
+        let len = u32::try_from(frame.len())
+            .ok()
+            .filter(|&len| len as usize <= this.max_frame)
+            .ok_or_else(|| too_long(io::ErrorKind::InvalidInput, this.max_frame))?;
+        this.header = FixedEncoder::u32::<T>(len);
+        this.unflushed += 4 + frame.len();
+        this.frame = frame;
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_frame(cx))?;
+        this.poll_flush_dst(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_frame(cx))?;
+        Pin::new(&mut this.dst).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,377 @@
+//! The futures returned by this crate's methods.
+//!
+//! Most code never needs to name these, since `.await` works on them directly. They are listed
+//! here for code that stores them, such as hand-written state machines.
+//!
+//! Every future in this module is [`Unpin`] whenever the stream (or reference to it) it holds
+//! is, and [`Send`] and [`Sync`] whenever that stream is. The futures returned for a
+//! `&mut S` are therefore always `Unpin`, and are `Send` if `S` is. The byte order type
+//! parameter never affects either property. Each future also implements [`Debug`] when its
+//! stream does.
+//!
+//! The futures for the other I/O trait families live in the `future` module of the matching
+//! backend, such as [`futures::future`](../futures/future/index.html).
+//!
+//! [`Unpin`]: https://doc.rust-lang.org/std/marker/trait.Unpin.html
+//! [`Send`]: https://doc.rust-lang.org/std/marker/trait.Send.html
+//! [`Sync`]: https://doc.rust-lang.org/std/marker/trait.Sync.html
+//! [`Debug`]: https://doc.rust-lang.org/std/fmt/trait.Debug.html
+
+use crate::*;
+use pin_project_lite::pin_project;
+
+pub use crate::amqp::{ReadAmqpFieldTable, ReadAmqpShortString, WriteAmqpFieldTable};
+pub use crate::avro::{ReadAvroBytes, WriteAvroBytes};
+pub use crate::ble::{ReadBleAdStructure, WriteBleUuid};
+pub use crate::buf::{Peek, ReadBuffered, ResyncTo, ResyncToByte};
+pub use crate::byte_reader::{PeekExact, ReadTracked};
+pub use crate::byte_writer::WriteBuffered;
+pub use crate::cbor::ReadCborHead;
+#[cfg(feature = "crc")]
+pub use crate::checksum::ReadCheckedHeader;
+pub use crate::cobs::{ReadCobsFrame, WriteCobsFrame};
+pub use crate::decoder::Decode;
+pub use crate::dicom::{ReadDicomElementHeader, WriteDicomElementHeader};
+pub use crate::dns::ReadDnsName;
+pub use crate::endian::{DetectEndianness, ReadRuntime, WriteRuntime};
+#[cfg(feature = "bitflags")]
+pub use crate::flags::ReadFlags;
+pub use crate::git::{ReadGitObjectHeader, ReadGitOfsDeltaOffset, WriteGitObjectHeader};
+pub use crate::group_varint::{ReadVarintBlock, WriteVarintBlock};
+pub use crate::kafka::{ReadKafkaBytes, WriteKafkaBytes};
+pub use crate::mavlink::{ReadMavlinkFrame, WriteMavlinkFrame};
+pub use crate::minecraft::ReadMinecraftVarint;
+pub use crate::mpeg_ts::{ReadMpegTs, WriteMpegTs};
+pub use crate::msgpack::ReadMsgpack;
+pub use crate::mysql::{ReadMysqlLenencInt, ReadMysqlLenencStr, WriteMysql};
+pub use crate::nbt::{ReadNbtArray, ReadNbtString, ReadNbtTag, WriteNbt};
+pub use crate::nibble::ReadNibbles;
+pub use crate::ninep::Read9pString;
+pub use crate::owned::{ReadOwned, WriteOwned};
+pub use crate::pcm::{ReadI24Into, WriteI24From};
+pub use crate::postgres::{ReadPgMessageHeader, ReadPgString, WritePg};
+pub use crate::prefixed::{ReadLengthPrefixedBytes, ReadLengthPrefixedInto, WriteLengthPrefixed};
+pub use crate::read_at::ReadAt;
+pub use crate::rtp::{ReadRtpHeader, ReadRtspInterleavedHeader};
+pub use crate::slip::{ReadSlipFrame, WriteSlipFrame};
+pub use crate::stats::ReadCounted;
+pub use crate::stun::{ReadStunAttribute, ReadStunHeader, WriteStun};
+pub use crate::thrift::{ReadThriftFieldHeader, WriteThriftFieldHeader};
+#[cfg(feature = "time")]
+pub use crate::timeout::ReadTimeout;
+pub use crate::tuple::ReadTuple;
+pub use crate::utf8_coded::{ReadUtf8Coded, WriteUtf8Coded};
+pub use crate::varint::{ReadDeltaVarints, ReadVarint, WriteVarints};
+pub use crate::write_at::{Fill, Reserve};
+
+macro_rules! reader {
+    ($name:ident, $ty:ty, $decoder:ident) => {
+        pin_project! {
+            #[doc = concat!("Future returned by [`read_", stringify!($decoder), "`](../trait.AsyncReadBytesExt.html#method.read_", stringify!($decoder), ") and its `_be`, `_le` and `_ne` variants.")]
+            #[derive(Debug)]
+            pub struct $name<R, T> {
+                decoder: sans_io::FixedDecoder<$ty>,
+                #[pin]
+                src: R,
+                bo: PhantomData<fn() -> T>,
+            }
+        }
+
+        impl<R, T: ByteOrder> $name<R, T> {
+            pub(crate) fn new(r: R) -> Self {
+                $name {
+                    decoder: sans_io::FixedDecoder::$decoder::<T>(),
+                    src: r,
+                    bo: PhantomData,
+                }
+            }
+        }
+
+        impl<R, T> Future for $name<R, T>
+        where
+            R: io::AsyncRead,
+            T: ByteOrder,
+        {
+            type Output = io::Result<$ty>;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                let res = poll_decode(this.src, cx, this.decoder);
+                #[cfg(feature = "tracing")]
+                if let Poll::Ready(Ok(v)) = &res {
+                    let endian = Some(crate::trace::endian::<T>());
+                    crate::trace::read(stringify!($decoder), endian, v, this.decoder.last(), None);
+                }
+                res
+            }
+        }
+    };
+}
+
+macro_rules! reader8 {
+    ($name:ident, $ty:ident) => {
+        pin_project! {
+            #[doc = concat!("Future returned by [`read_", stringify!($ty), "`](../trait.AsyncReadBytesExt.html#method.read_", stringify!($ty), ").")]
+            #[derive(Debug)]
+            pub struct $name<R> {
+                decoder: sans_io::FixedDecoder<$ty>,
+                #[pin]
+                src: R,
+            }
+        }
+
+        impl<R> $name<R> {
+            pub(crate) fn new(r: R) -> Self {
+                $name {
+                    decoder: sans_io::FixedDecoder::$ty(),
+                    src: r,
+                }
+            }
+        }
+
+        impl<R> Future for $name<R>
+        where
+            R: io::AsyncRead,
+        {
+            type Output = io::Result<$ty>;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                let res = poll_decode(this.src, cx, this.decoder);
+                #[cfg(feature = "tracing")]
+                if let Poll::Ready(Ok(v)) = &res {
+                    crate::trace::read(stringify!($ty), None, v, this.decoder.last(), None);
+                }
+                res
+            }
+        }
+    };
+}
+
+reader8!(ReadU8, u8);
+reader8!(ReadI8, i8);
+
+reader!(ReadF32, f32, f32);
+reader!(ReadF64, f64, f64);
+reader!(ReadU16, u16, u16);
+reader!(ReadU24, u32, u24);
+reader!(ReadU32, u32, u32);
+reader!(ReadU48, u64, u48);
+reader!(ReadU64, u64, u64);
+reader!(ReadU128, u128, u128);
+reader!(ReadI16, i16, i16);
+reader!(ReadI24, i32, i24);
+reader!(ReadI32, i32, i32);
+reader!(ReadI48, i64, i48);
+reader!(ReadI64, i64, i64);
+reader!(ReadI128, i128, i128);
+
+macro_rules! writer {
+    ($name:ident, $ty:ty, $encoder:ident) => {
+        pin_project! {
+            #[doc = concat!("Future returned by [`write_", stringify!($encoder), "`](../trait.AsyncWriteBytesExt.html#method.write_", stringify!($encoder), ") and its `_be`, `_le` and `_ne` variants.")]
+            #[derive(Debug)]
+            pub struct $name<W> {
+                encoder: sans_io::FixedEncoder,
+                #[pin]
+                dst: W,
+            }
+        }
+
+        impl<W> $name<W> {
+            pub(crate) fn new<T: ByteOrder>(w: W, value: $ty) -> Self {
+                let encoder = sans_io::FixedEncoder::$encoder::<T>(value);
+                #[cfg(feature = "tracing")]
+                {
+                    let endian = Some(crate::trace::endian::<T>());
+                    crate::trace::write(stringify!($encoder), endian, &value, encoder.encoded());
+                }
+                $name { encoder, dst: w }
+            }
+
+            /// Flushes the writer once the value has been written.
+            ///
+            /// See [`WriteAndFlush`](struct.WriteAndFlush.html).
+            pub fn and_flush(self) -> WriteAndFlush<W> {
+                WriteAndFlush::new(self.dst, self.encoder)
+            }
+        }
+
+        impl<W> Future for $name<W>
+        where
+            W: io::AsyncWrite,
+        {
+            type Output = io::Result<()>;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                poll_write_encoder(this.dst, cx, this.encoder)
+            }
+        }
+    };
+}
+
+macro_rules! writer8 {
+    ($name:ident, $ty:ident) => {
+        pin_project! {
+            #[doc = concat!("Future returned by [`write_", stringify!($ty), "`](../trait.AsyncWriteBytesExt.html#method.write_", stringify!($ty), ").")]
+            #[derive(Debug)]
+            pub struct $name<W> {
+                encoder: sans_io::FixedEncoder,
+                #[pin]
+                dst: W,
+            }
+        }
+
+        impl<W> $name<W> {
+            pub(crate) fn new(w: W, n: $ty) -> Self {
+                let encoder = sans_io::FixedEncoder::$ty(n);
+                #[cfg(feature = "tracing")]
+                crate::trace::write(stringify!($ty), None, &n, encoder.encoded());
+                $name { encoder, dst: w }
+            }
+
+            /// Flushes the writer once the value has been written.
+            ///
+            /// See [`WriteAndFlush`](struct.WriteAndFlush.html).
+            pub fn and_flush(self) -> WriteAndFlush<W> {
+                WriteAndFlush::new(self.dst, self.encoder)
+            }
+        }
+
+        impl<W> Future for $name<W>
+        where
+            W: io::AsyncWrite,
+        {
+            type Output = io::Result<()>;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                poll_write_encoder(this.dst, cx, this.encoder)
+            }
+        }
+    };
+}
+
+writer8!(WriteU8, u8);
+writer8!(WriteI8, i8);
+
+writer!(WriteF32, f32, f32);
+writer!(WriteF64, f64, f64);
+writer!(WriteU16, u16, u16);
+writer!(WriteU24, u32, u24);
+writer!(WriteU32, u32, u32);
+writer!(WriteU48, u64, u48);
+writer!(WriteU64, u64, u64);
+writer!(WriteU128, u128, u128);
+writer!(WriteI16, i16, i16);
+writer!(WriteI24, i32, i24);
+writer!(WriteI32, i32, i32);
+writer!(WriteI48, i64, i48);
+writer!(WriteI64, i64, i64);
+writer!(WriteI128, i128, i128);
+
+pin_project! {
+    /// Future returned by
+    /// [`read_primitive`](../trait.AsyncReadBytesExt.html#method.read_primitive).
+    #[derive(Debug)]
+    pub struct ReadPrimitive<R, V> {
+        decoder: sans_io::FixedDecoder<V>,
+        #[pin]
+        src: R,
+    }
+}
+
+impl<R, V> ReadPrimitive<R, V> {
+    pub(crate) fn new(src: R, decoder: sans_io::FixedDecoder<V>) -> Self {
+        ReadPrimitive { decoder, src }
+    }
+}
+
+impl<R, V> Future for ReadPrimitive<R, V>
+where
+    R: io::AsyncRead,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        poll_decode(this.src, cx, this.decoder)
+    }
+}
+
+pin_project! {
+    /// Future returned by
+    /// [`write_primitive`](../trait.AsyncWriteBytesExt.html#method.write_primitive).
+    #[derive(Debug)]
+    pub struct WritePrimitive<W> {
+        encoder: sans_io::FixedEncoder,
+        #[pin]
+        dst: W,
+    }
+}
+
+impl<W> WritePrimitive<W> {
+    pub(crate) fn new(dst: W, encoder: sans_io::FixedEncoder) -> Self {
+        WritePrimitive { encoder, dst }
+    }
+
+    /// Flushes the writer once the value has been written.
+    ///
+    /// See [`WriteAndFlush`](struct.WriteAndFlush.html).
+    pub fn and_flush(self) -> WriteAndFlush<W> {
+        WriteAndFlush::new(self.dst, self.encoder)
+    }
+}
+
+impl<W> Future for WritePrimitive<W>
+where
+    W: io::AsyncWrite,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        poll_write_encoder(this.dst, cx, this.encoder)
+    }
+}
+
+pin_project! {
+    /// Future returned by the `and_flush` method of the futures that write a single number.
+    ///
+    /// It writes the number like the future it was made from would have, and then flushes the
+    /// writer, so that a value that must reach the other side before anything else happens
+    /// cannot be left sitting in a buffer by a forgotten second `.await`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio::io::BufWriter;
+    /// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut wtr = BufWriter::new(Vec::new());
+    ///     wtr.write_u32::<BigEndian>(1).and_flush().await.unwrap();
+    ///     assert_eq!(wtr.get_ref(), &[0, 0, 0, 1]);
+    /// }
+    /// ```
+    #[derive(Debug)]
+    pub struct WriteAndFlush<W> {
+        encoder: sans_io::FixedEncoder,
+        #[pin]
+        dst: W,
+    }
+}
+
+impl<W> WriteAndFlush<W> {
+    pub(crate) fn new(dst: W, encoder: sans_io::FixedEncoder) -> Self {
+        WriteAndFlush { encoder, dst }
+    }
+}
+
+impl<W> Future for WriteAndFlush<W>
+where
+    W: io::AsyncWrite,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        core::task::ready!(poll_write_encoder(this.dst.as_mut(), cx, this.encoder))?;
+        this.dst.poll_flush(cx)
+    }
+}
@@ -0,0 +1,86 @@
+//! Extension traits for the [`futures-io`] I/O traits.
+//!
+//! The traits in this module provide the same fixed-width reading and writing methods as the
+//! crate-level [`AsyncReadBytesExt`] and [`AsyncWriteBytesExt`], but for types that implement
+//! [`futures_io::AsyncRead`] and [`futures_io::AsyncWrite`] instead of tokio's traits. Those are
+//! the traits used by `async-std` (as `async_std::io::Read` and `async_std::io::Write`), `smol`,
+//! and `futures-lite`, among others, so a single set of traits covers all of them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_std::io::Cursor;
+//! use tokio_byteorder::futures::{AsyncReadBytesExt, AsyncWriteBytesExt};
+//! use tokio_byteorder::BigEndian;
+//!
+//! futures_lite::future::block_on(async {
+//!     let mut wtr = Cursor::new(Vec::new());
+//!     wtr.write_u32::<BigEndian>(267).await.unwrap();
+//!     let mut rdr = Cursor::new(wtr.into_inner());
+//!     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+//! });
+//! ```
+//!
+//! [`futures-io`]: https://docs.rs/futures-io/0.3/futures_io/
+//! [`AsyncReadBytesExt`]: ../trait.AsyncReadBytesExt.html
+//! [`AsyncWriteBytesExt`]: ../trait.AsyncWriteBytesExt.html
+//! [`futures_io::AsyncRead`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncRead.html
+//! [`futures_io::AsyncWrite`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncWrite.html
+
+use crate::compat::{AsyncByteSink, AsyncByteSource, Compat};
+use crate::*;
+use core::task::ready;
+
+/// Marks streams that implement the `futures-io` traits.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum FuturesIo {}
+
+impl<R> AsyncByteSource<FuturesIo> for R
+where
+    R: futures_io::AsyncRead + Unpin + ?Sized,
+{
+    fn poll_read_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let n = ready!(futures_io::AsyncRead::poll_read(
+            self,
+            cx,
+            buf.initialize_unfilled()
+        ))?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncByteSink<FuturesIo> for W
+where
+    W: futures_io::AsyncWrite + Unpin + ?Sized,
+{
+    fn poll_write_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        futures_io::AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_io::AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_close_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_io::AsyncWrite::poll_close(self, cx)
+    }
+}
+
+compat_ext!(
+    FuturesIo,
+    futures_io::AsyncRead,
+    futures_io::AsyncWrite,
+    "futures_io::AsyncRead",
+    "futures_io::AsyncWrite",
+    "https://docs.rs/futures-io/0.3/futures_io"
+);
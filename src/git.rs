@@ -0,0 +1,189 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::task::ready;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn is_object_type(kind: u8) -> bool {
+    matches!(kind, 1..=4 | 6 | 7)
+}
+
+/// Returns an encoder for the header of a packed object of the given type and size.
+fn object_header_encoder(kind: u8, size: u64) -> Result<FixedEncoder, &'static str> {
+    if !is_object_type(kind) {
+        return Err("not a git object type");
+    }
+    let len = if size < 16 {
+        1
+    } else {
+        1 + (64 - (size >> 4).leading_zeros() as usize).div_ceil(7)
+    };
+    Ok(FixedEncoder::new(len, |buf| {
+        buf[0] = kind << 4 | (size & 0x0f) as u8;
+        let mut rest = size >> 4;
+        for b in &mut buf[1..] {
+            *b = rest as u8 & 0x7f;
+            rest >>= 7;
+        }
+        for b in &mut buf[..len - 1] {
+            *b |= 0x80;
+        }
+    }))
+}
+
+/// Returns an encoder for the base offset of an `OFS_DELTA` object.
+pub(crate) fn ofs_delta_encoder(offset: u64) -> FixedEncoder {
+    // the same loop as git's, filling from the end
+    let mut tmp = [0u8; 10];
+    let mut pos = tmp.len() - 1;
+    let mut ofs = offset;
+    tmp[pos] = ofs as u8 & 0x7f;
+    ofs >>= 7;
+    while ofs != 0 {
+        ofs -= 1;
+        pos -= 1;
+        tmp[pos] = 0x80 | (ofs as u8 & 0x7f);
+        ofs >>= 7;
+    }
+    FixedEncoder::new(tmp.len() - pos, |buf| buf.copy_from_slice(&tmp[pos..]))
+}
+
+/// Future returned by
+/// [`read_git_object_header`](../trait.AsyncReadBytesExt.html#method.read_git_object_header).
+#[derive(Debug)]
+pub struct ReadGitObjectHeader<R> {
+    src: R,
+    kind: u8,
+    size: u64,
+    // bytes read so far
+    read: u32,
+}
+
+impl<R> ReadGitObjectHeader<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadGitObjectHeader {
+            src,
+            kind: 0,
+            size: 0,
+            read: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadGitObjectHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<(u8, u64)>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut b = [0; 1];
+            ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+            let b = b[0];
+            if this.read == 0 {
+                this.kind = (b >> 4) & 0x07;
+                if !is_object_type(this.kind) {
+                    return Poll::Ready(Err(invalid("invalid git object type")));
+                }
+                this.size = u64::from(b & 0x0f);
+            } else {
+                let shift = 4 + 7 * (this.read - 1);
+                let bits = u64::from(b & 0x7f);
+                if shift >= 64 || (bits << shift) >> shift != bits {
+                    return Poll::Ready(Err(invalid("git object size overflows a u64")));
+                }
+                this.size |= bits << shift;
+            }
+            this.read += 1;
+            if b & 0x80 == 0 {
+                return Poll::Ready(Ok((this.kind, this.size)));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`read_git_ofs_delta_offset`](../trait.AsyncReadBytesExt.html#method.read_git_ofs_delta_offset).
+#[derive(Debug)]
+pub struct ReadGitOfsDeltaOffset<R> {
+    src: R,
+    offset: u64,
+    started: bool,
+}
+
+impl<R> ReadGitOfsDeltaOffset<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadGitOfsDeltaOffset {
+            src,
+            offset: 0,
+            started: false,
+        }
+    }
+}
+
+impl<R> Future for ReadGitOfsDeltaOffset<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<u64>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut b = [0; 1];
+            ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+            let b = b[0];
+            let bits = u64::from(b & 0x7f);
+            if this.started {
+                // every byte after the first also adds one, so no offset has two encodings
+                this.offset = this
+                    .offset
+                    .checked_add(1)
+                    .filter(|&n| n >> 57 == 0)
+                    .ok_or_else(|| invalid("git delta offset overflows a u64"))?
+                    << 7
+                    | bits;
+            } else {
+                this.offset = bits;
+                this.started = true;
+            }
+            if b & 0x80 == 0 {
+                return Poll::Ready(Ok(this.offset));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`write_git_object_header`](../trait.AsyncWriteBytesExt.html#method.write_git_object_header).
+#[derive(Debug)]
+pub struct WriteGitObjectHeader<W> {
+    dst: W,
+    // the encoded header, or why it cannot be encoded
+    encoder: Result<FixedEncoder, &'static str>,
+}
+
+impl<W> WriteGitObjectHeader<W> {
+    pub(crate) fn new(dst: W, kind: u8, size: u64) -> Self {
+        WriteGitObjectHeader {
+            dst,
+            encoder: object_header_encoder(kind, size),
+        }
+    }
+}
+
+impl<W> Future for WriteGitObjectHeader<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.encoder {
+            Ok(encoder) => poll_write_encoder(Pin::new(&mut this.dst), cx, encoder),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::*;
+use std::vec::Vec;
+
+/// How a block of `u32`s is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    // a tag byte in front of each group of four values, first value in the high bits
+    Group,
+    // all the tags up front, first value in the low bits
+    StreamVByte,
+}
+
+impl Layout {
+    /// Returns the code of value `i` of a group from its tag.
+    fn code(self, tag: u8, i: usize) -> u8 {
+        match self {
+            Layout::Group => (tag >> (6 - 2 * i)) & 0b11,
+            Layout::StreamVByte => (tag >> (2 * i)) & 0b11,
+        }
+    }
+
+    /// Returns the tag bits for value `i` of a group with the given code.
+    fn tag(self, code: u8, i: usize) -> u8 {
+        match self {
+            Layout::Group => code << (6 - 2 * i),
+            Layout::StreamVByte => code << (2 * i),
+        }
+    }
+}
+
+/// Returns the number of bytes a value takes, less one.
+fn code(n: u32) -> u8 {
+    match n {
+        0..=0xff => 0,
+        0x100..=0xffff => 1,
+        0x1_0000..=0xff_ffff => 2,
+        _ => 3,
+    }
+}
+
+/// Returns the number of data bytes the `count` values of a group with the given tag take.
+fn group_len(layout: Layout, tag: u8, count: usize) -> usize {
+    (0..count).map(|i| layout.code(tag, i) as usize + 1).sum()
+}
+
+fn decode(bytes: &[u8]) -> u32 {
+    let mut n = [0; 4];
+    n[..bytes.len()].copy_from_slice(bytes);
+    u32::from_le_bytes(n)
+}
+
+/// Future returned by
+/// [`read_group_varint_u32`](../trait.AsyncReadBytesExt.html#method.read_group_varint_u32) and
+/// [`read_stream_vbyte_u32`](../trait.AsyncReadBytesExt.html#method.read_stream_vbyte_u32).
+#[derive(Debug)]
+pub struct ReadVarintBlock<'a, R: ?Sized> {
+    src: &'a mut R,
+    dst: &'a mut [u32],
+    layout: Layout,
+    buf: Vec<u8>,
+    // how many bytes of `buf` have been accounted for by the tags read so far
+    known: usize,
+    // how many values the tags read so far cover
+    tagged: usize,
+}
+
+impl<'a, R: ?Sized> ReadVarintBlock<'a, R> {
+    fn new(src: &'a mut R, dst: &'a mut [u32], layout: Layout) -> Self {
+        ReadVarintBlock {
+            src,
+            dst,
+            layout,
+            buf: Vec::new(),
+            known: 0,
+            tagged: 0,
+        }
+    }
+
+    pub(crate) fn group(src: &'a mut R, dst: &'a mut [u32]) -> Self {
+        Self::new(src, dst, Layout::Group)
+    }
+
+    pub(crate) fn stream_vbyte(src: &'a mut R, dst: &'a mut [u32]) -> Self {
+        Self::new(src, dst, Layout::StreamVByte)
+    }
+}
+
+impl<R> Future for ReadVarintBlock<'_, R>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let n = this.dst.len();
+        let layout = this.layout;
+        match layout {
+            Layout::Group => {
+                // a tag, then the data it describes, one group at a time
+                while this.tagged < n {
+                    core::task::ready!(poll_read_to_len(
+                        this.src,
+                        cx,
+                        &mut this.buf,
+                        this.known + 1
+                    ))?;
+                    let count = (n - this.tagged).min(4);
+                    let tag = this.buf[this.known];
+                    this.known += 1 + group_len(layout, tag, count);
+                    this.tagged += count;
+                }
+                core::task::ready!(poll_read_to_len(this.src, cx, &mut this.buf, this.known))?;
+
+                let mut at = 0;
+                for group in this.dst.chunks_mut(4) {
+                    let tag = this.buf[at];
+                    at += 1;
+                    for (i, v) in group.iter_mut().enumerate() {
+                        let len = layout.code(tag, i) as usize + 1;
+                        *v = decode(&this.buf[at..at + len]);
+                        at += len;
+                    }
+                }
+            }
+            Layout::StreamVByte => {
+                // all of the tags, then all of the data
+                let tags = n.div_ceil(4);
+                if this.tagged < n {
+                    core::task::ready!(poll_read_to_len(this.src, cx, &mut this.buf, tags))?;
+                    this.known = tags;
+                    for (g, &tag) in this.buf.iter().enumerate() {
+                        this.known += group_len(layout, tag, (n - 4 * g).min(4));
+                    }
+                    this.tagged = n;
+                }
+                core::task::ready!(poll_read_to_len(this.src, cx, &mut this.buf, this.known))?;
+
+                let (tags, mut data) = this.buf.split_at(tags);
+                for (group, &tag) in this.dst.chunks_mut(4).zip(tags) {
+                    for (i, v) in group.iter_mut().enumerate() {
+                        let len = layout.code(tag, i) as usize + 1;
+                        *v = decode(&data[..len]);
+                        data = &data[len..];
+                    }
+                }
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by
+/// [`write_group_varint_u32`](../trait.AsyncWriteBytesExt.html#method.write_group_varint_u32)
+/// and
+/// [`write_stream_vbyte_u32`](../trait.AsyncWriteBytesExt.html#method.write_stream_vbyte_u32).
+#[derive(Debug)]
+pub struct WriteVarintBlock<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteVarintBlock<W> {
+    pub(crate) fn group(dst: W, src: &[u32]) -> Self {
+        let layout = Layout::Group;
+        let mut buf = Vec::with_capacity(src.len() * 5 / 4 + 1);
+        for group in src.chunks(4) {
+            let at = buf.len();
+            buf.push(0);
+            for (i, &v) in group.iter().enumerate() {
+                let code = code(v);
+                buf[at] |= layout.tag(code, i);
+                buf.extend_from_slice(&v.to_le_bytes()[..code as usize + 1]);
+            }
+        }
+        WriteVarintBlock {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn stream_vbyte(dst: W, src: &[u32]) -> Self {
+        let layout = Layout::StreamVByte;
+        let tags = src.len().div_ceil(4);
+        let mut buf = vec![0; tags];
+        buf.reserve(src.len() * 4);
+        for (g, group) in src.chunks(4).enumerate() {
+            for (i, &v) in group.iter().enumerate() {
+                let code = code(v);
+                buf[g] |= layout.tag(code, i);
+                buf.extend_from_slice(&v.to_le_bytes()[..code as usize + 1]);
+            }
+        }
+        WriteVarintBlock {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteVarintBlock<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
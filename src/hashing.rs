@@ -0,0 +1,157 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use digest::{FixedOutputReset, Output, Update};
+use tokio::io;
+
+/// A reader that feeds every byte read through it into a [`digest`] hasher.
+///
+/// The hash covers exactly the bytes that were handed out to the caller, including those of
+/// partial reads, so it matches what a decoder on top actually consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// use sha2::{Digest, Sha256};
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, HashingReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = HashingReader::new(&b"\x00\x2aabc"[..], Sha256::new());
+///     assert_eq!(42, rdr.read_u16::<BigEndian>().await.unwrap());
+///     let (_, hasher) = rdr.into_parts();
+///     assert_eq!(hasher.finalize(), Sha256::digest(b"\x00\x2a"));
+/// }
+/// ```
+///
+/// [`digest`]: https://docs.rs/digest/0.10/digest/
+#[derive(Debug)]
+pub struct HashingReader<R, D> {
+    inner: R,
+    digest: D,
+}
+
+/// A writer that feeds every byte written through it into a [`digest`] hasher.
+///
+/// Only bytes that the underlying writer has accepted are hashed.
+///
+/// # Examples
+///
+/// ```rust
+/// use sha2::{Digest, Sha256};
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, HashingWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = HashingWriter::new(Vec::new(), Sha256::new());
+///     wtr.write_u16::<BigEndian>(42).await.unwrap();
+///     assert_eq!(wtr.finalize_reset(), Sha256::digest(b"\x00\x2a"));
+/// }
+/// ```
+///
+/// [`digest`]: https://docs.rs/digest/0.10/digest/
+#[derive(Debug)]
+pub struct HashingWriter<W, D> {
+    inner: W,
+    digest: D,
+}
+
+macro_rules! hashing_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, D: Update> $name<$inner, D> {
+            /// Wraps the given stream, feeding the bytes that pass through into `digest`.
+            pub fn new(inner: $inner, digest: D) -> Self {
+                $name { inner, digest }
+            }
+
+            /// Gets a reference to the hasher.
+            pub fn digest(&self) -> &D {
+                &self.digest
+            }
+
+            /// Gets a mutable reference to the hasher.
+            pub fn digest_mut(&mut self) -> &mut D {
+                &mut self.digest
+            }
+
+            /// Returns the hash of the bytes seen so far, and resets the hasher.
+            ///
+            /// This is handy for hashing a stream one frame at a time.
+            pub fn finalize_reset(&mut self) -> Output<D>
+            where
+                D: FixedOutputReset,
+            {
+                self.digest.finalize_fixed_reset()
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            ///
+            /// Bytes transferred directly through the returned reference are not hashed.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream and the hasher.
+            pub fn into_parts(self) -> ($inner, D) {
+                (self.inner, self.digest)
+            }
+        }
+    };
+}
+
+hashing_common!(HashingReader, R);
+hashing_common!(HashingWriter, W);
+
+impl<R, D> io::AsyncRead for HashingReader<R, D>
+where
+    R: io::AsyncRead + Unpin,
+    D: Update + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let res = Pin::new(&mut this.inner).poll_read(cx, buf);
+        this.digest.update(&buf.filled()[before..]);
+        res
+    }
+}
+
+impl<W, D> io::AsyncWrite for HashingWriter<W, D>
+where
+    W: io::AsyncWrite + Unpin,
+    D: Update + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = res {
+            this.digest.update(&buf[..n]);
+        }
+        res
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,100 @@
+/// The Internet checksum of RFC 1071, as used by IPv4, ICMP, TCP, and UDP headers.
+///
+/// It is the one's complement of the one's complement sum of the bytes taken as big-endian
+/// 16-bit words, where an odd byte at the end is padded with a zero. Bytes can be fed in
+/// pieces of any length, odd ones included, and the result is the same as if they had been fed
+/// at once. TCP and UDP pseudo-headers are covered by feeding them in first.
+///
+/// To verify a header, feed it in whole, checksum field included: the value is then zero if
+/// the checksum is correct. To compute one, feed the header with the checksum field zeroed.
+///
+/// With the `crc` feature, it also implements [`Checksum`], so that it can be computed over
+/// a stream with [`CrcReader`] and [`CrcWriter`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::InetChecksum;
+///
+/// // an IPv4 header
+/// let header = [
+///     0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xb8, 0x61, 0xc0, 0xa8,
+///     0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7,
+/// ];
+/// let mut sum = InetChecksum::new();
+/// sum.update(&header);
+/// assert_eq!(sum.value(), 0);
+/// ```
+///
+/// [`Checksum`]: trait.Checksum.html
+/// [`CrcReader`]: struct.CrcReader.html
+/// [`CrcWriter`]: struct.CrcWriter.html
+#[derive(Debug, Clone, Default)]
+pub struct InetChecksum {
+    sum: u64,
+    // the first byte of a word whose second byte has not been fed in yet
+    odd: Option<u8>,
+}
+
+impl InetChecksum {
+    /// Creates a new Internet checksum.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds `bytes` into the checksum.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        if let Some(hi) = self.odd.take() {
+            match bytes.split_first() {
+                Some((&lo, rest)) => {
+                    self.sum += u64::from(u16::from_be_bytes([hi, lo]));
+                    bytes = rest;
+                }
+                None => {
+                    self.odd = Some(hi);
+                    return;
+                }
+            }
+        }
+        let mut words = bytes.chunks_exact(2);
+        for word in &mut words {
+            self.sum += u64::from(u16::from_be_bytes([word[0], word[1]]));
+        }
+        self.odd = words.remainder().first().copied();
+        // fold early enough that the sum can never overflow
+        if self.sum >> 48 != 0 {
+            self.sum = (self.sum & 0xffff_ffff) + (self.sum >> 32);
+        }
+    }
+
+    /// Returns the checksum of all the bytes fed in since creation or the last reset.
+    pub fn value(&self) -> u16 {
+        let mut sum = self.sum + self.odd.map_or(0, |hi| u64::from(hi) << 8);
+        while sum >> 16 != 0 {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(feature = "crc")]
+impl crate::Checksum for InetChecksum {
+    type Output = u16;
+
+    fn update(&mut self, bytes: &[u8]) {
+        InetChecksum::update(self, bytes)
+    }
+
+    fn value(&self) -> u16 {
+        InetChecksum::value(self)
+    }
+
+    fn reset(&mut self) {
+        InetChecksum::reset(self)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::varint::{encode_varint, zigzag_decode, VarintDecoder};
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) fn decode_varint(n: u64) -> io::Result<i32> {
+    i32::try_from(zigzag_decode(n)).map_err(|_| invalid("kafka varint does not fit in an i32"))
+}
+
+pub(crate) fn decode_unsigned_varint(n: u64) -> io::Result<u32> {
+    u32::try_from(n).map_err(|_| invalid("kafka unsigned varint does not fit in a u32"))
+}
+
+fn to_string(buf: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(buf).map_err(|_| invalid("kafka string is not valid utf-8"))
+}
+
+/// Future returned by
+/// [`read_kafka_nullable_string`](../trait.AsyncReadBytesExt.html#method.read_kafka_nullable_string),
+/// [`read_kafka_compact_string`](../trait.AsyncReadBytesExt.html#method.read_kafka_compact_string),
+/// and
+/// [`read_kafka_compact_bytes`](../trait.AsyncReadBytesExt.html#method.read_kafka_compact_bytes).
+#[derive(Debug)]
+pub struct ReadKafkaBytes<R, V> {
+    src: R,
+    max_len: usize,
+    // whether the length is an unsigned varint one more than it, rather than an `i16`
+    compact: bool,
+    decoder: VarintDecoder,
+    prefix: [u8; 2],
+    filled: usize,
+    // the length of the value, or `None` for null, once its prefix is in
+    len: Option<Option<usize>>,
+    buf: Vec<u8>,
+    finish: fn(Vec<u8>) -> io::Result<V>,
+}
+
+impl<R> ReadKafkaBytes<R, String> {
+    pub(crate) fn nullable_string(src: R, max_len: usize) -> Self {
+        Self::new(src, max_len, false, to_string)
+    }
+
+    pub(crate) fn compact_string(src: R, max_len: usize) -> Self {
+        Self::new(src, max_len, true, to_string)
+    }
+}
+
+impl<R> ReadKafkaBytes<R, Vec<u8>> {
+    pub(crate) fn compact_bytes(src: R, max_len: usize) -> Self {
+        Self::new(src, max_len, true, Ok)
+    }
+}
+
+impl<R, V> ReadKafkaBytes<R, V> {
+    fn new(src: R, max_len: usize, compact: bool, finish: fn(Vec<u8>) -> io::Result<V>) -> Self {
+        ReadKafkaBytes {
+            src,
+            max_len,
+            compact,
+            decoder: VarintDecoder::default(),
+            prefix: [0; 2],
+            filled: 0,
+            len: None,
+            buf: Vec::new(),
+            finish,
+        }
+    }
+}
+
+impl<R, V> Future for ReadKafkaBytes<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Option<V>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = if this.compact {
+                    let n = ready!(this.decoder.poll_read(&mut this.src, cx))?;
+                    let n = decode_unsigned_varint(n)?;
+                    n.checked_sub(1).map(u64::from)
+                } else {
+                    ready!(poll_read_exact(
+                        &mut this.src,
+                        cx,
+                        &mut this.prefix,
+                        &mut this.filled
+                    ))?;
+                    match BigEndian::read_i16(&this.prefix) {
+                        -1 => None,
+                        n => Some(
+                            u64::try_from(n)
+                                .map_err(|_| invalid("kafka string length is negative"))?,
+                        ),
+                    }
+                };
+                let len = match len.map(usize::try_from) {
+                    None => None,
+                    Some(Ok(len)) if len <= this.max_len => Some(len),
+                    Some(_) => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "kafka length is larger than the maximum of {} bytes",
+                                this.max_len
+                            ),
+                        )));
+                    }
+                };
+                *this.len.insert(len)
+            }
+        };
+        let len = match len {
+            Some(len) => len,
+            None => return Poll::Ready(Ok(None)),
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready((this.finish)(core::mem::take(&mut this.buf)).map(Some))
+    }
+}
+
+/// Future returned by
+/// [`write_kafka_nullable_string`](../trait.AsyncWriteBytesExt.html#method.write_kafka_nullable_string),
+/// [`write_kafka_compact_string`](../trait.AsyncWriteBytesExt.html#method.write_kafka_compact_string),
+/// and
+/// [`write_kafka_compact_bytes`](../trait.AsyncWriteBytesExt.html#method.write_kafka_compact_bytes).
+#[derive(Debug)]
+pub struct WriteKafkaBytes<W> {
+    dst: W,
+    // `None` if the value is too long for its length prefix
+    buf: Option<Vec<u8>>,
+    written: usize,
+}
+
+impl<W> WriteKafkaBytes<W> {
+    pub(crate) fn nullable_string(dst: W, s: Option<&str>) -> Self {
+        let buf = match s {
+            None => Some(vec![0xff, 0xff]),
+            Some(s) => i16::try_from(s.len()).ok().map(|len| {
+                let mut buf = Vec::with_capacity(2 + s.len());
+                buf.extend_from_slice(&len.to_be_bytes());
+                buf.extend_from_slice(s.as_bytes());
+                buf
+            }),
+        };
+        WriteKafkaBytes {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn compact(dst: W, data: Option<&[u8]>) -> Self {
+        let buf = match data {
+            None => Some(vec![0]),
+            Some(data) => u32::try_from(data.len())
+                .ok()
+                .and_then(|len| len.checked_add(1))
+                .map(|len| {
+                    let mut buf = Vec::with_capacity(5 + data.len());
+                    encode_varint(u64::from(len), &mut buf);
+                    buf.extend_from_slice(data);
+                    buf
+                }),
+        };
+        WriteKafkaBytes {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteKafkaBytes<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Some(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            None => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "kafka value is too long for its length prefix",
+            ))),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+/// Declares a struct along with [`AsyncDecode`] and [`AsyncEncode`] implementations for it.
+///
+/// This covers the same ground as the derive macros, but needs neither the `derive` feature nor
+/// a proc-macro build dependency. Each field is followed by `as` and its wire type: one of
+/// `u8` and `i8`, or one of `u16`, `i16`, `u24`, `i24`, `u32`, `i32`, `u48`, `i48`, `u64`,
+/// `i64`, `u128`, `i128`, `f32`, and `f64` along with the byte order to use for it. The field's
+/// own type must be the one that the matching `read_` method returns, so `u24` fields are
+/// `u32`s, for example. A field without `as` is encoded through its type's own
+/// [`AsyncDecode`] and [`AsyncEncode`] implementations.
+///
+/// Fields are encoded in declaration order, with nothing in between.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{byte_layout, AsyncDecode, AsyncEncode, BigEndian, LittleEndian};
+///
+/// byte_layout! {
+///     /// The fixed part of a record header.
+///     #[derive(Debug, PartialEq)]
+///     pub struct Header {
+///         pub kind: u8 as u8,
+///         pub len: u32 as u24<BigEndian>,
+///         pub crc: u16 as u16<LittleEndian>,
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let header = Header { kind: 1, len: 258, crc: 0xbeef };
+///     let mut buf = Vec::new();
+///     header.write_to(&mut buf).await.unwrap();
+///     assert_eq!(buf, [0x01, 0x00, 0x01, 0x02, 0xef, 0xbe]);
+///     assert_eq!(Header::read_from(&mut &buf[..]).await.unwrap(), header);
+/// }
+/// ```
+///
+/// [`AsyncDecode`]: trait.AsyncDecode.html
+/// [`AsyncEncode`]: trait.AsyncEncode.html
+#[macro_export]
+macro_rules! byte_layout {
+    (@read $src:ident, $ty:ty) => {
+        <$ty as $crate::AsyncDecode>::read_from($src).await?
+    };
+    (@read $src:ident, $ty:ty, $wire:ident $(<$e:ty>)?) => {
+        $crate::__private::wire::$wire::read::<$($e,)? _>($src).await?
+    };
+    (@write $dst:ident, $v:expr, $ty:ty) => {
+        <$ty as $crate::AsyncEncode>::write_to(&$v, $dst).await?
+    };
+    (@write $dst:ident, $v:expr, $ty:ty, $wire:ident $(<$e:ty>)?) => {
+        $crate::__private::wire::$wire::write::<$($e,)? _>($dst, $v).await?
+    };
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$fattr:meta])*
+                $fvis:vis $field:ident : $ty:ty $(as $wire:ident $(<$e:ty>)?)?
+            ),* $(,)?
+        }
+    )*) => {$(
+        $(#[$attr])*
+        $vis struct $name {
+            $(
+                $(#[$fattr])*
+                $fvis $field: $ty,
+            )*
+        }
+
+        impl $crate::AsyncDecode for $name {
+            fn read_from<R>(
+                src: &mut R,
+            ) -> impl ::core::future::Future<Output = ::std::io::Result<Self>> + ::core::marker::Send
+            where
+                R: $crate::__private::AsyncRead
+                    + ::core::marker::Unpin
+                    + ::core::marker::Send
+                    + ?::core::marker::Sized,
+            {
+                async move {
+                    $(
+                        let $field = $crate::byte_layout!(@read src, $ty $(, $wire $(<$e>)?)?);
+                    )*
+                    $crate::__private::Ok($name { $($field),* })
+                }
+            }
+        }
+
+        impl $crate::AsyncEncode for $name {
+            fn write_to<W>(
+                &self,
+                dst: &mut W,
+            ) -> impl ::core::future::Future<Output = ::std::io::Result<()>> + ::core::marker::Send
+            where
+                W: $crate::__private::AsyncWrite
+                    + ::core::marker::Unpin
+                    + ::core::marker::Send
+                    + ?::core::marker::Sized,
+            {
+                async move {
+                    $(
+                        $crate::byte_layout!(@write dst, self.$field, $ty $(, $wire $(<$e>)?)?);
+                    )*
+                    $crate::__private::Ok(())
+                }
+            }
+        }
+    )*};
+}
+
+/// One module per wire type that `byte_layout!` understands, each with a `read` and a `write`
+/// function, so that the macro can turn the wire type's name into a path.
+#[doc(hidden)]
+pub mod wire {
+    macro_rules! wire8 {
+        ($($name:ident($ty:ty, $read:ident, $write:ident)),* $(,)?) => {$(
+            pub mod $name {
+                use crate::*;
+
+                pub async fn read<R>(src: &mut R) -> io::Result<$ty>
+                where
+                    R: io::AsyncRead + Unpin + ?Sized,
+                {
+                    AsyncReadBytesExt::$read(src).await
+                }
+
+                pub async fn write<W>(dst: &mut W, n: $ty) -> io::Result<()>
+                where
+                    W: io::AsyncWrite + Unpin + ?Sized,
+                {
+                    AsyncWriteBytesExt::$write(dst, n).await
+                }
+            }
+        )*};
+    }
+
+    macro_rules! wire {
+        ($($name:ident($ty:ty, $read:ident, $write:ident)),* $(,)?) => {$(
+            pub mod $name {
+                use crate::*;
+
+                pub async fn read<E, R>(src: &mut R) -> io::Result<$ty>
+                where
+                    E: ByteOrder,
+                    R: io::AsyncRead + Unpin + ?Sized,
+                {
+                    AsyncReadBytesExt::$read::<E>(src).await
+                }
+
+                pub async fn write<E, W>(dst: &mut W, n: $ty) -> io::Result<()>
+                where
+                    E: ByteOrder,
+                    W: io::AsyncWrite + Unpin + ?Sized,
+                {
+                    AsyncWriteBytesExt::$write::<E>(dst, n).await
+                }
+            }
+        )*};
+    }
+
+    wire8! {
+        u8(u8, read_u8, write_u8),
+        i8(i8, read_i8, write_i8),
+    }
+
+    wire! {
+        u16(u16, read_u16, write_u16),
+        i16(i16, read_i16, write_i16),
+        u24(u32, read_u24, write_u24),
+        i24(i32, read_i24, write_i24),
+        u32(u32, read_u32, write_u32),
+        i32(i32, read_i32, write_i32),
+        u48(u64, read_u48, write_u48),
+        i48(i64, read_i48, write_i48),
+        u64(u64, read_u64, write_u64),
+        i64(i64, read_i64, write_i64),
+        u128(u128, read_u128, write_u128),
+        i128(i128, read_i128, write_i128),
+        f32(f32, read_f32, write_f32),
+        f64(f64, read_f64, write_f64),
+    }
+}
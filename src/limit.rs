@@ -0,0 +1,151 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+
+/// A reader that holds the reader it wraps to an exact number of bytes.
+///
+/// Returned by [`limit`]. Unlike [`take`], which quietly reports the end of the stream once the
+/// limit is reached, a `Limit` fails any read that asks for more bytes than are left with a
+/// [`LimitError`], and [`expect_exhausted`] fails if any bytes were left unread. A decoder that
+/// reads a field with a declared length through a `Limit` therefore learns right away if it
+/// consumed too much or too little, rather than leaving the stream out of step.
+///
+/// A single read that asks for more bytes than are left still returns the bytes that are; it
+/// is the next read that fails. [`remaining`] tells how far the limit is.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, LimitError};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = &[0x00, 0x03, 0x01, 0x02, 0x03, 0xff][..];
+///     let len = rdr.read_u16::<BigEndian>().await.unwrap();
+///     let mut field = (&mut rdr).limit(len.into());
+///     assert_eq!(field.read_u16::<BigEndian>().await.unwrap(), 0x0102);
+///     assert_eq!(field.remaining(), 1);
+///     // a decoder that reads too much hears about it
+///     let err = field.read_u16::<BigEndian>().await.unwrap_err();
+///     assert!(LimitError::from_io(&err).unwrap().is_overrun());
+///     field.expect_exhausted().unwrap();
+///     assert_eq!(rdr, [0xff]);
+/// }
+/// ```
+///
+/// [`limit`]: trait.AsyncReadBytesExt.html#method.limit
+/// [`take`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncReadExt.html#method.take
+/// [`LimitError`]: struct.LimitError.html
+/// [`expect_exhausted`]: #method.expect_exhausted
+/// [`remaining`]: #method.remaining
+#[derive(Debug)]
+pub struct Limit<R> {
+    inner: R,
+    limit: u64,
+    remaining: u64,
+}
+
+impl<R> Limit<R> {
+    pub(crate) fn new(inner: R, limit: u64) -> Self {
+        Limit {
+            inner,
+            limit,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of bytes that can still be read.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Checks that every byte up to the limit has been read, and returns the underlying reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind `InvalidData` that carries a [`LimitError`] if any bytes
+    /// were left unread. They are left in the underlying reader, which is lost.
+    ///
+    /// [`LimitError`]: struct.LimitError.html
+    pub fn expect_exhausted(self) -> io::Result<R> {
+        if self.remaining == 0 {
+            Ok(self.inner)
+        } else {
+            Err(LimitError::new(self.limit, self.remaining).into())
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly through the returned reference do not count towards the limit.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Limit`, returning the underlying reader whether or not the limit was
+    /// reached.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> io::AsyncRead for Limit<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        if this.remaining == 0 {
+            return Poll::Ready(Err(LimitError::new(this.limit, 0).into()));
+        }
+        let max = usize::try_from(this.remaining).unwrap_or(usize::MAX);
+        let mut sub = buf.take(max);
+        let ptr = sub.filled().as_ptr();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, &mut sub))?;
+        assert_eq!(
+            ptr,
+            sub.filled().as_ptr(),
+            "AsyncRead::poll_read replaced the buffer it was given"
+        );
+        let n = sub.filled().len();
+        // SAFETY: the inner reader filled, and so initialized, the first `n` bytes
+        unsafe { buf.assume_init(n) };
+        buf.advance(n);
+        this.remaining -= n as u64;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> io::AsyncBufRead for Limit<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.remaining == 0 {
+            return Poll::Ready(Err(LimitError::new(this.limit, 0).into()));
+        }
+        let buf = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+        let max = usize::try_from(this.remaining).unwrap_or(usize::MAX);
+        Poll::Ready(Ok(&buf[..buf.len().min(max)]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        assert!(amt as u64 <= this.remaining, "consumed past the limit");
+        this.remaining -= amt as u64;
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
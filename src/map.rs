@@ -0,0 +1,236 @@
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+/// A reader that passes every byte read through it through a transform.
+///
+/// The transform is handed each run of bytes as it arrives from the underlying reader, in
+/// order, and changes them in place before any decoder on top gets to see them. It sees every
+/// byte exactly once, so it can carry state from one call to the next, like the position in an
+/// XOR mask or the register of a whitening LFSR. How the stream is cut into runs is up to the
+/// underlying reader, and a transform must not depend on it.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, MapBytesReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mask = [0x5a, 0xa5];
+///     let mut at = 0;
+///     let rdr = &[0x5a, 0xaf, 0x5b, 0xa5][..];
+///     let mut rdr = MapBytesReader::new(rdr, |buf: &mut [u8]| {
+///         for b in buf {
+///             *b ^= mask[at % mask.len()];
+///             at += 1;
+///         }
+///     });
+///     assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 0x000a);
+///     assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 0x0100);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MapBytesReader<R, F> {
+    inner: R,
+    f: F,
+}
+
+/// A writer that passes every byte written through it through a transform.
+///
+/// The transform is handed each run of bytes as it is written, in order, and changes them in
+/// place before they go to the underlying writer. It sees every byte exactly once, so it can
+/// carry state from one call to the next, like the position in an XOR mask or the register of a
+/// whitening LFSR.
+///
+/// Since the underlying writer may accept only part of what it is given, a `MapBytesWriter`
+/// holds on to the transformed bytes it could not write right away, and the next write,
+/// [`flush`], or [`shutdown`] writes them first. Flush it when done writing, as with a
+/// [`BufWriter`]. If the underlying writer fails, the transformed bytes it did not take are
+/// kept, and are written first when writing is tried again.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio::io::AsyncWriteExt;
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, MapBytesWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = MapBytesWriter::new(Vec::new(), |buf: &mut [u8]| {
+///         for b in buf {
+///             *b = !*b;
+///         }
+///     });
+///     AsyncWriteBytesExt::write_u16::<BigEndian>(&mut wtr, 0x00ff).await.unwrap();
+///     wtr.flush().await.unwrap();
+///     assert_eq!(wtr.into_inner(), [0xff, 0x00]);
+/// }
+/// ```
+///
+/// [`flush`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWriteExt.html#method.flush
+/// [`shutdown`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWriteExt.html#method.shutdown
+/// [`BufWriter`]: https://docs.rs/tokio/1/tokio/io/struct.BufWriter.html
+#[derive(Debug)]
+pub struct MapBytesWriter<W, F> {
+    inner: W,
+    f: F,
+    // transformed bytes; those before `pos` have been written
+    pending: Vec<u8>,
+    pos: usize,
+}
+
+macro_rules! map_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, F> $name<$inner, F> {
+            /// Gets a reference to the transform.
+            pub fn transform(&self) -> &F {
+                &self.f
+            }
+
+            /// Gets a mutable reference to the transform.
+            ///
+            /// This is handy for resetting its state at the start of a frame.
+            pub fn transform_mut(&mut self) -> &mut F {
+                &mut self.f
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            ///
+            /// Bytes transferred directly through the returned reference are not transformed.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+        }
+    };
+}
+
+map_common!(MapBytesReader, R);
+map_common!(MapBytesWriter, W);
+
+impl<R, F: FnMut(&mut [u8])> MapBytesReader<R, F> {
+    /// Wraps the given reader, passing the bytes read from it through `f`.
+    pub fn new(inner: R, f: F) -> Self {
+        MapBytesReader { inner, f }
+    }
+
+    /// Unwraps this `MapBytesReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Unwraps this `MapBytesReader`, returning the underlying reader and the transform.
+    pub fn into_parts(self) -> (R, F) {
+        (self.inner, self.f)
+    }
+}
+
+impl<W, F: FnMut(&mut [u8])> MapBytesWriter<W, F> {
+    /// Wraps the given writer, passing the bytes written to it through `f`.
+    pub fn new(inner: W, f: F) -> Self {
+        MapBytesWriter {
+            inner,
+            f,
+            pending: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the transformed bytes that have not been written to the underlying writer yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.pending[self.pos..]
+    }
+
+    /// Unwraps this `MapBytesWriter`, returning the underlying writer.
+    ///
+    /// Any [`pending`](#method.pending) bytes are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Unwraps this `MapBytesWriter`, returning the underlying writer and the transform.
+    ///
+    /// Any [`pending`](#method.pending) bytes are lost.
+    pub fn into_parts(self) -> (W, F) {
+        (self.inner, self.f)
+    }
+}
+
+impl<W, F> MapBytesWriter<W, F>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pos < self.pending.len() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending[self.pos..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.pos += n;
+        }
+        self.pending.clear();
+        self.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R, F> io::AsyncRead for MapBytesReader<R, F>
+where
+    R: io::AsyncRead + Unpin,
+    F: FnMut(&mut [u8]) + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        (this.f)(&mut buf.filled_mut()[before..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, F> io::AsyncWrite for MapBytesWriter<W, F>
+where
+    W: io::AsyncWrite + Unpin,
+    F: FnMut(&mut [u8]) + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        this.pending.extend_from_slice(buf);
+        (this.f)(&mut this.pending);
+        // hand as much as the writer takes right away; the rest goes out with the next call
+        while this.pos < this.pending.len() {
+            match Pin::new(&mut this.inner).poll_write(cx, &this.pending[this.pos..]) {
+                Poll::Ready(Ok(n)) if n > 0 => this.pos += n,
+                // the bytes are accepted either way, and any error comes back on the next call
+                _ => break,
+            }
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
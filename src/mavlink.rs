@@ -0,0 +1,288 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::fmt;
+use core::task::ready;
+use std::vec::Vec;
+
+const MAGIC_V1: u8 = 0xfe;
+const MAGIC_V2: u8 = 0xfd;
+const HEADER_LEN_V1: usize = 6;
+const HEADER_LEN_V2: usize = 10;
+const SIGNATURE_LEN: usize = 13;
+/// The only incompatibility flag defined so far: the packet is signed.
+const IFLAG_SIGNED: u8 = 0x01;
+
+/// The version of the MAVLink wire format a packet uses, from its magic byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MavlinkVersion {
+    /// MAVLink 1, whose packets start with `0xfe`.
+    V1,
+    /// MAVLink 2, whose packets start with `0xfd`.
+    V2,
+}
+
+/// A MAVLink packet, with the fields of its header and its payload.
+///
+/// Read with [`read_mavlink_frame`], and written with [`write_mavlink_frame`].
+///
+/// MAVLink 2 senders drop the zero bytes at the end of a payload, so the payload of a
+/// [`V2`](enum.MavlinkVersion.html#variant.V2) packet may be shorter than its message. Extend
+/// it with zeros to the length of the message before reading its little-endian fields.
+///
+/// [`read_mavlink_frame`]: trait.AsyncReadBytesExt.html#method.read_mavlink_frame
+/// [`write_mavlink_frame`]: trait.AsyncWriteBytesExt.html#method.write_mavlink_frame
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MavlinkFrame {
+    /// The version of the wire format.
+    pub version: MavlinkVersion,
+    /// Flags that a receiver must understand to handle the packet; always 0 for MAVLink 1.
+    pub incompat_flags: u8,
+    /// Flags that a receiver may ignore; always 0 for MAVLink 1.
+    pub compat_flags: u8,
+    /// The sequence number of the packet, which the sender increments with every packet.
+    pub seq: u8,
+    /// The id of the system that sent the packet.
+    pub sysid: u8,
+    /// The id of the component of that system that sent the packet.
+    pub compid: u8,
+    /// The id of the message in the payload; at most 255 for MAVLink 1, and 24 bits for
+    /// MAVLink 2.
+    pub msgid: u32,
+    /// The payload, at most 255 bytes.
+    pub payload: Vec<u8>,
+    /// The signature that follows a MAVLink 2 packet if it is signed.
+    ///
+    /// It is not verified, since that takes the secret key of the link.
+    pub signature: Option<[u8; SIGNATURE_LEN]>,
+}
+
+/// Computes the checksum of a MAVLink packet.
+///
+/// This is the X.25 CRC (CRC-16/MCRF4XX) over `bytes`, which are the header after the magic
+/// byte followed by the payload, and then over `crc_extra`. The extra byte is derived from the
+/// definition of the message, so that a sender and a receiver that disagree on it fail the
+/// check rather than misread the payload.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::mavlink_crc;
+///
+/// assert_eq!(mavlink_crc(b"12345678", b'9'), 0x6f91);
+/// ```
+pub fn mavlink_crc(bytes: &[u8], crc_extra: u8) -> u16 {
+    let mut crc = 0xffff;
+    for &b in bytes.iter().chain(Some(&crc_extra)) {
+        let mut tmp = b ^ crc as u8;
+        tmp ^= tmp << 4;
+        let tmp = u16::from(tmp);
+        crc = (crc >> 8) ^ (tmp << 8) ^ (tmp << 3) ^ (tmp >> 4);
+    }
+    crc
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Future returned by
+/// [`read_mavlink_frame`](../trait.AsyncReadBytesExt.html#method.read_mavlink_frame).
+pub struct ReadMavlinkFrame<R, F> {
+    src: R,
+    crc_extra: F,
+    buf: Vec<u8>,
+    filled: usize,
+}
+
+impl<R, F> ReadMavlinkFrame<R, F> {
+    pub(crate) fn new(src: R, crc_extra: F) -> Self {
+        ReadMavlinkFrame {
+            src,
+            crc_extra,
+            buf: Vec::new(),
+            filled: 0,
+        }
+    }
+
+    /// Returns how many bytes of the packet to read next, given the ones read so far.
+    fn target(&self) -> io::Result<usize> {
+        if self.filled == 0 {
+            return Ok(1);
+        }
+        let header_len = match self.buf[0] {
+            MAGIC_V1 => HEADER_LEN_V1,
+            MAGIC_V2 => HEADER_LEN_V2,
+            _ => return Err(invalid("not the start of a mavlink packet")),
+        };
+        if self.filled < header_len {
+            return Ok(header_len);
+        }
+        let mut len = header_len + usize::from(self.buf[1]) + 2;
+        if self.buf[0] == MAGIC_V2 {
+            let incompat_flags = self.buf[2];
+            if incompat_flags & !IFLAG_SIGNED != 0 {
+                return Err(invalid("unsupported mavlink incompatibility flags"));
+            }
+            if incompat_flags & IFLAG_SIGNED != 0 {
+                len += SIGNATURE_LEN;
+            }
+        }
+        Ok(len)
+    }
+}
+
+impl<R: fmt::Debug, F> fmt::Debug for ReadMavlinkFrame<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadMavlinkFrame")
+            .field("src", &self.src)
+            .field("buf", &self.buf)
+            .field("filled", &self.filled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, F> Future for ReadMavlinkFrame<R, F>
+where
+    R: io::AsyncRead + Unpin,
+    F: FnMut(u32) -> Option<u8> + Unpin,
+{
+    type Output = io::Result<MavlinkFrame>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let target = this.target()?;
+            if this.filled == target {
+                break;
+            }
+            this.buf.resize(target, 0);
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf,
+                &mut this.filled
+            ))?;
+        }
+
+        let buf = core::mem::take(&mut this.buf);
+        this.filled = 0;
+        let (version, header_len) = if buf[0] == MAGIC_V1 {
+            (MavlinkVersion::V1, HEADER_LEN_V1)
+        } else {
+            (MavlinkVersion::V2, HEADER_LEN_V2)
+        };
+        let end = header_len + usize::from(buf[1]);
+        let (incompat_flags, compat_flags, rest) = match version {
+            MavlinkVersion::V1 => (0, 0, &buf[2..header_len]),
+            MavlinkVersion::V2 => (buf[2], buf[3], &buf[4..header_len]),
+        };
+        let msgid = match version {
+            MavlinkVersion::V1 => u32::from(rest[3]),
+            MavlinkVersion::V2 => LittleEndian::read_u24(&rest[3..]),
+        };
+        let crc_extra =
+            (this.crc_extra)(msgid).ok_or_else(|| invalid("unknown mavlink message id"))?;
+        let expected = LittleEndian::read_u16(&buf[end..]);
+        let actual = mavlink_crc(&buf[1..end], crc_extra);
+        if actual != expected {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "mavlink checksum mismatch: expected {:?}, computed {:?}",
+                    expected, actual
+                ),
+            )));
+        }
+        let signature = (incompat_flags & IFLAG_SIGNED != 0).then(|| {
+            let mut signature = [0; SIGNATURE_LEN];
+            signature.copy_from_slice(&buf[end + 2..]);
+            signature
+        });
+        Poll::Ready(Ok(MavlinkFrame {
+            version,
+            incompat_flags,
+            compat_flags,
+            seq: rest[0],
+            sysid: rest[1],
+            compid: rest[2],
+            msgid,
+            payload: buf[header_len..end].to_vec(),
+            signature,
+        }))
+    }
+}
+
+/// Encodes a whole packet, or returns why it cannot be encoded.
+fn encode(frame: &MavlinkFrame, crc_extra: u8) -> Result<Vec<u8>, &'static str> {
+    let len = u8::try_from(frame.payload.len())
+        .map_err(|_| "mavlink payload is longer than 255 bytes")?;
+    let mut buf = Vec::with_capacity(HEADER_LEN_V2 + frame.payload.len() + 2 + SIGNATURE_LEN);
+    match frame.version {
+        MavlinkVersion::V1 => {
+            let msgid =
+                u8::try_from(frame.msgid).map_err(|_| "mavlink 1 message id is larger than 255")?;
+            if frame.incompat_flags != 0 || frame.compat_flags != 0 || frame.signature.is_some() {
+                return Err("mavlink 1 packet has flags or a signature");
+            }
+            buf.extend_from_slice(&[MAGIC_V1, len, frame.seq, frame.sysid, frame.compid, msgid]);
+        }
+        MavlinkVersion::V2 => {
+            if frame.msgid >= 1 << 24 {
+                return Err("mavlink 2 message id does not fit in 24 bits");
+            }
+            if (frame.incompat_flags & IFLAG_SIGNED != 0) != frame.signature.is_some() {
+                return Err("mavlink 2 signature does not match the signed flag");
+            }
+            buf.extend_from_slice(&[
+                MAGIC_V2,
+                len,
+                frame.incompat_flags,
+                frame.compat_flags,
+                frame.seq,
+                frame.sysid,
+                frame.compid,
+            ]);
+            buf.extend_from_slice(&frame.msgid.to_le_bytes()[..3]);
+        }
+    }
+    buf.extend_from_slice(&frame.payload);
+    let crc = mavlink_crc(&buf[1..], crc_extra);
+    buf.extend_from_slice(&crc.to_le_bytes());
+    if let Some(signature) = &frame.signature {
+        buf.extend_from_slice(signature);
+    }
+    Ok(buf)
+}
+
+/// Future returned by
+/// [`write_mavlink_frame`](../trait.AsyncWriteBytesExt.html#method.write_mavlink_frame).
+#[derive(Debug)]
+pub struct WriteMavlinkFrame<W> {
+    dst: W,
+    // the encoded packet, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteMavlinkFrame<W> {
+    pub(crate) fn new(dst: W, frame: &MavlinkFrame, crc_extra: u8) -> Self {
+        WriteMavlinkFrame {
+            dst,
+            buf: encode(frame, crc_extra),
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteMavlinkFrame<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
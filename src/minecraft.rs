@@ -0,0 +1,78 @@
+use crate::*;
+use core::task::ready;
+
+/// Future returned by
+/// [`read_minecraft_varint`](../trait.AsyncReadBytesExt.html#method.read_minecraft_varint)
+/// and
+/// [`read_minecraft_varlong`](../trait.AsyncReadBytesExt.html#method.read_minecraft_varlong).
+#[derive(Debug)]
+pub struct ReadMinecraftVarint<R, V> {
+    src: R,
+    value: u64,
+    read: u32,
+    // the most bytes the value may take: 5 for a VarInt, and 10 for a VarLong
+    max: u32,
+    map: fn(u64) -> V,
+    too_big: &'static str,
+}
+
+impl<R> ReadMinecraftVarint<R, i32> {
+    pub(crate) fn varint(src: R) -> Self {
+        Self::new(
+            src,
+            5,
+            |n| n as u32 as i32,
+            "minecraft VarInt is longer than 5 bytes",
+        )
+    }
+}
+
+impl<R> ReadMinecraftVarint<R, i64> {
+    pub(crate) fn varlong(src: R) -> Self {
+        Self::new(
+            src,
+            10,
+            |n| n as i64,
+            "minecraft VarLong is longer than 10 bytes",
+        )
+    }
+}
+
+impl<R, V> ReadMinecraftVarint<R, V> {
+    fn new(src: R, max: u32, map: fn(u64) -> V, too_big: &'static str) -> Self {
+        ReadMinecraftVarint {
+            src,
+            value: 0,
+            read: 0,
+            max,
+            map,
+            too_big,
+        }
+    }
+}
+
+impl<R, V> Future for ReadMinecraftVarint<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut b = [0; 1];
+            ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+            // like the vanilla server, bits past the top of the value are dropped
+            this.value |= u64::from(b[0] & 0x7f) << (7 * this.read);
+            this.read += 1;
+            if b[0] & 0x80 == 0 {
+                return Poll::Ready(Ok((this.map)(this.value)));
+            }
+            if this.read == this.max {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    this.too_big,
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::task::ready;
+
+/// The largest value a 33 bit PTS, DTS, or PCR base can hold.
+const MAX_TIMESTAMP: u64 = (1 << 33) - 1;
+
+/// The largest PCR, in 27 MHz ticks.
+const MAX_PCR: u64 = MAX_TIMESTAMP * 300 + 299;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn decode_pes_timestamp(buf: &[u8]) -> io::Result<(u8, u64)> {
+    if buf[0] & 1 == 0 || buf[2] & 1 == 0 || buf[4] & 1 == 0 {
+        return Err(invalid("pes timestamp marker bit is not set"));
+    }
+    let ts = u64::from(buf[0] >> 1 & 0x07) << 30
+        | u64::from(BigEndian::read_u16(&buf[1..]) >> 1) << 15
+        | u64::from(BigEndian::read_u16(&buf[3..]) >> 1);
+    Ok((buf[0] >> 4, ts))
+}
+
+fn pes_timestamp_encoder(prefix: u8, ts: u64) -> Result<FixedEncoder, &'static str> {
+    if prefix > 0x0f {
+        return Err("pes timestamp prefix does not fit in 4 bits");
+    }
+    if ts > MAX_TIMESTAMP {
+        return Err("pes timestamp does not fit in 33 bits");
+    }
+    Ok(FixedEncoder::new(5, |buf| {
+        buf[0] = prefix << 4 | ((ts >> 30) as u8 & 0x07) << 1 | 1;
+        BigEndian::write_u16(&mut buf[1..], ((ts >> 15) as u16) << 1 | 1);
+        BigEndian::write_u16(&mut buf[3..], (ts as u16) << 1 | 1);
+    }))
+}
+
+fn decode_pcr(buf: &[u8]) -> io::Result<u64> {
+    let bits = BigEndian::read_u48(buf);
+    let base = bits >> 15;
+    let extension = bits & 0x1ff;
+    if extension >= 300 {
+        return Err(invalid("pcr extension is not less than 300"));
+    }
+    Ok(base * 300 + extension)
+}
+
+fn pcr_encoder(pcr: u64) -> Result<FixedEncoder, &'static str> {
+    if pcr > MAX_PCR {
+        return Err("pcr is larger than the largest pcr");
+    }
+    // the six reserved bits between the base and the extension are set
+    let bits = (pcr / 300) << 15 | 0x3f << 9 | (pcr % 300);
+    Ok(FixedEncoder::new(6, |buf| BigEndian::write_u48(buf, bits)))
+}
+
+/// Future returned by
+/// [`read_mpeg_pes_timestamp`](../trait.AsyncReadBytesExt.html#method.read_mpeg_pes_timestamp)
+/// and [`read_mpeg_pcr`](../trait.AsyncReadBytesExt.html#method.read_mpeg_pcr).
+#[derive(Debug)]
+pub struct ReadMpegTs<R, V> {
+    src: R,
+    buf: [u8; 6],
+    len: usize,
+    filled: usize,
+    decode: fn(&[u8]) -> io::Result<V>,
+}
+
+impl<R> ReadMpegTs<R, (u8, u64)> {
+    pub(crate) fn pes_timestamp(src: R) -> Self {
+        Self::new(src, 5, decode_pes_timestamp)
+    }
+}
+
+impl<R> ReadMpegTs<R, u64> {
+    pub(crate) fn pcr(src: R) -> Self {
+        Self::new(src, 6, decode_pcr)
+    }
+}
+
+impl<R, V> ReadMpegTs<R, V> {
+    fn new(src: R, len: usize, decode: fn(&[u8]) -> io::Result<V>) -> Self {
+        ReadMpegTs {
+            src,
+            buf: [0; 6],
+            len,
+            filled: 0,
+            decode,
+        }
+    }
+}
+
+impl<R, V> Future for ReadMpegTs<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..this.len],
+            &mut this.filled
+        ))?;
+        Poll::Ready((this.decode)(&this.buf[..this.len]))
+    }
+}
+
+/// Future returned by
+/// [`write_mpeg_pes_timestamp`](../trait.AsyncWriteBytesExt.html#method.write_mpeg_pes_timestamp)
+/// and [`write_mpeg_pcr`](../trait.AsyncWriteBytesExt.html#method.write_mpeg_pcr).
+#[derive(Debug)]
+pub struct WriteMpegTs<W> {
+    dst: W,
+    // the encoded value, or why it cannot be encoded
+    encoder: Result<FixedEncoder, &'static str>,
+}
+
+impl<W> WriteMpegTs<W> {
+    pub(crate) fn pes_timestamp(dst: W, prefix: u8, ts: u64) -> Self {
+        WriteMpegTs {
+            dst,
+            encoder: pes_timestamp_encoder(prefix, ts),
+        }
+    }
+
+    pub(crate) fn pcr(dst: W, pcr: u64) -> Self {
+        WriteMpegTs {
+            dst,
+            encoder: pcr_encoder(pcr),
+        }
+    }
+}
+
+impl<W> Future for WriteMpegTs<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.encoder {
+            Ok(encoder) => poll_write_encoder(Pin::new(&mut this.dst), cx, encoder),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
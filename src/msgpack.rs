@@ -0,0 +1,154 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Returns the number of bytes that follow an integer marker, or `None` if it is not one.
+fn int_len(marker: u8) -> Option<usize> {
+    match marker {
+        0x00..=0x7f | 0xe0..=0xff => Some(0),
+        0xcc | 0xd0 => Some(1),
+        0xcd | 0xd1 => Some(2),
+        0xce | 0xd2 => Some(4),
+        0xcf | 0xd3 => Some(8),
+        _ => None,
+    }
+}
+
+fn decode_int(buf: &[u8]) -> io::Result<i64> {
+    let data = &buf[1..];
+    Ok(match buf[0] {
+        0xcc => i64::from(data[0]),
+        0xcd => i64::from(BigEndian::read_u16(data)),
+        0xce => i64::from(BigEndian::read_u32(data)),
+        0xcf => i64::try_from(BigEndian::read_u64(data))
+            .map_err(|_| invalid("msgpack integer does not fit in an i64"))?,
+        0xd0 => i64::from(data[0] as i8),
+        0xd1 => i64::from(BigEndian::read_i16(data)),
+        0xd2 => i64::from(BigEndian::read_i32(data)),
+        0xd3 => BigEndian::read_i64(data),
+        // a positive or negative fixint
+        marker => i64::from(marker as i8),
+    })
+}
+
+/// Returns the number of bytes that follow a float marker, or `None` if it is not one.
+fn float_len(marker: u8) -> Option<usize> {
+    match marker {
+        0xca => Some(4),
+        0xcb => Some(8),
+        _ => None,
+    }
+}
+
+fn decode_float(buf: &[u8]) -> io::Result<f64> {
+    Ok(match buf[0] {
+        0xca => f64::from(BigEndian::read_f32(&buf[1..])),
+        _ => BigEndian::read_f64(&buf[1..]),
+    })
+}
+
+/// Returns an encoder for `n` in the smallest msgpack integer format that holds it.
+pub(crate) fn int_encoder(n: i64) -> FixedEncoder {
+    let (marker, len) = match n {
+        -32..=0x7f => return FixedEncoder::u8(n as u8),
+        0x80..=0xff => (0xcc, 1),
+        0x100..=0xffff => (0xcd, 2),
+        0x1_0000..=0xffff_ffff => (0xce, 4),
+        0x1_0000_0000..=i64::MAX => (0xcf, 8),
+        -0x80..=-33 => (0xd0, 1),
+        -0x8000..=-0x81 => (0xd1, 2),
+        -0x8000_0000..=-0x8001 => (0xd2, 4),
+        _ => (0xd3, 8),
+    };
+    FixedEncoder::new(1 + len, |buf| {
+        buf[0] = marker;
+        buf[1..].copy_from_slice(&n.to_be_bytes()[8 - len..]);
+    })
+}
+
+pub(crate) fn f32_encoder(n: f32) -> FixedEncoder {
+    FixedEncoder::new(5, |buf| {
+        buf[0] = 0xca;
+        BigEndian::write_f32(&mut buf[1..], n);
+    })
+}
+
+pub(crate) fn f64_encoder(n: f64) -> FixedEncoder {
+    FixedEncoder::new(9, |buf| {
+        buf[0] = 0xcb;
+        BigEndian::write_f64(&mut buf[1..], n);
+    })
+}
+
+/// Future returned by
+/// [`read_msgpack_int`](../trait.AsyncReadBytesExt.html#method.read_msgpack_int) and
+/// [`read_msgpack_f64`](../trait.AsyncReadBytesExt.html#method.read_msgpack_f64).
+#[derive(Debug)]
+pub struct ReadMsgpack<R, V> {
+    src: R,
+    buf: [u8; 9],
+    filled: usize,
+    len: fn(u8) -> Option<usize>,
+    decode: fn(&[u8]) -> io::Result<V>,
+    expected: &'static str,
+}
+
+impl<R> ReadMsgpack<R, i64> {
+    pub(crate) fn int(src: R) -> Self {
+        ReadMsgpack {
+            src,
+            buf: [0; 9],
+            filled: 0,
+            len: int_len,
+            decode: decode_int,
+            expected: "expected a msgpack integer",
+        }
+    }
+}
+
+impl<R> ReadMsgpack<R, f64> {
+    pub(crate) fn float(src: R) -> Self {
+        ReadMsgpack {
+            src,
+            buf: [0; 9],
+            filled: 0,
+            len: float_len,
+            decode: decode_float,
+            expected: "expected a msgpack float",
+        }
+    }
+}
+
+impl<R, V> Future for ReadMsgpack<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled == 0 {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf[..1],
+                &mut this.filled
+            ))?;
+        }
+        let len = match (this.len)(this.buf[0]) {
+            Some(len) => len,
+            None => return Poll::Ready(Err(invalid(this.expected))),
+        };
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..1 + len],
+            &mut this.filled
+        ))?;
+        Poll::Ready((this.decode)(&this.buf[..1 + len]))
+    }
+}
@@ -0,0 +1,232 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::vec::Vec;
+
+/// The first byte of a length-encoded integer that stands for SQL `NULL`.
+const NULL: u8 = 0xfb;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) fn packet_header_decoder() -> FixedDecoder<(u32, u8)> {
+    FixedDecoder::new(4, |buf| (LittleEndian::read_u24(buf), buf[3]))
+}
+
+/// Returns the number of bytes in the length-encoded integer `n`.
+fn lenenc_len(n: u64) -> usize {
+    match n {
+        0..=0xfa => 1,
+        0xfb..=0xffff => 3,
+        0x1_0000..=0xff_ffff => 4,
+        _ => 9,
+    }
+}
+
+fn encode_lenenc(n: u64, buf: &mut [u8]) {
+    let (marker, len) = match buf.len() {
+        1 => {
+            buf[0] = n as u8;
+            return;
+        }
+        3 => (0xfc, 2),
+        4 => (0xfd, 3),
+        _ => (0xfe, 8),
+    };
+    buf[0] = marker;
+    buf[1..].copy_from_slice(&n.to_le_bytes()[..len]);
+}
+
+pub(crate) fn lenenc_int_encoder(n: u64) -> FixedEncoder {
+    FixedEncoder::new(lenenc_len(n), |buf| encode_lenenc(n, buf))
+}
+
+/// Reads a length-encoded integer a byte at a time, keeping its progress across polls.
+#[derive(Debug, Default)]
+struct LenencDecoder {
+    buf: [u8; 9],
+    filled: usize,
+}
+
+impl LenencDecoder {
+    /// Returns the integer, or `None` if it is the marker for `NULL`.
+    fn poll_read<R>(&mut self, src: &mut R, cx: &mut Context<'_>) -> Poll<io::Result<Option<u64>>>
+    where
+        R: io::AsyncRead + Unpin,
+    {
+        if self.filled == 0 {
+            ready!(poll_read_exact(
+                src,
+                cx,
+                &mut self.buf[..1],
+                &mut self.filled
+            ))?;
+        }
+        let len = match self.buf[0] {
+            n @ 0..=0xfa => return Poll::Ready(Ok(Some(u64::from(n)))),
+            NULL => return Poll::Ready(Ok(None)),
+            0xfc => 2,
+            0xfd => 3,
+            0xfe => 8,
+            _ => {
+                return Poll::Ready(Err(invalid(
+                    "0xff does not start a mysql length-encoded integer",
+                )))
+            }
+        };
+        ready!(poll_read_exact(
+            src,
+            cx,
+            &mut self.buf[..1 + len],
+            &mut self.filled
+        ))?;
+        Poll::Ready(Ok(Some(LittleEndian::read_uint(&self.buf[1..], len))))
+    }
+}
+
+/// Future returned by
+/// [`read_mysql_lenenc_int`](../trait.AsyncReadBytesExt.html#method.read_mysql_lenenc_int).
+#[derive(Debug)]
+pub struct ReadMysqlLenencInt<R> {
+    src: R,
+    decoder: LenencDecoder,
+}
+
+impl<R> ReadMysqlLenencInt<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadMysqlLenencInt {
+            src,
+            decoder: LenencDecoder::default(),
+        }
+    }
+}
+
+impl<R> Future for ReadMysqlLenencInt<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<u64>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match ready!(this.decoder.poll_read(&mut this.src, cx))? {
+            Some(n) => Poll::Ready(Ok(n)),
+            None => Poll::Ready(Err(invalid("mysql length-encoded integer is NULL"))),
+        }
+    }
+}
+
+/// Future returned by
+/// [`read_mysql_lenenc_str`](../trait.AsyncReadBytesExt.html#method.read_mysql_lenenc_str).
+#[derive(Debug)]
+pub struct ReadMysqlLenencStr<R> {
+    src: R,
+    max_len: usize,
+    decoder: LenencDecoder,
+    // the length of the string, once its prefix is in
+    len: Option<usize>,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadMysqlLenencStr<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadMysqlLenencStr {
+            src,
+            max_len,
+            decoder: LenencDecoder::default(),
+            len: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadMysqlLenencStr<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Option<Vec<u8>>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = match ready!(this.decoder.poll_read(&mut this.src, cx))? {
+                    Some(len) => len,
+                    None => return Poll::Ready(Ok(None)),
+                };
+                match usize::try_from(len) {
+                    Ok(len) if len <= this.max_len => *this.len.insert(len),
+                    _ => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "mysql string is longer than the maximum of {} bytes",
+                                this.max_len
+                            ),
+                        )));
+                    }
+                }
+            }
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready(Ok(Some(core::mem::take(&mut this.buf))))
+    }
+}
+
+/// Future returned by
+/// [`write_mysql_packet_header`](../trait.AsyncWriteBytesExt.html#method.write_mysql_packet_header)
+/// and
+/// [`write_mysql_lenenc_str`](../trait.AsyncWriteBytesExt.html#method.write_mysql_lenenc_str).
+#[derive(Debug)]
+pub struct WriteMysql<W> {
+    dst: W,
+    // the encoded header or string, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteMysql<W> {
+    pub(crate) fn packet_header(dst: W, len: u32, seq: u8) -> Self {
+        let buf = if len < 1 << 24 {
+            let mut buf = vec![0; 4];
+            LittleEndian::write_u24(&mut buf, len);
+            buf[3] = seq;
+            Ok(buf)
+        } else {
+            Err("mysql packet length does not fit in 24 bits")
+        };
+        WriteMysql {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn lenenc_str(dst: W, data: &[u8]) -> Self {
+        let n = data.len() as u64;
+        let prefix = lenenc_len(n);
+        let mut buf = vec![0; prefix];
+        encode_lenenc(n, &mut buf);
+        buf.extend_from_slice(data);
+        WriteMysql {
+            dst,
+            buf: Ok(buf),
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteMysql<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
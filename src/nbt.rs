@@ -0,0 +1,344 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+/// The type of an NBT tag, from the byte in front of its name.
+///
+/// Read with [`read_nbt_tag`], and written with [`write_nbt_tag`]. The numeric payloads that
+/// follow the tags are big-endian, and are read with [`read_i8`], [`read_i16`], and their
+/// siblings, with [`BigEndian`] as the byte order.
+///
+/// [`read_nbt_tag`]: trait.AsyncReadBytesExt.html#method.read_nbt_tag
+/// [`write_nbt_tag`]: trait.AsyncWriteBytesExt.html#method.write_nbt_tag
+/// [`read_i8`]: trait.AsyncReadBytesExt.html#method.read_i8
+/// [`read_i16`]: trait.AsyncReadBytesExt.html#method.read_i16
+/// [`BigEndian`]: enum.BigEndian.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NbtTag {
+    /// The end of a compound; it has no name and no payload.
+    End = 0,
+    /// An `i8`.
+    Byte = 1,
+    /// An `i16`.
+    Short = 2,
+    /// An `i32`.
+    Int = 3,
+    /// An `i64`.
+    Long = 4,
+    /// An `f32`.
+    Float = 5,
+    /// An `f64`.
+    Double = 6,
+    /// An array of `i8`s, with an `i32` length in front.
+    ByteArray = 7,
+    /// A string, with a `u16` length in front.
+    String = 8,
+    /// A list: the tag of its elements, then an `i32` count, then the elements' payloads.
+    List = 9,
+    /// A compound: named tags, up to an [`End`](#variant.End).
+    Compound = 10,
+    /// An array of `i32`s, with an `i32` length in front.
+    IntArray = 11,
+    /// An array of `i64`s, with an `i32` length in front.
+    LongArray = 12,
+}
+
+impl NbtTag {
+    fn from_u8(b: u8) -> Option<Self> {
+        Some(match b {
+            0 => NbtTag::End,
+            1 => NbtTag::Byte,
+            2 => NbtTag::Short,
+            3 => NbtTag::Int,
+            4 => NbtTag::Long,
+            5 => NbtTag::Float,
+            6 => NbtTag::Double,
+            7 => NbtTag::ByteArray,
+            8 => NbtTag::String,
+            9 => NbtTag::List,
+            10 => NbtTag::Compound,
+            11 => NbtTag::IntArray,
+            12 => NbtTag::LongArray,
+            _ => return None,
+        })
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Decodes Java's modified UTF-8, which writes NUL as `0xc0 0x80`, and characters outside the
+/// Basic Multilingual Plane as the three-byte encodings of their two surrogates.
+///
+/// Standard four-byte sequences are accepted too, since many writers produce them.
+fn decode_mutf8(buf: Vec<u8>) -> io::Result<String> {
+    let buf = match String::from_utf8(buf) {
+        Ok(s) => return Ok(s),
+        Err(e) => e.into_bytes(),
+    };
+    let err = || invalid("nbt string is not valid modified utf-8");
+    let mut units = Vec::with_capacity(buf.len());
+    let mut rest = &buf[..];
+    while let Some(&b) = rest.first() {
+        let len = match b {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(err()),
+        };
+        if rest.len() < len || rest[1..len].iter().any(|&c| c & 0xc0 != 0x80) {
+            return Err(err());
+        }
+        let mut c = u32::from(b & [0x7f, 0x1f, 0x0f, 0x07][len - 1]);
+        for &cont in &rest[1..len] {
+            c = c << 6 | u32::from(cont & 0x3f);
+        }
+        if len == 4 {
+            let c = char::from_u32(c).ok_or_else(err)?;
+            units.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        } else {
+            units.push(c as u16);
+        }
+        rest = &rest[len..];
+    }
+    String::from_utf16(&units).map_err(|_| err())
+}
+
+fn encode_mutf8(s: &str, out: &mut Vec<u8>) {
+    for c in s.chars() {
+        match c {
+            '\0' => out.extend_from_slice(&[0xc0, 0x80]),
+            c if c.len_utf8() < 4 => out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            c => {
+                for &unit in c.encode_utf16(&mut [0; 2]).iter() {
+                    out.extend_from_slice(&[
+                        0xe0 | (unit >> 12) as u8,
+                        0x80 | ((unit >> 6) as u8 & 0x3f),
+                        0x80 | (unit as u8 & 0x3f),
+                    ]);
+                }
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`read_nbt_tag`](../trait.AsyncReadBytesExt.html#method.read_nbt_tag).
+#[derive(Debug)]
+pub struct ReadNbtTag<R> {
+    src: R,
+}
+
+impl<R> ReadNbtTag<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadNbtTag { src }
+    }
+}
+
+impl<R> Future for ReadNbtTag<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<NbtTag>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut b = [0; 1];
+        ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+        Poll::Ready(NbtTag::from_u8(b[0]).ok_or_else(|| invalid("unknown nbt tag type")))
+    }
+}
+
+/// Future returned by
+/// [`read_nbt_string`](../trait.AsyncReadBytesExt.html#method.read_nbt_string).
+#[derive(Debug)]
+pub struct ReadNbtString<R> {
+    src: R,
+    max_len: usize,
+    prefix: [u8; 2],
+    filled: usize,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadNbtString<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadNbtString {
+            src,
+            max_len,
+            prefix: [0; 2],
+            filled: 0,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadNbtString<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<String>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < this.prefix.len() {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.prefix,
+                &mut this.filled
+            ))?;
+        }
+        let len = usize::from(BigEndian::read_u16(&this.prefix));
+        if len > this.max_len {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "nbt string is longer than the maximum of {} bytes",
+                    this.max_len
+                ),
+            )));
+        }
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready(decode_mutf8(core::mem::take(&mut this.buf)))
+    }
+}
+
+/// Future returned by
+/// [`read_nbt_byte_array`](../trait.AsyncReadBytesExt.html#method.read_nbt_byte_array),
+/// [`read_nbt_int_array`](../trait.AsyncReadBytesExt.html#method.read_nbt_int_array), and
+/// [`read_nbt_long_array`](../trait.AsyncReadBytesExt.html#method.read_nbt_long_array).
+#[derive(Debug)]
+pub struct ReadNbtArray<R, V> {
+    src: R,
+    max_len: usize,
+    prefix: [u8; 4],
+    filled: usize,
+    buf: Vec<u8>,
+    _elem: PhantomData<fn() -> V>,
+}
+
+impl<R, V> ReadNbtArray<R, V> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadNbtArray {
+            src,
+            max_len,
+            prefix: [0; 4],
+            filled: 0,
+            buf: Vec::new(),
+            _elem: PhantomData,
+        }
+    }
+}
+
+impl<R, V> Future for ReadNbtArray<R, V>
+where
+    R: io::AsyncRead + Unpin,
+    V: Primitive,
+{
+    type Output = io::Result<Vec<V>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < this.prefix.len() {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.prefix,
+                &mut this.filled
+            ))?;
+        }
+        let len = match usize::try_from(BigEndian::read_i32(&this.prefix)) {
+            Ok(len) if len <= this.max_len => len,
+            Ok(_) => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "nbt array is longer than the maximum of {} elements",
+                        this.max_len
+                    ),
+                )));
+            }
+            Err(_) => return Poll::Ready(Err(invalid("nbt array length is negative"))),
+        };
+        let mut decoder = V::decoder::<BigEndian>();
+        let size = decoder.remaining();
+        ready!(poll_read_to_len(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            len * size
+        ))?;
+        let mut rest = &this.buf[..];
+        let values = (0..len)
+            .map(|_| decoder.push_bytes(&mut rest).unwrap())
+            .collect();
+        this.buf = Vec::new();
+        Poll::Ready(Ok(values))
+    }
+}
+
+/// Future returned by
+/// [`write_nbt_string`](../trait.AsyncWriteBytesExt.html#method.write_nbt_string),
+/// [`write_nbt_byte_array`](../trait.AsyncWriteBytesExt.html#method.write_nbt_byte_array),
+/// [`write_nbt_int_array`](../trait.AsyncWriteBytesExt.html#method.write_nbt_int_array), and
+/// [`write_nbt_long_array`](../trait.AsyncWriteBytesExt.html#method.write_nbt_long_array).
+#[derive(Debug)]
+pub struct WriteNbt<W> {
+    dst: W,
+    // the encoded value, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteNbt<W> {
+    pub(crate) fn string(dst: W, s: &str) -> Self {
+        let mut buf = vec![0; 2];
+        encode_mutf8(s, &mut buf);
+        let buf = match u16::try_from(buf.len() - 2) {
+            Ok(len) => {
+                BigEndian::write_u16(&mut buf, len);
+                Ok(buf)
+            }
+            Err(_) => Err("nbt string is longer than 65535 bytes"),
+        };
+        WriteNbt {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn array<V: Primitive + Copy>(dst: W, values: &[V]) -> Self {
+        let buf = match i32::try_from(values.len()) {
+            Ok(len) => {
+                let mut buf = Vec::with_capacity(4 + core::mem::size_of_val(values));
+                buf.extend_from_slice(&len.to_be_bytes());
+                for &v in values {
+                    buf.extend_from_slice(v.encoder::<BigEndian>().pending());
+                }
+                Ok(buf)
+            }
+            Err(_) => Err("nbt array has more than i32::MAX elements"),
+        };
+        WriteNbt {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteNbt<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
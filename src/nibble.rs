@@ -0,0 +1,117 @@
+use crate::*;
+use core::task::ready;
+
+/// An iterator over the 4-bit halves of a byte slice.
+///
+/// Each byte yields two nibbles, in the [`BitOrder`] given: [`MsbFirst`] yields the high nibble
+/// of each byte before the low one, as in most BCD and hex-packed identifiers, while
+/// [`LsbFirst`] yields the low nibble first, as in the swapped BCD digits of telephony formats
+/// like GSM and ISDN. See [`read_nibbles`] for reading them straight from a stream.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BitOrder, Nibbles};
+///
+/// let digits: Vec<u8> = Nibbles::new(&[0x21, 0x43], BitOrder::LsbFirst).collect();
+/// assert_eq!(digits, [1, 2, 3, 4]);
+/// ```
+///
+/// [`BitOrder`]: enum.BitOrder.html
+/// [`MsbFirst`]: enum.BitOrder.html#variant.MsbFirst
+/// [`LsbFirst`]: enum.BitOrder.html#variant.LsbFirst
+/// [`read_nibbles`]: trait.AsyncReadBytesExt.html#method.read_nibbles
+#[derive(Debug, Clone)]
+pub struct Nibbles<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+    // index of the next nibble, counting from the front, and of the one after the last
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Nibbles<'a> {
+    /// Returns an iterator over every nibble of `bytes`, in the order `order`.
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        Nibbles {
+            bytes,
+            order,
+            front: 0,
+            back: bytes.len() * 2,
+        }
+    }
+
+    fn get(&self, i: usize) -> u8 {
+        let byte = self.bytes[i / 2];
+        let high_first = self.order == BitOrder::MsbFirst;
+        if (i % 2 == 0) == high_first {
+            byte >> 4
+        } else {
+            byte & 0x0f
+        }
+    }
+}
+
+impl Iterator for Nibbles<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.get(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for Nibbles<'_> {
+    fn next_back(&mut self) -> Option<u8> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl ExactSizeIterator for Nibbles<'_> {}
+
+/// Future returned by
+/// [`read_nibbles`](../trait.AsyncReadBytesExt.html#method.read_nibbles).
+#[derive(Debug)]
+pub struct ReadNibbles<R> {
+    src: R,
+    n: usize,
+    order: BitOrder,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadNibbles<R> {
+    pub(crate) fn new(src: R, n: usize, order: BitOrder) -> Self {
+        ReadNibbles {
+            src,
+            n,
+            order,
+            buf: Vec::with_capacity(n.div_ceil(2)),
+        }
+    }
+}
+
+impl<R> Future for ReadNibbles<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Vec<u8>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = this.n.div_ceil(2);
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        let nibbles = Nibbles::new(&this.buf, this.order).take(this.n).collect();
+        Poll::Ready(Ok(nibbles))
+    }
+}
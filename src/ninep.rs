@@ -0,0 +1,105 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+/// The server's unique identification of a file in the 9P2000 protocol.
+///
+/// Read with [`read_9p_qid`], and written with [`write_9p_qid`]; on the wire it is 13 bytes,
+/// with the numbers in little-endian byte order.
+///
+/// [`read_9p_qid`]: trait.AsyncReadBytesExt.html#method.read_9p_qid
+/// [`write_9p_qid`]: trait.AsyncWriteBytesExt.html#method.write_9p_qid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Qid {
+    /// The type of the file, such as `0x80` for a directory; the high byte of its mode.
+    pub qid_type: u8,
+    /// The version of the file, which changes whenever the file does.
+    pub version: u32,
+    /// The number that is unique to the file among those the server holds.
+    pub path: u64,
+}
+
+pub(crate) const QID_LEN: usize = 13;
+
+pub(crate) fn qid_decoder() -> FixedDecoder<Qid> {
+    FixedDecoder::new(QID_LEN, |buf| Qid {
+        qid_type: buf[0],
+        version: LittleEndian::read_u32(&buf[1..]),
+        path: LittleEndian::read_u64(&buf[5..]),
+    })
+}
+
+pub(crate) fn qid_encoder(qid: &Qid) -> FixedEncoder {
+    FixedEncoder::new(QID_LEN, |buf| {
+        buf[0] = qid.qid_type;
+        LittleEndian::write_u32(&mut buf[1..], qid.version);
+        LittleEndian::write_u64(&mut buf[5..], qid.path);
+    })
+}
+
+pub(crate) fn header_encoder(size: u32, msg_type: u8, tag: u16) -> FixedEncoder {
+    FixedEncoder::new(7, |buf| {
+        LittleEndian::write_u32(buf, size);
+        buf[4] = msg_type;
+        LittleEndian::write_u16(&mut buf[5..], tag);
+    })
+}
+
+/// Future returned by
+/// [`read_9p_string`](../trait.AsyncReadBytesExt.html#method.read_9p_string).
+#[derive(Debug)]
+pub struct Read9pString<R> {
+    src: R,
+    max_len: usize,
+    prefix: [u8; 2],
+    filled: usize,
+    buf: Vec<u8>,
+}
+
+impl<R> Read9pString<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        Read9pString {
+            src,
+            max_len,
+            prefix: [0; 2],
+            filled: 0,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for Read9pString<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<String>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < this.prefix.len() {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.prefix,
+                &mut this.filled
+            ))?;
+        }
+        let len = usize::from(LittleEndian::read_u16(&this.prefix));
+        if len > this.max_len {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "9p string is longer than the maximum of {} bytes",
+                    this.max_len
+                ),
+            )));
+        }
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready(
+            String::from_utf8(core::mem::take(&mut this.buf)).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "9p string is not valid utf-8")
+            }),
+        )
+    }
+}
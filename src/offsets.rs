@@ -0,0 +1,113 @@
+use crate::*;
+use core::convert::TryFrom;
+use std::vec::Vec;
+use tokio::io::AsyncWriteExt;
+
+/// A list of stream positions, to be written out as an offset table.
+///
+/// Container formats usually end, or begin, with an index of where each of their entries
+/// starts. An `OffsetTable` takes care of the bookkeeping: [`record`] the position of a
+/// [`CountingWriter`] just before writing each entry, and once they are all written, [`write_to`]
+/// emits the table with whatever width and byte order the format calls for.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, CountingWriter, OffsetTable};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = CountingWriter::new(Vec::new());
+///     let mut index = OffsetTable::new();
+///     for entry in [&b"one"[..], b"three"] {
+///         index.record(&wtr);
+///         wtr.write_u8(entry.len() as u8).await.unwrap();
+///         tokio::io::AsyncWriteExt::write_all(&mut wtr, entry).await.unwrap();
+///     }
+///     let index_at = wtr.position();
+///     index.write_to::<u16, BigEndian, _>(&mut wtr).await.unwrap();
+///     wtr.write_u32::<BigEndian>(index_at as u32).await.unwrap();
+///
+///     assert_eq!(
+///         wtr.into_inner(),
+///         b"\x03one\x05three\x00\x00\x00\x04\x00\x00\x00\x0a"
+///     );
+/// }
+/// ```
+///
+/// [`record`]: #method.record
+/// [`write_to`]: #method.write_to
+/// [`CountingWriter`]: struct.CountingWriter.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetTable {
+    offsets: Vec<u64>,
+}
+
+impl OffsetTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the current position of `wtr` to the table.
+    pub fn record<W>(&mut self, wtr: &CountingWriter<W>) {
+        self.offsets.push(wtr.position());
+    }
+
+    /// Adds `offset` to the table.
+    pub fn push(&mut self, offset: u64) {
+        self.offsets.push(offset);
+    }
+
+    /// Returns the offsets in the table, in the order they were added.
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
+
+    /// Returns the number of offsets in the table.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if the table holds no offsets.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Writes every offset to `dst` as a `P` in the byte order `T`, in the order they were
+    /// added, with a single write.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// In addition, an error of the kind `InvalidInput` is returned, and nothing is written, if
+    /// any of the offsets does not fit in a `P`.
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_to<P, T, W>(&self, dst: &mut W) -> io::Result<()>
+    where
+        P: Primitive + TryFrom<u64>,
+        T: ByteOrder,
+        W: io::AsyncWrite + Unpin + ?Sized,
+    {
+        let width = P::decoder::<T>().remaining();
+        let mut buf = vec![0; width * self.offsets.len()];
+        for (&offset, out) in self.offsets.iter().zip(buf.chunks_exact_mut(width)) {
+            let offset = P::try_from(offset).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("offset {} does not fit in the offset table", offset),
+                )
+            })?;
+            offset.encoder::<T>().pull_bytes(out);
+        }
+        dst.write_all(&buf).await
+    }
+}
+
+impl Extend<u64> for OffsetTable {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        self.offsets.extend(iter);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use core::task::ready;
+
+/// Future returned by the `_owned` read methods of
+/// [`AsyncReadBytesExt`](../trait.AsyncReadBytesExt.html).
+///
+/// It resolves to the reader along with the result, so the reader is not lost on error.
+#[derive(Debug)]
+pub struct ReadOwned<R, V> {
+    src: Option<R>,
+    decoder: FixedDecoder<V>,
+}
+
+impl<R, V> ReadOwned<R, V> {
+    pub(crate) fn new(src: R, decoder: FixedDecoder<V>) -> Self {
+        ReadOwned {
+            src: Some(src),
+            decoder,
+        }
+    }
+}
+
+impl<R, V> Future for ReadOwned<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = (R, io::Result<V>);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let src = this.src.as_mut().expect("polled after completion");
+        let res = ready!(poll_decode(Pin::new(src), cx, &mut this.decoder));
+        Poll::Ready((this.src.take().unwrap(), res))
+    }
+}
+
+/// Future returned by the `_owned` write methods of
+/// [`AsyncWriteBytesExt`](../trait.AsyncWriteBytesExt.html).
+///
+/// It resolves to the writer along with the result, so the writer is not lost on error.
+#[derive(Debug)]
+pub struct WriteOwned<W> {
+    dst: Option<W>,
+    encoder: FixedEncoder,
+}
+
+impl<W> WriteOwned<W> {
+    pub(crate) fn new(dst: W, encoder: FixedEncoder) -> Self {
+        WriteOwned {
+            dst: Some(dst),
+            encoder,
+        }
+    }
+}
+
+impl<W> Future for WriteOwned<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = (W, io::Result<()>);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let dst = this.dst.as_mut().expect("polled after completion");
+        let res = ready!(poll_write_encoder(Pin::new(dst), cx, &mut this.encoder));
+        Poll::Ready((this.dst.take().unwrap(), res))
+    }
+}
@@ -0,0 +1,223 @@
+use crate::*;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::io::IoSlice;
+use std::vec::Vec;
+use tokio::io::AsyncWriteExt;
+
+/// A message that is assembled in memory and then written out all at once.
+///
+/// Each method appends one value and returns the builder, so a whole message reads as a single
+/// chain of calls. Nothing is written until [`write_to`], which hands the finished message to
+/// the writer in one go, rather than as one small write per field.
+///
+/// Large payloads that are already in a [`Bytes`] can be appended with [`payload`], which does
+/// not copy them. If the writer supports vectored writes, the payload is then handed to it
+/// alongside the bytes around it, and the message is never copied into one contiguous buffer.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::PacketBuilder;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let payload = b"hello";
+///     let packet = PacketBuilder::new()
+///         .u8(1)
+///         .u16_be(payload.len() as u16)
+///         .bytes(payload);
+///
+///     let mut wtr = Vec::new();
+///     packet.write_to(&mut wtr).await.unwrap();
+///     assert_eq!(wtr, b"\x01\x00\x05hello");
+/// }
+/// ```
+///
+/// [`write_to`]: #method.write_to
+/// [`payload`]: #method.payload
+/// [`Bytes`]: https://docs.rs/bytes/1/bytes/struct.Bytes.html
+#[derive(Debug, Clone, Default)]
+pub struct PacketBuilder {
+    /// Everything before `buf`, in order.
+    chunks: Vec<Bytes>,
+    buf: BytesMut,
+}
+
+impl PacketBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty builder with room for `capacity` bytes before it has to reallocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PacketBuilder {
+            chunks: Vec::new(),
+            buf: BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of bytes in the message so far.
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(Bytes::len).sum::<usize>() + self.buf.len()
+    }
+
+    /// Returns true if nothing has been appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the finished message without writing it anywhere.
+    ///
+    /// This copies any [`payload`](#method.payload)s into place.
+    pub fn into_inner(self) -> BytesMut {
+        if self.chunks.is_empty() {
+            return self.buf;
+        }
+        let mut out = BytesMut::with_capacity(self.len());
+        for chunk in &self.chunks {
+            out.extend_from_slice(chunk);
+        }
+        out.extend_from_slice(&self.buf);
+        out
+    }
+
+    fn slices(&self) -> impl Iterator<Item = &[u8]> {
+        self.chunks
+            .iter()
+            .map(|c| &c[..])
+            .chain(core::iter::once(&self.buf[..]))
+            .filter(|c| !c.is_empty())
+    }
+
+    /// Appends an unsigned 8 bit integer.
+    pub fn u8(mut self, n: u8) -> Self {
+        self.buf.put_u8(n);
+        self
+    }
+
+    /// Appends a signed 8 bit integer.
+    pub fn i8(mut self, n: i8) -> Self {
+        self.buf.put_i8(n);
+        self
+    }
+
+    /// Appends `bytes` as they are.
+    ///
+    /// The bytes are copied into the builder; see [`payload`](#method.payload) for a way to
+    /// avoid that.
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.buf.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends `payload` as it is, without copying it.
+    pub fn payload(mut self, payload: Bytes) -> Self {
+        if !self.buf.is_empty() {
+            self.chunks.push(self.buf.split().freeze());
+        }
+        self.chunks.push(payload);
+        self
+    }
+
+    /// Writes the message to `dst`.
+    ///
+    /// If `dst` supports [vectored writes], the message is written with those, without first
+    /// copying it into one buffer. Otherwise, it is written with a single [`write_all`].
+    ///
+    /// The builder is left as it was, so the same message can be written again.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [vectored writes]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#method.is_write_vectored
+    /// [`write_all`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWriteExt.html#method.write_all
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_to<W>(&self, dst: &mut W) -> io::Result<()>
+    where
+        W: io::AsyncWrite + Unpin + ?Sized,
+    {
+        if self.chunks.is_empty() {
+            return dst.write_all(&self.buf[..]).await;
+        }
+        if !dst.is_write_vectored() {
+            return dst.write_all(&self.clone().into_inner()).await;
+        }
+
+        let mut slices: Vec<&[u8]> = self.slices().collect();
+        let mut first = 0;
+        while first < slices.len() {
+            let bufs: Vec<_> = slices[first..].iter().map(|s| IoSlice::new(s)).collect();
+            let mut n = dst.write_vectored(&bufs).await?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            while n > 0 {
+                let len = slices[first].len();
+                if n < len {
+                    slices[first] = &slices[first][n..];
+                    break;
+                }
+                n -= len;
+                first += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn put<F: FnOnce(&mut [u8])>(mut self, len: usize, encode: F) -> Self {
+        let start = self.buf.len();
+        self.buf.resize(start + len, 0);
+        encode(&mut self.buf[start..]);
+        self
+    }
+}
+
+macro_rules! packet_impl {
+    ($(($name:ident, $be:ident, $le:ident, $ne:ident, $write:ident, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        impl PacketBuilder {
+            $(
+                #[doc = concat!("Appends ", $desc, " in the byte order `T`.")]
+                pub fn $name<T: ByteOrder>(self, n: $ty) -> Self {
+                    self.put($bytes, |buf| T::$write(buf, n))
+                }
+
+                #[doc = concat!("Appends ", $desc, " in big-endian order.")]
+                pub fn $be(self, n: $ty) -> Self {
+                    self.$name::<BigEndian>(n)
+                }
+
+                #[doc = concat!("Appends ", $desc, " in little-endian order.")]
+                pub fn $le(self, n: $ty) -> Self {
+                    self.$name::<LittleEndian>(n)
+                }
+
+                #[doc = concat!("Appends ", $desc, " in native-endian order.")]
+                pub fn $ne(self, n: $ty) -> Self {
+                    self.$name::<NativeEndian>(n)
+                }
+            )*
+        }
+    };
+}
+
+packet_impl! {
+    (u16, u16_be, u16_le, u16_ne, write_u16, u16, 2, "an unsigned 16 bit integer"),
+    (i16, i16_be, i16_le, i16_ne, write_i16, i16, 2, "a signed 16 bit integer"),
+    (u24, u24_be, u24_le, u24_ne, write_u24, u32, 3, "an unsigned 24 bit integer"),
+    (i24, i24_be, i24_le, i24_ne, write_i24, i32, 3, "a signed 24 bit integer"),
+    (u32, u32_be, u32_le, u32_ne, write_u32, u32, 4, "an unsigned 32 bit integer"),
+    (i32, i32_be, i32_le, i32_ne, write_i32, i32, 4, "a signed 32 bit integer"),
+    (u48, u48_be, u48_le, u48_ne, write_u48, u64, 6, "an unsigned 48 bit integer"),
+    (i48, i48_be, i48_le, i48_ne, write_i48, i64, 6, "a signed 48 bit integer"),
+    (u64, u64_be, u64_le, u64_ne, write_u64, u64, 8, "an unsigned 64 bit integer"),
+    (i64, i64_be, i64_le, i64_ne, write_i64, i64, 8, "a signed 64 bit integer"),
+    (u128, u128_be, u128_le, u128_ne, write_u128, u128, 16, "an unsigned 128 bit integer"),
+    (i128, i128_be, i128_le, i128_ne, write_i128, i128, 16, "a signed 128 bit integer"),
+    (f32, f32_be, f32_le, f32_ne, write_f32, f32, 4, "a IEEE754 single-precision floating point number"),
+    (f64, f64_be, f64_le, f64_ne, write_f64, f64, 8, "a IEEE754 double-precision floating point number"),
+}
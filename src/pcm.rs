@@ -0,0 +1,351 @@
+use crate::coop::{yield_now, Budget};
+use crate::*;
+use tokio::io::AsyncReadExt;
+
+/// A PCM sample encoding that [`PcmReader`] can decode.
+///
+/// This is implemented for `i16`, `i32`, and `f32`, which decode to themselves, and for
+/// [`I24`], which decodes to an `i32`.
+///
+/// [`PcmReader`]: struct.PcmReader.html
+/// [`I24`]: struct.I24.html
+pub trait PcmSample {
+    /// The type that samples are decoded to.
+    type Value: Copy;
+
+    /// The number of bytes each sample takes up.
+    const WIDTH: usize;
+
+    /// Decodes a single sample from the first [`WIDTH`](#associatedconstant.WIDTH) bytes of
+    /// `buf`.
+    fn decode<T: ByteOrder>(buf: &[u8]) -> Self::Value;
+}
+
+/// Packed 24 bit signed samples, which are decoded to `i32`s in the range of an `i24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct I24;
+
+impl PcmSample for i16 {
+    type Value = i16;
+    const WIDTH: usize = 2;
+    fn decode<T: ByteOrder>(buf: &[u8]) -> i16 {
+        T::read_i16(buf)
+    }
+}
+
+impl PcmSample for I24 {
+    type Value = i32;
+    const WIDTH: usize = 3;
+    fn decode<T: ByteOrder>(buf: &[u8]) -> i32 {
+        T::read_i24(buf)
+    }
+}
+
+impl PcmSample for i32 {
+    type Value = i32;
+    const WIDTH: usize = 4;
+    fn decode<T: ByteOrder>(buf: &[u8]) -> i32 {
+        T::read_i32(buf)
+    }
+}
+
+impl PcmSample for f32 {
+    type Value = f32;
+    const WIDTH: usize = 4;
+    fn decode<T: ByteOrder>(buf: &[u8]) -> f32 {
+        T::read_f32(buf)
+    }
+}
+
+/// A reader for interleaved PCM audio that decodes many frames at once.
+///
+/// A frame holds one sample of type `S` for each channel, in the byte order `T`. Rather than
+/// reading every sample on its own, each call reads the bytes of all the frames it was asked
+/// for in as few reads as the underlying reader allows, and then decodes them in one go. The
+/// samples can be kept interleaved, or split up by channel.
+///
+/// Bytes that were read but do not make up a whole frame yet, including ones read by a call
+/// that was cancelled, are kept for the next call.
+///
+/// # Examples
+///
+/// Read stereo 16 bit little-endian audio into one buffer per channel:
+///
+/// ```rust
+/// use tokio_byteorder::{LittleEndian, PcmReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let input = [0x01, 0x00, 0xff, 0xff, 0x02, 0x00, 0xfe, 0xff];
+///     let mut rdr = PcmReader::<_, i16, LittleEndian>::new(&input[..], 2);
+///
+///     let (mut left, mut right) = ([0; 4], [0; 4]);
+///     let frames = rdr.read_planar(&mut [&mut left[..], &mut right[..]]).await.unwrap();
+///     assert_eq!(frames, 2);
+///     assert_eq!(left[..frames], [1, 2]);
+///     assert_eq!(right[..frames], [-1, -2]);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PcmReader<R, S, T> {
+    inner: R,
+    channels: usize,
+    buf: Vec<u8>,
+    filled: usize,
+    format: PhantomData<fn() -> (S, T)>,
+}
+
+impl<R, S: PcmSample, T: ByteOrder> PcmReader<R, S, T> {
+    /// Wraps the given reader, which holds frames of `channels` samples each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is zero.
+    pub fn new(inner: R, channels: usize) -> Self {
+        assert!(channels > 0, "there must be at least one channel");
+        PcmReader {
+            inner,
+            channels,
+            buf: Vec::new(),
+            filled: 0,
+            format: PhantomData,
+        }
+    }
+
+    /// Returns the number of channels in each frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns the number of bytes each frame takes up.
+    pub fn frame_len(&self) -> usize {
+        self.channels * S::WIDTH
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the returned reference skips any bytes that are held back for an
+    /// incomplete frame.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying reader.
+    ///
+    /// Any bytes that are held back for an incomplete frame are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, S, T> PcmReader<R, S, T>
+where
+    R: io::AsyncRead + Unpin,
+    S: PcmSample,
+    T: ByteOrder,
+{
+    /// Reads up to `frames` frames, and returns the bytes of the ones that are complete.
+    async fn fill(&mut self, frames: usize) -> io::Result<&[u8]> {
+        let frame_len = self.frame_len();
+        let want = frames * frame_len;
+        if self.buf.len() < want {
+            self.buf.resize(want, 0);
+        }
+        let mut budget = Budget::new();
+        while self.filled < want {
+            match self.inner.read(&mut self.buf[self.filled..want]).await {
+                Ok(0) => break,
+                Ok(n) => {
+                    self.filled += n;
+                    if self.filled < want && budget.charge(n) {
+                        yield_now().await;
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let used = core::cmp::min(self.filled / frame_len, frames) * frame_len;
+        if used == 0 && self.filled > 0 && frames > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream ended in the middle of a frame",
+            ));
+        }
+        // move the leftover bytes to the front, and hand out the frames from a copy at the back
+        self.buf.rotate_left(used);
+        self.filled -= used;
+        let end = self.buf.len();
+        Ok(&self.buf[end - used..])
+    }
+
+    /// Reads as many frames as fit in `out`, keeping the samples interleaved, and returns the
+    /// number of frames that were read.
+    ///
+    /// Fewer frames are only read if the underlying reader reaches end-of-file first, so a
+    /// return value of zero means that the stream is over.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read`]. If the stream ends partway
+    /// through a frame, and no whole frames are left, an error of the kind
+    /// [`ErrorKind::UnexpectedEof`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `out` is not a multiple of the number of channels.
+    ///
+    /// [`Read::read`]: https://doc.rust-lang.org/std/io/trait.Read.html#tymethod.read
+    /// [`ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    pub async fn read_interleaved(&mut self, out: &mut [S::Value]) -> io::Result<usize> {
+        assert!(
+            out.len() % self.channels == 0,
+            "the output must hold a whole number of frames"
+        );
+        let frame_len = self.frame_len();
+        let bytes = self.fill(out.len() / self.channels).await?;
+        for (sample, buf) in out.iter_mut().zip(bytes.chunks_exact(S::WIDTH)) {
+            *sample = S::decode::<T>(buf);
+        }
+        Ok(bytes.len() / frame_len)
+    }
+
+    /// Reads as many frames as fit in the shortest buffer in `out`, splitting the samples up by
+    /// channel, and returns the number of frames that were read.
+    ///
+    /// `out` holds one buffer per channel. Fewer frames are only read if the underlying reader
+    /// reaches end-of-file first, so a return value of zero means that the stream is over.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as
+    /// [`read_interleaved`](#method.read_interleaved).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` does not hold exactly one buffer per channel.
+    pub async fn read_planar(&mut self, out: &mut [&mut [S::Value]]) -> io::Result<usize> {
+        assert_eq!(
+            out.len(),
+            self.channels,
+            "there must be one output buffer per channel"
+        );
+        let frame_len = self.frame_len();
+        let frames = out.iter().map(|c| c.len()).min().unwrap_or(0);
+        let bytes = self.fill(frames).await?;
+        for (i, frame) in bytes.chunks_exact(frame_len).enumerate() {
+            for (channel, buf) in out.iter_mut().zip(frame.chunks_exact(S::WIDTH)) {
+                channel[i] = S::decode::<T>(buf);
+            }
+        }
+        Ok(bytes.len() / frame_len)
+    }
+}
+
+/// How 24 bit samples map onto the `i32`s they are unpacked into.
+///
+/// Passed to [`read_i24_into_unpacked`] and [`write_i24_from`].
+///
+/// [`read_i24_into_unpacked`]: trait.AsyncReadBytesExt.html#method.read_i24_into_unpacked
+/// [`write_i24_from`]: trait.AsyncWriteBytesExt.html#method.write_i24_from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum I24Scaling {
+    /// Samples keep their value, and are sign-extended into the low 24 bits of the `i32`.
+    ///
+    /// When writing, values outside the range of an `i24` lose their top bits.
+    SignExtend,
+    /// Samples are shifted into the top 24 bits of the `i32`, so that they span the full range
+    /// of an `i32`, as 32 bit audio code expects.
+    ///
+    /// When writing, the low 8 bits of each value are dropped.
+    LeftJustify,
+}
+
+/// Future returned by
+/// [`read_i24_into_unpacked`](../trait.AsyncReadBytesExt.html#method.read_i24_into_unpacked).
+#[derive(Debug)]
+pub struct ReadI24Into<'a, R: ?Sized, T> {
+    src: &'a mut R,
+    dst: &'a mut [i32],
+    scaling: I24Scaling,
+    buf: Vec<u8>,
+    bo: PhantomData<fn() -> T>,
+}
+
+impl<'a, R: ?Sized, T> ReadI24Into<'a, R, T> {
+    pub(crate) fn new(src: &'a mut R, dst: &'a mut [i32], scaling: I24Scaling) -> Self {
+        ReadI24Into {
+            src,
+            dst,
+            scaling,
+            buf: Vec::new(),
+            bo: PhantomData,
+        }
+    }
+}
+
+impl<R, T> Future for ReadI24Into<'_, R, T>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+    T: ByteOrder,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = this.dst.len() * 3;
+        core::task::ready!(poll_read_to_len(this.src, cx, &mut this.buf, len))?;
+        let shift = match this.scaling {
+            I24Scaling::SignExtend => 0,
+            I24Scaling::LeftJustify => 8,
+        };
+        for (sample, buf) in this.dst.iter_mut().zip(this.buf.chunks_exact(3)) {
+            *sample = T::read_i24(buf) << shift;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by
+/// [`write_i24_from`](../trait.AsyncWriteBytesExt.html#method.write_i24_from).
+#[derive(Debug)]
+pub struct WriteI24From<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteI24From<W> {
+    pub(crate) fn new<T: ByteOrder>(dst: W, src: &[i32], scaling: I24Scaling) -> Self {
+        let mut buf = vec![0; src.len() * 3];
+        for (buf, &sample) in buf.chunks_exact_mut(3).zip(src) {
+            let sample = match scaling {
+                // keep the low 24 bits, which byteorder insists are in range
+                I24Scaling::SignExtend => sample << 8 >> 8,
+                I24Scaling::LeftJustify => sample >> 8,
+            };
+            T::write_i24(buf, sample);
+        }
+        WriteI24From {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteI24From<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
@@ -0,0 +1,296 @@
+//! `poll_`-style functions for hand-written futures and streams.
+//!
+//! The futures returned by [`AsyncReadBytesExt`] and [`AsyncWriteBytesExt`] borrow the stream
+//! for as long as they live, which makes them awkward to store inside a hand-written [`Future`]
+//! or `Stream` that owns that stream. The functions in this module do the same work, but keep
+//! their progress in a small [`ReadState`] or [`WriteState`] that you store alongside the stream
+//! instead. They take the stream as `Pin<&mut R>`, so they work with `!Unpin` streams too.
+//!
+//! A state must only be used for one value at a time: keep passing it (and, for writes, the
+//! same value) until the call returns `Poll::Ready`, after which it is ready for the next value.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::future::Future;
+//! use std::io;
+//! use std::pin::Pin;
+//! use std::task::{ready, Context, Poll};
+//! use tokio::io::AsyncRead;
+//! use tokio_byteorder::poll::{poll_read_u16, ReadState};
+//! use tokio_byteorder::BigEndian;
+//!
+//! /// Sums big-endian `u16`s until the stream ends.
+//! struct Sum<R> {
+//!     src: R,
+//!     state: ReadState,
+//!     total: u64,
+//! }
+//!
+//! impl<R: AsyncRead + Unpin> Future for Sum<R> {
+//!     type Output = io::Result<u64>;
+//!     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+//!         let this = self.get_mut();
+//!         loop {
+//!             match ready!(poll_read_u16::<BigEndian, _>(Pin::new(&mut this.src), cx, &mut this.state)) {
+//!                 Ok(n) => this.total += u64::from(n),
+//!                 Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+//!                     return Poll::Ready(Ok(this.total))
+//!                 }
+//!                 Err(e) => return Poll::Ready(Err(e)),
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let src = &[0, 1, 0, 2, 1, 0][..];
+//!     let sum = Sum { src, state: ReadState::new(), total: 0 };
+//!     assert_eq!(sum.await.unwrap(), 259);
+//! }
+//! ```
+//!
+//! [`AsyncReadBytesExt`]: ../trait.AsyncReadBytesExt.html
+//! [`AsyncWriteBytesExt`]: ../trait.AsyncWriteBytesExt.html
+//! [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+//! [`ReadState`]: struct.ReadState.html
+//! [`WriteState`]: struct.WriteState.html
+
+use crate::*;
+
+/// The progress of one of the `poll_read_` functions in this module.
+#[derive(Debug, Clone, Default)]
+pub struct ReadState {
+    buf: [u8; 16],
+    filled: usize,
+}
+
+impl ReadState {
+    /// Creates a state that has not read anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bytes of the current value that have been read so far.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    /// Discards any partially read value.
+    pub fn reset(&mut self) {
+        self.filled = 0;
+    }
+
+    fn poll<R, V>(
+        &mut self,
+        mut src: Pin<&mut R>,
+        cx: &mut Context<'_>,
+        len: usize,
+        decode: fn(&[u8]) -> V,
+    ) -> Poll<io::Result<V>>
+    where
+        R: io::AsyncRead + ?Sized,
+    {
+        while self.filled < len {
+            let mut rbuf = io::ReadBuf::new(&mut self.buf[self.filled..len]);
+            match src.as_mut().poll_read(cx, &mut rbuf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(())) if rbuf.filled().is_empty() => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    )));
+                }
+                Poll::Ready(Ok(())) => self.filled += rbuf.filled().len(),
+            }
+        }
+        self.filled = 0;
+        Poll::Ready(Ok(decode(&self.buf[..len])))
+    }
+}
+
+/// The progress of one of the `poll_write_` functions in this module.
+#[derive(Debug, Clone, Default)]
+pub struct WriteState {
+    buf: [u8; 16],
+    len: usize,
+    written: usize,
+}
+
+impl WriteState {
+    /// Creates a state that has not written anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if a value has been started but not yet completely written.
+    pub fn is_writing(&self) -> bool {
+        self.len != 0
+    }
+
+    /// Discards any partially written value.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.written = 0;
+    }
+
+    fn poll<W>(
+        &mut self,
+        mut dst: Pin<&mut W>,
+        cx: &mut Context<'_>,
+        len: usize,
+        encode: impl FnOnce(&mut [u8]),
+    ) -> Poll<io::Result<()>>
+    where
+        W: io::AsyncWrite + ?Sized,
+    {
+        if self.len == 0 {
+            encode(&mut self.buf[..len]);
+            self.len = len;
+        }
+        while self.written < self.len {
+            match dst
+                .as_mut()
+                .poll_write(cx, &self.buf[self.written..self.len])
+            {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    )));
+                }
+                Poll::Ready(Ok(n)) => self.written += n,
+            }
+        }
+        self.reset();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Attempts to read an unsigned 8 bit integer from `src`.
+///
+/// # Errors
+///
+/// This function returns the same errors as [`Read::read_exact`].
+///
+/// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+pub fn poll_read_u8<R>(
+    src: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    state: &mut ReadState,
+) -> Poll<io::Result<u8>>
+where
+    R: io::AsyncRead + ?Sized,
+{
+    state.poll(src, cx, 1, |buf| buf[0])
+}
+
+/// Attempts to read a signed 8 bit integer from `src`.
+///
+/// See [`poll_read_u8`](fn.poll_read_u8.html).
+pub fn poll_read_i8<R>(
+    src: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    state: &mut ReadState,
+) -> Poll<io::Result<i8>>
+where
+    R: io::AsyncRead + ?Sized,
+{
+    state.poll(src, cx, 1, |buf| buf[0] as i8)
+}
+
+/// Attempts to write the unsigned 8 bit integer `n` to `dst`.
+///
+/// `n` is only looked at on the first call for a given value; keep passing the same `state`
+/// until this returns `Poll::Ready`.
+///
+/// # Errors
+///
+/// This function returns the same errors as [`Write::write_all`]. The bytes not yet written are
+/// kept in `state`, so the write can be retried.
+///
+/// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+pub fn poll_write_u8<W>(
+    dst: Pin<&mut W>,
+    cx: &mut Context<'_>,
+    state: &mut WriteState,
+    n: u8,
+) -> Poll<io::Result<()>>
+where
+    W: io::AsyncWrite + ?Sized,
+{
+    state.poll(dst, cx, 1, |buf| buf[0] = n)
+}
+
+/// Attempts to write the signed 8 bit integer `n` to `dst`.
+///
+/// See [`poll_write_u8`](fn.poll_write_u8.html).
+pub fn poll_write_i8<W>(
+    dst: Pin<&mut W>,
+    cx: &mut Context<'_>,
+    state: &mut WriteState,
+    n: i8,
+) -> Poll<io::Result<()>>
+where
+    W: io::AsyncWrite + ?Sized,
+{
+    state.poll(dst, cx, 1, |buf| buf[0] = n as u8)
+}
+
+macro_rules! poll_impl {
+    ($(($read:ident, $write:ident, $codec_read:ident, $codec_write:ident, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Attempts to read ", $desc, " in the byte order `T` from `src`.")]
+            ///
+            /// See [`poll_read_u8`](fn.poll_read_u8.html).
+            pub fn $read<T: ByteOrder, R>(
+                src: Pin<&mut R>,
+                cx: &mut Context<'_>,
+                state: &mut ReadState,
+            ) -> Poll<io::Result<$ty>>
+            where
+                R: io::AsyncRead + ?Sized,
+            {
+                state.poll(src, cx, $bytes, T::$codec_read)
+            }
+
+            #[doc = concat!("Attempts to write ", $desc, " `n` in the byte order `T` to `dst`.")]
+            ///
+            /// See [`poll_write_u8`](fn.poll_write_u8.html).
+            pub fn $write<T: ByteOrder, W>(
+                dst: Pin<&mut W>,
+                cx: &mut Context<'_>,
+                state: &mut WriteState,
+                n: $ty,
+            ) -> Poll<io::Result<()>>
+            where
+                W: io::AsyncWrite + ?Sized,
+            {
+                state.poll(dst, cx, $bytes, |buf| T::$codec_write(buf, n))
+            }
+        )*
+    };
+}
+
+poll_impl! {
+    (poll_read_u16, poll_write_u16, read_u16, write_u16, u16, 2, "an unsigned 16 bit integer"),
+    (poll_read_i16, poll_write_i16, read_i16, write_i16, i16, 2, "a signed 16 bit integer"),
+    (poll_read_u24, poll_write_u24, read_u24, write_u24, u32, 3, "an unsigned 24 bit integer"),
+    (poll_read_i24, poll_write_i24, read_i24, write_i24, i32, 3, "a signed 24 bit integer"),
+    (poll_read_u32, poll_write_u32, read_u32, write_u32, u32, 4, "an unsigned 32 bit integer"),
+    (poll_read_i32, poll_write_i32, read_i32, write_i32, i32, 4, "a signed 32 bit integer"),
+    (poll_read_u48, poll_write_u48, read_u48, write_u48, u64, 6, "an unsigned 48 bit integer"),
+    (poll_read_i48, poll_write_i48, read_i48, write_i48, i64, 6, "a signed 48 bit integer"),
+    (poll_read_u64, poll_write_u64, read_u64, write_u64, u64, 8, "an unsigned 64 bit integer"),
+    (poll_read_i64, poll_write_i64, read_i64, write_i64, i64, 8, "a signed 64 bit integer"),
+    (poll_read_u128, poll_write_u128, read_u128, write_u128, u128, 16, "an unsigned 128 bit integer"),
+    (poll_read_i128, poll_write_i128, read_i128, write_i128, i128, 16, "a signed 128 bit integer"),
+    (poll_read_f32, poll_write_f32, read_f32, write_f32, f32, 4, "a IEEE754 single-precision floating point number"),
+    (poll_read_f64, poll_write_f64, read_f64, write_f64, f64, 8, "a IEEE754 double-precision floating point number"),
+}
@@ -0,0 +1,176 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::string::String;
+use std::vec::Vec;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Future returned by
+/// [`read_pg_message_header`](../trait.AsyncReadBytesExt.html#method.read_pg_message_header).
+#[derive(Debug)]
+pub struct ReadPgMessageHeader<R> {
+    src: R,
+    buf: [u8; 5],
+    filled: usize,
+}
+
+impl<R> ReadPgMessageHeader<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadPgMessageHeader {
+            src,
+            buf: [0; 5],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadPgMessageHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<(u8, usize)>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            &mut this.filled
+        ))?;
+        // the length counts itself, but not the type
+        let len = BigEndian::read_i32(&this.buf[1..]);
+        match usize::try_from(len) {
+            Ok(len) if len >= 4 => Poll::Ready(Ok((this.buf[0], len - 4))),
+            _ => Poll::Ready(Err(invalid("postgres message length is less than 4"))),
+        }
+    }
+}
+
+/// Future returned by
+/// [`read_pg_string`](../trait.AsyncBufReadBytesExt.html#method.read_pg_string).
+#[derive(Debug)]
+pub struct ReadPgString<R> {
+    src: R,
+    max_len: usize,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadPgString<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadPgString {
+            src,
+            max_len,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadPgString<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<String>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let buf = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if buf.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "reached the end of the stream before the end of the postgres string",
+                )));
+            }
+            let nul = buf.iter().position(|&b| b == 0);
+            let n = nul.unwrap_or(buf.len());
+            if this.buf.len() + n > this.max_len {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "postgres string is longer than the maximum of {} bytes",
+                        this.max_len
+                    ),
+                )));
+            }
+            this.buf.extend_from_slice(&buf[..n]);
+            match nul {
+                Some(_) => {
+                    src.consume(n + 1);
+                    let s = core::mem::take(&mut this.buf);
+                    return Poll::Ready(
+                        String::from_utf8(s)
+                            .map_err(|_| invalid("postgres string is not valid utf-8")),
+                    );
+                }
+                None => src.consume(n),
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`write_pg_message_header`](../trait.AsyncWriteBytesExt.html#method.write_pg_message_header)
+/// and [`write_pg_string`](../trait.AsyncWriteBytesExt.html#method.write_pg_string).
+#[derive(Debug)]
+pub struct WritePg<W> {
+    dst: W,
+    // the encoded header or string, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WritePg<W> {
+    pub(crate) fn message_header(dst: W, msg_type: u8, body_len: usize) -> Self {
+        // the length counts itself, but not the type
+        let buf = match body_len.checked_add(4).map(i32::try_from) {
+            Some(Ok(len)) => {
+                let mut buf = Vec::with_capacity(5);
+                buf.push(msg_type);
+                buf.extend_from_slice(&len.to_be_bytes());
+                Ok(buf)
+            }
+            _ => Err("postgres message is too long for its length field"),
+        };
+        WritePg {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn string(dst: W, s: &str) -> Self {
+        let buf = if s.as_bytes().contains(&0) {
+            Err("postgres string holds a NUL byte")
+        } else {
+            let mut buf = Vec::with_capacity(s.len() + 1);
+            buf.extend_from_slice(s.as_bytes());
+            buf.push(0);
+            Ok(buf)
+        };
+        WritePg {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WritePg<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
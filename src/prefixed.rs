@@ -0,0 +1,267 @@
+use crate::coop::Budget;
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use crate::*;
+use bytes::{Buf, Bytes, BytesMut};
+use core::convert::TryFrom;
+use core::mem::MaybeUninit;
+use core::task::ready;
+
+mod sealed {
+    use core::mem::MaybeUninit;
+
+    /// A growable byte buffer that can be read into without zeroing its spare capacity first.
+    pub trait SpareCapacity {
+        fn len(&self) -> usize;
+        fn clear(&mut self);
+        fn reserve(&mut self, additional: usize);
+        fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>];
+        /// Extends the buffer over the first `n` bytes of its spare capacity.
+        ///
+        /// # Safety
+        ///
+        /// Those bytes must have been initialized.
+        unsafe fn grow(&mut self, n: usize);
+    }
+}
+
+pub(crate) use sealed::SpareCapacity;
+
+macro_rules! spare_capacity {
+    ($($ty:ty),*) => {
+        $(
+            impl SpareCapacity for $ty {
+                fn len(&self) -> usize {
+                    self.len()
+                }
+
+                fn clear(&mut self) {
+                    self.clear();
+                }
+
+                fn reserve(&mut self, additional: usize) {
+                    self.reserve(additional);
+                }
+
+                fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+                    self.spare_capacity_mut()
+                }
+
+                unsafe fn grow(&mut self, n: usize) {
+                    self.set_len(self.len() + n);
+                }
+            }
+
+            impl ScratchBuffer for $ty {}
+        )*
+    };
+}
+
+/// A buffer that can be read into over and over, reusing its allocation.
+///
+/// This is implemented for `Vec<u8>` and [`BytesMut`], and cannot be implemented outside of
+/// this crate. Methods like [`read_length_prefixed_into`] clear the buffer and read into its
+/// spare capacity, so that a buffer that is reused for every message only allocates when a
+/// message is larger than any before it.
+///
+/// [`BytesMut`]: https://docs.rs/bytes/1/bytes/struct.BytesMut.html
+/// [`read_length_prefixed_into`]: trait.AsyncReadBytesExt.html#method.read_length_prefixed_into
+pub trait ScratchBuffer: SpareCapacity {}
+
+spare_capacity!(Vec<u8>, BytesMut);
+
+fn too_long(max_len: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "length prefix is larger than the maximum of {} bytes",
+            max_len
+        ),
+    )
+}
+
+/// Decodes a length prefix, and checks it against `max_len`.
+fn poll_length<R, P>(
+    src: &mut R,
+    cx: &mut Context<'_>,
+    header: &mut FixedDecoder<P>,
+    max_len: usize,
+) -> Poll<io::Result<usize>>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+    usize: TryFrom<P>,
+{
+    let len = ready!(poll_decode(Pin::new(src), cx, header))?;
+    match usize::try_from(len) {
+        Ok(len) if len <= max_len => Poll::Ready(Ok(len)),
+        _ => Poll::Ready(Err(too_long(max_len))),
+    }
+}
+
+/// Future returned by
+/// [`read_length_prefixed_into`](../trait.AsyncReadBytesExt.html#method.read_length_prefixed_into).
+#[derive(Debug)]
+pub struct ReadLengthPrefixedInto<'a, R: ?Sized, P> {
+    src: &'a mut R,
+    buf: &'a mut dyn ScratchBuffer,
+    header: FixedDecoder<P>,
+    max_len: usize,
+    // the length of the value, once its prefix is in
+    len: Option<usize>,
+}
+
+impl core::fmt::Debug for dyn ScratchBuffer + '_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScratchBuffer")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, R: ?Sized, P> ReadLengthPrefixedInto<'a, R, P> {
+    pub(crate) fn new(
+        src: &'a mut R,
+        buf: &'a mut dyn ScratchBuffer,
+        header: FixedDecoder<P>,
+        max_len: usize,
+    ) -> Self {
+        ReadLengthPrefixedInto {
+            src,
+            buf,
+            header,
+            max_len,
+            len: None,
+        }
+    }
+}
+
+impl<R, P> Future for ReadLengthPrefixedInto<'_, R, P>
+where
+    R: io::AsyncRead + Unpin + ?Sized,
+    usize: TryFrom<P>,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = ready!(poll_length(this.src, cx, &mut this.header, this.max_len))?;
+                this.buf.clear();
+                *this.len.insert(len)
+            }
+        };
+        poll_read_to_len(this.src, cx, this.buf, len)
+    }
+}
+
+/// Future returned by
+/// [`read_length_prefixed_bytes`](../trait.AsyncReadBytesExt.html#method.read_length_prefixed_bytes).
+#[derive(Debug)]
+pub struct ReadLengthPrefixedBytes<R, P> {
+    src: R,
+    header: FixedDecoder<P>,
+    max_len: usize,
+    buf: BytesMut,
+    // the length of the value, once its prefix is in
+    len: Option<usize>,
+}
+
+impl<R, P> ReadLengthPrefixedBytes<R, P> {
+    pub(crate) fn new(src: R, header: FixedDecoder<P>, max_len: usize) -> Self {
+        ReadLengthPrefixedBytes {
+            src,
+            header,
+            max_len,
+            buf: BytesMut::new(),
+            len: None,
+        }
+    }
+}
+
+impl<R, P> Future for ReadLengthPrefixedBytes<R, P>
+where
+    R: io::AsyncRead + Unpin,
+    usize: TryFrom<P>,
+{
+    type Output = io::Result<Bytes>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = match this.len {
+            Some(len) => len,
+            None => {
+                let len = ready!(poll_length(
+                    &mut this.src,
+                    cx,
+                    &mut this.header,
+                    this.max_len
+                ))?;
+                *this.len.insert(len)
+            }
+        };
+        ready!(poll_read_to_len(&mut this.src, cx, &mut this.buf, len))?;
+        Poll::Ready(Ok(core::mem::take(&mut this.buf).freeze()))
+    }
+}
+
+/// Future returned by
+/// [`write_length_prefixed`](../trait.AsyncWriteBytesExt.html#method.write_length_prefixed).
+#[derive(Debug)]
+pub struct WriteLengthPrefixed<W, B> {
+    dst: W,
+    // `None` if the length does not fit in the prefix type
+    header: Option<FixedEncoder>,
+    buf: B,
+}
+
+impl<W, B: Buf> WriteLengthPrefixed<W, B> {
+    pub(crate) fn new<P, T>(dst: W, buf: B) -> Self
+    where
+        P: Primitive + TryFrom<usize>,
+        T: ByteOrder,
+    {
+        let header = P::try_from(buf.remaining())
+            .ok()
+            .map(|len| len.encoder::<T>());
+        WriteLengthPrefixed { dst, header, buf }
+    }
+}
+
+impl<W, B> Future for WriteLengthPrefixed<W, B>
+where
+    W: io::AsyncWrite + Unpin,
+    B: Buf + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let header = match &mut this.header {
+            Some(header) => header,
+            None => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "length does not fit in the length prefix",
+                )));
+            }
+        };
+        ready!(poll_write_encoder(Pin::new(&mut this.dst), cx, header))?;
+        let mut budget = Budget::new();
+        while this.buf.has_remaining() {
+            let n = match ready!(Pin::new(&mut this.dst).poll_write(cx, this.buf.chunk())) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Poll::Ready(Err(e)),
+                Ok(0) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    )));
+                }
+                Ok(n) => n,
+            };
+            this.buf.advance(n);
+            if this.buf.has_remaining() {
+                ready!(budget.poll_charge(cx, n));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
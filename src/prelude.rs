@@ -0,0 +1,70 @@
+//! A glob-importable set of this crate's extension traits and byte order types.
+//!
+//! `use tokio_byteorder::prelude::*;` brings in the byte order types along with
+//! `AsyncReadBytesExt` and `AsyncWriteBytesExt` for a single I/O backend, chosen from the
+//! enabled features:
+//!
+//!  - if exactly one of `futures-traits`, `tokio02`, and `tokio03` is enabled, and `tokio1` is
+//!    not, the traits of that backend;
+//!  - otherwise, the tokio 1.x traits (and [`AsyncBufReadBytesExt`], [`AsyncReadAtBytesExt`],
+//!    and [`AsyncWriteAtBytesExt`]).
+//!
+//! Only one backend is ever picked because many types, like `Vec<u8>` and `&[u8]`, implement
+//! the I/O traits of several backends at once, and importing several sets of extension traits
+//! would make every method call on them ambiguous. Code that uses more than one backend should
+//! import the traits from the backend modules directly.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tokio_byteorder::prelude::*;
+//!
+//! # #[cfg(not(any(feature = "futures-traits", feature = "tokio02", feature = "tokio03")))]
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut wtr = Vec::new();
+//!     wtr.write_u16::<BigEndian>(517).await.unwrap();
+//!     wtr.write_i8(-1).await.unwrap();
+//!     let mut rdr = &wtr[..];
+//!     assert_eq!(517, rdr.read_u16::<BigEndian>().await.unwrap());
+//!     assert_eq!(-1, rdr.read_i8().await.unwrap());
+//! }
+//! # #[cfg(any(feature = "futures-traits", feature = "tokio02", feature = "tokio03"))]
+//! # fn main() {}
+//! ```
+//!
+//! [`AsyncBufReadBytesExt`]: ../trait.AsyncBufReadBytesExt.html
+//! [`AsyncReadAtBytesExt`]: ../trait.AsyncReadAtBytesExt.html
+//! [`AsyncWriteAtBytesExt`]: ../trait.AsyncWriteAtBytesExt.html
+
+pub use crate::{BigEndian, LittleEndian, NativeEndian, NetworkEndian, RuntimeEndian};
+
+#[cfg(all(
+    feature = "futures-traits",
+    not(any(feature = "tokio1", feature = "tokio02", feature = "tokio03"))
+))]
+pub use crate::futures::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+#[cfg(all(
+    feature = "tokio02",
+    not(any(feature = "tokio1", feature = "futures-traits", feature = "tokio03"))
+))]
+pub use crate::tokio02::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+#[cfg(all(
+    feature = "tokio03",
+    not(any(feature = "tokio1", feature = "futures-traits", feature = "tokio02"))
+))]
+pub use crate::tokio03::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+#[cfg(any(
+    feature = "tokio1",
+    not(any(feature = "futures-traits", feature = "tokio02", feature = "tokio03")),
+    all(feature = "futures-traits", feature = "tokio02"),
+    all(feature = "futures-traits", feature = "tokio03"),
+    all(feature = "tokio02", feature = "tokio03"),
+))]
+pub use crate::{
+    AsyncBufReadBytesExt, AsyncReadAtBytesExt, AsyncReadBytesExt, AsyncWriteAtBytesExt,
+    AsyncWriteBytesExt,
+};
@@ -0,0 +1,123 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use byteorder::ByteOrder;
+
+/// A fixed-width value that can be read and written in a given byte order.
+///
+/// This is implemented for every integer type from `u8` to `i128`, and for `f32` and `f64`, so
+/// that code which is generic over its element type can read and write those elements with
+/// [`read_primitive`] and [`write_primitive`] rather than by matching on the type. The 24 and
+/// 48 bit encodings have no type of their own, and so are not covered.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio::io::AsyncRead;
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, Primitive};
+///
+/// async fn read_matrix<V, R>(src: &mut R, rows: usize, cols: usize) -> std::io::Result<Vec<V>>
+/// where
+///     V: Primitive,
+///     R: AsyncRead + Unpin,
+/// {
+///     let mut m = Vec::with_capacity(rows * cols);
+///     for _ in 0..rows * cols {
+///         m.push(src.read_primitive::<V, BigEndian>().await?);
+///     }
+///     Ok(m)
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = &[0x00, 0x01, 0x00, 0x02, 0xff, 0xff][..];
+///     let m: Vec<i16> = read_matrix(&mut rdr, 1, 3).await.unwrap();
+///     assert_eq!(m, [1, 2, -1]);
+/// }
+/// ```
+///
+/// # Implementing `Primitive`
+///
+/// Other crates can implement this trait for their own wire types of up to
+/// [`sans_io::MAX_LEN`] bytes by building on [`FixedDecoder::new`] and [`FixedEncoder::new`].
+/// Such types then work with [`read_primitive`] and [`write_primitive`], and with the other
+/// places that take a `Primitive`, exactly like the built-in numbers:
+///
+/// ```rust
+/// use std::convert::TryInto;
+/// use tokio_byteorder::sans_io::{FixedDecoder, FixedEncoder};
+/// use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, ByteOrder, Primitive};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct DeviceId([u8; 12]);
+///
+/// impl Primitive for DeviceId {
+///     fn decoder<T: ByteOrder>() -> FixedDecoder<Self> {
+///         // an ID is a string of bytes, so it reads the same in either byte order
+///         FixedDecoder::new(12, |buf| DeviceId(buf.try_into().unwrap()))
+///     }
+///
+///     fn encoder<T: ByteOrder>(self) -> FixedEncoder {
+///         FixedEncoder::new(12, |buf| buf.copy_from_slice(&self.0))
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let id = DeviceId(*b"sensor-00042");
+///     let mut buf = Vec::new();
+///     buf.write_primitive::<_, tokio_byteorder::BigEndian>(id).await.unwrap();
+///     let mut rdr = &buf[..];
+///     let back = rdr.read_primitive::<DeviceId, tokio_byteorder::BigEndian>().await.unwrap();
+///     assert_eq!(back, id);
+/// }
+/// ```
+///
+/// [`read_primitive`]: trait.AsyncReadBytesExt.html#method.read_primitive
+/// [`write_primitive`]: trait.AsyncWriteBytesExt.html#method.write_primitive
+/// [`sans_io::MAX_LEN`]: sans_io/constant.MAX_LEN.html
+/// [`FixedDecoder::new`]: sans_io/struct.FixedDecoder.html#method.new
+/// [`FixedEncoder::new`]: sans_io/struct.FixedEncoder.html#method.new
+pub trait Primitive: Sized {
+    /// Creates a decoder for values of this type in the byte order `T`.
+    fn decoder<T: ByteOrder>() -> FixedDecoder<Self>;
+
+    /// Creates an encoder for this value in the byte order `T`.
+    fn encoder<T: ByteOrder>(self) -> FixedEncoder;
+}
+
+impl Primitive for u8 {
+    fn decoder<T: ByteOrder>() -> FixedDecoder<Self> {
+        FixedDecoder::u8()
+    }
+
+    fn encoder<T: ByteOrder>(self) -> FixedEncoder {
+        FixedEncoder::u8(self)
+    }
+}
+
+impl Primitive for i8 {
+    fn decoder<T: ByteOrder>() -> FixedDecoder<Self> {
+        FixedDecoder::i8()
+    }
+
+    fn encoder<T: ByteOrder>(self) -> FixedEncoder {
+        FixedEncoder::i8(self)
+    }
+}
+
+macro_rules! primitive_impl {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl Primitive for $ty {
+                fn decoder<T: ByteOrder>() -> FixedDecoder<Self> {
+                    FixedDecoder::$ty::<T>()
+                }
+
+                fn encoder<T: ByteOrder>(self) -> FixedEncoder {
+                    FixedEncoder::$ty::<T>(self)
+                }
+            }
+        )*
+    };
+}
+
+primitive_impl!(u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
@@ -0,0 +1,146 @@
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+/// A reader that remembers what was read through it, so that it can be read again.
+///
+/// A freshly created `Probe` records every byte read through it. Once enough has been read to
+/// tell what kind of stream it is, [`rewind`] stops the recording and puts the recorded bytes
+/// back in front of the rest of the stream, so that the parser that is picked gets to see the
+/// stream from the start. [`commit`] instead stops the recording and lets go of the bytes, for
+/// when they were only a header that the parser does not need.
+///
+/// This is how a format is told apart by its magic bytes, such as gzip from plain data or one
+/// version of a protocol header from another, when the stream cannot seek. Only the bytes that
+/// are read while recording are held on to, so probing a few bytes of a long stream costs no
+/// more than those bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio::io::AsyncReadExt;
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, Probe};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = Probe::new(&b"\x1f\x8b\x08\x00"[..]);
+///     let gzip = AsyncReadBytesExt::read_u16::<BigEndian>(&mut rdr).await.unwrap() == 0x1f8b;
+///     assert!(gzip);
+///     rdr.rewind();
+///
+///     // the decoder sees the whole stream
+///     let mut all = Vec::new();
+///     rdr.read_to_end(&mut all).await.unwrap();
+///     assert_eq!(all, b"\x1f\x8b\x08\x00");
+/// }
+/// ```
+///
+/// [`rewind`]: #method.rewind
+/// [`commit`]: #method.commit
+#[derive(Debug)]
+pub struct Probe<R> {
+    inner: R,
+    // bytes that were read while recording; those before `pos` have been read (again)
+    buf: Vec<u8>,
+    pos: usize,
+    recording: bool,
+}
+
+impl<R> Probe<R> {
+    /// Wraps the given reader, and starts recording.
+    pub fn new(inner: R) -> Self {
+        Probe {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            recording: true,
+        }
+    }
+
+    /// Returns true until the probe is rewound or committed.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Returns the bytes that were read while recording.
+    ///
+    /// Once the probe has been rewound, these are the bytes that are yet to be read again.
+    pub fn probed(&self) -> &[u8] {
+        if self.recording {
+            &self.buf
+        } else {
+            &self.buf[self.pos..]
+        }
+    }
+
+    /// Stops recording, and makes the next reads return the recorded bytes again before
+    /// carrying on with the rest of the stream.
+    ///
+    /// Rewinding again afterwards does nothing; bytes that were read after the first rewind are
+    /// not recorded.
+    pub fn rewind(&mut self) {
+        if self.recording {
+            self.recording = false;
+            self.pos = 0;
+        }
+    }
+
+    /// Stops recording, and drops the recorded bytes that have been read.
+    pub fn commit(&mut self) {
+        self.recording = false;
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly through the returned reference skip ahead of any recorded bytes that
+    /// have not been read again, and are not recorded.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Probe`, returning the underlying reader.
+    ///
+    /// Any recorded bytes that have not been read again are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> io::AsyncRead for Probe<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.recording && this.pos < this.buf.len() {
+            let replay = &this.buf[this.pos..];
+            let n = replay.len().min(buf.remaining());
+            buf.put_slice(&replay[..n]);
+            this.pos += n;
+            if this.pos == this.buf.len() {
+                this.buf = Vec::new();
+                this.pos = 0;
+            }
+            return Poll::Ready(Ok(()));
+        }
+
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        if this.recording {
+            this.buf.extend_from_slice(&buf.filled()[before..]);
+            this.pos = this.buf.len();
+        }
+        Poll::Ready(Ok(()))
+    }
+}
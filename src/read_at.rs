@@ -0,0 +1,172 @@
+use crate::sans_io::FixedDecoder;
+use crate::*;
+use core::task::ready;
+use std::io::SeekFrom;
+
+macro_rules! read_at_impl {
+    ($(($name:ident, $decoder:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Reads ", $desc, " at byte offset `pos`.")]
+            ///
+            /// # Errors
+            ///
+            /// This method returns the same errors as [`read_u8_at`](#method.read_u8_at).
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self, pos: u64) -> ReadAt<&mut Self, $ty>
+            where
+                Self: Unpin,
+            {
+                ReadAt::new(self, pos, FixedDecoder::$decoder::<T>())
+            }
+        )*
+    };
+}
+
+/// Extends [`AsyncRead`] + [`AsyncSeek`] with methods for reading numbers at a given offset.
+///
+/// Each method seeks to an absolute position and then reads a single value there, as one
+/// future. This suits formats that are navigated by offset, like file footers, page tables, and
+/// on-disk tree nodes, where every read would otherwise be preceded by a separate seek. The
+/// stream is left positioned just past the value that was read.
+///
+/// # Examples
+///
+/// Read the offset of an index from the last eight bytes of a file, and then the first entry
+/// of the index:
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use tokio_byteorder::{AsyncReadAtBytesExt, BigEndian};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut file = Cursor::new(vec![0, 0, 0x01, 0x0b, 0, 0, 0, 0, 0, 0, 0, 2]);
+///     let len = file.get_ref().len() as u64;
+///     let index = file.read_u64_at::<BigEndian>(len - 8).await.unwrap();
+///     assert_eq!(267, file.read_u16_at::<BigEndian>(index).await.unwrap());
+/// }
+/// ```
+///
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+/// [`AsyncSeek`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncSeek.html
+pub trait AsyncReadAtBytesExt: io::AsyncRead + io::AsyncSeek {
+    /// Reads an unsigned 8 bit integer at byte offset `pos`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Seek::seek`] and [`Read::read_exact`].
+    ///
+    /// [`Seek::seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html#tymethod.seek
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    #[inline]
+    fn read_u8_at(&mut self, pos: u64) -> ReadAt<&mut Self, u8>
+    where
+        Self: Unpin,
+    {
+        ReadAt::new(self, pos, FixedDecoder::u8())
+    }
+
+    /// Reads a signed 8 bit integer at byte offset `pos`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_u8_at`](#method.read_u8_at).
+    #[inline]
+    fn read_i8_at(&mut self, pos: u64) -> ReadAt<&mut Self, i8>
+    where
+        Self: Unpin,
+    {
+        ReadAt::new(self, pos, FixedDecoder::i8())
+    }
+
+    read_at_impl! {
+        (read_u16_at, u16, u16, "an unsigned 16 bit integer"),
+        (read_i16_at, i16, i16, "a signed 16 bit integer"),
+        (read_u24_at, u24, u32, "an unsigned 24 bit integer"),
+        (read_i24_at, i24, i32, "a signed 24 bit integer"),
+        (read_u32_at, u32, u32, "an unsigned 32 bit integer"),
+        (read_i32_at, i32, i32, "a signed 32 bit integer"),
+        (read_u48_at, u48, u64, "an unsigned 48 bit integer"),
+        (read_i48_at, i48, i64, "a signed 48 bit integer"),
+        (read_u64_at, u64, u64, "an unsigned 64 bit integer"),
+        (read_i64_at, i64, i64, "a signed 64 bit integer"),
+        (read_u128_at, u128, u128, "an unsigned 128 bit integer"),
+        (read_i128_at, i128, i128, "a signed 128 bit integer"),
+        (read_f32_at, f32, f32, "a IEEE754 single-precision floating point number"),
+        (read_f64_at, f64, f64, "a IEEE754 double-precision floating point number"),
+    }
+
+    /// Reads a number of any [`Primitive`] type at byte offset `pos`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`read_u8_at`](#method.read_u8_at).
+    ///
+    /// [`Primitive`]: trait.Primitive.html
+    #[inline]
+    fn read_primitive_at<V: Primitive, T: ByteOrder>(&mut self, pos: u64) -> ReadAt<&mut Self, V>
+    where
+        Self: Unpin,
+    {
+        ReadAt::new(self, pos, V::decoder::<T>())
+    }
+}
+
+/// All types that implement `AsyncRead` and `AsyncSeek` get methods defined in
+/// `AsyncReadAtBytesExt` for free.
+impl<R: io::AsyncRead + io::AsyncSeek + ?Sized> AsyncReadAtBytesExt for R {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    Seeking,
+    Reading,
+}
+
+/// Future returned by the methods of
+/// [`AsyncReadAtBytesExt`](../trait.AsyncReadAtBytesExt.html).
+#[derive(Debug)]
+pub struct ReadAt<R, V> {
+    src: R,
+    pos: u64,
+    state: State,
+    decoder: FixedDecoder<V>,
+}
+
+impl<R, V> ReadAt<R, V> {
+    pub(crate) fn new(src: R, pos: u64, decoder: FixedDecoder<V>) -> Self {
+        ReadAt {
+            src,
+            pos,
+            state: State::Start,
+            decoder,
+        }
+    }
+}
+
+impl<R, V> Future for ReadAt<R, V>
+where
+    R: io::AsyncRead + io::AsyncSeek + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match this.state {
+                State::Start => {
+                    // let any seek that is still in flight finish before starting ours
+                    ready!(Pin::new(&mut this.src).poll_complete(cx))?;
+                    Pin::new(&mut this.src).start_seek(SeekFrom::Start(this.pos))?;
+                    this.state = State::Seeking;
+                }
+                State::Seeking => {
+                    ready!(Pin::new(&mut this.src).poll_complete(cx))?;
+                    this.state = State::Reading;
+                }
+                State::Reading => {
+                    return poll_decode(Pin::new(&mut this.src), cx, &mut this.decoder);
+                }
+            }
+        }
+    }
+}
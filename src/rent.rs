@@ -0,0 +1,222 @@
+use crate::sans_io::{FixedDecoder, FixedEncoder};
+use byteorder::ByteOrder;
+use monoio::buf::{IoBuf, IoBufMut};
+use monoio::io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRent, AsyncWriteRentExt};
+use tokio::io;
+
+type Scratch = Box<[u8; 16]>;
+
+/// A reader for completion-based runtimes like [`monoio`], with all of this crate's decoding
+/// methods built in.
+///
+/// Completion-based I/O hands buffers over to the kernel for the duration of an operation, so
+/// [`AsyncReadRent`] takes buffers by value rather than as `&mut [u8]`. `RentReader` owns a small
+/// scratch buffer that it lends out for every value it reads, and decodes each value once the
+/// buffer comes back. If one of its futures is dropped mid-read the scratch buffer goes with it
+/// and a new one is allocated for the next read.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, RentReader};
+///
+/// #[monoio::main(driver = "legacy")]
+/// async fn main() {
+///     let mut rdr = RentReader::new(&[0x01, 0x00, 0x00, 0x01, 0x0b][..]);
+///     assert_eq!(1, rdr.read_u8().await.unwrap());
+///     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+/// }
+/// ```
+///
+/// [`monoio`]: https://docs.rs/monoio/0.2/monoio/
+/// [`AsyncReadRent`]: https://docs.rs/monoio/0.2/monoio/io/trait.AsyncReadRent.html
+#[derive(Debug)]
+pub struct RentReader<R> {
+    inner: R,
+    scratch: Option<Scratch>,
+}
+
+/// A writer for completion-based runtimes like [`monoio`], with all of this crate's encoding
+/// methods built in.
+///
+/// This is the write-side counterpart of [`RentReader`]: each value is encoded into an owned
+/// scratch buffer that is handed to [`AsyncWriteRent`] for the duration of the write.
+///
+/// # Examples
+///
+/// ```rust
+/// use monoio::net::UnixStream;
+/// use tokio_byteorder::{LittleEndian, RentReader, RentWriter};
+///
+/// #[monoio::main(driver = "legacy")]
+/// async fn main() {
+///     let (tx, rx) = UnixStream::pair().unwrap();
+///     let mut wtr = RentWriter::new(tx);
+///     wtr.write_u16::<LittleEndian>(517).await.unwrap();
+///     wtr.write_i8(-1).await.unwrap();
+///
+///     let mut rdr = RentReader::new(rx);
+///     assert_eq!(517, rdr.read_u16::<LittleEndian>().await.unwrap());
+///     assert_eq!(-1, rdr.read_i8().await.unwrap());
+/// }
+/// ```
+///
+/// [`monoio`]: https://docs.rs/monoio/0.2/monoio/
+/// [`RentReader`]: struct.RentReader.html
+/// [`AsyncWriteRent`]: https://docs.rs/monoio/0.2/monoio/io/trait.AsyncWriteRent.html
+#[derive(Debug)]
+pub struct RentWriter<W> {
+    inner: W,
+    scratch: Option<Scratch>,
+}
+
+macro_rules! rent_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner> $name<$inner> {
+            /// Wraps the given stream.
+            pub fn new(inner: $inner) -> Self {
+                $name {
+                    inner,
+                    scratch: None,
+                }
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Unwraps this value, returning the underlying stream.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+        }
+    };
+}
+
+rent_common!(RentReader, R);
+rent_common!(RentWriter, W);
+
+impl<R: AsyncReadRent> RentReader<R> {
+    async fn decode<V>(&mut self, mut decoder: FixedDecoder<V>) -> io::Result<V> {
+        let len = decoder.remaining();
+        let scratch = self.scratch.take().unwrap_or_default();
+        let (res, scratch) = self.inner.read_exact(scratch.slice_mut(..len)).await;
+        let scratch = scratch.into_inner();
+        let res = res.map(|_| {
+            let v = decoder.push_bytes(&mut &scratch[..len]);
+            v.expect("read_exact filled the value")
+        });
+        self.scratch = Some(scratch);
+        res
+    }
+
+    /// Reads an unsigned 8 bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    pub async fn read_u8(&mut self) -> io::Result<u8> {
+        self.decode(FixedDecoder::u8()).await
+    }
+
+    /// Reads a signed 8 bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Read::read_exact`].
+    ///
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    pub async fn read_i8(&mut self) -> io::Result<i8> {
+        self.decode(FixedDecoder::i8()).await
+    }
+}
+
+impl<W: AsyncWriteRent> RentWriter<W> {
+    async fn encode(&mut self, encoder: FixedEncoder) -> io::Result<()> {
+        let bytes = encoder.pending();
+        let len = bytes.len();
+        let mut scratch = self.scratch.take().unwrap_or_default();
+        scratch[..len].copy_from_slice(bytes);
+        let (res, scratch) = self.inner.write_all(scratch.slice(..len)).await;
+        self.scratch = Some(scratch.into_inner());
+        res.map(|_| ())
+    }
+
+    /// Writes an unsigned 8 bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_u8(&mut self, n: u8) -> io::Result<()> {
+        self.encode(FixedEncoder::u8(n)).await
+    }
+
+    /// Writes a signed 8 bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub async fn write_i8(&mut self, n: i8) -> io::Result<()> {
+        self.encode(FixedEncoder::i8(n)).await
+    }
+
+    /// Flushes the underlying writer.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+}
+
+macro_rules! rent_impl {
+    ($(($read:ident, $write:ident, $codec:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        impl<R: AsyncReadRent> RentReader<R> {
+            $(
+                #[doc = concat!("Reads ", $desc, ".")]
+                ///
+                /// See [`read_u8`](#method.read_u8).
+                pub async fn $read<T: ByteOrder>(&mut self) -> io::Result<$ty> {
+                    self.decode(FixedDecoder::$codec::<T>()).await
+                }
+            )*
+        }
+
+        impl<W: AsyncWriteRent> RentWriter<W> {
+            $(
+                #[doc = concat!("Writes ", $desc, ".")]
+                ///
+                /// See [`write_u8`](#method.write_u8).
+                pub async fn $write<T: ByteOrder>(&mut self, n: $ty) -> io::Result<()> {
+                    self.encode(FixedEncoder::$codec::<T>(n)).await
+                }
+            )*
+        }
+    };
+}
+
+rent_impl! {
+    (read_u16, write_u16, u16, u16, "an unsigned 16 bit integer"),
+    (read_i16, write_i16, i16, i16, "a signed 16 bit integer"),
+    (read_u24, write_u24, u24, u32, "an unsigned 24 bit integer"),
+    (read_i24, write_i24, i24, i32, "a signed 24 bit integer"),
+    (read_u32, write_u32, u32, u32, "an unsigned 32 bit integer"),
+    (read_i32, write_i32, i32, i32, "a signed 32 bit integer"),
+    (read_u48, write_u48, u48, u64, "an unsigned 48 bit integer"),
+    (read_i48, write_i48, i48, i64, "a signed 48 bit integer"),
+    (read_u64, write_u64, u64, u64, "an unsigned 64 bit integer"),
+    (read_i64, write_i64, i64, i64, "a signed 64 bit integer"),
+    (read_u128, write_u128, u128, u128, "an unsigned 128 bit integer"),
+    (read_i128, write_i128, i128, i128, "a signed 128 bit integer"),
+    (read_f32, write_f32, f32, f32, "a IEEE754 single-precision floating point number"),
+    (read_f64, write_f64, f64, f64, "a IEEE754 double-precision floating point number"),
+}
@@ -0,0 +1,201 @@
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+const FIXED_LEN: usize = 12;
+const MAX_LEN: usize = FIXED_LEN + 15 * 4;
+
+/// The fixed header of an RTP packet, with the list of contributing sources that follows it
+/// (RFC 3550 §5.1).
+///
+/// Read from a stream with [`read_rtp_header`], or from the front of a buffer with
+/// [`from_slice`]. If [`extension`] is set, a header extension follows, which is left for the
+/// caller to read.
+///
+/// [`read_rtp_header`]: trait.AsyncReadBytesExt.html#method.read_rtp_header
+/// [`from_slice`]: #method.from_slice
+/// [`extension`]: #structfield.extension
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RtpHeader {
+    /// Whether the payload is followed by padding, whose length is its last byte.
+    pub padding: bool,
+    /// Whether the header is followed by a header extension.
+    pub extension: bool,
+    /// The marker bit, whose meaning is defined by the profile.
+    pub marker: bool,
+    /// The type of the payload, from 0 to 127.
+    pub payload_type: u8,
+    /// The sequence number, which increments by one with every packet.
+    pub sequence_number: u16,
+    /// The sampling instant of the first byte of the payload.
+    pub timestamp: u32,
+    /// The synchronization source.
+    pub ssrc: u32,
+    /// The contributing sources, at most 15 of them.
+    pub csrcs: Vec<u32>,
+}
+
+impl RtpHeader {
+    /// Parses the header at the front of `input`, and returns it along with the bytes that
+    /// follow it.
+    ///
+    /// This is for packets that are already in memory, such as UDP datagrams, or the buffer
+    /// of a reader that was peeked at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind `UnexpectedEof` if `input` is too short to hold the header
+    /// and its contributing sources, and an error of the kind `InvalidData` if the version is
+    /// not 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::RtpHeader;
+    ///
+    /// let packet = [
+    ///     0x80, 0xe0, 0x00, 0x2a, 0x00, 0x00, 0x03, 0xe8, 0x12, 0x34, 0x56, 0x78, 0xff,
+    /// ];
+    /// let (header, payload) = RtpHeader::from_slice(&packet).unwrap();
+    /// assert!(header.marker);
+    /// assert_eq!(header.payload_type, 96);
+    /// assert_eq!(header.sequence_number, 42);
+    /// assert_eq!(header.timestamp, 1000);
+    /// assert_eq!(header.ssrc, 0x1234_5678);
+    /// assert_eq!(payload, [0xff]);
+    /// ```
+    pub fn from_slice(input: &[u8]) -> io::Result<(Self, &[u8])> {
+        let len = header_len(input)?;
+        if input.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "rtp header is cut short",
+            ));
+        }
+        Ok((decode(&input[..len]), &input[len..]))
+    }
+}
+
+/// Returns the length of the header that starts with `input`, including its contributing
+/// sources, once the first byte is in.
+fn header_len(input: &[u8]) -> io::Result<usize> {
+    let first = match input.first() {
+        Some(&first) => first,
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "rtp header is cut short",
+            ))
+        }
+    };
+    if first >> 6 != 2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "rtp version is not 2",
+        ));
+    }
+    Ok(FIXED_LEN + usize::from(first & 0x0f) * 4)
+}
+
+fn decode(buf: &[u8]) -> RtpHeader {
+    RtpHeader {
+        padding: buf[0] & 0x20 != 0,
+        extension: buf[0] & 0x10 != 0,
+        marker: buf[1] & 0x80 != 0,
+        payload_type: buf[1] & 0x7f,
+        sequence_number: BigEndian::read_u16(&buf[2..]),
+        timestamp: BigEndian::read_u32(&buf[4..]),
+        ssrc: BigEndian::read_u32(&buf[8..]),
+        csrcs: buf[FIXED_LEN..]
+            .chunks_exact(4)
+            .map(BigEndian::read_u32)
+            .collect(),
+    }
+}
+
+/// Future returned by
+/// [`read_rtp_header`](../trait.AsyncReadBytesExt.html#method.read_rtp_header).
+#[derive(Debug)]
+pub struct ReadRtpHeader<R> {
+    src: R,
+    buf: [u8; MAX_LEN],
+    filled: usize,
+}
+
+impl<R> ReadRtpHeader<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadRtpHeader {
+            src,
+            buf: [0; MAX_LEN],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadRtpHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<RtpHeader>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < FIXED_LEN {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf[..FIXED_LEN],
+                &mut this.filled
+            ))?;
+        }
+        let len = header_len(&this.buf)?;
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..len],
+            &mut this.filled
+        ))?;
+        Poll::Ready(Ok(decode(&this.buf[..len])))
+    }
+}
+
+/// Future returned by
+/// [`read_rtsp_interleaved_header`](../trait.AsyncReadBytesExt.html#method.read_rtsp_interleaved_header).
+#[derive(Debug)]
+pub struct ReadRtspInterleavedHeader<R> {
+    src: R,
+    buf: [u8; 4],
+    filled: usize,
+}
+
+impl<R> ReadRtspInterleavedHeader<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadRtspInterleavedHeader {
+            src,
+            buf: [0; 4],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadRtspInterleavedHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<(u8, u16)>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            &mut this.filled
+        ))?;
+        if this.buf[0] != b'$' {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "rtsp interleaved frame does not start with '$'",
+            )));
+        }
+        Poll::Ready(Ok((this.buf[1], BigEndian::read_u16(&this.buf[2..]))))
+    }
+}
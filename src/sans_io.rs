@@ -0,0 +1,297 @@
+//! Encoders and decoders that do not perform any I/O themselves.
+//!
+//! The types in this module are plain state machines: a [`FixedDecoder`] is fed bytes as they
+//! become available and hands back the value once it is complete, and a [`FixedEncoder`] hands
+//! out the bytes of an encoded value in whatever chunk sizes the output can take. They are what
+//! the futures in the rest of this crate are built on, so code running on completion-based
+//! runtimes, custom event loops, or synchronous code can reuse the exact same logic.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tokio_byteorder::sans_io::{FixedDecoder, FixedEncoder};
+//! use tokio_byteorder::BigEndian;
+//!
+//! let mut encoder = FixedEncoder::u32::<BigEndian>(267);
+//! let mut out = [0; 3];
+//! assert_eq!(encoder.pull_bytes(&mut out), 3);
+//! assert_eq!(encoder.pending(), [0x0b]);
+//!
+//! let mut decoder = FixedDecoder::u32::<BigEndian>();
+//! let mut input = &out[..];
+//! assert_eq!(decoder.push_bytes(&mut input), None);
+//! let mut input = &[0x0b, 0xff][..];
+//! assert_eq!(decoder.push_bytes(&mut input), Some(267));
+//! // bytes past the end of the value are left in the input
+//! assert_eq!(input, [0xff]);
+//! ```
+
+use byteorder::ByteOrder;
+
+/// The largest encoded length, in bytes, of a value handled by [`FixedDecoder`] and
+/// [`FixedEncoder`].
+pub const MAX_LEN: usize = 16;
+
+/// A decoder for a single fixed-width value.
+///
+/// Once a value has been returned, the decoder starts over with the next one.
+#[derive(Debug, Clone)]
+pub struct FixedDecoder<V> {
+    buf: [u8; MAX_LEN],
+    len: usize,
+    filled: usize,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<V> FixedDecoder<V> {
+    /// Creates a decoder for values that are encoded as `len` bytes.
+    ///
+    /// Once all `len` bytes of a value have been pushed, they are passed to `decode`. This is
+    /// how types defined outside this crate implement [`Primitive`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than [`MAX_LEN`].
+    ///
+    /// [`Primitive`]: ../trait.Primitive.html
+    pub fn new(len: usize, decode: fn(&[u8]) -> V) -> Self {
+        assert!(
+            len <= MAX_LEN,
+            "values can be at most {} bytes long",
+            MAX_LEN
+        );
+        FixedDecoder {
+            buf: [0; MAX_LEN],
+            len,
+            filled: 0,
+            decode,
+        }
+    }
+
+    /// Feeds bytes from the front of `input` into the decoder.
+    ///
+    /// Only as many bytes as the current value still needs are taken, and `input` is advanced
+    /// past them. Returns the value once all of its bytes have been pushed.
+    pub fn push_bytes(&mut self, input: &mut &[u8]) -> Option<V> {
+        let n = core::cmp::min(self.remaining(), input.len());
+        self.buf[self.filled..self.filled + n].copy_from_slice(&input[..n]);
+        self.filled += n;
+        *input = &input[n..];
+        if self.filled < self.len {
+            return None;
+        }
+        self.filled = 0;
+        Some((self.decode)(&self.buf[..self.len]))
+    }
+
+    /// Returns the number of bytes still needed to complete the current value.
+    pub fn remaining(&self) -> usize {
+        self.len - self.filled
+    }
+
+    /// Returns the bytes of the current value that have been pushed so far.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    /// Returns the bytes of the value that was returned last.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn last(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Discards any partially decoded value.
+    pub fn reset(&mut self) {
+        self.filled = 0;
+    }
+}
+
+impl FixedDecoder<u8> {
+    /// Creates a decoder for unsigned 8 bit integers.
+    pub fn u8() -> Self {
+        Self::new(1, |buf| buf[0])
+    }
+}
+
+impl FixedDecoder<i8> {
+    /// Creates a decoder for signed 8 bit integers.
+    pub fn i8() -> Self {
+        Self::new(1, |buf| buf[0] as i8)
+    }
+}
+
+/// An encoder for a single fixed-width value.
+#[derive(Debug, Clone)]
+pub struct FixedEncoder {
+    buf: [u8; MAX_LEN],
+    len: usize,
+    written: usize,
+}
+
+impl FixedEncoder {
+    /// Creates an encoder for a value that is encoded as `len` bytes.
+    ///
+    /// `encode` is called right away to write the value into a buffer of exactly `len` bytes.
+    /// This is how types defined outside this crate implement [`Primitive`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than [`MAX_LEN`].
+    ///
+    /// [`Primitive`]: ../trait.Primitive.html
+    pub fn new(len: usize, encode: impl FnOnce(&mut [u8])) -> Self {
+        assert!(
+            len <= MAX_LEN,
+            "values can be at most {} bytes long",
+            MAX_LEN
+        );
+        let mut buf = [0; MAX_LEN];
+        encode(&mut buf[..len]);
+        FixedEncoder {
+            buf,
+            len,
+            written: 0,
+        }
+    }
+
+    /// Creates an encoder for an unsigned 8 bit integer.
+    pub fn u8(n: u8) -> Self {
+        Self::new(1, |buf| buf[0] = n)
+    }
+
+    /// Creates an encoder for a signed 8 bit integer.
+    pub fn i8(n: i8) -> Self {
+        Self::new(1, |buf| buf[0] = n as u8)
+    }
+
+    /// Returns all of the encoded bytes, including those that have been taken.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn encoded(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the encoded bytes that have not been taken yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buf[self.written..self.len]
+    }
+
+    /// Marks the first `n` pending bytes as taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than the number of pending bytes.
+    pub fn advance(&mut self, n: usize) {
+        assert!(n <= self.len - self.written, "advanced past the end");
+        self.written += n;
+    }
+
+    /// Copies as many pending bytes as fit into `out`, and returns how many were copied.
+    pub fn pull_bytes(&mut self, out: &mut [u8]) -> usize {
+        let n = core::cmp::min(out.len(), self.len - self.written);
+        out[..n].copy_from_slice(&self.pending()[..n]);
+        self.written += n;
+        n
+    }
+
+    /// Returns true once every encoded byte has been taken.
+    pub fn is_done(&self) -> bool {
+        self.written == self.len
+    }
+}
+
+macro_rules! fixed_impl {
+    ($(($name:ident, $read:ident, $write:ident, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        $(
+            impl FixedDecoder<$ty> {
+                #[doc = concat!("Creates a decoder for ", $desc, "s in the byte order `T`.")]
+                pub fn $name<T: ByteOrder>() -> Self {
+                    Self::new($bytes, T::$read)
+                }
+            }
+
+            impl FixedEncoder {
+                #[doc = concat!("Creates an encoder for the ", $desc, " `n` in the byte order `T`.")]
+                pub fn $name<T: ByteOrder>(n: $ty) -> Self {
+                    Self::new($bytes, |buf| T::$write(buf, n))
+                }
+            }
+        )*
+    };
+}
+
+fixed_impl! {
+    (u16, read_u16, write_u16, u16, 2, "unsigned 16 bit integer"),
+    (i16, read_i16, write_i16, i16, 2, "signed 16 bit integer"),
+    (u24, read_u24, write_u24, u32, 3, "unsigned 24 bit integer"),
+    (i24, read_i24, write_i24, i32, 3, "signed 24 bit integer"),
+    (u32, read_u32, write_u32, u32, 4, "unsigned 32 bit integer"),
+    (i32, read_i32, write_i32, i32, 4, "signed 32 bit integer"),
+    (u48, read_u48, write_u48, u64, 6, "unsigned 48 bit integer"),
+    (i48, read_i48, write_i48, i64, 6, "signed 48 bit integer"),
+    (u64, read_u64, write_u64, u64, 8, "unsigned 64 bit integer"),
+    (i64, read_i64, write_i64, i64, 8, "signed 64 bit integer"),
+    (u128, read_u128, write_u128, u128, 16, "unsigned 128 bit integer"),
+    (i128, read_i128, write_i128, i128, 16, "signed 128 bit integer"),
+    (f32, read_f32, write_f32, f32, 4, "IEEE754 single-precision floating point number"),
+    (f64, read_f64, write_f64, f64, 8, "IEEE754 double-precision floating point number"),
+}
+
+/// The bit pattern that NaNs are replaced with by [`FixedDecoder::f32_canonical`] and
+/// [`FixedEncoder::f32_canonical`]: the quiet NaN with an empty payload.
+pub const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+
+/// The bit pattern that NaNs are replaced with by [`FixedDecoder::f64_canonical`] and
+/// [`FixedEncoder::f64_canonical`]: the quiet NaN with an empty payload.
+pub const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+fn canonical_f32(n: f32) -> f32 {
+    if n.is_nan() {
+        f32::from_bits(CANONICAL_NAN_F32)
+    } else {
+        n
+    }
+}
+
+fn canonical_f64(n: f64) -> f64 {
+    if n.is_nan() {
+        f64::from_bits(CANONICAL_NAN_F64)
+    } else {
+        n
+    }
+}
+
+impl FixedDecoder<f32> {
+    /// Creates a decoder for IEEE754 single-precision floating point numbers in the byte order
+    /// `T` that replaces every NaN with [`CANONICAL_NAN_F32`].
+    ///
+    /// NaNs can carry a sign and a payload, and those bits differ between platforms and between
+    /// the operations that produced them. Canonicalizing them makes equal-looking streams
+    /// bit-for-bit equal, which hashing and replay depend on.
+    pub fn f32_canonical<T: ByteOrder>() -> Self {
+        Self::new(4, |buf| canonical_f32(T::read_f32(buf)))
+    }
+}
+
+impl FixedDecoder<f64> {
+    /// Creates a decoder for IEEE754 double-precision floating point numbers in the byte order
+    /// `T` that replaces every NaN with [`CANONICAL_NAN_F64`].
+    ///
+    /// See [`f32_canonical`](#method.f32_canonical) for why.
+    pub fn f64_canonical<T: ByteOrder>() -> Self {
+        Self::new(8, |buf| canonical_f64(T::read_f64(buf)))
+    }
+}
+
+impl FixedEncoder {
+    /// Creates an encoder for the IEEE754 single-precision floating point number `n` in the
+    /// byte order `T`, writing [`CANONICAL_NAN_F32`] in place of any NaN.
+    pub fn f32_canonical<T: ByteOrder>(n: f32) -> Self {
+        Self::f32::<T>(canonical_f32(n))
+    }
+
+    /// Creates an encoder for the IEEE754 double-precision floating point number `n` in the
+    /// byte order `T`, writing [`CANONICAL_NAN_F64`] in place of any NaN.
+    pub fn f64_canonical<T: ByteOrder>(n: f64) -> Self {
+        Self::f64::<T>(canonical_f64(n))
+    }
+}
@@ -0,0 +1,799 @@
+//! A compact binary [serde] data format that reads from and writes to async streams.
+//!
+//! The format is in the style of `bincode`: numbers are written at their full fixed width in a
+//! configurable byte order, and nothing else is written that the type does not need. Strings,
+//! byte strings, sequences, and maps are prefixed with their length, and the width of that
+//! prefix is configurable as well. Specifically:
+//!
+//!  - `bool` is a single byte, `0` or `1`, and `char` is its `u32` code point;
+//!  - `Option` is a `0` byte for `None`, or a `1` byte followed by the value;
+//!  - unit types and unit structs take no space, and newtype structs are their inner value;
+//!  - tuples and structs are their fields in order, with no prefix;
+//!  - enum variants are their index as a `u32`, followed by their fields.
+//!
+//! Since the format does not describe itself, it cannot be used with types whose
+//! [`Deserialize`] implementation relies on `deserialize_any`, such as untagged enums.
+//!
+//! serde's traits are synchronous, so [`to_writer`] encodes the whole value into memory before
+//! writing it out, and [`from_reader`] decodes from the read buffer of an [`AsyncBufRead`] and
+//! retries as more bytes arrive. Either way, only the bytes of the value itself are written or
+//! consumed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use tokio::io::BufReader;
+//! use tokio_byteorder::serde::{from_reader, to_writer, Config, LenWidth};
+//! use tokio_byteorder::RuntimeEndian;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Login {
+//!     user: String,
+//!     token: Option<u64>,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let config = Config::new()
+//!         .endian(RuntimeEndian::Little)
+//!         .len_width(LenWidth::U8);
+//!     let login = Login { user: "jon".into(), token: None };
+//!
+//!     let mut buf = Vec::new();
+//!     to_writer(&mut buf, &login, config).await.unwrap();
+//!     assert_eq!(buf, b"\x03jon\x00");
+//!
+//!     let mut rdr = BufReader::new(&buf[..]);
+//!     let back: Login = from_reader(&mut rdr, config).await.unwrap();
+//!     assert_eq!(back, login);
+//! }
+//! ```
+//!
+//! [serde]: https://serde.rs/
+//! [`Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html
+//! [`to_writer`]: fn.to_writer.html
+//! [`from_reader`]: fn.from_reader.html
+//! [`AsyncBufRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html
+
+use crate::endian::RuntimeCodec;
+use crate::RuntimeEndian;
+use ::serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::ser::{self, Serialize};
+use core::convert::TryFrom;
+use core::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt};
+
+/// The width of the length prefix of strings, byte strings, sequences, and maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LenWidth {
+    /// An unsigned 8 bit prefix.
+    U8,
+    /// An unsigned 16 bit prefix.
+    U16,
+    /// An unsigned 32 bit prefix.
+    U32,
+    /// An unsigned 64 bit prefix.
+    U64,
+}
+
+/// The options of the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config {
+    endian: RuntimeEndian,
+    len: LenWidth,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            endian: RuntimeEndian::Big,
+            len: LenWidth::U64,
+        }
+    }
+}
+
+impl Config {
+    /// Creates the default configuration: big-endian, with 64 bit length prefixes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the byte order of every number, including length prefixes and enum variant indices.
+    pub fn endian(mut self, endian: RuntimeEndian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Sets the width of length prefixes.
+    pub fn len_width(mut self, len: LenWidth) -> Self {
+        self.len = len;
+        self
+    }
+}
+
+/// An error from serializing or deserializing a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input ended in the middle of a value.
+    Eof,
+    /// A sequence or map was serialized without knowing its length up front.
+    LengthRequired,
+    /// A length does not fit in the configured length prefix, or in a `usize`.
+    LengthOverflow,
+    /// A `bool` or `Option` tag byte was neither `0` nor `1`.
+    InvalidTag(u8),
+    /// A `char` was not a valid Unicode scalar value.
+    InvalidChar(u32),
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+    /// The type being deserialized needs a self-describing format.
+    AnyNotSupported,
+    /// An error reported by a `Serialize` or `Deserialize` implementation.
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Eof => f.write_str("unexpected end of input"),
+            Error::LengthRequired => f.write_str("sequence or map length must be known up front"),
+            Error::LengthOverflow => f.write_str("length does not fit in its prefix"),
+            Error::InvalidTag(b) => write!(f, "invalid tag byte {}", b),
+            Error::InvalidChar(c) => write!(f, "invalid char {:#x}", c),
+            Error::InvalidUtf8 => f.write_str("string is not valid UTF-8"),
+            Error::AnyNotSupported => f.write_str("the format is not self-describing"),
+            Error::Message(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::Eof => io::ErrorKind::UnexpectedEof,
+            Error::LengthRequired | Error::LengthOverflow => io::ErrorKind::InvalidInput,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// Serializes `value` and appends it to `out`.
+pub fn to_vec<T>(out: &mut Vec<u8>, value: &T, config: Config) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(&mut Serializer::new(out, config))
+}
+
+/// Deserializes a value from the front of `input`, and returns it along with the bytes that
+/// follow it.
+pub fn from_slice<'de, T>(input: &'de [u8], config: Config) -> Result<(T, &'de [u8]), Error>
+where
+    T: de::Deserialize<'de>,
+{
+    let mut de = Deserializer::new(input, config);
+    let v = T::deserialize(&mut de)?;
+    Ok((v, de.input))
+}
+
+/// Serializes `value` and writes it to `dst`.
+///
+/// # Errors
+///
+/// This function returns the same errors as [`Write::write_all`], and an [`Error`] wrapped in
+/// an `io::Error` if `value` cannot be serialized.
+///
+/// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+/// [`Error`]: enum.Error.html
+pub async fn to_writer<W, T>(dst: &mut W, value: &T, config: Config) -> io::Result<()>
+where
+    W: io::AsyncWrite + Unpin + ?Sized,
+    T: Serialize + ?Sized,
+{
+    let mut buf = Vec::new();
+    to_vec(&mut buf, value, config)?;
+    dst.write_all(&buf).await
+}
+
+/// Reads and deserializes a value from `src`.
+///
+/// Bytes past the end of the value are left in `src`.
+///
+/// # Errors
+///
+/// This function returns the same errors as [`Read::read_exact`], and an [`Error`] wrapped in
+/// an `io::Error` if the bytes do not form a valid value.
+///
+/// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+/// [`Error`]: enum.Error.html
+pub async fn from_reader<R, T>(src: &mut R, config: Config) -> io::Result<T>
+where
+    R: io::AsyncBufRead + Unpin + ?Sized,
+    T: DeserializeOwned,
+{
+    // the bytes consumed so far, all of which belong to the value
+    let mut taken = Vec::new();
+    loop {
+        let buf = src.fill_buf().await?;
+        let eof = buf.is_empty();
+        let attempt = if taken.is_empty() {
+            from_slice::<T>(buf, config).map(|(v, rest)| (v, buf.len() - rest.len()))
+        } else {
+            let start = taken.len();
+            taken.extend_from_slice(buf);
+            let res =
+                from_slice::<T>(&taken, config).map(|(v, rest)| (v, taken.len() - rest.len()));
+            taken.truncate(start);
+            res.map(|(v, used)| (v, used - start))
+        };
+        match attempt {
+            Ok((v, used)) => {
+                src.consume(used);
+                return Ok(v);
+            }
+            Err(Error::Eof) if !eof => {
+                let n = buf.len();
+                taken.extend_from_slice(buf);
+                src.consume(n);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// A serde `Serializer` that appends to a `Vec<u8>`.
+#[derive(Debug)]
+pub struct Serializer<'a> {
+    out: &'a mut Vec<u8>,
+    config: Config,
+}
+
+impl<'a> Serializer<'a> {
+    /// Creates a serializer that appends to `out`.
+    pub fn new(out: &'a mut Vec<u8>, config: Config) -> Self {
+        Serializer { out, config }
+    }
+
+    fn number<V: RuntimeCodec>(&mut self, v: V, width: usize) {
+        let start = self.out.len();
+        self.out.resize(start + width, 0);
+        V::write(self.config.endian, &mut self.out[start..], v);
+    }
+
+    fn len(&mut self, len: usize) -> Result<(), Error> {
+        let overflow = |_| Error::LengthOverflow;
+        match self.config.len {
+            LenWidth::U8 => self.out.push(u8::try_from(len).map_err(overflow)?),
+            LenWidth::U16 => self.number(u16::try_from(len).map_err(overflow)?, 2),
+            LenWidth::U32 => self.number(u32::try_from(len).map_err(overflow)?, 4),
+            LenWidth::U64 => self.number(u64::try_from(len).map_err(overflow)?, 8),
+        }
+        Ok(())
+    }
+}
+
+macro_rules! serialize_number {
+    ($($method:ident($ty:ty, $width:expr)),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<(), Error> {
+                self.number(v, $width);
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    serialize_number! {
+        serialize_u16(u16, 2),
+        serialize_i16(i16, 2),
+        serialize_u32(u32, 4),
+        serialize_i32(i32, 4),
+        serialize_u64(u64, 8),
+        serialize_i64(i64, 8),
+        serialize_u128(u128, 16),
+        serialize_i128(i128, 16),
+        serialize_f32(f32, 4),
+        serialize_f64(f64, 8),
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.out.push(v);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.out.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.len(v.len())?;
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.out.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.out.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_u32(index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.serialize_u32(index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+        self.len(len.ok_or(Error::LengthRequired)?)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_u32(index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+        self.len(len.ok_or(Error::LengthRequired)?)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_u32(index)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! serialize_compound {
+    ($($trait:ident, $method:ident($($key:ident)?);)*) => {
+        $(
+            impl<'a, 'b> ser::$trait for &'a mut Serializer<'b> {
+                type Ok = ();
+                type Error = Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, $($key: &'static str,)? value: &T) -> Result<(), Error> {
+                    $(let _ = $key;)?
+                    value.serialize(&mut **self)
+                }
+
+                fn end(self) -> Result<(), Error> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+serialize_compound! {
+    SerializeSeq, serialize_element();
+    SerializeTuple, serialize_element();
+    SerializeTupleStruct, serialize_field();
+    SerializeTupleVariant, serialize_field();
+    SerializeStruct, serialize_field(key);
+    SerializeStructVariant, serialize_field(key);
+}
+
+impl<'a, 'b> ser::SerializeMap for &'a mut Serializer<'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A serde `Deserializer` that reads from a byte slice.
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    config: Config,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer that reads from the front of `input`.
+    pub fn new(input: &'de [u8], config: Config) -> Self {
+        Deserializer { input, config }
+    }
+
+    /// Returns the input that has not been deserialized yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'de [u8], Error> {
+        if self.input.len() < n {
+            return Err(Error::Eof);
+        }
+        let (head, tail) = self.input.split_at(n);
+        self.input = tail;
+        Ok(head)
+    }
+
+    fn number<V: RuntimeCodec>(&mut self, width: usize) -> Result<V, Error> {
+        let endian = self.config.endian;
+        Ok(V::read(endian, self.take(width)?))
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn tag(&mut self) -> Result<bool, Error> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(Error::InvalidTag(b)),
+        }
+    }
+
+    fn len(&mut self) -> Result<usize, Error> {
+        let len = match self.config.len {
+            LenWidth::U8 => u64::from(self.byte()?),
+            LenWidth::U16 => u64::from(self.number::<u16>(2)?),
+            LenWidth::U32 => u64::from(self.number::<u32>(4)?),
+            LenWidth::U64 => self.number::<u64>(8)?,
+        };
+        usize::try_from(len).map_err(|_| Error::LengthOverflow)
+    }
+
+    fn bytes(&mut self) -> Result<&'de [u8], Error> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    fn str(&mut self) -> Result<&'de str, Error> {
+        core::str::from_utf8(self.bytes()?).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident, $visit:ident($ty:ty, $width:expr);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.number::<$ty>($width)?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    deserialize_number! {
+        deserialize_u16, visit_u16(u16, 2);
+        deserialize_i16, visit_i16(i16, 2);
+        deserialize_u32, visit_u32(u32, 4);
+        deserialize_i32, visit_i32(i32, 4);
+        deserialize_u64, visit_u64(u64, 8);
+        deserialize_i64, visit_i64(i64, 8);
+        deserialize_u128, visit_u128(u128, 16);
+        deserialize_i128, visit_i128(i128, 16);
+        deserialize_f32, visit_f32(f32, 4);
+        deserialize_f64, visit_f64(f64, 8);
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+        Err(Error::AnyNotSupported)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+        Err(Error::AnyNotSupported)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_bool(self.tag()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u8(self.byte()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i8(self.byte()? as i8)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let c = self.number::<u32>(4)?;
+        visitor.visit_char(char::from_u32(c).ok_or(Error::InvalidChar(c))?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_bytes(self.bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.tag()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = self.len()?;
+        visitor.visit_seq(Counted {
+            de: self,
+            left: len,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Counted {
+            de: self,
+            left: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = self.len()?;
+        visitor.visit_map(Counted {
+            de: self,
+            left: len,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u32(self.number::<u32>(4)?)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Hands out a known number of sequence elements or map entries.
+struct Counted<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    left: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Counted<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // the length comes from the input, so don't let it preallocate arbitrarily much
+        Some(core::cmp::min(self.left, 4096))
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Counted<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(core::cmp::min(self.left, 4096))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let index = self.number::<u32>(4)?;
+        let v = seed.deserialize(index.into_deserializer())?;
+        Ok((v, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
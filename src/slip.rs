@@ -0,0 +1,168 @@
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+const END: u8 = 0xc0;
+const ESC: u8 = 0xdb;
+const ESC_END: u8 = 0xdc;
+const ESC_ESC: u8 = 0xdd;
+
+/// Encodes `data` as a SLIP packet, with an `END` byte on either side.
+fn encode(data: &[u8]) -> Vec<u8> {
+    let escapes = data.iter().filter(|&&b| b == END || b == ESC).count();
+    let mut out = Vec::with_capacity(data.len() + escapes + 2);
+    out.push(END);
+    for &b in data {
+        match b {
+            END => out.extend_from_slice(&[ESC, ESC_END]),
+            ESC => out.extend_from_slice(&[ESC, ESC_ESC]),
+            b => out.push(b),
+        }
+    }
+    out.push(END);
+    out
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Future returned by
+/// [`read_slip_frame`](../trait.AsyncBufReadBytesExt.html#method.read_slip_frame).
+#[derive(Debug)]
+pub struct ReadSlipFrame<R> {
+    src: R,
+    max_len: usize,
+    frame: Vec<u8>,
+    // whether the last byte read was an `ESC`
+    escaped: bool,
+    // the error to return once the rest of a bad frame has been discarded
+    error: Option<&'static str>,
+}
+
+impl<R> ReadSlipFrame<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadSlipFrame {
+            src,
+            max_len,
+            frame: Vec::new(),
+            escaped: false,
+            error: None,
+        }
+    }
+}
+
+/// Adds decoded bytes to `frame`, unless the frame has already gone bad.
+fn push(frame: &mut Vec<u8>, max_len: usize, error: &mut Option<&'static str>, data: &[u8]) {
+    if error.is_some() {
+        return;
+    }
+    if frame.len() + data.len() > max_len {
+        *error = Some("slip frame exceeds maximum length");
+        *frame = Vec::new();
+    } else {
+        frame.extend_from_slice(data);
+    }
+}
+
+impl<R> Future for ReadSlipFrame<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    type Output = io::Result<Vec<u8>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let mut src = Pin::new(&mut this.src);
+            let buf = match ready!(src.as_mut().poll_fill_buf(cx)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => res?,
+            };
+            if buf.is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "reached the end of the stream before the end of the slip frame",
+                )));
+            }
+            let mut i = 0;
+            let mut done = false;
+            while i < buf.len() {
+                if this.escaped {
+                    this.escaped = false;
+                    match buf[i] {
+                        ESC_END => push(&mut this.frame, this.max_len, &mut this.error, &[END]),
+                        ESC_ESC => push(&mut this.frame, this.max_len, &mut this.error, &[ESC]),
+                        // leave the `END` to finish the frame
+                        END => {
+                            this.error.get_or_insert("slip frame ended in an escape");
+                            continue;
+                        }
+                        _ => {
+                            this.error.get_or_insert("invalid slip escape sequence");
+                        }
+                    }
+                    i += 1;
+                    continue;
+                }
+
+                let run = &buf[i..];
+                let n = run
+                    .iter()
+                    .position(|&b| b == END || b == ESC)
+                    .unwrap_or(run.len());
+                push(&mut this.frame, this.max_len, &mut this.error, &run[..n]);
+                i += n;
+                if n == run.len() {
+                    break;
+                }
+                i += 1;
+                if run[n] == ESC {
+                    this.escaped = true;
+                } else if !this.frame.is_empty() || this.error.is_some() {
+                    done = true;
+                    break;
+                }
+                // otherwise, an `END` with no frame before it; there is nothing to return
+            }
+            src.consume(i);
+
+            if done {
+                if let Some(msg) = this.error.take() {
+                    this.frame.clear();
+                    return Poll::Ready(Err(invalid(msg)));
+                }
+                return Poll::Ready(Ok(core::mem::take(&mut this.frame)));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`write_slip_frame`](../trait.AsyncWriteBytesExt.html#method.write_slip_frame).
+#[derive(Debug)]
+pub struct WriteSlipFrame<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteSlipFrame<W> {
+    pub(crate) fn new(dst: W, data: &[u8]) -> Self {
+        WriteSlipFrame {
+            dst,
+            buf: encode(data),
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteSlipFrame<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::sans_io::FixedDecoder;
+use crate::*;
+
+/// The value types that [`StatsReader`] counts, in the order of [`ReadStats::values`].
+const TYPES: [&str; 16] = [
+    "u8", "i8", "u16", "i16", "u24", "i24", "u32", "i32", "u48", "i48", "u64", "i64", "u128",
+    "i128", "f32", "f64",
+];
+
+/// A snapshot of the statistics collected by a [`StatsReader`].
+///
+/// [`StatsReader`]: struct.StatsReader.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ReadStats {
+    /// The number of bytes read from the underlying reader.
+    pub bytes: u64,
+    /// The number of reads from the underlying reader that returned at least one byte.
+    pub reads: u64,
+    /// The number of reads from the underlying reader that returned at least one byte, but
+    /// fewer than were asked for.
+    ///
+    /// A high number here means that values often arrive split across several reads, which is
+    /// usually a sign that the reader should be buffered.
+    pub partial_reads: u64,
+    values: [u64; 16],
+}
+
+impl ReadStats {
+    /// Returns the number of values of the type named `ty`, like `"u32"`, that were read
+    /// through the `read_` methods of [`StatsReader`].
+    ///
+    /// Returns zero for names that are not one of the types that these methods read.
+    ///
+    /// [`StatsReader`]: struct.StatsReader.html
+    pub fn values(&self, ty: &str) -> u64 {
+        TYPES
+            .iter()
+            .position(|&t| t == ty)
+            .map_or(0, |i| self.values[i])
+    }
+
+    /// Returns the name of each type along with the number of values of that type that were
+    /// read, for every type at least one value was read of.
+    pub fn iter_values(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        TYPES
+            .iter()
+            .zip(&self.values)
+            .filter(|(_, &n)| n > 0)
+            .map(|(&ty, &n)| (ty, n))
+    }
+}
+
+/// A reader that collects statistics about the reads made through it.
+///
+/// At the I/O level, it counts the bytes read and the number of reads, along with how many of
+/// those reads returned fewer bytes than were asked for. Values read through its own `read_`
+/// methods, which work just like those of [`AsyncReadBytesExt`], are additionally counted by
+/// type. This shows what a protocol server actually spends its decoding on, which socket-level
+/// byte counts alone do not.
+///
+/// `StatsReader` implements [`AsyncRead`], so anything else can be read through it as well, and
+/// is still counted at the I/O level.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{BigEndian, StatsReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = StatsReader::new(&[0, 0, 0, 1, 0, 0, 0, 2, 3][..]);
+///     rdr.read_u32::<BigEndian>().await.unwrap();
+///     rdr.read_u32::<BigEndian>().await.unwrap();
+///     rdr.read_u8().await.unwrap();
+///
+///     let stats = rdr.stats();
+///     assert_eq!(stats.bytes, 9);
+///     assert_eq!(stats.values("u32"), 2);
+///     assert_eq!(stats.values("u8"), 1);
+/// }
+/// ```
+///
+/// [`AsyncReadBytesExt`]: trait.AsyncReadBytesExt.html
+/// [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+#[derive(Debug)]
+pub struct StatsReader<R> {
+    inner: R,
+    stats: ReadStats,
+}
+
+impl<R> StatsReader<R> {
+    /// Wraps the given reader, with all statistics starting at zero.
+    pub fn new(inner: R) -> Self {
+        StatsReader {
+            inner,
+            stats: ReadStats::default(),
+        }
+    }
+
+    /// Returns a snapshot of the statistics collected so far.
+    pub fn stats(&self) -> ReadStats {
+        self.stats.clone()
+    }
+
+    /// Sets all statistics back to zero, and returns what they were.
+    ///
+    /// This is handy for reporting statistics per interval.
+    pub fn reset_stats(&mut self) -> ReadStats {
+        core::mem::take(&mut self.stats)
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly through the returned reference are not counted.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> io::AsyncRead for StatsReader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let wanted = buf.remaining();
+        let before = buf.filled().len();
+        let res = Pin::new(&mut this.inner).poll_read(cx, buf);
+        let n = buf.filled().len() - before;
+        if n > 0 {
+            this.stats.bytes += n as u64;
+            this.stats.reads += 1;
+            if n < wanted {
+                this.stats.partial_reads += 1;
+            }
+        }
+        res
+    }
+}
+
+impl<R: io::AsyncRead + Unpin> StatsReader<R> {
+    /// Reads an unsigned 8 bit integer, and counts it.
+    ///
+    /// See [`AsyncReadBytesExt::read_u8`](trait.AsyncReadBytesExt.html#method.read_u8).
+    #[inline]
+    pub fn read_u8(&mut self) -> ReadCounted<'_, R, u8> {
+        ReadCounted::new(self, FixedDecoder::u8(), 0)
+    }
+
+    /// Reads a signed 8 bit integer, and counts it.
+    ///
+    /// See [`AsyncReadBytesExt::read_i8`](trait.AsyncReadBytesExt.html#method.read_i8).
+    #[inline]
+    pub fn read_i8(&mut self) -> ReadCounted<'_, R, i8> {
+        ReadCounted::new(self, FixedDecoder::i8(), 1)
+    }
+}
+
+macro_rules! stats_impl {
+    ($(($read:ident, $decoder:ident, $ty:ty, $index:expr, $desc:literal)),* $(,)?) => {
+        impl<R: io::AsyncRead + Unpin> StatsReader<R> {
+            $(
+                #[doc = concat!("Reads ", $desc, ", and counts it.")]
+                ///
+                #[doc = concat!("See [`AsyncReadBytesExt::", stringify!($read), "`](trait.AsyncReadBytesExt.html#method.", stringify!($read), ").")]
+                #[inline]
+                pub fn $read<T: ByteOrder>(&mut self) -> ReadCounted<'_, R, $ty> {
+                    ReadCounted::new(self, FixedDecoder::$decoder::<T>(), $index)
+                }
+            )*
+        }
+    };
+}
+
+stats_impl! {
+    (read_u16, u16, u16, 2, "an unsigned 16 bit integer"),
+    (read_i16, i16, i16, 3, "a signed 16 bit integer"),
+    (read_u24, u24, u32, 4, "an unsigned 24 bit integer"),
+    (read_i24, i24, i32, 5, "a signed 24 bit integer"),
+    (read_u32, u32, u32, 6, "an unsigned 32 bit integer"),
+    (read_i32, i32, i32, 7, "a signed 32 bit integer"),
+    (read_u48, u48, u64, 8, "an unsigned 48 bit integer"),
+    (read_i48, i48, i64, 9, "a signed 48 bit integer"),
+    (read_u64, u64, u64, 10, "an unsigned 64 bit integer"),
+    (read_i64, i64, i64, 11, "a signed 64 bit integer"),
+    (read_u128, u128, u128, 12, "an unsigned 128 bit integer"),
+    (read_i128, i128, i128, 13, "a signed 128 bit integer"),
+    (read_f32, f32, f32, 14, "a IEEE754 single-precision floating point number"),
+    (read_f64, f64, f64, 15, "a IEEE754 double-precision floating point number"),
+}
+
+/// Future returned by the `read_` methods of [`StatsReader`](../struct.StatsReader.html).
+#[derive(Debug)]
+pub struct ReadCounted<'a, R, V> {
+    src: &'a mut StatsReader<R>,
+    decoder: FixedDecoder<V>,
+    index: usize,
+}
+
+impl<'a, R, V> ReadCounted<'a, R, V> {
+    fn new(src: &'a mut StatsReader<R>, decoder: FixedDecoder<V>, index: usize) -> Self {
+        ReadCounted {
+            src,
+            decoder,
+            index,
+        }
+    }
+}
+
+impl<R, V> Future for ReadCounted<'_, R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let res = poll_decode(Pin::new(&mut *this.src), cx, &mut this.decoder);
+        if let Poll::Ready(Ok(_)) = res {
+            this.src.stats.values[this.index] += 1;
+        }
+        res
+    }
+}
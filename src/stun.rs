@@ -0,0 +1,325 @@
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::vec::Vec;
+
+/// The magic cookie that is in every STUN message header since RFC 5389.
+pub const STUN_MAGIC_COOKIE: u32 = 0x2112_a442;
+
+const HEADER_LEN: usize = 20;
+
+/// The header that starts every STUN message (RFC 8489 §5).
+///
+/// Read with [`read_stun_header`], and written with [`write_stun_header`]. The magic cookie
+/// is not a field, since it is always [`STUN_MAGIC_COOKIE`].
+///
+/// [`read_stun_header`]: trait.AsyncReadBytesExt.html#method.read_stun_header
+/// [`write_stun_header`]: trait.AsyncWriteBytesExt.html#method.write_stun_header
+/// [`STUN_MAGIC_COOKIE`]: constant.STUN_MAGIC_COOKIE.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StunHeader {
+    /// The type of the message, which combines its method and class; the top two bits are
+    /// always zero.
+    pub message_type: u16,
+    /// The length of the attributes that follow the header, in bytes, which is a multiple
+    /// of 4.
+    pub length: u16,
+    /// The id that matches a response to its request.
+    pub transaction_id: [u8; 12],
+}
+
+/// An attribute of a STUN message, with the padding after its value removed.
+///
+/// Read with [`read_stun_attribute`], and written with [`write_stun_attribute`].
+///
+/// [`read_stun_attribute`]: trait.AsyncReadBytesExt.html#method.read_stun_attribute
+/// [`write_stun_attribute`]: trait.AsyncWriteBytesExt.html#method.write_stun_attribute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StunAttribute {
+    /// The type of the attribute.
+    pub attr_type: u16,
+    /// The value of the attribute.
+    pub value: Vec<u8>,
+}
+
+impl StunAttribute {
+    /// The type of the `XOR-MAPPED-ADDRESS` attribute.
+    pub const XOR_MAPPED_ADDRESS: u16 = 0x0020;
+    /// The type of TURN's `XOR-PEER-ADDRESS` attribute.
+    pub const XOR_PEER_ADDRESS: u16 = 0x0012;
+    /// The type of TURN's `XOR-RELAYED-ADDRESS` attribute.
+    pub const XOR_RELAYED_ADDRESS: u16 = 0x0016;
+
+    /// Creates an attribute of type `attr_type` that holds `addr` in the obfuscated form of
+    /// `XOR-MAPPED-ADDRESS`.
+    ///
+    /// The port is XORed with the top half of the magic cookie, an IPv4 address with the
+    /// magic cookie, and an IPv6 address with the magic cookie followed by `transaction_id`.
+    /// TURN's `XOR-PEER-ADDRESS` and `XOR-RELAYED-ADDRESS` use the same form.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tokio_byteorder::StunAttribute;
+    ///
+    /// let addr = "192.0.2.1:32853".parse().unwrap();
+    /// let attr = StunAttribute::xor_address(StunAttribute::XOR_MAPPED_ADDRESS, addr, &[0; 12]);
+    /// assert_eq!(attr.value, [0x00, 0x01, 0xa1, 0x47, 0xe1, 0x12, 0xa6, 0x43]);
+    /// assert_eq!(attr.decode_xor_address(&[0; 12]).unwrap(), addr);
+    /// ```
+    pub fn xor_address(attr_type: u16, addr: SocketAddr, transaction_id: &[u8; 12]) -> Self {
+        let mask = xor_mask(transaction_id);
+        let mut value = Vec::with_capacity(20);
+        value.push(0);
+        value.push(match addr {
+            SocketAddr::V4(_) => 0x01,
+            SocketAddr::V6(_) => 0x02,
+        });
+        value.extend_from_slice(&(addr.port() ^ (STUN_MAGIC_COOKIE >> 16) as u16).to_be_bytes());
+        match addr.ip() {
+            IpAddr::V4(ip) => value.extend_from_slice(&ip.octets()),
+            IpAddr::V6(ip) => value.extend_from_slice(&ip.octets()),
+        }
+        for (b, m) in value[4..].iter_mut().zip(&mask) {
+            *b ^= m;
+        }
+        StunAttribute { attr_type, value }
+    }
+
+    /// Decodes the address in the value of an `XOR-MAPPED-ADDRESS` attribute, or of another
+    /// attribute that uses its form; see [`xor_address`](#method.xor_address).
+    ///
+    /// The type of the attribute is not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind `InvalidData` if the address family is neither IPv4 nor
+    /// IPv6, or if the value is not as long as that family needs.
+    pub fn decode_xor_address(&self, transaction_id: &[u8; 12]) -> io::Result<SocketAddr> {
+        let value = &self.value[..];
+        let len = match value.get(1) {
+            Some(0x01) => 8,
+            Some(0x02) => 20,
+            _ => return Err(invalid("unknown stun address family")),
+        };
+        if value.len() != len {
+            return Err(invalid("stun address has the wrong length"));
+        }
+        let port = BigEndian::read_u16(&value[2..]) ^ (STUN_MAGIC_COOKIE >> 16) as u16;
+        let mut ip = [0; 16];
+        for ((b, v), m) in ip
+            .iter_mut()
+            .zip(&value[4..])
+            .zip(&xor_mask(transaction_id))
+        {
+            *b = v ^ m;
+        }
+        let ip = if len == 8 {
+            IpAddr::V4(Ipv4Addr::new(ip[0], ip[1], ip[2], ip[3]))
+        } else {
+            IpAddr::V6(Ipv6Addr::from(ip))
+        };
+        Ok(SocketAddr::new(ip, port))
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Returns the magic cookie followed by the transaction id, which addresses are XORed with.
+fn xor_mask(transaction_id: &[u8; 12]) -> [u8; 16] {
+    let mut mask = [0; 16];
+    mask[..4].copy_from_slice(&STUN_MAGIC_COOKIE.to_be_bytes());
+    mask[4..].copy_from_slice(transaction_id);
+    mask
+}
+
+/// Returns the number of zero bytes that pad a value of `len` bytes to a multiple of 4.
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+/// Future returned by
+/// [`read_stun_header`](../trait.AsyncReadBytesExt.html#method.read_stun_header).
+#[derive(Debug)]
+pub struct ReadStunHeader<R> {
+    src: R,
+    buf: [u8; HEADER_LEN],
+    filled: usize,
+}
+
+impl<R> ReadStunHeader<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadStunHeader {
+            src,
+            buf: [0; HEADER_LEN],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadStunHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<StunHeader>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf,
+            &mut this.filled
+        ))?;
+        let buf = &this.buf;
+        let message_type = BigEndian::read_u16(buf);
+        let length = BigEndian::read_u16(&buf[2..]);
+        if message_type & 0xc000 != 0 {
+            return Poll::Ready(Err(invalid("stun message type has its top bits set")));
+        }
+        if BigEndian::read_u32(&buf[4..]) != STUN_MAGIC_COOKIE {
+            return Poll::Ready(Err(invalid("stun message has the wrong magic cookie")));
+        }
+        if length % 4 != 0 {
+            return Poll::Ready(Err(invalid("stun message length is not a multiple of 4")));
+        }
+        let mut transaction_id = [0; 12];
+        transaction_id.copy_from_slice(&buf[8..]);
+        Poll::Ready(Ok(StunHeader {
+            message_type,
+            length,
+            transaction_id,
+        }))
+    }
+}
+
+/// Future returned by
+/// [`read_stun_attribute`](../trait.AsyncReadBytesExt.html#method.read_stun_attribute).
+#[derive(Debug)]
+pub struct ReadStunAttribute<R> {
+    src: R,
+    max_len: usize,
+    header: [u8; 4],
+    filled: usize,
+    value: Vec<u8>,
+}
+
+impl<R> ReadStunAttribute<R> {
+    pub(crate) fn new(src: R, max_len: usize) -> Self {
+        ReadStunAttribute {
+            src,
+            max_len,
+            header: [0; 4],
+            filled: 0,
+            value: Vec::new(),
+        }
+    }
+}
+
+impl<R> Future for ReadStunAttribute<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<StunAttribute>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled < this.header.len() {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.header,
+                &mut this.filled
+            ))?;
+            let len = usize::from(BigEndian::read_u16(&this.header[2..]));
+            if len > this.max_len {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "stun attribute is longer than the maximum of {} bytes",
+                        this.max_len
+                    ),
+                )));
+            }
+        }
+        let len = usize::from(BigEndian::read_u16(&this.header[2..]));
+        ready!(poll_read_to_len(
+            &mut this.src,
+            cx,
+            &mut this.value,
+            len + padding(len)
+        ))?;
+        let mut value = core::mem::take(&mut this.value);
+        value.truncate(len);
+        Poll::Ready(Ok(StunAttribute {
+            attr_type: BigEndian::read_u16(&this.header),
+            value,
+        }))
+    }
+}
+
+/// Future returned by
+/// [`write_stun_header`](../trait.AsyncWriteBytesExt.html#method.write_stun_header) and
+/// [`write_stun_attribute`](../trait.AsyncWriteBytesExt.html#method.write_stun_attribute).
+#[derive(Debug)]
+pub struct WriteStun<W> {
+    dst: W,
+    // the encoded header or attribute, or why it cannot be encoded
+    buf: Result<Vec<u8>, &'static str>,
+    written: usize,
+}
+
+impl<W> WriteStun<W> {
+    pub(crate) fn header(dst: W, header: &StunHeader) -> Self {
+        let buf = if header.message_type & 0xc000 == 0 {
+            let mut buf = Vec::with_capacity(HEADER_LEN);
+            buf.extend_from_slice(&header.message_type.to_be_bytes());
+            buf.extend_from_slice(&header.length.to_be_bytes());
+            buf.extend_from_slice(&STUN_MAGIC_COOKIE.to_be_bytes());
+            buf.extend_from_slice(&header.transaction_id);
+            Ok(buf)
+        } else {
+            Err("the top two bits of a stun message type are not zero")
+        };
+        WriteStun {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+
+    pub(crate) fn attribute(dst: W, attr_type: u16, value: &[u8]) -> Self {
+        let buf = match u16::try_from(value.len()) {
+            Ok(len) => {
+                let padded_len = 4 + value.len() + padding(value.len());
+                let mut buf = Vec::with_capacity(padded_len);
+                buf.extend_from_slice(&attr_type.to_be_bytes());
+                buf.extend_from_slice(&len.to_be_bytes());
+                buf.extend_from_slice(value);
+                buf.resize(padded_len, 0);
+                Ok(buf)
+            }
+            Err(_) => Err("stun attribute is longer than 65535 bytes"),
+        };
+        WriteStun {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteStun<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &this.buf {
+            Ok(buf) => poll_write_all(&mut this.dst, cx, buf, &mut this.written),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::*;
+use bytes::{Buf, BytesMut};
+use core::task::ready;
+use tokio::io::AsyncWriteExt;
+
+/// A reader that copies every byte read through it into a capture writer.
+///
+/// This records the exact byte stream that a decoder on top consumed, so that it can be
+/// inspected or replayed later, for example when tracking down why a decoder lost sync with a
+/// peer. The capture can be anything that implements [`AsyncWrite`], like a file or a `Vec<u8>`.
+///
+/// Bytes are handed to the capture on the read after the one that returned them, so that a
+/// slow capture never holds up bytes that have already been read. Call [`flush_capture`] once
+/// done to write out the last of them. An error from the capture is returned from the read
+/// that tried to write to it, and the read then does not consume anything from the underlying
+/// reader.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian, TeeReader};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = TeeReader::new(&[0x00, 0x2a, 0xff][..], Vec::new());
+///     assert_eq!(42, rdr.read_u16::<BigEndian>().await.unwrap());
+///     rdr.flush_capture().await.unwrap();
+///     assert_eq!(rdr.capture(), &[0x00, 0x2a]);
+/// }
+/// ```
+///
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`flush_capture`]: #method.flush_capture
+#[derive(Debug)]
+pub struct TeeReader<R, C> {
+    inner: R,
+    capture: C,
+    pending: BytesMut,
+}
+
+/// A writer that copies every byte written through it into a capture writer.
+///
+/// Only bytes that the underlying writer has accepted are captured, so the capture holds
+/// exactly what was sent. See [`TeeReader`] for the reading side.
+///
+/// Bytes are handed to the capture on the write after the one that accepted them, and when
+/// the writer is flushed or shut down. An error from the capture is returned from the call
+/// that tried to write to it, and nothing is written to the underlying writer by that call.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, TeeWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = TeeWriter::new(Vec::new(), Vec::new());
+///     wtr.write_u16::<BigEndian>(42).await.unwrap();
+///     tokio::io::AsyncWriteExt::flush(&mut wtr).await.unwrap();
+///     let (sent, capture) = wtr.into_parts();
+///     assert_eq!(sent, capture);
+/// }
+/// ```
+///
+/// [`TeeReader`]: struct.TeeReader.html
+#[derive(Debug)]
+pub struct TeeWriter<W, C> {
+    inner: W,
+    capture: C,
+    pending: BytesMut,
+}
+
+/// Writes all of `pending` to `capture`.
+fn poll_drain<C>(
+    capture: &mut C,
+    pending: &mut BytesMut,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>>
+where
+    C: io::AsyncWrite + Unpin,
+{
+    while !pending.is_empty() {
+        match ready!(Pin::new(&mut *capture).poll_write(cx, pending)) {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Poll::Ready(Err(e)),
+            Ok(0) => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            Ok(n) => pending.advance(n),
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+macro_rules! tee_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, C> $name<$inner, C> {
+            /// Wraps the given stream, copying the bytes that pass through into `capture`.
+            pub fn new(inner: $inner, capture: C) -> Self {
+                $name {
+                    inner,
+                    capture,
+                    pending: BytesMut::new(),
+                }
+            }
+
+            /// Gets a reference to the underlying stream.
+            pub fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Gets a mutable reference to the underlying stream.
+            ///
+            /// Bytes transferred directly through the returned reference are not captured.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Gets a reference to the capture.
+            ///
+            /// The most recent bytes may not have reached it yet; see
+            /// [`flush_capture`](#method.flush_capture).
+            pub fn capture(&self) -> &C {
+                &self.capture
+            }
+
+            /// Gets a mutable reference to the capture.
+            pub fn capture_mut(&mut self) -> &mut C {
+                &mut self.capture
+            }
+
+            /// Unwraps this value, returning the underlying stream and the capture.
+            ///
+            /// Bytes that have not reached the capture yet are lost.
+            pub fn into_parts(self) -> ($inner, C) {
+                (self.inner, self.capture)
+            }
+        }
+
+        impl<$inner, C: io::AsyncWrite + Unpin> $name<$inner, C> {
+            /// Writes every byte that has passed through so far to the capture, and flushes it.
+            ///
+            /// # Errors
+            ///
+            /// This method returns the same errors as [`Write::write_all`] and
+            /// [`Write::flush`] on the capture.
+            ///
+            /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+            /// [`Write::flush`]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
+            pub async fn flush_capture(&mut self) -> io::Result<()> {
+                self.capture.write_all_buf(&mut self.pending).await?;
+                self.capture.flush().await
+            }
+        }
+    };
+}
+
+tee_common!(TeeReader, R);
+tee_common!(TeeWriter, W);
+
+impl<R, C> io::AsyncRead for TeeReader<R, C>
+where
+    R: io::AsyncRead + Unpin,
+    C: io::AsyncWrite + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.capture, &mut this.pending, cx))?;
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.pending.extend_from_slice(&buf.filled()[before..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, C> io::AsyncWrite for TeeWriter<W, C>
+where
+    W: io::AsyncWrite + Unpin,
+    C: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.capture, &mut this.pending, cx))?;
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        this.pending.extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.capture, &mut this.pending, cx))?;
+        ready!(Pin::new(&mut this.capture).poll_flush(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.capture, &mut this.pending, cx))?;
+        ready!(Pin::new(&mut this.capture).poll_flush(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
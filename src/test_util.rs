@@ -0,0 +1,408 @@
+//! Mock I/O types for testing decoders and encoders against awkward streams.
+//!
+//! Real sockets and pipes rarely hand over a whole value in a single read or take one in a
+//! single write, but in-memory test streams always do, so code that mishandles partial I/O
+//! tends to pass its tests anyway. The types in this module wrap an in-memory stream and make
+//! it behave like a slow or failing network connection, deterministically, so that every path
+//! through a decoder or encoder can be exercised.
+//!
+//! The mocks implement tokio's I/O traits, and with the `futures-traits` feature also those of
+//! [`futures-io`], so the same tests can be run against both.
+//!
+//! This module is only available with the `test_util` feature, which is meant to be enabled
+//! in `[dev-dependencies]`.
+//!
+//! [`futures-io`]: https://docs.rs/futures-io/0.3/futures_io/
+
+use crate::*;
+use core::task::ready;
+
+/// A reader that hands out at most a fixed number of bytes per read.
+///
+/// Optionally, it also returns [`Poll::Pending`] once before every read, after waking the task
+/// so that it is polled again right away. This forces a future to be suspended and resumed
+/// between every chunk, which catches state that is lost across polls.
+///
+/// If the underlying reader implements [`AsyncBufRead`], so does `ChunkedReader`, with at most
+/// a chunk's worth of bytes available at a time.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+/// use tokio_byteorder::test_util::ChunkedReader;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut rdr = ChunkedReader::new(&[0x00, 0x00, 0x01, 0x0b][..], 1).pending_between(true);
+///     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+///     assert_eq!(4, rdr.reads());
+/// }
+/// ```
+///
+/// [`Poll::Pending`]: https://doc.rust-lang.org/std/task/enum.Poll.html#variant.Pending
+/// [`AsyncBufRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncBufRead.html
+#[derive(Debug)]
+pub struct ChunkedReader<R> {
+    inner: R,
+    chunk: usize,
+    pending: bool,
+    /// Whether `Pending` was already returned ahead of the next chunk.
+    primed: bool,
+    reads: u64,
+}
+
+impl<R> ChunkedReader<R> {
+    /// Wraps the given reader, handing out at most `chunk` bytes per read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is zero.
+    pub fn new(inner: R, chunk: usize) -> Self {
+        assert!(chunk > 0, "chunk size must be at least one byte");
+        ChunkedReader {
+            inner,
+            chunk,
+            pending: false,
+            primed: false,
+            reads: 0,
+        }
+    }
+
+    /// Sets whether to return `Pending` once before every chunk.
+    pub fn pending_between(mut self, pending: bool) -> Self {
+        self.pending = pending;
+        self
+    }
+
+    /// Returns the number of reads that completed with at least one byte.
+    pub fn reads(&self) -> u64 {
+        self.reads
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns `Pending` if one is due before the next chunk.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.pending && !self.primed {
+            self.primed = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(())
+    }
+
+    /// Records that `n` bytes were handed out.
+    fn advance(&mut self, n: usize) {
+        if n > 0 {
+            self.primed = false;
+            self.reads += 1;
+        }
+    }
+}
+
+impl<R> io::AsyncRead for ChunkedReader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        ready!(this.poll_pending(cx));
+        let n = std::cmp::min(this.chunk, buf.remaining());
+        let mut chunk = io::ReadBuf::new(buf.initialize_unfilled_to(n));
+        ready!(Pin::new(&mut this.inner).poll_read(cx, &mut chunk))?;
+        let read = chunk.filled().len();
+        buf.advance(read);
+        this.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> io::AsyncBufRead for ChunkedReader<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx));
+        let avail = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+        let n = std::cmp::min(this.chunk, avail.len());
+        Poll::Ready(Ok(&avail[..n]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = std::cmp::min(amt, this.chunk);
+        this.advance(amt);
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+#[cfg(feature = "futures-traits")]
+impl<R> futures_io::AsyncRead for ChunkedReader<R>
+where
+    R: futures_io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        ready!(this.poll_pending(cx));
+        let n = std::cmp::min(this.chunk, buf.len());
+        let read = ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf[..n]))?;
+        this.advance(read);
+        Poll::Ready(Ok(read))
+    }
+}
+
+/// A failure that a [`ChunkedWriter`] can be scripted to produce.
+///
+/// [`ChunkedWriter`]: struct.ChunkedWriter.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Return `Pending` once, after waking the task so that it is polled again right away.
+    Pending,
+    /// Fail once with `ErrorKind::WouldBlock`, as a non-blocking socket would.
+    WouldBlock,
+    /// Fail once with `ErrorKind::Interrupted`, which the writing code is expected to retry.
+    Interrupted,
+    /// Fail with an error of the given kind, and keep failing with it from then on.
+    Error(io::ErrorKind),
+}
+
+/// A writer that accepts at most a fixed number of bytes per write, and fails on cue.
+///
+/// Each [`Fault`] is scheduled at a position in the stream with [`fail_at`], and is produced
+/// by the first write that reaches that position. Writes before it are cut short so that they
+/// end exactly at the position. Like [`ChunkedReader`], the writer can also return `Pending`
+/// once before every chunk.
+///
+/// Flushing and shutting down are passed straight through to the underlying writer.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::ErrorKind;
+/// use tokio_byteorder::test_util::{ChunkedWriter, Fault};
+/// use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut wtr = ChunkedWriter::new(Vec::new(), 1)
+///         .fail_at(1, Fault::Interrupted)
+///         .fail_at(6, Fault::Error(ErrorKind::BrokenPipe));
+///
+///     wtr.write_u32::<BigEndian>(267).await.unwrap();
+///     let err = wtr.write_u32::<BigEndian>(267).await.unwrap_err();
+///     assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+///     assert_eq!(wtr.get_ref(), &[0x00, 0x00, 0x01, 0x0b, 0x00, 0x00]);
+/// }
+/// ```
+///
+/// [`Fault`]: enum.Fault.html
+/// [`fail_at`]: #method.fail_at
+/// [`ChunkedReader`]: struct.ChunkedReader.html
+#[derive(Debug)]
+pub struct ChunkedWriter<W> {
+    inner: W,
+    chunk: usize,
+    pending: bool,
+    /// Whether `Pending` was already returned ahead of the next chunk.
+    primed: bool,
+    /// The scheduled faults, ordered by position.
+    faults: std::collections::VecDeque<(u64, Fault)>,
+    broken: Option<io::ErrorKind>,
+    position: u64,
+    writes: u64,
+}
+
+impl<W> ChunkedWriter<W> {
+    /// Wraps the given writer, accepting at most `chunk` bytes per write.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is zero.
+    pub fn new(inner: W, chunk: usize) -> Self {
+        assert!(chunk > 0, "chunk size must be at least one byte");
+        ChunkedWriter {
+            inner,
+            chunk,
+            pending: false,
+            primed: false,
+            faults: std::collections::VecDeque::new(),
+            broken: None,
+            position: 0,
+            writes: 0,
+        }
+    }
+
+    /// Sets whether to return `Pending` once before every chunk.
+    pub fn pending_between(mut self, pending: bool) -> Self {
+        self.pending = pending;
+        self
+    }
+
+    /// Schedules `fault` for the first write that reaches `position` bytes into the stream.
+    ///
+    /// Several faults can be scheduled at the same position, and are produced in the order they
+    /// were scheduled in.
+    pub fn fail_at(mut self, position: u64, fault: Fault) -> Self {
+        let at = self.faults.partition_point(|&(p, _)| p <= position);
+        self.faults.insert(at, (position, fault));
+        self
+    }
+
+    /// Returns the number of bytes accepted so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the number of writes that accepted at least one byte.
+    pub fn writes(&self) -> u64 {
+        self.writes
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this value, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> ChunkedWriter<W> {
+    /// Produces the fault or `Pending` that is due before the next chunk, if any, and otherwise
+    /// returns how many of `len` bytes the next chunk may hold.
+    fn poll_chunk(&mut self, cx: &mut Context<'_>, len: usize) -> Poll<io::Result<usize>> {
+        if let Some(kind) = self.broken {
+            return Poll::Ready(Err(io::Error::new(kind, "injected fault")));
+        }
+        if let Some(&(at, fault)) = self.faults.front() {
+            if at <= self.position {
+                self.faults.pop_front();
+                let kind = match fault {
+                    Fault::Pending => {
+                        cx.waker().wake_by_ref();
+                        return Poll::Pending;
+                    }
+                    Fault::WouldBlock => io::ErrorKind::WouldBlock,
+                    Fault::Interrupted => io::ErrorKind::Interrupted,
+                    Fault::Error(kind) => {
+                        self.broken = Some(kind);
+                        kind
+                    }
+                };
+                return Poll::Ready(Err(io::Error::new(kind, "injected fault")));
+            }
+        }
+        if self.pending && !self.primed {
+            self.primed = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let mut n = std::cmp::min(self.chunk, len);
+        if let Some(&(at, _)) = self.faults.front() {
+            n = std::cmp::min(n as u64, at - self.position) as usize;
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    /// Records that `n` bytes were accepted.
+    fn advance(&mut self, n: usize) {
+        if n > 0 {
+            self.primed = false;
+            self.position += n as u64;
+            self.writes += 1;
+        }
+    }
+}
+
+impl<W> io::AsyncWrite for ChunkedWriter<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() && this.broken.is_none() {
+            return Poll::Ready(Ok(0));
+        }
+        let n = ready!(this.poll_chunk(cx, buf.len()))?;
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, &buf[..n]))?;
+        this.advance(written);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(feature = "futures-traits")]
+impl<W> futures_io::AsyncWrite for ChunkedWriter<W>
+where
+    W: futures_io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() && this.broken.is_none() {
+            return Poll::Ready(Ok(0));
+        }
+        let n = ready!(this.poll_chunk(cx, buf.len()))?;
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, &buf[..n]))?;
+        this.advance(written);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
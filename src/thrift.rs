@@ -0,0 +1,154 @@
+use crate::sans_io::FixedEncoder;
+use crate::varint::{zigzag_decode, zigzag_encode, VarintDecoder};
+use crate::*;
+use core::convert::TryFrom;
+use core::task::ready;
+
+/// The header of a field of a struct encoded with Thrift's compact protocol.
+///
+/// Read with [`read_thrift_field_header`], and written with [`write_thrift_field_header`].
+///
+/// [`read_thrift_field_header`]: trait.AsyncReadBytesExt.html#method.read_thrift_field_header
+/// [`write_thrift_field_header`]: trait.AsyncWriteBytesExt.html#method.write_thrift_field_header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThriftFieldHeader {
+    /// The id of the field.
+    pub id: i16,
+    /// The compact type of the field's value, from 1 to 15.
+    ///
+    /// A boolean field has no value after its header; its type is 1 if it is true, and 2 if it
+    /// is false.
+    pub field_type: u8,
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) fn decode_i16(n: u64) -> io::Result<i16> {
+    i16::try_from(zigzag_decode(n)).map_err(|_| invalid("thrift i16 does not fit in an i16"))
+}
+
+pub(crate) fn decode_i32(n: u64) -> io::Result<i32> {
+    i32::try_from(zigzag_decode(n)).map_err(|_| invalid("thrift i32 does not fit in an i32"))
+}
+
+/// Returns an encoder for `header`, given the id of the field before it.
+fn field_header_encoder(
+    last_id: i16,
+    header: ThriftFieldHeader,
+) -> Result<FixedEncoder, &'static str> {
+    if !(1..=15).contains(&header.field_type) {
+        return Err("thrift compact type is not between 1 and 15");
+    }
+    let delta = i32::from(header.id) - i32::from(last_id);
+    if (1..=15).contains(&delta) {
+        return Ok(FixedEncoder::u8((delta as u8) << 4 | header.field_type));
+    }
+    let id = zigzag_encode(i64::from(header.id));
+    let id_len = if id < 1 << 7 {
+        1
+    } else if id < 1 << 14 {
+        2
+    } else {
+        3
+    };
+    Ok(FixedEncoder::new(1 + id_len, |buf| {
+        buf[0] = header.field_type;
+        let mut id = id;
+        for b in &mut buf[1..] {
+            *b = id as u8 | 0x80;
+            id >>= 7;
+        }
+        buf[id_len] &= 0x7f;
+    }))
+}
+
+/// Future returned by
+/// [`read_thrift_field_header`](../trait.AsyncReadBytesExt.html#method.read_thrift_field_header).
+#[derive(Debug)]
+pub struct ReadThriftFieldHeader<R> {
+    src: R,
+    last_id: i16,
+    // the type of the field, once the first byte is in
+    field_type: Option<u8>,
+    decoder: VarintDecoder,
+}
+
+impl<R> ReadThriftFieldHeader<R> {
+    pub(crate) fn new(src: R, last_id: i16) -> Self {
+        ReadThriftFieldHeader {
+            src,
+            last_id,
+            field_type: None,
+            decoder: VarintDecoder::default(),
+        }
+    }
+}
+
+impl<R> Future for ReadThriftFieldHeader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Option<ThriftFieldHeader>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let field_type = match this.field_type {
+            Some(field_type) => field_type,
+            None => {
+                let mut b = [0; 1];
+                ready!(poll_read_exact(&mut this.src, cx, &mut b, &mut 0))?;
+                if b[0] == 0 {
+                    return Poll::Ready(Ok(None));
+                }
+                let field_type = b[0] & 0x0f;
+                if field_type == 0 {
+                    return Poll::Ready(Err(invalid("thrift field header has no type")));
+                }
+                let delta = b[0] >> 4;
+                if delta != 0 {
+                    let id = this
+                        .last_id
+                        .checked_add(i16::from(delta))
+                        .ok_or_else(|| invalid("thrift field id does not fit in an i16"))?;
+                    return Poll::Ready(Ok(Some(ThriftFieldHeader { id, field_type })));
+                }
+                *this.field_type.insert(field_type)
+            }
+        };
+        let id = decode_i16(ready!(this.decoder.poll_read(&mut this.src, cx))?)?;
+        Poll::Ready(Ok(Some(ThriftFieldHeader { id, field_type })))
+    }
+}
+
+/// Future returned by
+/// [`write_thrift_field_header`](../trait.AsyncWriteBytesExt.html#method.write_thrift_field_header).
+#[derive(Debug)]
+pub struct WriteThriftFieldHeader<W> {
+    dst: W,
+    // the encoded header, or why it cannot be encoded
+    encoder: Result<FixedEncoder, &'static str>,
+}
+
+impl<W> WriteThriftFieldHeader<W> {
+    pub(crate) fn new(dst: W, last_id: i16, header: ThriftFieldHeader) -> Self {
+        WriteThriftFieldHeader {
+            dst,
+            encoder: field_header_encoder(last_id, header),
+        }
+    }
+}
+
+impl<W> Future for WriteThriftFieldHeader<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.encoder {
+            Ok(encoder) => poll_write_encoder(Pin::new(&mut this.dst), cx, encoder),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
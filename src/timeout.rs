@@ -0,0 +1,141 @@
+use crate::*;
+use core::task::ready;
+use std::time::Duration;
+use tokio::time::{sleep, Sleep};
+
+impl<R: io::AsyncRead + Unpin> ByteReader<R> {
+    /// Reads an unsigned 8 bit integer, giving up if it does not arrive within `timeout`.
+    ///
+    /// Bytes are only consumed once the whole value has arrived. If the timeout elapses first,
+    /// any bytes that did arrive stay buffered, so the read can simply be retried without
+    /// desynchronizing the stream.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error of the kind [`ErrorKind::TimedOut`] if the timeout elapses,
+    /// and otherwise the same errors as [`Read::read_exact`].
+    ///
+    /// [`ErrorKind::TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut
+    /// [`Read::read_exact`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.read_exact
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use tokio::io::AsyncWriteExt;
+    /// use tokio_byteorder::{BigEndian, ByteReader};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (mut tx, rx) = tokio::io::duplex(64);
+    ///     let mut rdr = ByteReader::new(rx);
+    ///     let timeout = Duration::from_millis(10);
+    ///
+    ///     tx.write_all(&[0x00, 0x00]).await.unwrap();
+    ///     let err = rdr.read_u32_timeout::<BigEndian>(timeout).await.unwrap_err();
+    ///     assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    ///
+    ///     tx.write_all(&[0x01, 0x0b]).await.unwrap();
+    ///     assert_eq!(267, rdr.read_u32_timeout::<BigEndian>(timeout).await.unwrap());
+    /// }
+    /// ```
+    #[inline]
+    pub fn read_u8_timeout(&mut self, timeout: Duration) -> ReadTimeout<'_, R, u8> {
+        ReadTimeout::new(self, 1, |buf| buf[0], timeout)
+    }
+
+    /// Reads a signed 8 bit integer, giving up if it does not arrive within `timeout`.
+    ///
+    /// See [`read_u8_timeout`](#method.read_u8_timeout).
+    #[inline]
+    pub fn read_i8_timeout(&mut self, timeout: Duration) -> ReadTimeout<'_, R, i8> {
+        ReadTimeout::new(self, 1, |buf| buf[0] as i8, timeout)
+    }
+}
+
+macro_rules! timeout_impl {
+    ($(($name:ident, $decode:ident, $ty:ty, $bytes:expr, $desc:literal)),* $(,)?) => {
+        impl<R: io::AsyncRead + Unpin> ByteReader<R> {
+            $(
+                #[doc = concat!("Reads ", $desc, ", giving up if it does not arrive within `timeout`.")]
+                ///
+                /// See [`read_u8_timeout`](#method.read_u8_timeout).
+                #[inline]
+                pub fn $name<T: ByteOrder>(&mut self, timeout: Duration) -> ReadTimeout<'_, R, $ty> {
+                    ReadTimeout::new(self, $bytes, T::$decode, timeout)
+                }
+            )*
+        }
+    };
+}
+
+timeout_impl! {
+    (read_u16_timeout, read_u16, u16, 2, "an unsigned 16 bit integer"),
+    (read_i16_timeout, read_i16, i16, 2, "a signed 16 bit integer"),
+    (read_u24_timeout, read_u24, u32, 3, "an unsigned 24 bit integer"),
+    (read_i24_timeout, read_i24, i32, 3, "a signed 24 bit integer"),
+    (read_u32_timeout, read_u32, u32, 4, "an unsigned 32 bit integer"),
+    (read_i32_timeout, read_i32, i32, 4, "a signed 32 bit integer"),
+    (read_u48_timeout, read_u48, u64, 6, "an unsigned 48 bit integer"),
+    (read_i48_timeout, read_i48, i64, 6, "a signed 48 bit integer"),
+    (read_u64_timeout, read_u64, u64, 8, "an unsigned 64 bit integer"),
+    (read_i64_timeout, read_i64, i64, 8, "a signed 64 bit integer"),
+    (read_u128_timeout, read_u128, u128, 16, "an unsigned 128 bit integer"),
+    (read_i128_timeout, read_i128, i128, 16, "a signed 128 bit integer"),
+    (read_f32_timeout, read_f32, f32, 4, "a IEEE754 single-precision floating point number"),
+    (read_f64_timeout, read_f64, f64, 8, "a IEEE754 double-precision floating point number"),
+}
+
+/// Future returned by the `_timeout` read methods of [`ByteReader`](../struct.ByteReader.html).
+#[derive(Debug)]
+pub struct ReadTimeout<'a, R, V> {
+    src: &'a mut ByteReader<R>,
+    len: usize,
+    decode: fn(&[u8]) -> V,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl<'a, R, V> ReadTimeout<'a, R, V> {
+    fn new(
+        src: &'a mut ByteReader<R>,
+        len: usize,
+        decode: fn(&[u8]) -> V,
+        timeout: Duration,
+    ) -> Self {
+        ReadTimeout {
+            src,
+            len,
+            decode,
+            sleep: Box::pin(sleep(timeout)),
+        }
+    }
+}
+
+impl<R, V> Future for ReadTimeout<'_, R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match this.src.poll_fill_to(cx, this.len) {
+            Poll::Ready(Ok(true)) => {
+                let v = (this.decode)(&this.src.buffer()[..this.len]);
+                io::AsyncBufRead::consume(Pin::new(&mut *this.src), this.len);
+                Poll::Ready(Ok(v))
+            }
+            Poll::Ready(Ok(false)) => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => {
+                ready!(this.sleep.as_mut().poll(cx));
+                Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "timed out waiting for value",
+                )))
+            }
+        }
+    }
+}
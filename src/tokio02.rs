@@ -0,0 +1,81 @@
+//! Extension traits for the I/O traits of tokio 0.2.
+//!
+//! The traits in this module provide the same fixed-width reading and writing methods as the
+//! crate-level [`AsyncReadBytesExt`] and [`AsyncWriteBytesExt`], but for types that implement
+//! the `AsyncRead` and `AsyncWrite` traits from tokio 0.2. This lets applications that have not
+//! yet moved off tokio 0.2 share a version of this crate with libraries built on tokio 1.x.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tokio_byteorder::tokio02::{AsyncReadBytesExt, AsyncWriteBytesExt};
+//! use tokio_byteorder::BigEndian;
+//!
+//! futures_lite::future::block_on(async {
+//!     let mut wtr = Vec::new();
+//!     wtr.write_u32::<BigEndian>(267).await.unwrap();
+//!     let mut rdr = &wtr[..];
+//!     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+//! });
+//! ```
+//!
+//! [`AsyncReadBytesExt`]: ../trait.AsyncReadBytesExt.html
+//! [`AsyncWriteBytesExt`]: ../trait.AsyncWriteBytesExt.html
+
+use crate::compat::{AsyncByteSink, AsyncByteSource, Compat};
+use crate::*;
+use core::task::ready;
+
+/// Marks streams that implement the I/O traits of tokio 0.2.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum Tokio02 {}
+
+impl<R> AsyncByteSource<Tokio02> for R
+where
+    R: ::tokio02::io::AsyncRead + Unpin + ?Sized,
+{
+    fn poll_read_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let n = ready!(::tokio02::io::AsyncRead::poll_read(
+            self,
+            cx,
+            buf.initialize_unfilled()
+        ))?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncByteSink<Tokio02> for W
+where
+    W: ::tokio02::io::AsyncWrite + Unpin + ?Sized,
+{
+    fn poll_write_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        ::tokio02::io::AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ::tokio02::io::AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_close_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ::tokio02::io::AsyncWrite::poll_shutdown(self, cx)
+    }
+}
+
+compat_ext!(
+    Tokio02,
+    ::tokio02::io::AsyncRead,
+    ::tokio02::io::AsyncWrite,
+    "tokio::io::AsyncRead",
+    "tokio::io::AsyncWrite",
+    "https://docs.rs/tokio/0.2/tokio/io"
+);
@@ -0,0 +1,79 @@
+//! Extension traits for the I/O traits of tokio 0.3.
+//!
+//! The traits in this module provide the same fixed-width reading and writing methods as the
+//! crate-level [`AsyncReadBytesExt`] and [`AsyncWriteBytesExt`], but for types that implement
+//! the `AsyncRead` and `AsyncWrite` traits from tokio 0.3. This lets applications that have not
+//! yet moved off tokio 0.3 share a version of this crate with libraries built on tokio 1.x.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tokio_byteorder::tokio03::{AsyncReadBytesExt, AsyncWriteBytesExt};
+//! use tokio_byteorder::BigEndian;
+//!
+//! futures_lite::future::block_on(async {
+//!     let mut wtr = Vec::new();
+//!     wtr.write_u32::<BigEndian>(267).await.unwrap();
+//!     let mut rdr = &wtr[..];
+//!     assert_eq!(267, rdr.read_u32::<BigEndian>().await.unwrap());
+//! });
+//! ```
+//!
+//! [`AsyncReadBytesExt`]: ../trait.AsyncReadBytesExt.html
+//! [`AsyncWriteBytesExt`]: ../trait.AsyncWriteBytesExt.html
+
+use crate::compat::{AsyncByteSink, AsyncByteSource, Compat};
+use crate::*;
+use core::task::ready;
+
+/// Marks streams that implement the I/O traits of tokio 0.3.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum Tokio03 {}
+
+impl<R> AsyncByteSource<Tokio03> for R
+where
+    R: ::tokio03::io::AsyncRead + Unpin + ?Sized,
+{
+    fn poll_read_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut inner = ::tokio03::io::ReadBuf::new(buf.initialize_unfilled());
+        ready!(::tokio03::io::AsyncRead::poll_read(self, cx, &mut inner))?;
+        let n = inner.filled().len();
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncByteSink<Tokio03> for W
+where
+    W: ::tokio03::io::AsyncWrite + Unpin + ?Sized,
+{
+    fn poll_write_bytes(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        ::tokio03::io::AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ::tokio03::io::AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_close_bytes(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ::tokio03::io::AsyncWrite::poll_shutdown(self, cx)
+    }
+}
+
+compat_ext!(
+    Tokio03,
+    ::tokio03::io::AsyncRead,
+    ::tokio03::io::AsyncWrite,
+    "tokio::io::AsyncRead",
+    "tokio::io::AsyncWrite",
+    "https://docs.rs/tokio/0.3/tokio/io"
+);
@@ -0,0 +1,58 @@
+//! `tracing` events for the values that pass through this crate.
+//!
+//! Every event is emitted at the `TRACE` level with the `tokio_byteorder` target, so that they
+//! can be turned on with `RUST_LOG=tokio_byteorder=trace` in most subscribers.
+
+use core::fmt;
+
+/// Returns the name of the byte order type `T`, without its path.
+pub(crate) fn endian<T>() -> &'static str {
+    let name = core::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Formats bytes as space-separated hex pairs.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports a value that was decoded from `bytes`, which started at `offset` if that is known.
+pub(crate) fn read(
+    ty: &str,
+    endian: Option<&str>,
+    value: &dyn fmt::Debug,
+    bytes: &[u8],
+    offset: Option<u64>,
+) {
+    tracing::trace!(
+        target: "tokio_byteorder",
+        ty,
+        endian,
+        value = ?value,
+        offset,
+        bytes = %Hex(bytes),
+        "read",
+    );
+}
+
+/// Reports a value that was encoded to `bytes` to be written.
+pub(crate) fn write(ty: &str, endian: Option<&str>, value: &dyn fmt::Debug, bytes: &[u8]) {
+    tracing::trace!(
+        target: "tokio_byteorder",
+        ty,
+        endian,
+        value = ?value,
+        bytes = %Hex(bytes),
+        "write",
+    );
+}
@@ -0,0 +1,115 @@
+use crate::sans_io::MAX_LEN;
+use crate::*;
+use core::fmt;
+use core::task::ready;
+
+/// The most fields that a [`PrimitiveTuple`] can have.
+const MAX_FIELDS: usize = 12;
+
+mod sealed {
+    use byteorder::ByteOrder;
+
+    /// The encoded layout of a tuple of primitives.
+    pub trait Fields: Sized {
+        /// The total encoded length of the fields, in bytes.
+        fn len<T: ByteOrder>() -> usize;
+        /// Decodes the fields from exactly `len::<T>()` bytes.
+        fn decode<T: ByteOrder>(buf: &[u8]) -> Self;
+    }
+}
+
+use sealed::Fields;
+
+/// A tuple of [`Primitive`] values that can be read all at once.
+///
+/// This is implemented for tuples of one to twelve `Primitive`s, and cannot be implemented
+/// outside of this crate. See [`read_tuple`].
+///
+/// [`Primitive`]: trait.Primitive.html
+/// [`read_tuple`]: trait.AsyncReadBytesExt.html#method.read_tuple
+pub trait PrimitiveTuple: Fields {}
+
+macro_rules! tuple_impl {
+    ($(($($field:ident),+))*) => {
+        $(
+            impl<$($field: Primitive),+> Fields for ($($field,)+) {
+                fn len<T: ByteOrder>() -> usize {
+                    0 $(+ $field::decoder::<T>().remaining())+
+                }
+
+                fn decode<T: ByteOrder>(mut buf: &[u8]) -> Self {
+                    ($(
+                        $field::decoder::<T>()
+                            .push_bytes(&mut buf)
+                            .expect("the buffer holds every field"),
+                    )+)
+                }
+            }
+
+            impl<$($field: Primitive),+> PrimitiveTuple for ($($field,)+) {}
+        )*
+    };
+}
+
+tuple_impl! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+/// Future returned by
+/// [`read_tuple`](../trait.AsyncReadBytesExt.html#method.read_tuple).
+pub struct ReadTuple<R, V> {
+    src: R,
+    buf: [u8; MAX_FIELDS * MAX_LEN],
+    len: usize,
+    filled: usize,
+    decode: fn(&[u8]) -> V,
+}
+
+impl<R, V> ReadTuple<R, V> {
+    pub(crate) fn new<T: ByteOrder>(src: R) -> Self
+    where
+        V: PrimitiveTuple,
+    {
+        ReadTuple {
+            src,
+            buf: [0; MAX_FIELDS * MAX_LEN],
+            len: V::len::<T>(),
+            filled: 0,
+            decode: V::decode::<T>,
+        }
+    }
+}
+
+impl<R: fmt::Debug, V> fmt::Debug for ReadTuple<R, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadTuple")
+            .field("src", &self.src)
+            .field("buffered", &&self.buf[..self.filled])
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<R, V> Future for ReadTuple<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let buf = &mut this.buf[..this.len];
+        ready!(poll_read_exact(&mut this.src, cx, buf, &mut this.filled))?;
+        Poll::Ready(Ok((this.decode)(buf)))
+    }
+}
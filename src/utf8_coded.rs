@@ -0,0 +1,132 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::task::ready;
+
+/// The largest value a UTF-8 coded number can hold, in its seven-byte form.
+const MAX_UTF8_CODED: u64 = (1 << 36) - 1;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Returns the number of bytes in the number that starts with `lead`, or `None` if no number
+/// starts with it.
+fn coded_len(lead: u8) -> Option<usize> {
+    match lead.leading_ones() {
+        0 => Some(1),
+        1 | 8 => None,
+        n => Some(n as usize),
+    }
+}
+
+/// Returns an encoder for `n` in the shortest form that holds it.
+fn utf8_coded_encoder(n: u64) -> Result<FixedEncoder, &'static str> {
+    if n > MAX_UTF8_CODED {
+        return Err("number does not fit in 36 bits of utf-8 coding");
+    }
+    if n < 0x80 {
+        return Ok(FixedEncoder::u8(n as u8));
+    }
+    // each continuation byte holds six bits, and the lead byte 6 - len more
+    let len = (2..=7).find(|&len| n >> (5 * len + 1) == 0).unwrap();
+    Ok(FixedEncoder::new(len, |buf| {
+        let mut rest = n;
+        for b in buf[1..].iter_mut().rev() {
+            *b = 0x80 | (rest as u8 & 0x3f);
+            rest >>= 6;
+        }
+        buf[0] = !(0xffu8 >> len) | rest as u8;
+    }))
+}
+
+/// Future returned by
+/// [`read_utf8_coded_u64`](../trait.AsyncReadBytesExt.html#method.read_utf8_coded_u64).
+#[derive(Debug)]
+pub struct ReadUtf8Coded<R> {
+    src: R,
+    buf: [u8; 7],
+    filled: usize,
+}
+
+impl<R> ReadUtf8Coded<R> {
+    pub(crate) fn new(src: R) -> Self {
+        ReadUtf8Coded {
+            src,
+            buf: [0; 7],
+            filled: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadUtf8Coded<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<u64>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.filled == 0 {
+            ready!(poll_read_exact(
+                &mut this.src,
+                cx,
+                &mut this.buf[..1],
+                &mut this.filled
+            ))?;
+        }
+        let len = match coded_len(this.buf[0]) {
+            Some(len) => len,
+            None => return Poll::Ready(Err(invalid("invalid UTF-8 coded number lead byte"))),
+        };
+        ready!(poll_read_exact(
+            &mut this.src,
+            cx,
+            &mut this.buf[..len],
+            &mut this.filled
+        ))?;
+        let lead = this.buf[0];
+        let mut n = if len == 1 {
+            u64::from(lead)
+        } else {
+            u64::from(lead & (0x7f >> len))
+        };
+        for &b in &this.buf[1..len] {
+            if b & 0xc0 != 0x80 {
+                return Poll::Ready(Err(invalid("invalid UTF-8 coded number continuation byte")));
+            }
+            n = n << 6 | u64::from(b & 0x3f);
+        }
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// Future returned by
+/// [`write_utf8_coded_u64`](../trait.AsyncWriteBytesExt.html#method.write_utf8_coded_u64).
+#[derive(Debug)]
+pub struct WriteUtf8Coded<W> {
+    dst: W,
+    // the encoded number, or why it cannot be encoded
+    encoder: Result<FixedEncoder, &'static str>,
+}
+
+impl<W> WriteUtf8Coded<W> {
+    pub(crate) fn new(dst: W, n: u64) -> Self {
+        WriteUtf8Coded {
+            dst,
+            encoder: utf8_coded_encoder(n),
+        }
+    }
+}
+
+impl<W> Future for WriteUtf8Coded<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.encoder {
+            Ok(encoder) => poll_write_encoder(Pin::new(&mut this.dst), cx, encoder),
+            Err(msg) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, *msg))),
+        }
+    }
+}
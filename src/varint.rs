@@ -0,0 +1,208 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::task::ready;
+use std::vec::Vec;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Maps a signed integer to an unsigned one so that values close to zero, of either sign,
+/// map to small values.
+///
+/// Zero maps to zero, -1 to 1, 1 to 2, -2 to 3, and so on. Formats that store signed
+/// integers in varints, like protocol buffers' `sint64`, Avro, and Thrift's compact protocol,
+/// zigzag encode them first, so that a small negative value takes a byte or two rather than
+/// the ten a two's complement `u64` would.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio_byteorder::{zigzag_decode, zigzag_encode};
+///
+/// assert_eq!(zigzag_encode(-1), 1);
+/// assert_eq!(zigzag_encode(i64::MIN), u64::MAX);
+/// assert_eq!(zigzag_decode(4), 2);
+/// ```
+pub fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+/// Undoes [`zigzag_encode`](fn.zigzag_encode.html).
+pub fn zigzag_decode(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+/// Appends `n` to `out` as an unsigned LEB128 varint.
+pub(crate) fn encode_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Returns an encoder for `n` as an unsigned LEB128 varint.
+pub(crate) fn varint_encoder(n: u64) -> FixedEncoder {
+    let len = (64 - (n | 1).leading_zeros() as usize).div_ceil(7);
+    FixedEncoder::new(len, |buf| {
+        let mut n = n;
+        for b in buf.iter_mut() {
+            *b = n as u8 | 0x80;
+            n >>= 7;
+        }
+        buf[len - 1] &= 0x7f;
+    })
+}
+
+/// The state of an unsigned LEB128 varint that is partway decoded.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VarintDecoder {
+    value: u64,
+    shift: u32,
+}
+
+impl VarintDecoder {
+    /// Adds the next byte of the varint, and returns its value if that was the last byte.
+    pub(crate) fn push(&mut self, b: u8) -> io::Result<Option<u64>> {
+        let bits = u64::from(b & 0x7f);
+        if self.shift > 63 || (self.shift == 63 && bits > 1) {
+            return Err(invalid("varint overflows a u64"));
+        }
+        self.value |= bits << self.shift;
+        self.shift += 7;
+        if b & 0x80 != 0 {
+            return Ok(None);
+        }
+        let value = self.value;
+        *self = VarintDecoder::default();
+        Ok(Some(value))
+    }
+
+    /// Reads bytes from `src` until a whole varint has been decoded.
+    pub(crate) fn poll_read<R>(
+        &mut self,
+        src: &mut R,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<u64>>
+    where
+        R: io::AsyncRead + Unpin + ?Sized,
+    {
+        loop {
+            let mut b = [0; 1];
+            ready!(poll_read_exact(src, cx, &mut b, &mut 0))?;
+            if let Some(n) = self.push(b[0])? {
+                return Poll::Ready(Ok(n));
+            }
+        }
+    }
+}
+
+/// Future returned by
+/// [`read_varint_u64`](../trait.AsyncReadBytesExt.html#method.read_varint_u64),
+/// [`read_zigzag_varint`](../trait.AsyncReadBytesExt.html#method.read_zigzag_varint), and the
+/// methods built on them.
+#[derive(Debug)]
+pub struct ReadVarint<R, V> {
+    src: R,
+    decoder: VarintDecoder,
+    map: fn(u64) -> io::Result<V>,
+}
+
+impl<R, V> ReadVarint<R, V> {
+    pub(crate) fn new(src: R, map: fn(u64) -> io::Result<V>) -> Self {
+        ReadVarint {
+            src,
+            decoder: VarintDecoder::default(),
+            map,
+        }
+    }
+}
+
+impl<R, V> Future for ReadVarint<R, V>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<V>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let n = ready!(this.decoder.poll_read(&mut this.src, cx))?;
+        Poll::Ready((this.map)(n))
+    }
+}
+
+/// Future returned by
+/// [`read_delta_varint_sequence`](../trait.AsyncReadBytesExt.html#method.read_delta_varint_sequence).
+#[derive(Debug)]
+pub struct ReadDeltaVarints<R> {
+    src: R,
+    decoder: VarintDecoder,
+    count: usize,
+    values: Vec<i64>,
+    prev: i64,
+}
+
+impl<R> ReadDeltaVarints<R> {
+    pub(crate) fn new(src: R, count: usize) -> Self {
+        ReadDeltaVarints {
+            src,
+            decoder: VarintDecoder::default(),
+            count,
+            // `count` may come off the wire, so only trust it so far
+            values: Vec::with_capacity(count.min(4096)),
+            prev: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadDeltaVarints<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    type Output = io::Result<Vec<i64>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        while this.values.len() < this.count {
+            let delta = ready!(this.decoder.poll_read(&mut this.src, cx))?;
+            this.prev = this.prev.wrapping_add(zigzag_decode(delta));
+            this.values.push(this.prev);
+        }
+        Poll::Ready(Ok(core::mem::take(&mut this.values)))
+    }
+}
+
+/// Future returned by
+/// [`write_delta_varint_sequence`](../trait.AsyncWriteBytesExt.html#method.write_delta_varint_sequence).
+#[derive(Debug)]
+pub struct WriteVarints<W> {
+    dst: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> WriteVarints<W> {
+    pub(crate) fn delta(dst: W, src: &[i64]) -> Self {
+        let mut buf = Vec::with_capacity(src.len() * 2);
+        let mut prev = 0i64;
+        for &n in src {
+            encode_varint(zigzag_encode(n.wrapping_sub(prev)), &mut buf);
+            prev = n;
+        }
+        WriteVarints {
+            dst,
+            buf,
+            written: 0,
+        }
+    }
+}
+
+impl<W> Future for WriteVarints<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_write_all(&mut this.dst, cx, &this.buf, &mut this.written)
+    }
+}
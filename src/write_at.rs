@@ -0,0 +1,279 @@
+use crate::sans_io::FixedEncoder;
+use crate::*;
+use core::fmt;
+use core::task::ready;
+use std::io::SeekFrom;
+
+macro_rules! reserve_impl {
+    ($(($name:ident, $ty:ty, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Writes a placeholder for ", $desc, ", to be filled in later.")]
+            ///
+            /// # Errors
+            ///
+            /// This method returns the same errors as [`reserve_primitive`](#method.reserve_primitive).
+            #[inline]
+            fn $name<T: ByteOrder>(&mut self) -> Reserve<&mut Self, $ty, T>
+            where
+                Self: Unpin,
+            {
+                Reserve::new(self)
+            }
+        )*
+    };
+}
+
+/// Extends [`AsyncWrite`] + [`AsyncSeek`] with methods for writing values that are only known
+/// later.
+///
+/// File formats are full of sizes and offsets that come before the data they describe, like
+/// the length of a chunk or the position of an index at the end of a file. The `reserve_`
+/// methods write zeros where such a value goes and return a [`Placeholder`] that remembers
+/// where that was. Once the value is known, [`Placeholder::fill`] seeks back, writes it, and
+/// returns to where the stream was, so writing can carry on.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use tokio_byteorder::{AsyncWriteAtBytesExt, AsyncWriteBytesExt, BigEndian};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut file = Cursor::new(Vec::new());
+///     let size = file.reserve_u32::<BigEndian>().await.unwrap();
+///     file.write_u16::<BigEndian>(1).await.unwrap();
+///     file.write_u16::<BigEndian>(2).await.unwrap();
+///     size.fill(&mut file, 4).await.unwrap();
+///     file.write_u8(3).await.unwrap();
+///     assert_eq!(file.into_inner(), [0, 0, 0, 4, 0, 1, 0, 2, 3]);
+/// }
+/// ```
+///
+/// [`Placeholder`]: struct.Placeholder.html
+/// [`Placeholder::fill`]: struct.Placeholder.html#method.fill
+/// [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`AsyncSeek`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncSeek.html
+pub trait AsyncWriteAtBytesExt: io::AsyncWrite + io::AsyncSeek {
+    /// Writes a placeholder for a number of any [`Primitive`] type, to be filled in later.
+    ///
+    /// The placeholder is as wide as a `V`, and is written as zeros.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Seek::seek`] and [`Write::write_all`].
+    ///
+    /// [`Primitive`]: trait.Primitive.html
+    /// [`Seek::seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html#tymethod.seek
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    #[inline]
+    fn reserve_primitive<V: Primitive, T: ByteOrder>(&mut self) -> Reserve<&mut Self, V, T>
+    where
+        Self: Unpin,
+    {
+        Reserve::new(self)
+    }
+
+    reserve_impl! {
+        (reserve_u8, u8, "an unsigned 8 bit integer"),
+        (reserve_u16, u16, "an unsigned 16 bit integer"),
+        (reserve_u32, u32, "an unsigned 32 bit integer"),
+        (reserve_u64, u64, "an unsigned 64 bit integer"),
+    }
+}
+
+/// All types that implement `AsyncWrite` and `AsyncSeek` get methods defined in
+/// `AsyncWriteAtBytesExt` for free.
+impl<W: io::AsyncWrite + io::AsyncSeek + ?Sized> AsyncWriteAtBytesExt for W {}
+
+/// A spot in a stream where a value of type `V` in the byte order `T` is still to be written.
+///
+/// Returned by the `reserve_` methods of
+/// [`AsyncWriteAtBytesExt`](trait.AsyncWriteAtBytesExt.html).
+pub struct Placeholder<V, T> {
+    pos: u64,
+    bo: PhantomData<fn(V) -> T>,
+}
+
+impl<V, T> Placeholder<V, T> {
+    /// Returns the offset in the stream at which the value goes.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+}
+
+impl<V: Primitive, T: ByteOrder> Placeholder<V, T> {
+    /// Seeks back to the placeholder, writes `v` there, and seeks back to where `dst` was.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Seek::seek`] and [`Write::write_all`]. If it
+    /// fails, the position of `dst` is unspecified.
+    ///
+    /// [`Seek::seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html#tymethod.seek
+    /// [`Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    pub fn fill<W>(self, dst: &mut W, v: V) -> Fill<&mut W>
+    where
+        W: io::AsyncWrite + io::AsyncSeek + Unpin + ?Sized,
+    {
+        Fill {
+            dst,
+            pos: self.pos,
+            back: 0,
+            state: State::Start,
+            encoder: v.encoder::<T>(),
+        }
+    }
+}
+
+impl<V, T> fmt::Debug for Placeholder<V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Placeholder")
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    Locating,
+    Seeking,
+    Writing,
+    Returning,
+    Finishing,
+}
+
+/// Starts a seek once any seek that is still in flight has finished.
+fn poll_start_seek<W>(dst: &mut W, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<io::Result<()>>
+where
+    W: io::AsyncSeek + Unpin + ?Sized,
+{
+    ready!(Pin::new(&mut *dst).poll_complete(cx))?;
+    Poll::Ready(Pin::new(dst).start_seek(pos))
+}
+
+/// Future returned by the methods of
+/// [`AsyncWriteAtBytesExt`](../trait.AsyncWriteAtBytesExt.html).
+pub struct Reserve<W, V, T> {
+    dst: W,
+    pos: u64,
+    state: State,
+    encoder: FixedEncoder,
+    bo: PhantomData<fn(V) -> T>,
+}
+
+impl<W, V: Primitive, T: ByteOrder> Reserve<W, V, T> {
+    fn new(dst: W) -> Self {
+        Reserve {
+            dst,
+            pos: 0,
+            state: State::Start,
+            encoder: FixedEncoder::new(V::decoder::<T>().remaining(), |_| {}),
+            bo: PhantomData,
+        }
+    }
+}
+
+impl<W: fmt::Debug, V, T> fmt::Debug for Reserve<W, V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reserve")
+            .field("dst", &self.dst)
+            .field("pos", &self.pos)
+            .field("state", &self.state)
+            .field("encoder", &self.encoder)
+            .finish()
+    }
+}
+
+impl<W, V, T> Future for Reserve<W, V, T>
+where
+    W: io::AsyncWrite + io::AsyncSeek + Unpin,
+{
+    type Output = io::Result<Placeholder<V, T>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match this.state {
+                State::Start => {
+                    ready!(poll_start_seek(&mut this.dst, cx, SeekFrom::Current(0)))?;
+                    this.state = State::Locating;
+                }
+                State::Locating => {
+                    this.pos = ready!(Pin::new(&mut this.dst).poll_complete(cx))?;
+                    this.state = State::Writing;
+                }
+                _ => {
+                    ready!(poll_write_encoder(
+                        Pin::new(&mut this.dst),
+                        cx,
+                        &mut this.encoder
+                    ))?;
+                    return Poll::Ready(Ok(Placeholder {
+                        pos: this.pos,
+                        bo: PhantomData,
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Future returned by [`Placeholder::fill`](../struct.Placeholder.html#method.fill).
+#[derive(Debug)]
+pub struct Fill<W> {
+    dst: W,
+    pos: u64,
+    // where to return to once the value is written
+    back: u64,
+    state: State,
+    encoder: FixedEncoder,
+}
+
+impl<W> Future for Fill<W>
+where
+    W: io::AsyncWrite + io::AsyncSeek + Unpin,
+{
+    type Output = io::Result<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match this.state {
+                State::Start => {
+                    ready!(poll_start_seek(&mut this.dst, cx, SeekFrom::Current(0)))?;
+                    this.state = State::Locating;
+                }
+                State::Locating => {
+                    this.back = ready!(Pin::new(&mut this.dst).poll_complete(cx))?;
+                    Pin::new(&mut this.dst).start_seek(SeekFrom::Start(this.pos))?;
+                    this.state = State::Seeking;
+                }
+                State::Seeking => {
+                    ready!(Pin::new(&mut this.dst).poll_complete(cx))?;
+                    this.state = State::Writing;
+                }
+                State::Writing => {
+                    ready!(poll_write_encoder(
+                        Pin::new(&mut this.dst),
+                        cx,
+                        &mut this.encoder
+                    ))?;
+                    this.state = State::Returning;
+                }
+                State::Returning => {
+                    // the write may still be in flight, as with a `tokio::fs::File`
+                    ready!(poll_start_seek(
+                        &mut this.dst,
+                        cx,
+                        SeekFrom::Start(this.back)
+                    ))?;
+                    this.state = State::Finishing;
+                }
+                State::Finishing => {
+                    ready!(Pin::new(&mut this.dst).poll_complete(cx))?;
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "crc")]
+
+use tokio_byteorder::{
+    Adler32, AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, Checksum, Crc16, CrcReader,
+    CrcWriter,
+};
+
+#[test]
+fn adler32() {
+    let mut a = Adler32::new();
+    a.update(b"Wiki");
+    a.update(b"pedia");
+    assert_eq!(a.value(), 0x11e60398);
+    a.reset();
+    assert_eq!(a.value(), 1);
+
+    // long enough to exercise the deferred modulo
+    let mut a = Adler32::new();
+    a.update(&[0xff; 20000]);
+    assert_eq!(a.value(), 0x9f51_d664);
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let mut w = CrcWriter::new(Vec::new(), Crc16::xmodem());
+    w.write_u8(0x31).await.unwrap();
+    w.write_u64::<BigEndian>(0x3233_3435_3637_3839)
+        .await
+        .unwrap();
+    // CRC-16/XMODEM check value for "123456789"
+    assert_eq!(w.checksum(), 0x31c3);
+    let crc = w.checksum();
+    w.get_mut().write_u16::<BigEndian>(crc).await.unwrap();
+
+    let bytes = w.into_inner();
+    let mut r = CrcReader::new(&bytes[..], Crc16::xmodem());
+    r.read_u8().await.unwrap();
+    r.read_u64::<BigEndian>().await.unwrap();
+    let expected = r.get_mut().read_u16::<BigEndian>().await.unwrap();
+    r.verify(expected).unwrap();
+    assert!(r.verify(expected ^ 1).is_err());
+    r.reset();
+    assert_eq!(r.checksum(), 0);
+}
+
+#[tokio::test]
+async fn checked_header() {
+    use tokio_byteorder::{ByteOrder, Crc32, LittleEndian};
+
+    let mut w = CrcWriter::new(Vec::new(), Crc32::iscsi());
+    w.write_u16::<LittleEndian>(7).await.unwrap();
+    w.write_u32::<LittleEndian>(1000).await.unwrap();
+    let crc = w.checksum();
+    w.get_mut().write_u32::<LittleEndian>(crc).await.unwrap();
+    let mut bytes = w.into_inner();
+    bytes.extend_from_slice(b"body");
+
+    let mut r = tokio::io::BufReader::with_capacity(1, &bytes[..]);
+    let header = r
+        .read_checked_header::<_, LittleEndian, _, _>(6, Crc32::iscsi(), |header| {
+            (
+                LittleEndian::read_u16(header),
+                LittleEndian::read_u32(&header[2..]),
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(header, (7, 1000));
+    let mut body = [0; 4];
+    tokio::io::AsyncReadExt::read_exact(&mut r, &mut body)
+        .await
+        .unwrap();
+    assert_eq!(&body, b"body");
+
+    // a checksum that is already running is restarted
+    let mut checksum = Crc32::iscsi();
+    checksum.update(b"stale");
+    let mut r = &bytes[..];
+    let header = r
+        .read_checked_header::<_, LittleEndian, _, _>(6, checksum, |header| header.to_vec())
+        .await
+        .unwrap();
+    assert_eq!(header, bytes[..6]);
+
+    bytes[1] ^= 0x80;
+    let mut r = &bytes[..];
+    let err = r
+        .read_checked_header::<_, LittleEndian, _, _>(6, Crc32::iscsi(), |_| {
+            panic!("a damaged header is parsed")
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut r = &bytes[..8];
+    let err = r
+        .read_checked_header::<_, LittleEndian, _, _>(6, Crc32::iscsi(), |_| ())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[tokio::test]
+async fn inet_checksum_write() {
+    use tokio_byteorder::InetChecksum;
+
+    // a UDP header and payload with its pseudo-header fed in first
+    let mut pseudo = InetChecksum::new();
+    pseudo.update(&[192, 168, 0, 1, 192, 168, 0, 199, 0, 17, 0, 10]);
+    let mut w = CrcWriter::new(Vec::new(), pseudo);
+    w.write_u16::<BigEndian>(1234).await.unwrap();
+    w.write_u16::<BigEndian>(53).await.unwrap();
+    w.write_u16::<BigEndian>(10).await.unwrap();
+    w.write_u16::<BigEndian>(0).await.unwrap();
+    w.write_u8(b'h').await.unwrap();
+    w.write_u8(b'i').await.unwrap();
+    let sum = w.checksum();
+    let mut bytes = w.into_inner();
+    bytes[6..8].copy_from_slice(&sum.to_be_bytes());
+
+    let mut check = InetChecksum::new();
+    check.update(&[192, 168, 0, 1, 192, 168, 0, 199, 0, 17, 0, 10]);
+    let mut r = CrcReader::new(&bytes[..], check);
+    let mut all = Vec::new();
+    tokio::io::AsyncReadExt::read_to_end(&mut r, &mut all)
+        .await
+        .unwrap();
+    r.verify(0).unwrap();
+}
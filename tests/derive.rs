@@ -0,0 +1,94 @@
+#![cfg(feature = "derive")]
+
+use tokio_byteorder::{AsyncDecode, AsyncEncode};
+
+#[derive(Debug, Clone, PartialEq, AsyncEncode, AsyncDecode)]
+struct Point(i16, #[byteorder(endian = "little")] i16);
+
+#[derive(Debug, PartialEq, AsyncEncode, AsyncDecode)]
+#[byteorder(endian = "little")]
+struct Shape {
+    kind: u8,
+    #[byteorder(width = 48)]
+    id: i64,
+    #[byteorder(width = 24, endian = "big")]
+    area: i32,
+    #[byteorder(pad = 2)]
+    scale: f32,
+    origin: Point,
+    #[byteorder(len = "u16")]
+    points: Vec<Point>,
+    #[byteorder(len = "u8")]
+    tags: Vec<[u8; 2]>,
+}
+
+#[derive(Debug, PartialEq, AsyncEncode, AsyncDecode)]
+struct Wrapper<T> {
+    count: u32,
+    inner: T,
+}
+
+#[derive(Debug, PartialEq, AsyncEncode, AsyncDecode)]
+struct Empty {}
+
+#[tokio::test]
+async fn round_trip() {
+    let shape = Shape {
+        kind: 3,
+        id: -5,
+        area: -70000,
+        scale: 1.5,
+        origin: Point(1, 2),
+        points: vec![Point(-1, 256), Point(7, -7)],
+        tags: vec![*b"ab"],
+    };
+    let mut buf = Vec::new();
+    shape.write_to(&mut buf).await.unwrap();
+    assert_eq!(buf.len(), 1 + 6 + 3 + 2 + 4 + 4 + 2 + 8 + 1 + 2);
+    assert_eq!(&buf[..10], b"\x03\xfb\xff\xff\xff\xff\xff\xfe\xee\x90");
+    assert_eq!(&buf[10..12], [0, 0]);
+    // the point's first field is big-endian, its second little-endian
+    assert_eq!(&buf[16..20], [0, 1, 2, 0]);
+    assert_eq!(Shape::read_from(&mut &buf[..]).await.unwrap(), shape);
+}
+
+#[tokio::test]
+async fn generic_and_empty() {
+    let w = Wrapper {
+        count: 2,
+        inner: Point(3, 4),
+    };
+    let mut buf = Vec::new();
+    w.write_to(&mut buf).await.unwrap();
+    Empty {}.write_to(&mut buf).await.unwrap();
+    assert_eq!(buf, [0, 0, 0, 2, 0, 3, 4, 0]);
+    let mut r = &buf[..];
+    assert_eq!(Wrapper::<Point>::read_from(&mut r).await.unwrap(), w);
+    assert_eq!(Empty::read_from(&mut r).await.unwrap(), Empty {});
+}
+
+#[tokio::test]
+async fn errors() {
+    #[derive(AsyncEncode, AsyncDecode)]
+    struct Short {
+        #[byteorder(len = "u8")]
+        data: Vec<u8>,
+    }
+
+    let too_long = Short { data: vec![0; 256] };
+    let err = too_long.write_to(&mut Vec::new()).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let err = Short::read_from(&mut &[3, 1, 2][..]).await.err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[tokio::test]
+async fn send() {
+    let handle = tokio::spawn(async {
+        let (mut a, mut b) = tokio::io::duplex(64);
+        Point(5, 6).write_to(&mut a).await.unwrap();
+        Point::read_from(&mut b).await.unwrap()
+    });
+    assert_eq!(handle.await.unwrap(), Point(5, 6));
+}
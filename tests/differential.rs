@@ -0,0 +1,170 @@
+#![cfg(feature = "test_util")]
+
+//! Decodes random byte strings with both the synchronous `byteorder` crate and this crate's
+//! futures, and checks that they agree on every value and every error.
+//!
+//! The futures are driven over chunked readers, so that values are split across reads at
+//! arbitrary points.
+
+use byteorder::ReadBytesExt as _;
+use futures_lite::future::block_on;
+use proptest::prelude::*;
+use std::io::{self, Cursor};
+use tokio_byteorder::test_util::ChunkedReader;
+use tokio_byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+/// The outcome of a single read, with floats compared by their bits.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U24(u32),
+    I24(i32),
+    U32(u32),
+    I32(i32),
+    U48(u64),
+    I48(i64),
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    I128(i128),
+    F32(u32),
+    F64(u64),
+    Err(io::ErrorKind),
+}
+
+fn outcome<V>(res: io::Result<V>, f: impl FnOnce(V) -> Outcome) -> Outcome {
+    match res {
+        Ok(v) => f(v),
+        Err(e) => Outcome::Err(e.kind()),
+    }
+}
+
+macro_rules! ops {
+    ($($read:ident => $variant:expr),* $(,)?) => {
+        const OPS: usize = 2 + [$(stringify!($read)),*].len();
+
+        fn sync_read<T: ByteOrder>(op: usize, rdr: &mut Cursor<&[u8]>) -> Outcome {
+            match op {
+                0 => outcome(rdr.read_u8(), Outcome::U8),
+                1 => outcome(rdr.read_i8(), Outcome::I8),
+                _ => {
+                    let mut i = 2;
+                    $(
+                        if op == i {
+                            return outcome(rdr.$read::<T>(), $variant);
+                        }
+                        i += 1;
+                    )*
+                    unreachable!("no operation {} of {}", op, i)
+                }
+            }
+        }
+
+        async fn async_read<T: ByteOrder>(op: usize, rdr: &mut ChunkedReader<&[u8]>) -> Outcome {
+            use tokio_byteorder::AsyncReadBytesExt;
+            match op {
+                0 => outcome(rdr.read_u8().await, Outcome::U8),
+                1 => outcome(rdr.read_i8().await, Outcome::I8),
+                _ => {
+                    let mut i = 2;
+                    $(
+                        if op == i {
+                            return outcome(rdr.$read::<T>().await, $variant);
+                        }
+                        i += 1;
+                    )*
+                    unreachable!("no operation {} of {}", op, i)
+                }
+            }
+        }
+    };
+}
+
+ops! {
+    read_u16 => Outcome::U16,
+    read_i16 => Outcome::I16,
+    read_u24 => Outcome::U24,
+    read_i24 => Outcome::I24,
+    read_u32 => Outcome::U32,
+    read_i32 => Outcome::I32,
+    read_u48 => Outcome::U48,
+    read_i48 => Outcome::I48,
+    read_u64 => Outcome::U64,
+    read_i64 => Outcome::I64,
+    read_u128 => Outcome::U128,
+    read_i128 => Outcome::I128,
+    read_f32 => |v: f32| Outcome::F32(v.to_bits()),
+    read_f64 => |v: f64| Outcome::F64(v.to_bits()),
+}
+
+/// Decodes `bytes` as the sequence `ops` with both implementations, up to the first error.
+fn check(bytes: &[u8], ops: &[(usize, bool)], chunk: usize, pending: bool) {
+    let mut sync = Cursor::new(bytes);
+    let mut rdr = ChunkedReader::new(bytes, chunk).pending_between(pending);
+    block_on(async {
+        for (i, &(op, big)) in ops.iter().enumerate() {
+            let (expected, actual) = if big {
+                (
+                    sync_read::<BigEndian>(op, &mut sync),
+                    async_read::<BigEndian>(op, &mut rdr).await,
+                )
+            } else {
+                (
+                    sync_read::<LittleEndian>(op, &mut sync),
+                    async_read::<LittleEndian>(op, &mut rdr).await,
+                )
+            };
+            assert_eq!(
+                expected, actual,
+                "operation {} ({}) on {:02x?}",
+                i, op, bytes
+            );
+            if let Outcome::Err(_) = actual {
+                break;
+            }
+        }
+    });
+}
+
+/// Bytes that favor the values where sign extension goes wrong.
+fn byte() -> impl Strategy<Value = u8> {
+    prop_oneof![any::<u8>(), Just(0x00), Just(0x7f), Just(0x80), Just(0xff)]
+}
+
+proptest! {
+    #[test]
+    fn agrees_with_byteorder(
+        bytes in prop::collection::vec(byte(), 0..64),
+        ops in prop::collection::vec((0..OPS, any::<bool>()), 1..16),
+        chunk in 1..=17usize,
+        pending in any::<bool>(),
+    ) {
+        check(&bytes, &ops, chunk, pending);
+    }
+}
+
+#[test]
+fn sign_extension() {
+    let patterns: [&[u8]; 6] = [
+        &[0x80, 0x00, 0x00, 0x00, 0x00, 0x00],
+        &[0x00, 0x00, 0x80, 0x00, 0x00, 0x80],
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff],
+        &[0xff, 0xff, 0x7f, 0xff, 0xff, 0x7f],
+        &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    ];
+    // i24, u24, i48, and u48 in both byte orders, twice each, so that the 48-bit reads also
+    // run into the end of the stream
+    for bytes in &patterns {
+        for &op in &[5, 4, 9, 8] {
+            for &big in &[true, false] {
+                for chunk in 1..=7 {
+                    check(bytes, &[(op, big), (op, big)], chunk, chunk % 2 == 0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "bitflags")]
+
+use bitflags::bitflags;
+use std::io::ErrorKind;
+use tokio_byteorder::{AsyncReadBytesExt, BigEndian, LittleEndian, UnknownBits};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Flags: u32 {
+        const SYN = 0x01;
+        const ACK = 0x10;
+        const WIDE = 0x0100_0000;
+    }
+}
+
+#[tokio::test]
+async fn known() {
+    let mut rdr = &[0x01, 0x00, 0x00, 0x11][..];
+    let flags = rdr
+        .read_flags::<Flags, u32, BigEndian>(UnknownBits::Error)
+        .await
+        .unwrap();
+    assert_eq!(flags, Flags::WIDE | Flags::SYN | Flags::ACK);
+}
+
+#[tokio::test]
+async fn narrower_wire_type() {
+    let mut rdr = &[0x10, 0x01][..];
+    let flags = rdr
+        .read_flags::<Flags, u8, LittleEndian>(UnknownBits::Error)
+        .await
+        .unwrap();
+    assert_eq!(flags, Flags::ACK);
+    let flags = rdr
+        .read_flags::<Flags, u8, LittleEndian>(UnknownBits::Error)
+        .await
+        .unwrap();
+    assert_eq!(flags, Flags::SYN);
+}
+
+#[tokio::test]
+async fn unknown() {
+    let raw = [0x00, 0x00, 0x80, 0x11];
+
+    let flags = (&raw[..])
+        .read_flags::<Flags, u32, BigEndian>(UnknownBits::Truncate)
+        .await
+        .unwrap();
+    assert_eq!(flags, Flags::SYN | Flags::ACK);
+
+    let flags = (&raw[..])
+        .read_flags::<Flags, u32, BigEndian>(UnknownBits::Retain)
+        .await
+        .unwrap();
+    assert_eq!(flags.bits(), 0x8011);
+    assert!(flags.contains(Flags::SYN | Flags::ACK));
+
+    let err = (&raw[..])
+        .read_flags::<Flags, u32, BigEndian>(UnknownBits::Error)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+async fn eof() {
+    let err = (&[0x00][..])
+        .read_flags::<Flags, u16, BigEndian>(UnknownBits::Truncate)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
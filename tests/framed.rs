@@ -0,0 +1,132 @@
+#![cfg(feature = "framed")]
+
+use bytes::Bytes;
+use futures_lite::future::poll_fn;
+use futures_lite::StreamExt;
+use futures_sink::Sink;
+use std::io::ErrorKind;
+use std::pin::Pin;
+use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian};
+
+async fn send<S: Sink<Bytes, Error = std::io::Error> + Unpin>(
+    sink: &mut S,
+    frame: &'static [u8],
+) -> std::io::Result<()> {
+    poll_fn(|cx| Pin::new(&mut *sink).poll_ready(cx)).await?;
+    Pin::new(&mut *sink).start_send(Bytes::from_static(frame))
+}
+
+async fn flush<S: Sink<Bytes, Error = std::io::Error> + Unpin>(
+    sink: &mut S,
+) -> std::io::Result<()> {
+    poll_fn(|cx| Pin::new(&mut *sink).poll_flush(cx)).await
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let mut sink = Vec::new().into_framed_u32_sink::<LittleEndian>(16);
+    send(&mut sink, b"hello").await.unwrap();
+    send(&mut sink, b"").await.unwrap();
+    send(&mut sink, b"world!").await.unwrap();
+    flush(&mut sink).await.unwrap();
+    let buf = sink.into_inner();
+    assert_eq!(&buf[..9], b"\x05\x00\x00\x00hello");
+
+    let frames: Vec<_> = (&buf[..])
+        .into_framed_u32::<LittleEndian>(16)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(frames, [&b"hello"[..], b"", b"world!"]);
+}
+
+#[tokio::test]
+async fn over_duplex() {
+    let (tx, rx) = tokio::io::duplex(3);
+    let writer = tokio::spawn(async move {
+        let mut sink = tx.into_framed_u32_sink::<BigEndian>(1024);
+        for frame in [&b"abc"[..], b"defghijk", b"l"] {
+            send(&mut sink, frame).await.unwrap();
+        }
+        poll_fn(|cx| Pin::new(&mut sink).poll_close(cx))
+            .await
+            .unwrap();
+    });
+    let frames: Vec<_> = rx
+        .into_framed_u32::<BigEndian>(1024)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(frames, [&b"abc"[..], b"defghijk", b"l"]);
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn too_long() {
+    let mut frames = (&b"\x00\x00\x00\x05hello"[..]).into_framed_u32::<BigEndian>(4);
+    let err = frames.next().await.unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let mut sink = Vec::new().into_framed_u32_sink::<BigEndian>(4);
+    let err = send(&mut sink, b"hello").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    send(&mut sink, b"hi").await.unwrap();
+    flush(&mut sink).await.unwrap();
+    assert_eq!(sink.into_inner(), b"\x00\x00\x00\x02hi");
+}
+
+#[tokio::test]
+async fn truncated() {
+    for input in [&b"\x00\x00"[..], b"\x00\x00\x00\x05hel"] {
+        let mut frames = input.into_framed_u32::<BigEndian>(16);
+        let err = frames.next().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+#[tokio::test]
+async fn reuses_memory() {
+    let rdr = &b"\x00\x00\x00\x05hello\x00\x00\x00\x05world\x00\x00\x00\x03new"[..];
+    let mut frames = rdr.into_framed_u32::<BigEndian>(16);
+    let first = frames.next().await.unwrap().unwrap();
+    let ptr = first.as_ptr();
+    drop(first);
+    let second = frames.next().await.unwrap().unwrap();
+    assert_eq!(second, "world");
+    assert_eq!(second.as_ptr(), ptr);
+
+    // a frame that is still held on to is left alone
+    let third = frames.next().await.unwrap().unwrap();
+    assert_eq!(third, "new");
+    assert_eq!(second, "world");
+}
+
+#[tokio::test]
+async fn flush_policy() {
+    use tokio_byteorder::{ByteWriter, FlushPolicy};
+
+    // an unbounded `ByteWriter` only passes bytes on when it is flushed
+    let mut sink = ByteWriter::unbounded(Vec::new()).into_framed_u32_sink::<BigEndian>(16);
+    assert_eq!(sink.flush_policy(), FlushPolicy::Manual);
+    send(&mut sink, b"a").await.unwrap();
+    send(&mut sink, b"b").await.unwrap();
+    assert!(sink.get_ref().get_ref().is_empty());
+    flush(&mut sink).await.unwrap();
+    assert_eq!(sink.get_ref().get_ref().len(), 10);
+
+    let mut sink = ByteWriter::unbounded(Vec::new()).into_framed_u32_sink::<BigEndian>(16);
+    sink.set_flush_policy(FlushPolicy::Frame);
+    send(&mut sink, b"a").await.unwrap();
+    send(&mut sink, b"b").await.unwrap();
+    assert_eq!(sink.get_ref().get_ref(), b"\x00\x00\x00\x01a");
+
+    let mut sink = ByteWriter::unbounded(Vec::new()).into_framed_u32_sink::<BigEndian>(16);
+    sink.set_flush_policy(FlushPolicy::Watermark(12));
+    for frame in [&b"ab"[..], b"cd", b"ef", b"gh"] {
+        send(&mut sink, frame).await.unwrap();
+    }
+    // flushed once the first two frames were out, and not again for the third
+    assert_eq!(sink.get_ref().get_ref().len(), 12);
+    flush(&mut sink).await.unwrap();
+    assert_eq!(sink.get_ref().get_ref().len(), 24);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "futures-traits")]
+
+use futures_lite::future::block_on;
+
+#[cfg(feature = "async-std-traits")]
+#[test]
+fn async_std_round_trip() {
+    use async_std::io::Cursor;
+    use tokio_byteorder::async_std::{AsyncReadBytesExt, AsyncWriteBytesExt};
+    use tokio_byteorder::{BigEndian, LittleEndian};
+
+    block_on(async {
+        let mut w = Cursor::new(Vec::new());
+        w.write_i8(-2).await.unwrap();
+        w.write_u48::<LittleEndian>(200598257150769).await.unwrap();
+        w.write_f32::<BigEndian>(1.25).await.unwrap();
+        let bytes = w.into_inner();
+        assert_eq!(bytes.len(), 11);
+
+        let mut r = Cursor::new(bytes);
+        assert_eq!(r.read_i8().await.unwrap(), -2);
+        assert_eq!(r.read_u48::<LittleEndian>().await.unwrap(), 200598257150769);
+        assert_eq!(r.read_f32::<BigEndian>().await.unwrap(), 1.25);
+        let err = r.read_u8().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    });
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn smol_round_trip() {
+    use futures_lite::io::{AsyncWriteExt, Cursor};
+    use tokio_byteorder::smol::{AsyncReadBytesExt, AsyncWriteBytesExt};
+    use tokio_byteorder::{BigEndian, LittleEndian};
+
+    block_on(async {
+        let mut w = Cursor::new(Vec::new());
+        w.write_u128::<BigEndian>(u128::MAX / 3).await.unwrap();
+        w.write_i16::<LittleEndian>(-300).await.unwrap();
+        w.flush().await.unwrap();
+
+        let bytes = w.into_inner();
+        let mut r = &bytes[..];
+        assert_eq!(r.read_u128::<BigEndian>().await.unwrap(), u128::MAX / 3);
+        assert_eq!(r.read_i16::<LittleEndian>().await.unwrap(), -300);
+        assert!(r.is_empty());
+    });
+}
+
+#[cfg(not(any(feature = "tokio1", feature = "tokio02", feature = "tokio03")))]
+#[test]
+fn prelude_picks_futures_io() {
+    use tokio_byteorder::prelude::*;
+
+    block_on(async {
+        // `Vec<u8>` implements both tokio's and futures-io's `AsyncWrite`
+        let mut w = Vec::new();
+        w.write_u32::<BigEndian>(267).await.unwrap();
+        let mut r = futures_lite::io::Cursor::new(w);
+        assert_eq!(r.read_u32::<BigEndian>().await.unwrap(), 267);
+    });
+}
@@ -1,91 +1,4612 @@
-macro_rules! rtt {
-    ($name:ident, $write:path, $read:path, $v:expr) => {
-        #[tokio::test]
-        async fn $name() -> tokio::io::Result<()> {
-            #[allow(unused_imports)]
-            use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian};
-
-            let mut bytes = Vec::new();
-            let () = $write(&mut bytes, $v).await?;
-            let mut b = &bytes[..];
-            let v = $read(&mut b).await?;
-            assert_eq!(v, $v);
-            Ok(())
+#![cfg(feature = "std")]
+
+mod dns_name {
+    use tokio::io::ErrorKind;
+    use tokio_byteorder::AsyncReadBytesExt;
+
+    #[tokio::test]
+    async fn root() {
+        let mut b = &b"\x00"[..];
+        assert_eq!(b.read_dns_name(255).await.unwrap(), ".");
+    }
+
+    #[tokio::test]
+    async fn leaves_trailing() {
+        let mut b = &b"\x03foo\x00\xff"[..];
+        assert_eq!(b.read_dns_name(255).await.unwrap(), "foo");
+        assert_eq!(b, &[0xff][..]);
+    }
+
+    #[tokio::test]
+    async fn too_long() {
+        let mut b = &b"\x03www\x07example\x03com\x00"[..];
+        let e = b.read_dns_name(16).await.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn compressed() {
+        let mut b = &b"\x03www\xc0\x0c"[..];
+        let e = b.read_dns_name(255).await.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn truncated() {
+        let mut b = &b"\x03ww"[..];
+        let e = b.read_dns_name(255).await.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod detect_endianness {
+    use tokio::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, RuntimeEndian};
+
+    #[tokio::test]
+    async fn elf() {
+        let mut b = &b"\x7fELF\x01\x02\x01"[..];
+        assert_eq!(b.detect_endianness_elf().await.unwrap(), RuntimeEndian::Big);
+        assert_eq!(b, &[0x01][..]);
+    }
+
+    #[tokio::test]
+    async fn elf_bad_magic() {
+        let mut b = &b"\x7fELG\x01\x02"[..];
+        let e = b.detect_endianness_elf().await.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn tiff() {
+        let mut b = &b"II\x2a\x00"[..];
+        assert_eq!(
+            b.detect_endianness_tiff().await.unwrap(),
+            RuntimeEndian::Little
+        );
+        let mut b = &b"IM"[..];
+        let e = b.detect_endianness_tiff().await.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+    }
+}
+
+mod runtime_endian {
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian, NativeEndian, RuntimeEndian,
+    };
+
+    #[tokio::test]
+    async fn matches_static() {
+        let mut bytes = Vec::new();
+        bytes
+            .write_i24_rt(RuntimeEndian::Big, -34253)
+            .await
+            .unwrap();
+        bytes.write_i24::<BigEndian>(-34253).await.unwrap();
+        bytes
+            .write_u48_rt(RuntimeEndian::Little, 541)
+            .await
+            .unwrap();
+        bytes.write_u48::<LittleEndian>(541).await.unwrap();
+        bytes
+            .write_f64_rt(RuntimeEndian::Native, 1.5)
+            .await
+            .unwrap();
+        bytes.write_f64::<NativeEndian>(1.5).await.unwrap();
+        assert_eq!(bytes[..3], bytes[3..6]);
+        assert_eq!(bytes[6..12], bytes[12..18]);
+        assert_eq!(bytes[18..26], bytes[26..34]);
+
+        let mut b = &bytes[..];
+        assert_eq!(b.read_i24_rt(RuntimeEndian::Big).await.unwrap(), -34253);
+        assert_eq!(b.read_i24::<BigEndian>().await.unwrap(), -34253);
+        assert_eq!(b.read_u48_rt(RuntimeEndian::Little).await.unwrap(), 541);
+        assert_eq!(b.read_u48::<LittleEndian>().await.unwrap(), 541);
+        assert_eq!(b.read_f64_rt(RuntimeEndian::Native).await.unwrap(), 1.5);
+        assert_eq!(b.read_f64::<NativeEndian>().await.unwrap(), 1.5);
+        assert!(b.is_empty());
+    }
+
+    #[tokio::test]
+    async fn wide() {
+        let mut bytes = Vec::new();
+        bytes
+            .write_i128_rt(RuntimeEndian::Little, i128::MIN)
+            .await
+            .unwrap();
+        let mut b = &bytes[..];
+        assert_eq!(
+            b.read_i128_rt(RuntimeEndian::Little).await.unwrap(),
+            i128::MIN
+        );
+    }
+}
+
+mod endian_wrappers {
+    use tokio_byteorder::{AsyncReadBytesExt, BigEndian, BigEndianReader, BigEndianWriter};
+
+    #[tokio::test]
+    async fn round_trip() {
+        let mut w = BigEndianWriter::new(Vec::new());
+        w.write_u8(3).await.unwrap();
+        w.write_i24(-34253).await.unwrap();
+        w.write_f32(1.5).await.unwrap();
+        let bytes = w.into_inner();
+        assert_eq!(&bytes[..4], b"\x03\xff\x7a\x33");
+
+        let mut r = BigEndianReader::new(&bytes[..]);
+        assert_eq!(r.read_u8().await.unwrap(), 3);
+        assert_eq!(r.read_i24().await.unwrap(), -34253);
+        // the wrapper is itself a reader, so explicit byte orders still work
+        assert_eq!(
+            AsyncReadBytesExt::read_f32::<BigEndian>(&mut r)
+                .await
+                .unwrap(),
+            1.5
+        );
+        assert!(r.get_ref().is_empty());
+    }
+}
+
+mod suffixed {
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn round_trip() {
+        let mut bytes = Vec::new();
+        bytes.write_u32_be(267).await.unwrap();
+        bytes.write_u32_le(267).await.unwrap();
+        bytes.write_i48_ne(-77).await.unwrap();
+        bytes.write_f64_be(1.5).await.unwrap();
+        assert_eq!(&bytes[..8], b"\x00\x00\x01\x0b\x0b\x01\x00\x00");
+
+        let mut b = &bytes[..];
+        assert_eq!(b.read_u32_be().await.unwrap(), 267);
+        assert_eq!(b.read_u32_le().await.unwrap(), 267);
+        assert_eq!(b.read_i48_ne().await.unwrap(), -77);
+        assert_eq!(b.read_f64_be().await.unwrap(), 1.5);
+        assert!(b.is_empty());
+    }
+}
+
+mod network_endian {
+    use tokio_byteorder::{NetworkEndianReader, NetworkEndianWriter};
+
+    #[tokio::test]
+    async fn defaults_to_big_endian() {
+        let mut w = NetworkEndianWriter::new(Vec::new());
+        w.write_u32(267).await.unwrap();
+        let bytes = w.into_inner();
+        assert_eq!(bytes, b"\x00\x00\x01\x0b");
+        let mut r = NetworkEndianReader::new(&bytes[..]);
+        assert_eq!(r.read_u32().await.unwrap(), 267);
+    }
+}
+
+mod counting {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio_byteorder::{AsyncReadBytesExt, CountingReader, CountingWriter, LittleEndian};
+
+    #[tokio::test]
+    async fn reader() {
+        let bytes = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut r = CountingReader::with_position(&bytes[..], 10);
+        r.read_u8().await.unwrap();
+        r.read_u64::<LittleEndian>().await.unwrap();
+        assert_eq!(r.position(), 19);
+        assert!(r.read_u8().await.is_err());
+        assert_eq!(r.position(), 19);
+    }
+
+    #[tokio::test]
+    async fn buffered() {
+        let mut r = CountingReader::new(BufReader::new(&b"abc\ndef"[..]));
+        let mut line = String::new();
+        r.read_line(&mut line).await.unwrap();
+        assert_eq!(r.position(), 4);
+    }
+
+    #[tokio::test]
+    async fn writer() {
+        use tokio_byteorder::AsyncWriteBytesExt;
+        let mut w = CountingWriter::new(Vec::new());
+        w.write_u8(1).await.unwrap();
+        w.write_i128_le(-1).await.unwrap();
+        assert_eq!(w.position(), 17);
+        assert_eq!(w.get_ref().len(), 17);
+    }
+}
+
+mod peek {
+    use tokio::io::BufReader;
+    use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, LittleEndian};
+
+    #[tokio::test]
+    async fn does_not_consume() {
+        let mut r = BufReader::new(&b"\xff\x7a\x33\x01"[..]);
+        assert_eq!(r.peek_i24::<LittleEndian>().await.unwrap(), Some(0x337aff));
+        assert_eq!(r.peek_i8().await.unwrap(), Some(-1));
+        assert_eq!(r.read_u32::<LittleEndian>().await.unwrap(), 0x01337aff);
+    }
+
+    #[tokio::test]
+    async fn short_buffer() {
+        let mut r = BufReader::new(&b"\x01\x02"[..]);
+        assert_eq!(r.peek_u32::<LittleEndian>().await.unwrap(), None);
+        assert_eq!(r.peek_u16::<LittleEndian>().await.unwrap(), Some(0x0201));
+    }
+}
+
+mod buf_read {
+    use tokio::io::BufReader;
+    use tokio_byteorder::{AsyncBufReadBytesExt, BigEndian};
+
+    #[tokio::test]
+    async fn straddles_buffer() {
+        let bytes: Vec<u8> = (0..32).collect();
+        // a three byte buffer forces every u64 to be stitched together
+        let mut r = BufReader::with_capacity(3, &bytes[..]);
+        assert_eq!(r.buf_read_u8().await.unwrap(), 0);
+        assert_eq!(
+            r.buf_read_u64::<BigEndian>().await.unwrap(),
+            0x0102030405060708
+        );
+        assert_eq!(r.buf_read_u16::<BigEndian>().await.unwrap(), 0x090a);
+        r.buf_read_u128::<BigEndian>().await.unwrap();
+        assert!(r.buf_read_u64::<BigEndian>().await.is_err());
+    }
+}
+
+mod byte_reader {
+    use tokio::io::AsyncReadExt;
+    use tokio_byteorder::{AsyncReadBytesExt, BigEndian, ByteReader, LittleEndian};
+
+    #[tokio::test]
+    async fn peek_across_refill() {
+        let bytes: Vec<u8> = (0..40).collect();
+        let mut r = ByteReader::with_capacity(16, &bytes[..]);
+        r.read_u64::<BigEndian>().await.unwrap();
+        r.read_u32::<BigEndian>().await.unwrap();
+        // only four bytes are left in the buffer, so this has to compact and refill
+        assert_eq!(
+            r.peek_u64::<LittleEndian>().await.unwrap(),
+            Some(0x13121110_0f0e0d0c)
+        );
+        assert_eq!(
+            r.read_u64::<LittleEndian>().await.unwrap(),
+            0x13121110_0f0e0d0c
+        );
+        assert_eq!(r.position(), 20);
+        assert_eq!(
+            r.read_dns_name(255).await.unwrap_err().kind(),
+            tokio::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[tokio::test]
+    async fn peek_at_eof() {
+        let mut r = ByteReader::new(&[1, 2, 3][..]);
+        assert_eq!(r.peek_u32::<BigEndian>().await.unwrap(), None);
+        assert_eq!(r.peek_u24::<BigEndian>().await.unwrap(), Some(0x010203));
+        let mut rest = Vec::new();
+        r.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, [1, 2, 3]);
+        assert_eq!(r.position(), 3);
+        assert_eq!(r.peek_u8().await.unwrap(), None);
+    }
+}
+
+mod byte_writer {
+    use tokio::io::AsyncWriteExt;
+    use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, ByteWriter, FlushPolicy, LittleEndian};
+
+    #[tokio::test]
+    async fn writes_out_past_watermark() {
+        let mut w = ByteWriter::with_watermark(8, Vec::new());
+        w.write_u32::<BigEndian>(1).await.unwrap();
+        w.write_u16::<LittleEndian>(2).await.unwrap();
+        assert!(w.get_ref().is_empty());
+        assert_eq!(w.buffer(), b"\x00\x00\x00\x01\x02\x00");
+        w.write_u16::<BigEndian>(3).await.unwrap();
+        assert_eq!(w.get_ref(), b"\x00\x00\x00\x01\x02\x00\x00\x03");
+        assert!(w.buffer().is_empty());
+        // large writes bypass the buffer once it has been written out
+        w.write_u8(4).await.unwrap();
+        w.write_all(&[5; 10]).await.unwrap();
+        assert_eq!(w.get_ref().len(), 19);
+        assert!(w.buffer().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unbounded_until_flush() {
+        let mut w = ByteWriter::unbounded(Vec::new());
+        for i in 0..1000 {
+            w.write_u64::<BigEndian>(i).await.unwrap();
+        }
+        AsyncWriteBytesExt::write_i24::<LittleEndian>(&mut w, -1)
+            .await
+            .unwrap();
+        assert!(w.get_ref().is_empty());
+        assert_eq!(w.buffer().len(), 8003);
+        w.flush().await.unwrap();
+        assert_eq!(w.get_ref().len(), 8003);
+        assert_eq!(&w.get_ref()[8..16], &1u64.to_be_bytes());
+
+        w.set_watermark(Some(2));
+        w.write_i8(-1).await.unwrap();
+        assert_eq!(w.buffer(), b"\xff");
+        w.write_i8(-2).await.unwrap();
+        assert_eq!(w.into_inner().len(), 8005);
+    }
+
+    #[tokio::test]
+    async fn flush_policies() {
+        // the inner writer only passes bytes on when it is flushed
+        let mut w = ByteWriter::with_policy(FlushPolicy::Frame, ByteWriter::unbounded(Vec::new()));
+        assert_eq!(w.watermark(), None);
+        w.write_u16::<BigEndian>(1).await.unwrap();
+        w.write_u16::<BigEndian>(2).await.unwrap();
+        assert!(w.get_ref().buffer().is_empty());
+        w.end_frame().await.unwrap();
+        assert_eq!(w.get_ref().get_ref(), b"\x00\x01\x00\x02");
+
+        w.set_policy(FlushPolicy::Immediate);
+        w.write_u16::<BigEndian>(3).await.unwrap();
+        assert_eq!(w.get_ref().get_ref().len(), 6);
+        assert!(w.buffer().is_empty());
+
+        // frame boundaries mean nothing to the other policies
+        w.set_policy(FlushPolicy::Manual);
+        w.write_u16::<BigEndian>(4).await.unwrap();
+        w.end_frame().await.unwrap();
+        assert_eq!(w.buffer(), b"\x00\x04");
+        w.set_watermark(Some(4));
+        assert_eq!(w.policy(), FlushPolicy::Watermark(4));
+        w.end_frame().await.unwrap();
+        assert_eq!(w.buffer(), b"\x00\x04");
+        w.flush().await.unwrap();
+        assert_eq!(w.get_ref().get_ref().len(), 8);
+    }
+}
+
+mod decoder {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, ReadBuf};
+    use tokio_byteorder::{BigEndian, Decoder, LittleEndian};
+
+    /// Hands out one byte per poll, and is pending on every other poll.
+    struct Trickle<'a>(&'a [u8], bool);
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<tokio::io::Result<()>> {
+            self.1 = !self.1;
+            if self.1 {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if let Some((&b, rest)) = self.0.split_first() {
+                buf.put_slice(&[b]);
+                self.0 = rest;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn survives_dropped_futures() {
+        let mut src = Trickle(&[0x01, 0x02, 0x03, 0x04, 0x05, 0xff, 0xfe], false);
+        let mut decoder = Decoder::u48::<LittleEndian>();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        // poll a fresh future a few times, dropping each one before it can complete
+        for _ in 0..4 {
+            let mut fut = decoder.read(&mut src);
+            assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        }
+        assert_eq!(decoder.buffered(), [1, 2, 3]);
+        assert_eq!(decoder.read(&mut src).await.unwrap(), 0x0000_ff05_0403_0201);
+        assert!(decoder.buffered().is_empty());
+
+        let mut decoder = Decoder::i8();
+        assert_eq!(decoder.read(&mut src).await.unwrap(), -2);
+        let mut decoder = Decoder::u16::<BigEndian>();
+        let err = decoder.read(&mut src).await.unwrap_err();
+        assert_eq!(err.kind(), tokio::io::ErrorKind::UnexpectedEof);
+    }
+
+    fn noop_waker() -> std::task::Waker {
+        struct Noop;
+        impl std::task::Wake for Noop {
+            fn wake(self: std::sync::Arc<Self>) {}
+        }
+        std::sync::Arc::new(Noop).into()
+    }
+}
+
+mod sans_io {
+    use tokio_byteorder::sans_io::{FixedDecoder, FixedEncoder};
+    use tokio_byteorder::{BigEndian, LittleEndian};
+
+    #[test]
+    fn byte_at_a_time() {
+        let mut encoder = FixedEncoder::i48::<LittleEndian>(-108363435763825);
+        let mut decoder = FixedDecoder::i48::<LittleEndian>();
+        let mut value = None;
+        while !encoder.is_done() {
+            let mut b = [0];
+            assert_eq!(encoder.pull_bytes(&mut b), 1);
+            assert!(value.is_none());
+            value = decoder.push_bytes(&mut &b[..]);
+        }
+        assert_eq!(value, Some(-108363435763825));
+        assert!(decoder.buffered().is_empty());
+        assert_eq!(decoder.remaining(), 6);
+    }
+
+    #[test]
+    fn back_to_back_values() {
+        let mut bytes = Vec::new();
+        for v in [1.5f64, -0.25, f64::MAX] {
+            bytes.extend_from_slice(FixedEncoder::f64::<BigEndian>(v).pending());
+        }
+        let mut input = &bytes[..];
+        let mut decoder = FixedDecoder::f64::<BigEndian>();
+        assert_eq!(decoder.push_bytes(&mut input), Some(1.5));
+        assert_eq!(decoder.push_bytes(&mut input), Some(-0.25));
+        let mut head = &input[..5];
+        assert_eq!(decoder.push_bytes(&mut head), None);
+        assert!(head.is_empty());
+        decoder.reset();
+        assert_eq!(decoder.push_bytes(&mut input), Some(f64::MAX));
+        assert!(input.is_empty());
+
+        let mut encoder = FixedEncoder::u8(7);
+        encoder.advance(1);
+        assert!(encoder.is_done());
+        assert_eq!(FixedDecoder::i8().push_bytes(&mut &[0xff][..]), Some(-1));
+    }
+}
+
+mod owned {
+    use tokio::task::JoinSet;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, LittleEndian};
+
+    #[tokio::test]
+    async fn join_set() {
+        let mut set = JoinSet::new();
+        for i in 0..4u16 {
+            set.spawn(async move {
+                let (w, res) = Vec::new().write_u16_owned::<LittleEndian>(i).await;
+                res.unwrap();
+                let (w, res) = w.write_i8_owned(-1).await;
+                res.unwrap();
+                let (r, n) = std::io::Cursor::new(w)
+                    .read_u16_owned::<LittleEndian>()
+                    .await;
+                let (_, m) = r.read_i8_owned().await;
+                (n.unwrap(), m.unwrap())
+            });
+        }
+        let mut seen = Vec::new();
+        while let Some(res) = set.join_next().await {
+            let (n, m) = res.unwrap();
+            assert_eq!(m, -1);
+            seen.push(n);
+        }
+        seen.sort();
+        assert_eq!(seen, [0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn returns_reader_on_error() {
+        let (r, res) = (&[1, 2, 3][..]).read_f32_owned::<LittleEndian>().await;
+        assert_eq!(res.unwrap_err().kind(), tokio::io::ErrorKind::UnexpectedEof);
+        assert!(r.is_empty());
+
+        let mut buf = [0u8; 2];
+        let w = std::io::Cursor::new(&mut buf[..]);
+        let (w, res) = w.write_u24_owned::<LittleEndian>(1).await;
+        assert_eq!(res.unwrap_err().kind(), tokio::io::ErrorKind::WriteZero);
+        assert_eq!(w.position(), 2);
+    }
+}
+
+mod read_buf {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, ReadBuf};
+    use tokio_byteorder::{AsyncReadBytesExt, BigEndian};
+
+    /// Hands out one byte per read and checks that it is never given pre-zeroed memory.
+    struct Uninit<'a>(&'a [u8]);
+
+    impl AsyncRead for Uninit<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            assert!(buf.initialized().is_empty());
+            if let Some((&b, rest)) = self.0.split_first() {
+                buf.initialize_unfilled_to(1)[0] = b;
+                buf.advance(1);
+                self.0 = rest;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn does_not_zero() {
+        let mut r = Uninit(&[0x01, 0x02, 0x03, 0x04, 0xff]);
+        assert_eq!(r.read_u32::<BigEndian>().await.unwrap(), 0x0102_0304);
+        assert_eq!(r.read_i8().await.unwrap(), -1);
+        let err = r.read_u16::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
+
+mod dyn_traits {
+    use tokio::io::AsyncRead;
+    use tokio_byteorder::{AsyncReadBytesDyn, AsyncWriteBytesDyn, BoxFuture, RuntimeEndian};
+
+    trait Plugin {
+        fn decode<'a>(&self, src: &'a mut dyn AsyncReadBytesDyn) -> BoxFuture<'a, u64>;
+    }
+
+    struct Sum;
+
+    impl Plugin for Sum {
+        fn decode<'a>(&self, src: &'a mut dyn AsyncReadBytesDyn) -> BoxFuture<'a, u64> {
+            Box::pin(async move {
+                let a = src.read_u16_boxed(RuntimeEndian::Little).await.unwrap();
+                let b = src.read_i48_boxed(RuntimeEndian::Big).await.unwrap();
+                let c = src.read_f32_boxed(RuntimeEndian::Little).await.unwrap();
+                u64::from(a) + b as u64 + c as u64
+            })
         }
+    }
+
+    #[tokio::test]
+    async fn through_trait_objects() {
+        let mut buf = Vec::new();
+        {
+            let w: &mut dyn AsyncWriteBytesDyn = &mut buf;
+            w.write_u16_boxed(RuntimeEndian::Little, 40).await.unwrap();
+            w.write_i48_boxed(RuntimeEndian::Big, 2).await.unwrap();
+            w.write_f32_boxed(RuntimeEndian::Little, 0.5).await.unwrap();
+            w.write_u8_boxed(9).await.unwrap();
+        }
+        assert_eq!(buf.len(), 13);
+
+        let mut rdr: Box<dyn AsyncRead + Send + Unpin> = Box::new(std::io::Cursor::new(buf));
+        let plugins: Vec<Box<dyn Plugin>> = vec![Box::new(Sum)];
+        assert_eq!(plugins[0].decode(&mut rdr).await, 42);
+        assert_eq!(rdr.read_u8_boxed().await.unwrap(), 9);
+        let err = rdr.read_i8_boxed().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
+
+mod poll {
+    use std::future::poll_fn;
+    use std::marker::PhantomPinned;
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, ReadBuf};
+    use tokio_byteorder::poll::{
+        poll_read_f64, poll_read_i8, poll_write_f64, poll_write_u8, ReadState, WriteState,
     };
+    use tokio_byteorder::LittleEndian;
+
+    /// A `!Unpin` reader that returns `Pending` before every byte.
+    struct Trickle {
+        data: Vec<u8>,
+        ready: bool,
+        _pin: PhantomPinned,
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            // safety: no field is structurally pinned
+            let this = unsafe { self.get_unchecked_mut() };
+            if !this.ready {
+                this.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.ready = false;
+            if !this.data.is_empty() {
+                buf.put_slice(&[this.data.remove(0)]);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let mut buf = Vec::new();
+        let mut state = WriteState::new();
+        poll_fn(|cx| poll_write_u8(Pin::new(&mut buf), cx, &mut state, 3))
+            .await
+            .unwrap();
+        poll_fn(|cx| poll_write_f64::<LittleEndian, _>(Pin::new(&mut buf), cx, &mut state, 0.25))
+            .await
+            .unwrap();
+        assert!(!state.is_writing());
+        assert_eq!(buf.len(), 9);
+
+        let mut src = pin!(Trickle {
+            data: buf,
+            ready: false,
+            _pin: PhantomPinned,
+        });
+        let mut state = ReadState::new();
+        let n = poll_fn(|cx| poll_read_i8(src.as_mut(), cx, &mut state)).await;
+        assert_eq!(n.unwrap(), 3);
+        let f = poll_fn(|cx| poll_read_f64::<LittleEndian, _>(src.as_mut(), cx, &mut state)).await;
+        assert_eq!(f.unwrap(), 0.25);
+        assert!(state.buffered().is_empty());
+        let err = poll_fn(|cx| poll_read_i8(src.as_mut(), cx, &mut state)).await;
+        assert_eq!(err.unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
+
+mod not_unpin {
+    use std::marker::PhantomPinned;
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian};
+
+    /// An in-memory stream that is not `Unpin`.
+    #[derive(Default)]
+    struct Pinned {
+        data: Vec<u8>,
+        _pin: PhantomPinned,
+    }
+
+    impl Pinned {
+        fn data(self: Pin<&mut Self>) -> &mut Vec<u8> {
+            // safety: `data` is never structurally pinned
+            unsafe { &mut self.get_unchecked_mut().data }
+        }
+    }
+
+    impl AsyncRead for Pinned {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            let data = self.data();
+            let n = std::cmp::min(buf.remaining(), data.len());
+            buf.put_slice(&data[..n]);
+            data.drain(..n);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for Pinned {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.data().extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn through_pin() {
+        let mut s = pin!(Pinned::default());
+        s.write_u8(1).await.unwrap();
+        s.write_i32::<BigEndian>(-2).await.unwrap();
+        assert_eq!(s.read_u8().await.unwrap(), 1);
+        assert_eq!(s.read_i32::<BigEndian>().await.unwrap(), -2);
+    }
 }
 
-macro_rules! writes {
-    ($write:path, $v:expr) => {
-        #[tokio::test]
-        async fn writes() -> tokio::io::Result<()> {
-            #[allow(unused_imports)]
-            use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+mod future {
+    use std::io::Cursor;
+    use tokio_byteorder::future::{ReadU32, ReadU8, WriteF64};
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian};
 
-            let mut bytes = Vec::new();
-            let () = $write(&mut bytes, $v).await?;
-            assert_ne!(bytes.len(), 0);
-            Ok(())
+    fn assert_props<F: std::future::Future + Send + Sync + Unpin + std::fmt::Debug>(f: F) -> F {
+        f
+    }
+
+    struct Message<'a> {
+        len: ReadU32<&'a mut Cursor<Vec<u8>>, BigEndian>,
+    }
+
+    #[tokio::test]
+    async fn nameable() {
+        let mut r = Cursor::new(vec![0, 0, 1, 11, 7]);
+        let msg = Message {
+            len: assert_props(r.read_u32::<BigEndian>()),
+        };
+        assert!(format!("{:?}", msg.len).starts_with("ReadU32"));
+        assert_eq!(msg.len.await.unwrap(), 267);
+        let f: ReadU8<_> = assert_props(r.read_u8());
+        assert_eq!(f.await.unwrap(), 7);
+
+        let mut w = Vec::new();
+        let f: WriteF64<_> = assert_props(w.write_f64::<BigEndian>(1.0));
+        f.await.unwrap();
+        assert_eq!(w.len(), 8);
+    }
+}
+
+#[cfg(not(any(feature = "futures-traits", feature = "tokio02", feature = "tokio03")))]
+mod prelude {
+    use tokio_byteorder::prelude::*;
+
+    #[tokio::test]
+    async fn tokio_by_default() {
+        let mut w = Vec::new();
+        w.write_u24::<LittleEndian>(0x0a0b0c).await.unwrap();
+        w.write_u16_rt(RuntimeEndian::Big, 7).await.unwrap();
+        let mut r = &w[..];
+        assert_eq!(r.read_u24::<LittleEndian>().await.unwrap(), 0x0a0b0c);
+        assert_eq!(r.read_u16::<NetworkEndian>().await.unwrap(), 7);
+    }
+}
+
+mod byte_layout {
+    use tokio_byteorder::{byte_layout, AsyncDecode, AsyncEncode, BigEndian, LittleEndian};
+
+    byte_layout! {
+        #[derive(Debug, PartialEq)]
+        struct Inner {
+            a: i8 as i8,
+            b: i64 as i48<LittleEndian>,
+        }
+
+        #[derive(Debug, PartialEq)]
+        pub struct Outer {
+            pub tag: u16 as u16<BigEndian>,
+            inner: Inner,
+            ratio: f64 as f64<LittleEndian>,
+            big: u128 as u128<BigEndian>,
         }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let v = Outer {
+            tag: 0x0102,
+            inner: Inner { a: -1, b: -2 },
+            ratio: 0.25,
+            big: 3,
+        };
+        let mut buf = Vec::new();
+        v.write_to(&mut buf).await.unwrap();
+        assert_eq!(buf.len(), 2 + 1 + 6 + 8 + 16);
+        assert_eq!(&buf[..9], [1, 2, 0xff, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(Outer::read_from(&mut &buf[..]).await.unwrap(), v);
+
+        let err = Outer::read_from(&mut &buf[..20]).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
+
+mod primitive {
+    use tokio_byteorder::sans_io::{FixedDecoder, FixedEncoder};
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, ByteOrder, LittleEndian, Primitive,
     };
+
+    async fn round_trip<V, T>(values: &[V]) -> usize
+    where
+        V: Primitive + Copy + PartialEq + std::fmt::Debug,
+        T: ByteOrder,
+    {
+        let mut buf = Vec::new();
+        for &v in values {
+            buf.write_primitive::<V, T>(v).await.unwrap();
+        }
+        let mut rdr = &buf[..];
+        for &v in values {
+            assert_eq!(rdr.read_primitive::<V, T>().await.unwrap(), v);
+        }
+        assert!(rdr.read_primitive::<V, T>().await.is_err());
+        buf.len()
+    }
+
+    #[tokio::test]
+    async fn generic() {
+        assert_eq!(round_trip::<u8, BigEndian>(&[1, 255]).await, 2);
+        assert_eq!(round_trip::<i8, LittleEndian>(&[-1, 7]).await, 2);
+        assert_eq!(round_trip::<u16, LittleEndian>(&[0x0102]).await, 2);
+        assert_eq!(round_trip::<i32, BigEndian>(&[-5, 1 << 30]).await, 8);
+        assert_eq!(round_trip::<u64, BigEndian>(&[u64::MAX]).await, 8);
+        assert_eq!(round_trip::<i128, LittleEndian>(&[-1]).await, 16);
+        assert_eq!(round_trip::<f64, LittleEndian>(&[0.5, -2.0]).await, 16);
+
+        let mut buf = Vec::new();
+        buf.write_primitive::<u16, LittleEndian>(0x0102)
+            .await
+            .unwrap();
+        assert_eq!(buf, [2, 1]);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Rgb(u8, u8, u8);
+
+    impl Primitive for Rgb {
+        fn decoder<T: ByteOrder>() -> FixedDecoder<Self> {
+            FixedDecoder::new(3, |b| Rgb(b[0], b[1], b[2]))
+        }
+
+        fn encoder<T: ByteOrder>(self) -> FixedEncoder {
+            FixedEncoder::new(3, |b| b.copy_from_slice(&[self.0, self.1, self.2]))
+        }
+    }
+
+    #[tokio::test]
+    async fn custom() {
+        assert_eq!(
+            round_trip::<Rgb, BigEndian>(&[Rgb(1, 2, 3), Rgb(4, 5, 6)]).await,
+            6
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_long() {
+        let _ = FixedEncoder::new(17, |_| {});
+    }
+}
+
+mod packet {
+    use bytes::Bytes;
+    use std::io::IoSlice;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::AsyncWrite;
+    use tokio_byteorder::{BigEndian, LittleEndian, PacketBuilder};
+
+    /// Records every write, and takes at most `limit` bytes from each.
+    struct Recorder {
+        vectored: bool,
+        limit: usize,
+        out: Vec<u8>,
+        calls: Vec<usize>,
+    }
+
+    impl AsyncWrite for Recorder {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.poll_write_vectored(cx, &[IoSlice::new(buf)])
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            bufs: &[IoSlice<'_>],
+        ) -> Poll<std::io::Result<usize>> {
+            let mut n = 0;
+            for buf in bufs {
+                let take = std::cmp::min(buf.len(), self.limit - n);
+                self.out.extend_from_slice(&buf[..take]);
+                n += take;
+            }
+            self.calls.push(bufs.len());
+            Poll::Ready(Ok(n))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.vectored
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn vectored() {
+        let body = Bytes::from(vec![7; 100]);
+        let packet = PacketBuilder::new()
+            .u32_be(100)
+            .payload(body.clone())
+            .u16_le(0xabcd);
+        assert_eq!(packet.len(), 106);
+        let expected = packet.clone().into_inner();
+        assert_eq!(&expected[..4], [0, 0, 0, 100]);
+        assert_eq!(&expected[4..104], &body[..]);
+        assert_eq!(&expected[104..], [0xcd, 0xab]);
+
+        let mut w = Recorder {
+            vectored: true,
+            limit: 1000,
+            out: Vec::new(),
+            calls: Vec::new(),
+        };
+        packet.write_to(&mut w).await.unwrap();
+        assert_eq!(w.out, expected);
+        assert_eq!(w.calls, [3]);
+
+        // short writes resume in the middle of a chunk
+        let mut w = Recorder {
+            vectored: true,
+            limit: 50,
+            out: Vec::new(),
+            calls: Vec::new(),
+        };
+        packet.write_to(&mut w).await.unwrap();
+        assert_eq!(w.out, expected);
+        assert_eq!(w.calls, [3, 2, 2]);
+
+        let mut w = Recorder {
+            vectored: false,
+            limit: 1000,
+            out: Vec::new(),
+            calls: Vec::new(),
+        };
+        packet.write_to(&mut w).await.unwrap();
+        assert_eq!(w.out, expected);
+        assert_eq!(w.calls, [1]);
+    }
+
+    #[tokio::test]
+    async fn chained() {
+        let packet = PacketBuilder::with_capacity(32)
+            .u8(0xff)
+            .i8(-2)
+            .u16_be(0x0102)
+            .u24_le(0x030405)
+            .i48::<BigEndian>(-1)
+            .f32::<LittleEndian>(1.0)
+            .bytes(b"xy");
+        assert_eq!(packet.len(), 1 + 1 + 2 + 3 + 6 + 4 + 2);
+
+        let mut wtr = Vec::new();
+        packet.write_to(&mut wtr).await.unwrap();
+        packet.write_to(&mut wtr).await.unwrap();
+        assert_eq!(&wtr[..packet.len()], &wtr[packet.len()..]);
+        assert_eq!(&wtr[..7], [0xff, 0xfe, 0x01, 0x02, 0x05, 0x04, 0x03]);
+        assert_eq!(&wtr[7..13], [0xff; 6]);
+        assert_eq!(&wtr[13..19], b"\x00\x00\x80\x3fxy");
+        assert_eq!(&wtr[19..], packet.into_inner());
+    }
+}
+
+mod read_at {
+    use std::io::Cursor;
+    use tokio::io::AsyncSeekExt;
+    use tokio_byteorder::{AsyncReadAtBytesExt, AsyncReadBytesExt, BigEndian, LittleEndian};
+
+    #[tokio::test]
+    async fn jumps_around() {
+        let mut file = Cursor::new(vec![9, 0xff, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(file.read_u24_at::<BigEndian>(2).await.unwrap(), 0x010203);
+        assert_eq!(file.read_i8_at(1).await.unwrap(), -1);
+        assert_eq!(file.read_u16_at::<LittleEndian>(6).await.unwrap(), 0x0605);
+        assert_eq!(file.read_primitive_at::<u8, BigEndian>(0).await.unwrap(), 9);
+        // the stream is left just past the value
+        assert_eq!(file.stream_position().await.unwrap(), 1);
+        assert_eq!(file.read_u8().await.unwrap(), 0xff);
+
+        let err = file.read_u32_at::<BigEndian>(6).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn file() {
+        let path =
+            std::env::temp_dir().join(format!("tokio-byteorder-read-at-{}", std::process::id()));
+        tokio::fs::write(&path, [0, 0, 0, 0, 0x40, 0x49, 0x0f, 0xdb])
+            .await
+            .unwrap();
+        let mut f = tokio::fs::File::open(&path).await.unwrap();
+        let pi = f.read_f32_at::<BigEndian>(4).await.unwrap();
+        assert!((pi - std::f32::consts::PI).abs() < 1e-6);
+        assert_eq!(f.read_u32_at::<BigEndian>(0).await.unwrap(), 0);
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
+
+mod write_at {
+    use std::io::Cursor;
+    use tokio::io::AsyncSeekExt;
+    use tokio_byteorder::{AsyncWriteAtBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian};
+
+    #[tokio::test]
+    async fn fills_placeholders() {
+        let mut file = Cursor::new(Vec::new());
+        file.write_u8(0xaa).await.unwrap();
+        let count = file.reserve_u16::<LittleEndian>().await.unwrap();
+        let offset = file.reserve_primitive::<i32, BigEndian>().await.unwrap();
+        assert_eq!(count.position(), 1);
+        assert_eq!(offset.position(), 3);
+        assert_eq!(file.get_ref(), &[0xaa, 0, 0, 0, 0, 0, 0]);
+        for i in 0..3 {
+            file.write_u8(i).await.unwrap();
+        }
+        offset.fill(&mut file, -1).await.unwrap();
+        count.fill(&mut file, 3).await.unwrap();
+        // the stream is back where it was
+        assert_eq!(file.stream_position().await.unwrap(), 10);
+        file.write_u8(0xbb).await.unwrap();
+        assert_eq!(
+            file.into_inner(),
+            [0xaa, 3, 0, 0xff, 0xff, 0xff, 0xff, 0, 1, 2, 0xbb]
+        );
+    }
+
+    #[tokio::test]
+    async fn file() {
+        let path =
+            std::env::temp_dir().join(format!("tokio-byteorder-write-at-{}", std::process::id()));
+        let mut f = tokio::fs::File::create(&path).await.unwrap();
+        let len = f.reserve_u64::<BigEndian>().await.unwrap();
+        tokio::io::AsyncWriteExt::write_all(&mut f, b"chunk")
+            .await
+            .unwrap();
+        len.fill(&mut f, 5).await.unwrap();
+        tokio::io::AsyncWriteExt::flush(&mut f).await.unwrap();
+        drop(f);
+        assert_eq!(
+            tokio::fs::read(&path).await.unwrap(),
+            b"\0\0\0\0\0\0\0\x05chunk"
+        );
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
+
+mod interrupted {
+    use std::io::{Error, ErrorKind};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio_byteorder::poll::{poll_read_u32, poll_write_u32, ReadState, WriteState};
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, ByteWriter};
+
+    /// Fails every other call with `Interrupted`, and otherwise moves a single byte.
+    struct Flaky {
+        data: Vec<u8>,
+        interrupt: bool,
+    }
+
+    impl Flaky {
+        fn new(data: &[u8]) -> Self {
+            Flaky {
+                data: data.to_vec(),
+                interrupt: true,
+            }
+        }
+
+        fn interrupted(&mut self) -> bool {
+            self.interrupt = !self.interrupt;
+            !self.interrupt
+        }
+    }
+
+    impl AsyncRead for Flaky {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if self.interrupted() {
+                return Poll::Ready(Err(Error::from(ErrorKind::Interrupted)));
+            }
+            if !self.data.is_empty() {
+                let b = self.data.remove(0);
+                buf.put_slice(&[b]);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for Flaky {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            if self.interrupted() {
+                return Poll::Ready(Err(Error::from(ErrorKind::Interrupted)));
+            }
+            self.data.push(buf[0]);
+            Poll::Ready(Ok(1))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn retried() {
+        let mut r = Flaky::new(&[0, 0, 1, 11, 0xff, 1, 2]);
+        assert_eq!(r.read_u32::<BigEndian>().await.unwrap(), 267);
+        assert_eq!(r.read_i8().await.unwrap(), -1);
+        let mut state = ReadState::new();
+        let res = futures_lite::future::poll_fn(|cx| {
+            poll_read_u32::<BigEndian, _>(Pin::new(&mut r), cx, &mut state)
+        })
+        .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+        let mut w = Flaky::new(&[]);
+        w.write_u16::<BigEndian>(0x0102).await.unwrap();
+        w.write_u8(3).await.unwrap();
+        let mut state = WriteState::new();
+        futures_lite::future::poll_fn(|cx| {
+            poll_write_u32::<BigEndian, _>(Pin::new(&mut w), cx, &mut state, 4)
+        })
+        .await
+        .unwrap();
+        let mut bw = ByteWriter::new(w);
+        bw.write_u8(5).await.unwrap();
+        bw.flush().await.unwrap();
+        assert_eq!(bw.get_ref().data, [1, 2, 3, 0, 0, 0, 4, 5]);
+    }
+}
+
+mod write_zero {
+    use std::io::ErrorKind;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::AsyncWrite;
+    use tokio_byteorder::{AsyncWriteBytesExt, BigEndian};
+
+    /// Accepts `room` bytes, and then no more.
+    struct Full {
+        room: usize,
+    }
+
+    impl AsyncWrite for Full {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            let n = std::cmp::min(self.room, buf.len());
+            self.room -= n;
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn errors_instead_of_hanging() {
+        let mut w = Full { room: 3 };
+        w.write_u16::<BigEndian>(1).await.unwrap();
+        let err = w.write_u32::<BigEndian>(1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        let err = w.write_u8(1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        let err = w.write_i8(1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        let err = w.write_primitive::<f64, BigEndian>(1.0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        let (_, res) = w.write_u16_owned::<BigEndian>(1).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::WriteZero);
+    }
+}
+
+mod decode_error {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{BigEndian, ByteReader, DecodeError, LittleEndian};
+
+    #[tokio::test]
+    async fn context() {
+        let mut r = ByteReader::with_capacity(16, &[1, 2, 3, 4, 5, 6][..]);
+        r.read_u8().await.unwrap();
+        r.read_u24::<LittleEndian>().await.unwrap();
+        let err = r.read_i48::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        let ctx = DecodeError::from_io(&err).unwrap();
+        assert_eq!(ctx.what(), "i48");
+        assert_eq!(ctx.offset(), 4);
+        assert_eq!(ctx.available(), 2);
+        assert_eq!(ctx.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(
+            err.to_string(),
+            "failed to decode i48 at offset 4 (2 bytes available): failed to fill whole buffer"
+        );
+
+        // the short value was not consumed
+        assert_eq!(r.position(), 4);
+        assert_eq!(r.read_u16::<BigEndian>().await.unwrap(), 0x0506);
+        let err = r.read_u8().await.unwrap_err();
+        assert_eq!(DecodeError::from_io(&err).unwrap().what(), "u8");
+
+        let plain = std::io::Error::from(ErrorKind::Other);
+        assert!(DecodeError::from_io(&plain).is_none());
+    }
+}
+
+mod canonical_nan {
+    use tokio_byteorder::sans_io::{CANONICAL_NAN_F32, CANONICAL_NAN_F64};
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian};
+
+    #[tokio::test]
+    async fn read() {
+        let mut rdr = &[
+            0x7f, 0x80, 0x00, 0x01, // signalling NaN with a payload
+            0xff, 0xff, 0xff, 0xff, // negative quiet NaN
+            0x3f, 0x80, 0x00, 0x00, // 1.0
+        ][..];
+        for _ in 0..2 {
+            let n = rdr.read_f32_canonical::<BigEndian>().await.unwrap();
+            assert_eq!(n.to_bits(), CANONICAL_NAN_F32);
+        }
+        assert_eq!(rdr.read_f32_canonical::<BigEndian>().await.unwrap(), 1.0);
+
+        let mut rdr = &[0x01, 0, 0, 0, 0, 0, 0xf0, 0xff, 0, 0, 0, 0, 0, 0, 0, 0x80][..];
+        let n = rdr.read_f64_canonical::<LittleEndian>().await.unwrap();
+        assert_eq!(n.to_bits(), CANONICAL_NAN_F64);
+        // negative zero is not a NaN, and is left alone
+        let n = rdr.read_f64_canonical::<LittleEndian>().await.unwrap();
+        assert_eq!(n.to_bits(), (-0.0f64).to_bits());
+    }
+
+    #[tokio::test]
+    async fn write() {
+        let weird = f64::from_bits(0xfff0_0000_dead_beef);
+        assert!(weird.is_nan());
+
+        let mut wtr = Vec::new();
+        wtr.write_f64_canonical::<BigEndian>(weird).await.unwrap();
+        wtr.write_f64_canonical::<BigEndian>(-f64::NAN)
+            .await
+            .unwrap();
+        wtr.write_f64_canonical::<BigEndian>(2.5).await.unwrap();
+        wtr.write_f32_canonical::<LittleEndian>(f32::from_bits(0xffc0_0001))
+            .await
+            .unwrap();
+        assert_eq!(
+            wtr,
+            b"\x7f\xf8\0\0\0\0\0\0\x7f\xf8\0\0\0\0\0\0\x40\x04\0\0\0\0\0\0\0\0\xc0\x7f"
+        );
+    }
+}
+
+mod bit_reader {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{BitOrder, BitReader};
+
+    #[tokio::test]
+    async fn msb_first() {
+        let mut rdr = BitReader::msb_first(&[0b1011_0011, 0b1100_0101, 0xff, 0x12][..]);
+        assert!(rdr.read_bit().await.unwrap());
+        assert!(!rdr.read_bit().await.unwrap());
+        assert_eq!(rdr.read_bits(3).await.unwrap(), 0b110);
+        // spans the byte boundary
+        assert_eq!(rdr.read_bits(6).await.unwrap(), 0b011_110);
+        assert_eq!(rdr.read_bits(0).await.unwrap(), 0);
+        assert!(!rdr.is_aligned());
+        assert_eq!(rdr.align(), 5);
+        assert!(rdr.is_aligned());
+        assert_eq!(rdr.align(), 0);
+        assert_eq!(rdr.read_bits(16).await.unwrap(), 0xff12);
+        let err = rdr.read_bit().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn lsb_first() {
+        // DEFLATE block header: BFINAL = 1, BTYPE = 01 (fixed Huffman)
+        let mut rdr = BitReader::lsb_first(&[0b1010_1011, 0b0000_0110, 0x34, 0x12][..]);
+        assert_eq!(rdr.bit_order(), BitOrder::LsbFirst);
+        assert!(rdr.read_bit().await.unwrap());
+        assert_eq!(rdr.read_bits(2).await.unwrap(), 0b01);
+        // the rest of the first byte, then the low bits of the second
+        assert_eq!(rdr.read_bits(8).await.unwrap(), 0b110_10101);
+        rdr.align();
+        assert_eq!(rdr.read_bits(16).await.unwrap(), 0x1234);
+    }
+
+    #[tokio::test]
+    async fn wide() {
+        let bytes: Vec<u8> = (1..=9).collect();
+        let mut rdr = BitReader::msb_first(&bytes[..]);
+        rdr.read_bits(4).await.unwrap();
+        assert_eq!(rdr.read_bits(64).await.unwrap(), 0x1020_3040_5060_7080);
+        assert_eq!(rdr.read_bits(4).await.unwrap(), 0x9);
+
+        let mut rdr = BitReader::lsb_first(&bytes[..]);
+        assert_eq!(rdr.read_bits(64).await.unwrap(), 0x0807_0605_0403_0201);
+        assert_eq!(rdr.into_inner(), [9]);
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn too_wide() {
+        let _ = BitReader::msb_first(&[0; 9][..]).read_bits(65).await;
+    }
+}
+
+mod bit_writer {
+    use tokio_byteorder::{BitOrder, BitReader, BitWriter};
+
+    #[tokio::test]
+    async fn msb_first() {
+        let mut wtr = BitWriter::msb_first(Vec::new());
+        wtr.write_bit(true).await.unwrap();
+        wtr.write_bits(0b0110, 4).await.unwrap();
+        assert!(wtr.get_ref().is_empty());
+        // only the low bits are used
+        wtr.write_bits(0xfff0_0000_0000_0155, 10).await.unwrap();
+        assert!(!wtr.is_aligned());
+        assert_eq!(wtr.align().await.unwrap(), 1);
+        assert!(wtr.is_aligned());
+        assert_eq!(wtr.align().await.unwrap(), 0);
+        assert_eq!(wtr.into_inner(), [0b1011_0010, 0b1010_1010]);
+    }
+
+    #[tokio::test]
+    async fn lsb_first() {
+        let mut wtr = BitWriter::lsb_first(Vec::new());
+        wtr.write_bit(true).await.unwrap();
+        wtr.write_bits(0b01, 2).await.unwrap();
+        wtr.write_bits(0b110_10101, 8).await.unwrap();
+        wtr.align().await.unwrap();
+        wtr.write_bits(0x1234, 16).await.unwrap();
+        assert_eq!(wtr.into_inner(), [0b1010_1011, 0b0000_0110, 0x34, 0x12]);
+    }
+
+    #[tokio::test]
+    async fn padding() {
+        for (order, byte) in [
+            (BitOrder::MsbFirst, 0b0111_1111),
+            (BitOrder::LsbFirst, 0xfd),
+        ] {
+            let mut wtr = BitWriter::new(Vec::new(), order);
+            assert!(!wtr.padding());
+            wtr.set_padding(true);
+            wtr.write_bits(0b01, 2).await.unwrap();
+            assert_eq!(wtr.align().await.unwrap(), 6);
+            assert_eq!(wtr.into_inner(), [byte]);
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let fields = [
+            (5, 3),
+            (0x1ff, 9),
+            (0, 1),
+            (u64::MAX, 64),
+            (0x2a, 7),
+            (1, 1),
+        ];
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let mut wtr = BitWriter::new(Vec::new(), order);
+            for &(value, n) in &fields {
+                wtr.write_bits(value, n).await.unwrap();
+            }
+            wtr.align().await.unwrap();
+            let buf = wtr.into_inner();
+            assert_eq!(buf.len(), 11);
+
+            let mut rdr = BitReader::new(&buf[..], order);
+            for &(value, n) in &fields {
+                assert_eq!(rdr.read_bits(n).await.unwrap(), value);
+            }
+        }
+    }
+}
+
+mod nibbles {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, BitOrder, Nibbles};
+
+    #[test]
+    fn iter() {
+        let bytes = [0xab, 0xcd];
+        let high: Vec<u8> = Nibbles::new(&bytes, BitOrder::MsbFirst).collect();
+        assert_eq!(high, [0xa, 0xb, 0xc, 0xd]);
+        let low: Vec<u8> = Nibbles::new(&bytes, BitOrder::LsbFirst).collect();
+        assert_eq!(low, [0xb, 0xa, 0xd, 0xc]);
+        let back: Vec<u8> = Nibbles::new(&bytes, BitOrder::MsbFirst).rev().collect();
+        assert_eq!(back, [0xd, 0xc, 0xb, 0xa]);
+
+        let mut it = Nibbles::new(&bytes, BitOrder::MsbFirst);
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.next(), Some(0xa));
+        assert_eq!(it.next_back(), Some(0xd));
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.collect::<Vec<_>>(), [0xb, 0xc]);
+    }
+
+    #[tokio::test]
+    async fn read() {
+        let mut rdr = &[0x12, 0x34, 0x5f, 0x99][..];
+        let digits = rdr.read_nibbles(5, BitOrder::MsbFirst).await.unwrap();
+        assert_eq!(digits, [1, 2, 3, 4, 5]);
+        assert_eq!(rdr, [0x99]);
+
+        assert!(rdr
+            .read_nibbles(0, BitOrder::MsbFirst)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(rdr, [0x99]);
+
+        let err = rdr.read_nibbles(4, BitOrder::LsbFirst).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
 }
 
-macro_rules! rtts {
-    ($name:ident, $write:path, $read:path, $ty:tt) => {
-        mod $name {
-            rtt!(zero, $write, $read, 0 as $ty);
-            rtt!(min, $write, $read, $ty::min_value());
-            rtt!(max, $write, $read, $ty::max_value());
-            writes!($write, 0 as $ty);
+mod pcm {
+    use std::io::ErrorKind;
+    use tokio::io::AsyncWriteExt;
+    use tokio_byteorder::{BigEndian, LittleEndian, PcmReader, I24};
+
+    #[tokio::test]
+    async fn interleaved() {
+        let input = [
+            0x00, 0x00, 0x01, 0xff, 0xff, 0xff, 0x7f, 0xff, 0xff, 0x80, 0x00, 0x00,
+        ];
+        let mut rdr = PcmReader::<_, I24, BigEndian>::new(&input[..], 2);
+        assert_eq!(rdr.frame_len(), 6);
+        let mut out = [0; 4];
+        assert_eq!(rdr.read_interleaved(&mut out).await.unwrap(), 2);
+        assert_eq!(out, [1, -1, 0x7f_ffff, -0x80_0000]);
+        assert_eq!(rdr.read_interleaved(&mut out).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn planar() {
+        let mut input = Vec::new();
+        for i in 0..10i32 {
+            for c in 0..3 {
+                input.extend_from_slice(&((i * 3 + c) as f32).to_le_bytes());
+            }
         }
+        let mut rdr = PcmReader::<_, f32, LittleEndian>::new(&input[..], 3);
+        let mut chans = [[0.0; 4], [0.0; 4], [0.0; 4]];
+        let mut total = 0;
+        loop {
+            let [a, b, c] = &mut chans;
+            let n = rdr.read_planar(&mut [a, b, c]).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            for (c, chan) in chans.iter().enumerate() {
+                for (i, s) in chan[..n].iter().enumerate() {
+                    assert_eq!(*s, ((total + i) * 3 + c) as f32);
+                }
+            }
+            total += n;
+        }
+        assert_eq!(total, 10);
+    }
+
+    #[tokio::test]
+    async fn partial_frames() {
+        let (mut tx, rx) = tokio::io::duplex(64);
+        let mut rdr = PcmReader::<_, i16, LittleEndian>::new(rx, 2);
+        let mut out = [0; 6];
+
+        // a frame and a half arrive, then the rest of the frame along with a stray byte
+        tx.write_all(&[1, 0, 2, 0, 3, 0]).await.unwrap();
+        tx.write_all(&[4, 0, 5]).await.unwrap();
+        drop(tx);
+        assert_eq!(rdr.read_interleaved(&mut out).await.unwrap(), 2);
+        assert_eq!(out[..4], [1, 2, 3, 4]);
+        let err = rdr.read_interleaved(&mut out).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn uneven() {
+        let mut rdr = PcmReader::<_, i32, BigEndian>::new(&[][..], 2);
+        let _ = rdr.read_interleaved(&mut [0; 3]).await;
+    }
+}
+
+mod i24_bulk {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, I24Scaling, LittleEndian,
     };
+
+    #[tokio::test]
+    async fn sign_extend() {
+        let samples = [0, 1, -1, 0x7f_ffff, -0x80_0000, 0x12_3456];
+        let mut wtr = Vec::new();
+        wtr.write_i24_from::<LittleEndian>(&samples, I24Scaling::SignExtend)
+            .await
+            .unwrap();
+        assert_eq!(wtr.len(), 18);
+        assert_eq!(wtr[..6], [0, 0, 0, 1, 0, 0]);
+
+        let mut out = [0; 6];
+        (&wtr[..])
+            .read_i24_into_unpacked::<LittleEndian>(&mut out, I24Scaling::SignExtend)
+            .await
+            .unwrap();
+        assert_eq!(out, samples);
+
+        // out-of-range values wrap rather than panic
+        let mut wtr = Vec::new();
+        wtr.write_i24_from::<BigEndian>(&[0x0100_0001], I24Scaling::SignExtend)
+            .await
+            .unwrap();
+        assert_eq!(wtr, [0, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn left_justify() {
+        let mut wtr = Vec::new();
+        wtr.write_i24_from::<BigEndian>(&[i32::MAX, i32::MIN, 0x1ff], I24Scaling::LeftJustify)
+            .await
+            .unwrap();
+        assert_eq!(wtr, [0x7f, 0xff, 0xff, 0x80, 0, 0, 0, 0, 1]);
+
+        let mut out = [0; 3];
+        (&wtr[..])
+            .read_i24_into_unpacked::<BigEndian>(&mut out, I24Scaling::LeftJustify)
+            .await
+            .unwrap();
+        assert_eq!(out, [i32::MAX & !0xff, i32::MIN, 0x100]);
+    }
+
+    #[tokio::test]
+    async fn short() {
+        let mut out = [7; 2];
+        let err = (&[1, 2, 3, 4][..])
+            .read_i24_into_unpacked::<BigEndian>(&mut out, I24Scaling::SignExtend)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(out, [7, 7]);
+    }
+
+    /// A reader that fills a buffer of its own instead of the one it is given.
+    struct Swapper(Box<[u8; 8]>);
+
+    impl tokio::io::AsyncRead for Swapper {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            let own: &'static mut [u8; 8] = Box::leak(std::mem::take(&mut self.0));
+            let mut own = tokio::io::ReadBuf::new(&mut own[..]);
+            own.put_slice(&[1, 2, 3]);
+            *buf = own;
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "replaced the buffer")]
+    async fn swapped_buffer() {
+        let mut rdr = Swapper(Box::new([0; 8]));
+        let mut samples = [0; 4];
+        let _ = rdr
+            .read_i24_into_unpacked::<LittleEndian>(&mut samples, I24Scaling::SignExtend)
+            .await;
+    }
+}
+
+mod audio {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AiffCommon, AsyncDecode, AsyncEncode, WavFormat};
+
+    #[tokio::test]
+    async fn wav() {
+        let format = WavFormat {
+            format_tag: 0xfffe,
+            channels: 6,
+            sample_rate: 48000,
+            byte_rate: 48000 * 18,
+            block_align: 18,
+            bits_per_sample: 24,
+        };
+        let mut fmt = Vec::new();
+        format.write_to(&mut fmt).await.unwrap();
+        assert_eq!(fmt.len(), 16);
+        assert_eq!(WavFormat::read_from(&mut &fmt[..]).await.unwrap(), format);
+
+        // an odd-sized chunk before fmt, and a 22 byte extension after it
+        let mut file = b"RIFF\0\0\0\0WAVELIST\x03\0\0\0abc\0fmt \x28\0\0\0".to_vec();
+        file.extend_from_slice(&fmt);
+        file.extend_from_slice(&[0x16, 0x00]);
+        file.extend_from_slice(&[0xaa; 22]);
+        file.extend_from_slice(b"data");
+        let mut rdr = &file[..];
+        assert_eq!(WavFormat::read_from_wav(&mut rdr).await.unwrap(), format);
+        assert_eq!(rdr, b"data");
+    }
+
+    #[tokio::test]
+    async fn wav_errors() {
+        let err = WavFormat::read_from_wav(&mut &b"RIFF\0\0\0\0AVI "[..])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = WavFormat::read_from_wav(&mut &b"RIFF\0\0\0\0WAVEfmt \x0e\0\0\0"[..])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = WavFormat::read_from_wav(&mut &b"RIFF\0\0\0\0WAVEdata\0\0\0\0"[..])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn aiff() {
+        let rates: [(&[u8; 10], f64); 5] = [
+            (b"\x40\x0e\xac\x44\0\0\0\0\0\0", 44100.0),
+            (b"\x40\x0e\xbb\x80\0\0\0\0\0\0", 48000.0),
+            (b"\x40\x0b\xfa\x00\0\0\0\0\0\0", 8000.0),
+            (b"\x40\x0f\xbb\x80\0\0\0\0\0\0", 96000.0),
+            (b"\x3f\xfe\x80\x00\0\0\0\0\0\0", 0.5),
+        ];
+        for (rate, expected) in rates {
+            let mut comm = b"\x00\x02\x00\x01\x00\x00\x00\x10".to_vec();
+            comm.extend_from_slice(rate);
+            let decoded = AiffCommon::read_from(&mut &comm[..]).await.unwrap();
+            assert_eq!(decoded.channels, 2);
+            assert_eq!(decoded.sample_frames, 0x10000);
+            assert_eq!(decoded.sample_size, 16);
+            assert_eq!(decoded.sample_rate, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn aifc() {
+        // AIFF-C adds a compression type and name to COMM, which are skipped
+        let mut file = b"FORM\0\0\0\0AIFCFVER\0\0\0\x04\xa2\x80\x51\x40COMM\0\0\0\x17".to_vec();
+        file.extend_from_slice(b"\x00\x01\x00\x00\x00\x2a\x00\x20\x40\x0e\xbb\x80\0\0\0\0\0\0");
+        file.extend_from_slice(b"fl32\0\0");
+        file.extend_from_slice(b"SSND");
+        let mut rdr = &file[..];
+        let comm = AiffCommon::read_from_aiff(&mut rdr).await.unwrap();
+        assert_eq!(
+            comm,
+            AiffCommon {
+                channels: 1,
+                sample_frames: 42,
+                sample_size: 32,
+                sample_rate: 48000.0,
+            }
+        );
+        assert_eq!(rdr, b"SSND");
+    }
 }
 
-rtts!(
-    u8,
-    AsyncWriteBytesExt::write_u8,
-    AsyncReadBytesExt::read_u8,
-    u8
-);
-rtts!(
-    i8,
-    AsyncWriteBytesExt::write_i8,
-    AsyncReadBytesExt::read_i8,
-    i8
-);
-rtts!(
-    u16,
-    AsyncWriteBytesExt::write_u16::<BigEndian>,
-    AsyncReadBytesExt::read_u16::<BigEndian>,
-    u16
-);
-rtts!(
-    i16,
-    AsyncWriteBytesExt::write_i16::<BigEndian>,
-    AsyncReadBytesExt::read_i16::<BigEndian>,
-    i16
-);
-rtts!(
-    u32,
-    AsyncWriteBytesExt::write_u32::<BigEndian>,
-    AsyncReadBytesExt::read_u32::<BigEndian>,
-    u32
-);
-rtts!(
-    i32,
-    AsyncWriteBytesExt::write_i32::<BigEndian>,
-    AsyncReadBytesExt::read_i32::<BigEndian>,
-    i32
-);
-rtts!(
-    u64,
-    AsyncWriteBytesExt::write_u64::<BigEndian>,
-    AsyncReadBytesExt::read_u64::<BigEndian>,
-    u64
-);
-rtts!(
-    i64,
-    AsyncWriteBytesExt::write_i64::<BigEndian>,
-    AsyncReadBytesExt::read_i64::<BigEndian>,
-    i64
-);
+mod tee {
+    use std::io::ErrorKind;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::AsyncWrite;
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian, TeeReader, TeeWriter,
+    };
+
+    /// Accepts at most one byte per write.
+    struct Trickle(Vec<u8>);
+
+    impl AsyncWrite for Trickle {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            let n = buf.len().min(1);
+            self.0.extend_from_slice(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    struct Broken;
+
+    impl AsyncWrite for Broken {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            Poll::Ready(Err(ErrorKind::BrokenPipe.into()))
+        }
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn reader() {
+        let input: Vec<u8> = (0..100).collect();
+        let mut rdr = TeeReader::new(&input[..], Trickle(Vec::new()));
+        rdr.read_u32::<LittleEndian>().await.unwrap();
+        rdr.read_u8().await.unwrap();
+        let mut rest = Vec::new();
+        tokio::io::AsyncReadExt::read_to_end(&mut rdr, &mut rest)
+            .await
+            .unwrap();
+        rdr.flush_capture().await.unwrap();
+        let (inner, capture) = rdr.into_parts();
+        assert!(inner.is_empty());
+        assert_eq!(capture.0, input);
+    }
+
+    #[tokio::test]
+    async fn writer() {
+        let mut wtr = TeeWriter::new(Trickle(Vec::new()), Vec::new());
+        wtr.write_u64::<BigEndian>(0x0102_0304_0506_0708)
+            .await
+            .unwrap();
+        wtr.write_u8(9).await.unwrap();
+        tokio::io::AsyncWriteExt::shutdown(&mut wtr).await.unwrap();
+        let (inner, capture) = wtr.into_parts();
+        assert_eq!(inner.0, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(capture, inner.0);
+    }
+
+    #[tokio::test]
+    async fn capture_error() {
+        let mut rdr = TeeReader::new(&[1, 2, 3][..], Broken);
+        assert_eq!(rdr.read_u8().await.unwrap(), 1);
+        let err = rdr.read_u8().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+        // the failed read did not consume anything
+        assert_eq!(rdr.get_ref(), &[2, 3]);
+
+        let mut wtr = TeeWriter::new(Vec::new(), Broken);
+        wtr.write_u8(1).await.unwrap();
+        let err = tokio::io::AsyncWriteExt::flush(&mut wtr).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    }
+}
+
+mod stats {
+    use std::io::ErrorKind;
+    use tokio::io::AsyncWriteExt;
+    use tokio_byteorder::{AsyncReadBytesExt, BigEndian, LittleEndian, StatsReader};
+
+    #[tokio::test]
+    async fn counts() {
+        let (mut tx, rx) = tokio::io::duplex(64);
+        let mut rdr = StatsReader::new(rx);
+
+        // the u32 arrives in two pieces
+        tx.write_all(&[0, 1]).await.unwrap();
+        let read = tokio::spawn(async move {
+            let v = rdr.read_u32::<BigEndian>().await.unwrap();
+            (rdr, v)
+        });
+        tokio::task::yield_now().await;
+        tx.write_all(&[2, 3, 0xff, 0x2a, 0x00, 1]).await.unwrap();
+        let (mut rdr, v) = read.await.unwrap();
+        assert_eq!(v, 0x0001_0203);
+
+        rdr.read_i8().await.unwrap();
+        rdr.read_i16::<LittleEndian>().await.unwrap();
+        // reads through the extension trait are only counted at the I/O level
+        AsyncReadBytesExt::read_u8(&mut rdr).await.unwrap();
+        drop(tx);
+        let err = rdr.read_u16::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let stats = rdr.stats();
+        assert_eq!(stats.bytes, 8);
+        assert!(stats.partial_reads >= 1);
+        assert!(stats.reads >= 5);
+        assert_eq!(stats.values("u32"), 1);
+        assert_eq!(stats.values("i8"), 1);
+        assert_eq!(stats.values("i16"), 1);
+        assert_eq!(stats.values("u8"), 0);
+        assert_eq!(stats.values("u16"), 0);
+        assert_eq!(stats.values("varint"), 0);
+        assert_eq!(
+            stats.iter_values().collect::<Vec<_>>(),
+            [("i8", 1), ("i16", 1), ("u32", 1)]
+        );
+
+        let old = rdr.reset_stats();
+        assert_eq!(old, stats);
+        assert_eq!(rdr.stats().bytes, 0);
+    }
+}
+
+mod length_prefixed {
+    use bytes::{Buf, Bytes, BytesMut};
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian};
+
+    #[tokio::test]
+    async fn reuses_buffer() {
+        let mut rdr = &b"\x00\x00\x00\x05hello\x00\x00\x00\x02hi\x00\x00\x00\x00"[..];
+        let mut buf = Vec::with_capacity(64);
+        let ptr = buf.as_ptr();
+        rdr.read_length_prefixed_into::<u32, BigEndian>(&mut buf, 5)
+            .await
+            .unwrap();
+        assert_eq!(buf, b"hello");
+        rdr.read_length_prefixed_into::<u32, BigEndian>(&mut buf, 5)
+            .await
+            .unwrap();
+        assert_eq!(buf, b"hi");
+        rdr.read_length_prefixed_into::<u32, BigEndian>(&mut buf, 5)
+            .await
+            .unwrap();
+        assert!(buf.is_empty());
+        assert_eq!(buf.as_ptr(), ptr);
+    }
+
+    #[tokio::test]
+    async fn bytes_mut() {
+        let mut rdr = &b"\x03abc"[..];
+        let mut buf = BytesMut::from(&b"old"[..]);
+        rdr.read_length_prefixed_into::<u8, LittleEndian>(&mut buf, 16)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..], b"abc");
+    }
+
+    #[tokio::test]
+    async fn too_long() {
+        let mut buf = b"old".to_vec();
+        let mut rdr = &b"\x00\x06hello!"[..];
+        let err = rdr
+            .read_length_prefixed_into::<u16, BigEndian>(&mut buf, 5)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(buf, b"old");
+
+        let mut rdr = &b"\xff\xff"[..];
+        let err = rdr
+            .read_length_prefixed_into::<i16, BigEndian>(&mut buf, 5)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn short() {
+        let mut buf = Vec::new();
+        let mut rdr = &b"\x05hel"[..];
+        let err = rdr
+            .read_length_prefixed_into::<u8, BigEndian>(&mut buf, 16)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(buf, b"hel");
+    }
+
+    #[tokio::test]
+    async fn bytes_round_trip() {
+        let mut wtr = Vec::new();
+        let payload = Bytes::from_static(b"zero").chain(&b"-copy"[..]);
+        wtr.write_length_prefixed::<u32, LittleEndian, _>(payload)
+            .await
+            .unwrap();
+        wtr.write_length_prefixed::<u32, LittleEndian, _>(Bytes::new())
+            .await
+            .unwrap();
+        assert_eq!(&wtr[..4], b"\x09\x00\x00\x00");
+
+        let mut rdr = &wtr[..];
+        let payload = rdr
+            .read_length_prefixed_bytes::<u32, LittleEndian>(9)
+            .await
+            .unwrap();
+        assert_eq!(payload, "zero-copy");
+        let payload = rdr
+            .read_length_prefixed_bytes::<u32, LittleEndian>(9)
+            .await
+            .unwrap();
+        assert!(payload.is_empty());
+        assert!(rdr.is_empty());
+    }
+
+    #[tokio::test]
+    async fn bytes_errors() {
+        let mut rdr = &b"\x0a0123456789"[..];
+        let err = rdr
+            .read_length_prefixed_bytes::<u8, BigEndian>(9)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x0a01234"[..];
+        let err = rdr
+            .read_length_prefixed_bytes::<u8, BigEndian>(10)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_length_prefixed::<u8, BigEndian, _>(&[0; 256][..])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+}
+
+mod and_flush {
+    use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, ByteWriter, LittleEndian, RuntimeEndian};
+
+    #[tokio::test]
+    async fn flushes_after_write() {
+        // the inner writer only passes bytes on when it is flushed
+        let mut w = ByteWriter::unbounded(Vec::new());
+        AsyncWriteBytesExt::write_u8(&mut w, 1)
+            .and_flush()
+            .await
+            .unwrap();
+        AsyncWriteBytesExt::write_u16::<BigEndian>(&mut w, 2)
+            .and_flush()
+            .await
+            .unwrap();
+        w.write_primitive::<i32, LittleEndian>(-1)
+            .and_flush()
+            .await
+            .unwrap();
+        w.write_u16_rt(RuntimeEndian::Little, 3)
+            .and_flush()
+            .await
+            .unwrap();
+        assert!(w.buffer().is_empty());
+        assert_eq!(w.get_ref(), b"\x01\x00\x02\xff\xff\xff\xff\x03\x00");
+
+        // without it, the bytes stay buffered
+        AsyncWriteBytesExt::write_u8(&mut w, 4).await.unwrap();
+        assert_eq!(w.buffer(), b"\x04");
+    }
+}
+
+mod frame_writer {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncWriteBytesExt, BigEndian, FrameWriter, LittleEndian};
+
+    #[tokio::test]
+    async fn back_patches_length() {
+        let mut w = FrameWriter::<_, u32, LittleEndian>::new(Vec::new());
+        w.finish().await.unwrap();
+        w.write_u16::<BigEndian>(1).await.unwrap();
+        w.write_i24::<LittleEndian>(-2).await.unwrap();
+        assert_eq!(w.frame_len(), 5);
+        assert_eq!(w.frame(), b"\x00\x01\xfe\xff\xff");
+        w.finish().await.unwrap();
+        assert_eq!(w.frame_len(), 0);
+        assert_eq!(
+            w.into_inner(),
+            b"\x00\x00\x00\x00\x05\x00\x00\x00\x00\x01\xfe\xff\xff"
+        );
+    }
+
+    #[tokio::test]
+    async fn discards() {
+        let mut w = FrameWriter::<_, u8, BigEndian>::new(Vec::new());
+        w.write_u32::<BigEndian>(1).await.unwrap();
+        w.discard();
+        w.write_u8(2).await.unwrap();
+        w.finish().await.unwrap();
+        assert_eq!(w.get_ref(), b"\x01\x02");
+
+        // a frame that is too long for its prefix is not written
+        tokio::io::AsyncWriteExt::write_all(&mut w, &[0; 256])
+            .await
+            .unwrap();
+        let err = w.finish().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(w.frame_len(), 0);
+        assert_eq!(w.get_ref(), b"\x01\x02");
+
+        let err = w
+            .write_frame(|f| {
+                Box::pin(async move {
+                    f.write_u8(3).await?;
+                    Err::<(), _>(std::io::Error::from(ErrorKind::InvalidData))
+                })
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let n = w
+            .write_frame(|f| Box::pin(async move { f.write_u8(4).await.map(|_| 4) }))
+            .await
+            .unwrap();
+        assert_eq!(n, 4);
+        assert_eq!(w.get_ref(), b"\x01\x02\x01\x04");
+    }
+}
+
+mod offset_table {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{
+        AsyncWriteBytesExt, BigEndian, CountingWriter, LittleEndian, OffsetTable,
+    };
+
+    #[tokio::test]
+    async fn records_positions() {
+        let mut w = CountingWriter::with_position(Vec::new(), 0x100);
+        let mut table = OffsetTable::new();
+        assert!(table.is_empty());
+        for i in 0..3 {
+            table.record(&w);
+            w.write_u24::<BigEndian>(i).await.unwrap();
+        }
+        table.extend([0x1_0000_0000]);
+        assert_eq!(table.offsets(), [0x100, 0x103, 0x106, 0x1_0000_0000]);
+
+        let mut out = Vec::new();
+        table
+            .write_to::<u64, LittleEndian, _>(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(out.len(), 32);
+        assert_eq!(out[8..16], 0x103u64.to_le_bytes());
+
+        // an offset that does not fit fails the whole table
+        let mut out = Vec::new();
+        let err = table
+            .write_to::<u32, BigEndian, _>(&mut out)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+    }
+}
+
+mod limit {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{
+        AsyncBufReadBytesExt, AsyncReadBytesExt, BigEndian, LimitError, LittleEndian,
+    };
+
+    #[tokio::test]
+    async fn strict_exhaustion() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let mut rdr = &data[..];
+        let mut field = (&mut rdr).limit(4);
+        assert_eq!(field.read_u16::<LittleEndian>().await.unwrap(), 0x0201);
+        let err = field.expect_exhausted().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let ctx = LimitError::from_io(&err).unwrap();
+        assert!(!ctx.is_overrun());
+        assert_eq!((ctx.limit(), ctx.remaining()), (4, 2));
+        assert_eq!(err.to_string(), "2 of a 4 byte limit left unread");
+        assert_eq!(rdr, [3, 4, 5, 6]);
+
+        let mut field = (&mut rdr).limit(3);
+        let err = field.read_u32::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(LimitError::from_io(&err).unwrap().is_overrun());
+        assert_eq!(field.remaining(), 0);
+        field.expect_exhausted().unwrap();
+        assert_eq!(rdr, [6]);
+
+        // running out of stream first is the usual end of file
+        let mut field = (&mut rdr).limit(2);
+        let err = field.read_u16::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert!(LimitError::from_io(&err).is_none());
+    }
+
+    #[tokio::test]
+    async fn buffered() {
+        let mut field = (&[0, 1, 2, 3][..]).limit(3);
+        assert_eq!(field.peek_u16::<BigEndian>().await.unwrap(), Some(1));
+        assert_eq!(field.peek_u32::<BigEndian>().await.unwrap(), None);
+        assert_eq!(field.read_u24::<BigEndian>().await.unwrap(), 0x0102);
+        assert_eq!(field.expect_exhausted().unwrap(), [3]);
+    }
+
+    #[tokio::test]
+    async fn buffered_overrun() {
+        let data = [0, 1, 2, 3, 4, 5];
+        let mut field = (&data[..]).limit(3);
+        let err = field.buf_read_u32::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(LimitError::from_io(&err).unwrap().is_overrun());
+        assert_eq!(field.remaining(), 0);
+
+        // once the limit is reached, the buffered path fails rather than reporting end of file
+        let err = field.peek_u8().await.unwrap_err();
+        assert!(LimitError::from_io(&err).unwrap().is_overrun());
+        let err = field.resync_to(&[4], 8).await.unwrap_err();
+        assert!(LimitError::from_io(&err).unwrap().is_overrun());
+        field.expect_exhausted().unwrap();
+    }
+}
+
+mod probe {
+    use tokio::io::AsyncReadExt;
+    use tokio_byteorder::{AsyncReadBytesExt, BigEndian, LittleEndian, Probe};
+
+    #[tokio::test]
+    async fn rewinds() {
+        let mut rdr = Probe::new(&[1, 0, 2, 0, 3, 4][..]);
+        assert_eq!(
+            AsyncReadBytesExt::read_u16::<LittleEndian>(&mut rdr)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(rdr.probed(), [1, 0]);
+        rdr.rewind();
+        assert!(!rdr.is_recording());
+        // a read that spans the recorded bytes and the rest of the stream
+        assert_eq!(
+            AsyncReadBytesExt::read_u24::<BigEndian>(&mut rdr)
+                .await
+                .unwrap(),
+            0x010002
+        );
+        assert!(rdr.probed().is_empty());
+        rdr.rewind();
+        let mut rest = Vec::new();
+        rdr.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, [0, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn commits() {
+        let mut rdr = Probe::new(&b"V2\x00\x07"[..]);
+        let mut magic = [0; 2];
+        rdr.read_exact(&mut magic).await.unwrap();
+        assert_eq!(&magic, b"V2");
+        rdr.commit();
+        assert!(rdr.probed().is_empty());
+        assert_eq!(
+            AsyncReadBytesExt::read_u16::<BigEndian>(&mut rdr)
+                .await
+                .unwrap(),
+            7
+        );
+
+        // committing after a rewind keeps what has not been read again
+        let mut rdr = Probe::new(&[1, 2, 3][..]);
+        AsyncReadBytesExt::read_u16::<BigEndian>(&mut rdr)
+            .await
+            .unwrap();
+        rdr.rewind();
+        assert_eq!(AsyncReadBytesExt::read_u8(&mut rdr).await.unwrap(), 1);
+        rdr.commit();
+        assert_eq!(rdr.probed(), [2]);
+        let mut rest = Vec::new();
+        rdr.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, [2, 3]);
+    }
+}
+
+mod chain {
+    use tokio::io::AsyncReadExt;
+    use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, BigEndian, LittleEndian};
+
+    const BYTES: [u8; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+    #[tokio::test]
+    async fn u64_straddles_seam() {
+        for seam in 0..=BYTES.len() {
+            let (head, tail) = BYTES.split_at(seam);
+            let mut rdr = head.chain(tail);
+            assert_eq!(AsyncReadBytesExt::read_u8(&mut rdr).await.unwrap(), 1);
+            let n = AsyncReadBytesExt::read_u64::<BigEndian>(&mut rdr)
+                .await
+                .unwrap();
+            assert_eq!(n, 0x0203_0405_0607_0809, "seam at {}", seam);
+            let err = AsyncReadBytesExt::read_u16::<LittleEndian>(&mut rdr)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[tokio::test]
+    async fn buffered_u64_straddles_seam() {
+        for seam in 0..=BYTES.len() {
+            let (head, tail) = BYTES.split_at(seam);
+            let mut rdr = head.chain(tail);
+            assert_eq!(rdr.buf_read_u8().await.unwrap(), 1);
+            let n = rdr.buf_read_u64::<LittleEndian>().await.unwrap();
+            assert_eq!(n, 0x0908_0706_0504_0302, "seam at {}", seam);
+            assert_eq!(rdr.buf_read_u8().await.unwrap(), 10);
+        }
+    }
+
+    #[tokio::test]
+    async fn leftover_bytes() {
+        // a handshake parser that read too far puts the extra bytes back in front
+        let socket = &[0x00, 0x00, 0x01, 0x0b, 0xff][..];
+        let leftover = bytes::Bytes::from_static(&[0x00, 0x00, 0x00, 0x00]);
+        let mut rdr = std::io::Cursor::new(leftover).chain(socket);
+        let n = AsyncReadBytesExt::read_u64::<BigEndian>(&mut rdr)
+            .await
+            .unwrap();
+        assert_eq!(n, 0x010b);
+        assert_eq!(AsyncReadBytesExt::read_i8(&mut rdr).await.unwrap(), -1);
+    }
+}
+
+mod duplex {
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, Duplex, LittleEndian};
+
+    #[tokio::test]
+    async fn split_halves() {
+        let (client, server) = tokio::io::duplex(8);
+        let (mut rx, mut tx) = tokio::io::split(client);
+        let echo = async {
+            let mut server = Duplex::split(server);
+            for _ in 0..64 {
+                let n = server.read_u32::<LittleEndian>().await.unwrap();
+                server.write_u32::<BigEndian>(n * 2).await.unwrap();
+            }
+            server
+        };
+        // more than the pipe holds, so the writes only finish if the reads run alongside
+        let send = async {
+            for n in 0..64 {
+                tx.write_u32::<LittleEndian>(n).await.unwrap();
+            }
+        };
+        let recv = async {
+            for n in 0..64 {
+                assert_eq!(rx.read_u32::<BigEndian>().await.unwrap(), n * 2);
+            }
+        };
+        let (server, (), ()) = tokio::join!(echo, send, recv);
+
+        let mut client = rx.unsplit(tx);
+        let mut server = server.unsplit();
+        client.write_i16::<BigEndian>(-2).await.unwrap();
+        assert_eq!(server.read_i16::<BigEndian>().await.unwrap(), -2);
+    }
+
+    #[tokio::test]
+    async fn halves_mut() {
+        let (client, server) = tokio::io::duplex(4);
+        let mut client = Duplex::split(client);
+        let mut server = Duplex::split(server);
+        let (rx, tx) = client.halves_mut();
+        let send = async {
+            for n in 0..16u64 {
+                tx.write_u64::<BigEndian>(n).await.unwrap();
+            }
+        };
+        let echo = async {
+            for _ in 0..16 {
+                let n = server.read_u64::<BigEndian>().await.unwrap();
+                server.write_u64::<BigEndian>(n + 1).await.unwrap();
+            }
+        };
+        let recv = async {
+            for n in 0..16u64 {
+                assert_eq!(rx.read_u64::<BigEndian>().await.unwrap(), n + 1);
+            }
+        };
+        tokio::join!(send, echo, recv);
+    }
+
+    #[tokio::test]
+    async fn separate_reader_and_writer() {
+        let mut io = Duplex::new(&[0x01, 0x02][..], Vec::new());
+        let n = io.read_u16::<BigEndian>().await.unwrap();
+        io.write_u16::<LittleEndian>(n).await.unwrap();
+        let (rdr, wtr) = io.into_parts();
+        assert!(rdr.is_empty());
+        assert_eq!(wtr, [0x02, 0x01]);
+    }
+}
+
+mod map_bytes {
+    use tokio::io::AsyncWriteExt;
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, LittleEndian, MapBytesReader,
+        MapBytesWriter,
+    };
+
+    // the PN9 whitening sequence, which is its own inverse
+    fn pn9() -> impl FnMut(&mut [u8]) + Unpin {
+        let mut lfsr = 0x1ffu16;
+        move |buf: &mut [u8]| {
+            for b in buf {
+                let mut mask = 0;
+                for i in 0..8 {
+                    mask |= ((lfsr & 1) as u8) << i;
+                    let fb = (lfsr ^ (lfsr >> 5)) & 1;
+                    lfsr = (lfsr >> 1) | (fb << 8);
+                }
+                *b ^= mask;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn whitening_round_trip() {
+        let (tx, rx) = tokio::io::duplex(3);
+        let send = async {
+            let mut wtr = MapBytesWriter::new(tx, pn9());
+            for n in 0..100u32 {
+                AsyncWriteBytesExt::write_u32::<LittleEndian>(&mut wtr, n * 7)
+                    .await
+                    .unwrap();
+                AsyncWriteBytesExt::write_u8(&mut wtr, n as u8)
+                    .await
+                    .unwrap();
+            }
+            wtr.shutdown().await.unwrap();
+            assert!(wtr.pending().is_empty());
+        };
+        let recv = async {
+            let mut rdr = MapBytesReader::new(rx, pn9());
+            for n in 0..100u32 {
+                assert_eq!(rdr.read_u32::<LittleEndian>().await.unwrap(), n * 7);
+                assert_eq!(rdr.read_u8().await.unwrap(), n as u8);
+            }
+            let err = rdr.read_u8().await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        };
+        tokio::join!(send, recv);
+    }
+
+    #[tokio::test]
+    async fn whitened_bytes_differ() {
+        let mut wtr = MapBytesWriter::new(Vec::new(), pn9());
+        AsyncWriteBytesExt::write_u32::<BigEndian>(&mut wtr, 0)
+            .await
+            .unwrap();
+        wtr.flush().await.unwrap();
+        let (whitened, _) = wtr.into_parts();
+        assert_eq!(whitened, [0xff, 0xe1, 0x1d, 0x9a]);
+
+        let mut rdr = MapBytesReader::new(&whitened[..], pn9());
+        assert_eq!(rdr.read_u32::<BigEndian>().await.unwrap(), 0);
+    }
+}
+
+mod cobs {
+    use std::io::ErrorKind;
+    use tokio::io::BufReader;
+    use tokio_byteorder::{AsyncBufReadBytesExt, AsyncWriteBytesExt};
+
+    fn vectors() -> Vec<(Vec<u8>, Vec<u8>)> {
+        let run = |from: u8, to: u8| (from..=to).collect::<Vec<u8>>();
+        let cat = |parts: &[&[u8]]| parts.concat();
+        vec![
+            (vec![], vec![0x01, 0x00]),
+            (vec![0x00], vec![0x01, 0x01, 0x00]),
+            (vec![0x00, 0x00], vec![0x01, 0x01, 0x01, 0x00]),
+            (vec![0x00, 0x11, 0x00], vec![0x01, 0x02, 0x11, 0x01, 0x00]),
+            (
+                vec![0x11, 0x22, 0x33, 0x44],
+                vec![0x05, 0x11, 0x22, 0x33, 0x44, 0x00],
+            ),
+            (
+                vec![0x11, 0x00, 0x00, 0x00],
+                vec![0x02, 0x11, 0x01, 0x01, 0x01, 0x00],
+            ),
+            (run(0x01, 0xfe), cat(&[&[0xff], &run(0x01, 0xfe), &[0x00]])),
+            (
+                cat(&[&[0x00], &run(0x01, 0xfe)]),
+                cat(&[&[0x01, 0xff], &run(0x01, 0xfe), &[0x00]]),
+            ),
+            (
+                run(0x01, 0xff),
+                cat(&[&[0xff], &run(0x01, 0xfe), &[0x02, 0xff, 0x00]]),
+            ),
+            (
+                cat(&[&run(0x02, 0xff), &[0x00]]),
+                cat(&[&[0xff], &run(0x02, 0xff), &[0x01, 0x01, 0x00]]),
+            ),
+            (
+                cat(&[&run(0x03, 0xff), &[0x00, 0x01]]),
+                cat(&[&[0xfe], &run(0x03, 0xff), &[0x02, 0x01, 0x00]]),
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn known_vectors() {
+        for (data, encoded) in vectors() {
+            let mut wtr = Vec::new();
+            wtr.write_cobs_frame(&data).await.unwrap();
+            assert_eq!(wtr, encoded);
+
+            // one byte at a time, so that every block straddles a buffer
+            let mut rdr = BufReader::with_capacity(1, &encoded[..]);
+            assert_eq!(rdr.read_cobs_frame(data.len()).await.unwrap(), data);
+            let mut rdr = &encoded[..];
+            assert_eq!(rdr.read_cobs_frame(data.len()).await.unwrap(), data);
+            assert!(rdr.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn back_to_back_frames() {
+        let frames: Vec<Vec<u8>> = (0..600)
+            .step_by(37)
+            .map(|n| (0..n).map(|i| (i * 7 % 5) as u8).collect())
+            .collect();
+        let mut wire = vec![0x00];
+        for frame in &frames {
+            wire.write_cobs_frame(frame).await.unwrap();
+            // stray delimiters between frames are skipped
+            wire.push(0x00);
+        }
+        let mut rdr = BufReader::with_capacity(7, &wire[..]);
+        for frame in &frames {
+            assert_eq!(&rdr.read_cobs_frame(600).await.unwrap(), frame);
+        }
+        let err = rdr.read_cobs_frame(600).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn bad_frames_are_skipped() {
+        let mut wire = Vec::new();
+        wire.write_cobs_frame(b"too long").await.unwrap();
+        // a block that promises four bytes, but is cut short by a delimiter
+        wire.extend_from_slice(&[0x05, 0x11, 0x22, 0x00]);
+        wire.write_cobs_frame(b"ok").await.unwrap();
+        wire.extend_from_slice(&[0x03, 0x11]);
+
+        let mut rdr = BufReader::with_capacity(3, &wire[..]);
+        let err = rdr.read_cobs_frame(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = rdr.read_cobs_frame(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(rdr.read_cobs_frame(4).await.unwrap(), b"ok");
+        let err = rdr.read_cobs_frame(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod slip {
+    use std::io::ErrorKind;
+    use tokio::io::BufReader;
+    use tokio_byteorder::{AsyncBufReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn round_trip() {
+        let frames: Vec<Vec<u8>> = vec![
+            vec![0xc0],
+            vec![0xdb],
+            vec![0xdb, 0xdc, 0xc0, 0xdd],
+            (0..=255).collect(),
+            b"plain".to_vec(),
+        ];
+        let mut wire = Vec::new();
+        for frame in &frames {
+            wire.write_slip_frame(frame).await.unwrap();
+        }
+        assert_eq!(&wire[..6], b"\xc0\xdb\xdc\xc0\xc0\xdb");
+
+        // with buffers small enough that escapes straddle them
+        for capacity in [1, 2, 64] {
+            let mut rdr = BufReader::with_capacity(capacity, &wire[..]);
+            for frame in &frames {
+                assert_eq!(&rdr.read_slip_frame(300).await.unwrap(), frame);
+            }
+            let err = rdr.read_slip_frame(300).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[tokio::test]
+    async fn bad_frames_are_skipped() {
+        let mut wire = Vec::new();
+        wire.write_slip_frame(b"too long").await.unwrap();
+        wire.extend_from_slice(b"\x01\xdb\x02\x03\xc0");
+        wire.extend_from_slice(b"\x01\xdb\xc0");
+        wire.write_slip_frame(b"\xdbok").await.unwrap();
+        wire.extend_from_slice(b"\x01");
+
+        let mut rdr = BufReader::with_capacity(3, &wire[..]);
+        for _ in 0..3 {
+            let err = rdr.read_slip_frame(4).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+        assert_eq!(rdr.read_slip_frame(4).await.unwrap(), b"\xdbok");
+        let err = rdr.read_slip_frame(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod varint_block {
+    use tokio::io::BufReader;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    fn values(n: usize) -> Vec<u32> {
+        (0..n as u32)
+            .map(|i| match i % 5 {
+                0 => i,
+                1 => i << 9,
+                2 => i << 17,
+                3 => u32::MAX - i,
+                _ => 0,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        for n in 0..=9 {
+            let src = values(n);
+
+            let mut wtr = Vec::new();
+            wtr.write_group_varint_u32(&src).await.unwrap();
+            wtr.write_u8(0xaa).await.unwrap();
+            let mut rdr = BufReader::with_capacity(1, &wtr[..]);
+            let mut dst = vec![0; n];
+            rdr.read_group_varint_u32(&mut dst).await.unwrap();
+            assert_eq!(dst, src);
+            assert_eq!(rdr.read_u8().await.unwrap(), 0xaa);
+
+            let mut wtr = Vec::new();
+            wtr.write_stream_vbyte_u32(&src).await.unwrap();
+            wtr.write_u8(0xaa).await.unwrap();
+            let mut rdr = BufReader::with_capacity(1, &wtr[..]);
+            let mut dst = vec![0; n];
+            rdr.read_stream_vbyte_u32(&mut dst).await.unwrap();
+            assert_eq!(dst, src);
+            assert_eq!(rdr.read_u8().await.unwrap(), 0xaa);
+        }
+    }
+
+    #[tokio::test]
+    async fn layouts_differ() {
+        let src = [0x0102, 3, 0x04_0506, 0x0708_090a, 0x0b];
+
+        let mut wtr = Vec::new();
+        wtr.write_group_varint_u32(&src).await.unwrap();
+        assert_eq!(
+            wtr,
+            [
+                0b01_00_10_11,
+                0x02,
+                0x01,
+                0x03,
+                0x06,
+                0x05,
+                0x04,
+                0x0a,
+                0x09,
+                0x08,
+                0x07,
+                0b00_00_00_00,
+                0x0b,
+            ]
+        );
+
+        let mut wtr = Vec::new();
+        wtr.write_stream_vbyte_u32(&src).await.unwrap();
+        assert_eq!(
+            wtr,
+            [
+                0b11_10_00_01,
+                0b00_00_00_00,
+                0x02,
+                0x01,
+                0x03,
+                0x06,
+                0x05,
+                0x04,
+                0x0a,
+                0x09,
+                0x08,
+                0x07,
+                0x0b,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn truncated() {
+        let mut dst = [7; 4];
+        let mut rdr = &[0b00_00_00_11, 1, 2, 3, 4, 5, 6][..];
+        let err = rdr.read_group_varint_u32(&mut dst).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(dst, [7; 4]);
+    }
+}
+
+mod varint {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{zigzag_decode, zigzag_encode, AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[test]
+    fn zigzag() {
+        let pairs = [
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (2147483647, 4294967294),
+            (-2147483648, 4294967295),
+            (i64::MAX, u64::MAX - 1),
+            (i64::MIN, u64::MAX),
+        ];
+        for &(signed, unsigned) in &pairs {
+            assert_eq!(zigzag_encode(signed), unsigned);
+            assert_eq!(zigzag_decode(unsigned), signed);
+        }
+    }
+
+    #[tokio::test]
+    async fn zigzag_varint() {
+        for &n in &[0, -1, 63, -64, 64, i64::MAX, i64::MIN] {
+            let mut wtr = Vec::new();
+            wtr.write_zigzag_varint(n).await.unwrap();
+            let mut same = Vec::new();
+            same.write_varint_u64(zigzag_encode(n)).await.unwrap();
+            assert_eq!(wtr, same);
+            assert_eq!((&wtr[..]).read_zigzag_varint().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let values = [0, 1, 127, 128, 300, 16383, 16384, u32::MAX as u64, u64::MAX];
+        let lens = [1, 1, 1, 2, 2, 2, 3, 5, 10];
+        for (&n, &len) in values.iter().zip(&lens) {
+            let mut wtr = Vec::new();
+            wtr.write_varint_u64(n).await.unwrap();
+            assert_eq!(wtr.len(), len, "{}", n);
+            assert_eq!((&wtr[..]).read_varint_u64().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn overflow() {
+        let mut rdr = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02][..];
+        let err = rdr.read_varint_u64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x80; 11][..];
+        let err = rdr.read_varint_u64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x80, 0x80][..];
+        let err = rdr.read_varint_u64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn delta_sequence() {
+        let values = [
+            i64::MIN,
+            i64::MAX,
+            0,
+            -5,
+            -5,
+            1_700_000_000_000,
+            1_700_000_000_250,
+        ];
+        let mut wtr = Vec::new();
+        wtr.write_delta_varint_sequence(&values).await.unwrap();
+        wtr.write_u8(0xaa).await.unwrap();
+
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &wtr[..]);
+        let decoded = rdr.read_delta_varint_sequence(values.len()).await.unwrap();
+        assert_eq!(decoded, values);
+        assert_eq!(rdr.read_u8().await.unwrap(), 0xaa);
+
+        let mut rdr = &wtr[..wtr.len() - 2];
+        let err = rdr
+            .read_delta_varint_sequence(values.len())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod cbor {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, MajorType};
+
+    #[tokio::test]
+    async fn heads() {
+        // from the examples in RFC 8949 appendix A
+        let cases: &[(&[u8], MajorType, u64)] = &[
+            (&[0x00], MajorType::Unsigned, 0),
+            (&[0x17], MajorType::Unsigned, 23),
+            (&[0x18, 0x18], MajorType::Unsigned, 24),
+            (&[0x19, 0x03, 0xe8], MajorType::Unsigned, 1000),
+            (
+                &[0x1a, 0x00, 0x0f, 0x42, 0x40],
+                MajorType::Unsigned,
+                1_000_000,
+            ),
+            (
+                &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                MajorType::Unsigned,
+                u64::MAX,
+            ),
+            (&[0x38, 0x63], MajorType::Negative, 99),
+            (&[0x44, 1, 2, 3, 4], MajorType::Bytes, 4),
+            (&[0x64, b'I', b'E', b'T', b'F'], MajorType::Text, 4),
+            (&[0x98, 0x19], MajorType::Array, 25),
+            (&[0xa1], MajorType::Map, 1),
+            (&[0xc1], MajorType::Tag, 1),
+            (&[0xf5], MajorType::Simple, 21),
+            (&[0xf8, 0xff], MajorType::Simple, 255),
+        ];
+        for &(bytes, major, argument) in cases {
+            let mut rdr = bytes;
+            assert_eq!(rdr.read_cbor_head().await.unwrap(), (major, argument));
+
+            let mut wtr = Vec::new();
+            wtr.write_cbor_head(major, argument).await.unwrap();
+            assert_eq!(wtr, &bytes[..bytes.len() - rdr.len()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn floats_keep_their_bits() {
+        // 1.5 as a half, 100000.0 as a single
+        let mut rdr = &[0xf9, 0x3e, 0x00, 0xfa, 0x47, 0xc3, 0x50, 0x00][..];
+        assert_eq!(
+            rdr.read_cbor_head().await.unwrap(),
+            (MajorType::Simple, 0x3e00)
+        );
+        let (major, bits) = rdr.read_cbor_head().await.unwrap();
+        assert_eq!(major, MajorType::Simple);
+        assert_eq!(f32::from_bits(bits as u32), 100000.0);
+    }
+
+    #[tokio::test]
+    async fn unsupported() {
+        for bytes in [&[0x5f][..], &[0xff], &[0x1c], &[0x19, 0x03]] {
+            let mut rdr = bytes;
+            let err = rdr.read_cbor_head().await.unwrap_err();
+            let kind = if bytes.len() > 1 {
+                ErrorKind::UnexpectedEof
+            } else {
+                ErrorKind::InvalidData
+            };
+            assert_eq!(err.kind(), kind);
+        }
+    }
+}
+
+mod msgpack {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn ints() {
+        let cases: &[(i64, &[u8])] = &[
+            (0, &[0x00]),
+            (127, &[0x7f]),
+            (128, &[0xcc, 0x80]),
+            (255, &[0xcc, 0xff]),
+            (256, &[0xcd, 0x01, 0x00]),
+            (65536, &[0xce, 0x00, 0x01, 0x00, 0x00]),
+            (1 << 32, &[0xcf, 0, 0, 0, 1, 0, 0, 0, 0]),
+            (-1, &[0xff]),
+            (-32, &[0xe0]),
+            (-33, &[0xd0, 0xdf]),
+            (-129, &[0xd1, 0xff, 0x7f]),
+            (-32769, &[0xd2, 0xff, 0xff, 0x7f, 0xff]),
+            (i64::MIN, &[0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        for &(n, bytes) in cases {
+            let mut wtr = Vec::new();
+            wtr.write_msgpack_int(n).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", n);
+            assert_eq!((&wtr[..]).read_msgpack_int().await.unwrap(), n);
+        }
+
+        // wider formats than needed are read as well
+        let mut rdr = &[0xd3, 0, 0, 0, 0, 0, 0, 0, 0x05, 0xcd, 0x00, 0x07][..];
+        assert_eq!(rdr.read_msgpack_int().await.unwrap(), 5);
+        assert_eq!(rdr.read_msgpack_int().await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn floats() {
+        let mut wtr = Vec::new();
+        wtr.write_msgpack_f32(-0.25).await.unwrap();
+        wtr.write_msgpack_f64(1e300).await.unwrap();
+        let mut rdr = &wtr[..];
+        assert_eq!(rdr.read_msgpack_f64().await.unwrap(), -0.25);
+        assert_eq!(rdr.read_msgpack_f64().await.unwrap(), 1e300);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..];
+        let err = rdr.read_msgpack_int().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // nil is neither an integer nor a float
+        let mut rdr = &[0xc0, 0xc0, 0x01][..];
+        let err = rdr.read_msgpack_int().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = rdr.read_msgpack_f64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = rdr.read_msgpack_f64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(rdr.is_empty());
+
+        let mut rdr = &[0xce, 0x00][..];
+        let err = rdr.read_msgpack_int().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod avro {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn record() {
+        // {"id": -3, "name": "héllo", "blob": [0, 255], "score": 0.5, "ratio": -2.25}
+        let mut wtr = Vec::new();
+        wtr.write_avro_long(-3).await.unwrap();
+        wtr.write_avro_string("héllo").await.unwrap();
+        wtr.write_avro_bytes(&[0, 255]).await.unwrap();
+        wtr.write_avro_float(0.5).await.unwrap();
+        wtr.write_avro_double(-2.25).await.unwrap();
+        assert_eq!(&wtr[..8], b"\x05\x0ch\xc3\xa9llo");
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        assert_eq!(rdr.read_avro_int().await.unwrap(), -3);
+        assert_eq!(rdr.read_avro_string(16).await.unwrap(), "héllo");
+        assert_eq!(rdr.read_avro_bytes(16).await.unwrap(), [0, 255]);
+        assert_eq!(rdr.read_avro_float().await.unwrap(), 0.5);
+        assert_eq!(rdr.read_avro_double().await.unwrap(), -2.25);
+        let err = rdr.read_avro_long().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut wtr = Vec::new();
+        wtr.write_avro_long(i64::from(i32::MAX) + 1).await.unwrap();
+        let err = (&wtr[..]).read_avro_int().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            (&wtr[..]).read_avro_long().await.unwrap(),
+            i64::from(i32::MAX) + 1
+        );
+
+        // a negative length
+        let mut rdr = &[0x01, 0x00][..];
+        let err = rdr.read_avro_bytes(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(rdr, [0x00]);
+
+        // a length over the maximum is refused before the bytes are read
+        let mut rdr = &[0x08, b'a', b'b', b'c', b'd'][..];
+        let err = rdr.read_avro_string(3).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(rdr.len(), 4);
+
+        let mut rdr = &[0x04, 0xff, 0xfe][..];
+        let err = rdr.read_avro_string(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
+
+mod thrift {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, ThriftFieldHeader};
+
+    #[tokio::test]
+    async fn field_headers() {
+        let fields = [
+            (
+                0,
+                ThriftFieldHeader {
+                    id: 1,
+                    field_type: 5,
+                },
+                &[0x15][..],
+            ),
+            (
+                1,
+                ThriftFieldHeader {
+                    id: 16,
+                    field_type: 12,
+                },
+                &[0xfc],
+            ),
+            (
+                16,
+                ThriftFieldHeader {
+                    id: 32,
+                    field_type: 8,
+                },
+                &[0x08, 0x40],
+            ),
+            (
+                32,
+                ThriftFieldHeader {
+                    id: 2,
+                    field_type: 2,
+                },
+                &[0x02, 0x04],
+            ),
+            (
+                0,
+                ThriftFieldHeader {
+                    id: -1,
+                    field_type: 3,
+                },
+                &[0x03, 0x01],
+            ),
+            (
+                0,
+                ThriftFieldHeader {
+                    id: i16::MIN,
+                    field_type: 15,
+                },
+                &[0x0f, 0xff, 0xff, 0x03],
+            ),
+        ];
+        for &(last_id, header, bytes) in &fields {
+            let mut wtr = Vec::new();
+            wtr.write_thrift_field_header(last_id, header)
+                .await
+                .unwrap();
+            assert_eq!(wtr, bytes, "{:?}", header);
+            let mut rdr = tokio::io::BufReader::with_capacity(1, bytes);
+            let read = rdr.read_thrift_field_header(last_id).await.unwrap();
+            assert_eq!(read, Some(header));
+        }
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &[0x10][..];
+        let err = rdr.read_thrift_field_header(0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x15][..];
+        let err = rdr.read_thrift_field_header(i16::MAX).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut wtr = Vec::new();
+        wtr.write_zigzag_varint(i64::from(i16::MAX) + 1)
+            .await
+            .unwrap();
+        let err = (&wtr[..]).read_thrift_i16().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!((&wtr[..]).read_thrift_i32().await.unwrap(), 32768);
+    }
+
+    #[tokio::test]
+    async fn stop_is_not_a_type() {
+        let mut wtr = Vec::new();
+        for field_type in [0, 16] {
+            let header = ThriftFieldHeader { id: 1, field_type };
+            let err = wtr.write_thrift_field_header(0, header).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", field_type);
+        }
+        assert!(wtr.is_empty());
+    }
+}
+
+mod git {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn object_headers() {
+        let headers = [
+            (1, 0, &[0x10][..]),
+            (3, 15, &[0x3f]),
+            (3, 16, &[0xb0, 0x01]),
+            (7, 300, &[0xfc, 0x12]),
+            (
+                6,
+                u64::MAX,
+                &[0xef, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f],
+            ),
+        ];
+        for &(kind, size, bytes) in &headers {
+            let mut wtr = Vec::new();
+            wtr.write_git_object_header(kind, size).await.unwrap();
+            assert_eq!(wtr, bytes, "{} {}", kind, size);
+            let mut rdr = tokio::io::BufReader::with_capacity(1, bytes);
+            assert_eq!(rdr.read_git_object_header().await.unwrap(), (kind, size));
+        }
+    }
+
+    #[tokio::test]
+    async fn ofs_delta_offsets() {
+        let offsets = [
+            (0, &[0x00][..]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x00]),
+            (16511, &[0xff, 0x7f]),
+            (16512, &[0x80, 0x80, 0x00]),
+            (
+                u64::MAX,
+                &[0x80, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0x7f],
+            ),
+        ];
+        for &(offset, bytes) in &offsets {
+            let mut wtr = Vec::new();
+            wtr.write_git_ofs_delta_offset(offset).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", offset);
+            let mut rdr = tokio::io::BufReader::with_capacity(1, bytes);
+            assert_eq!(rdr.read_git_ofs_delta_offset().await.unwrap(), offset);
+        }
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        for &bytes in &[&[0x00][..], &[0x5f]] {
+            let mut rdr = bytes;
+            let err = rdr.read_git_object_header().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        let mut rdr = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x20][..];
+        let err = rdr.read_git_object_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x81, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0x7f][..];
+        let err = rdr.read_git_ofs_delta_offset().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x90][..];
+        let err = rdr.read_git_object_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn reserved_type() {
+        let mut wtr = Vec::new();
+        for kind in [0, 5, 8] {
+            let err = wtr.write_git_object_header(kind, 0).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", kind);
+        }
+        assert!(wtr.is_empty());
+    }
+}
+
+mod utf8_coded {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let numbers = [
+            (0, &[0x00][..]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0xc2, 0x80]),
+            (0x7ff, &[0xdf, 0xbf]),
+            (0xffff, &[0xef, 0xbf, 0xbf]),
+            (0x10_ffff, &[0xf4, 0x8f, 0xbf, 0xbf]),
+            (0x3ff_ffff, &[0xfb, 0xbf, 0xbf, 0xbf, 0xbf]),
+            (0x7fff_ffff, &[0xfd, 0xbf, 0xbf, 0xbf, 0xbf, 0xbf]),
+            (0x8000_0000, &[0xfe, 0x82, 0x80, 0x80, 0x80, 0x80, 0x80]),
+            ((1 << 36) - 1, &[0xfe, 0xbf, 0xbf, 0xbf, 0xbf, 0xbf, 0xbf]),
+        ];
+        for &(n, bytes) in &numbers {
+            let mut wtr = Vec::new();
+            wtr.write_utf8_coded_u64(n).await.unwrap();
+            assert_eq!(wtr, bytes, "{:#x}", n);
+            // UTF-8 itself agrees on the forms it has
+            if let Some(c) = std::char::from_u32(n as u32).filter(|_| n <= 0x10_ffff) {
+                assert_eq!(c.to_string().as_bytes(), bytes);
+            }
+            let mut rdr = tokio::io::BufReader::with_capacity(1, bytes);
+            assert_eq!(rdr.read_utf8_coded_u64().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn overlong() {
+        let mut rdr = &[0xc0, 0x81][..];
+        assert_eq!(rdr.read_utf8_coded_u64().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        for &bytes in &[&[0x80][..], &[0xff], &[0xe2, 0x82, 0x2c]] {
+            let mut rdr = bytes;
+            let err = rdr.read_utf8_coded_u64().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData, "{:x?}", bytes);
+        }
+
+        let mut rdr = &[0xe2, 0x82][..];
+        let err = rdr.read_utf8_coded_u64().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let mut wtr = Vec::new();
+        let err = wtr.write_utf8_coded_u64(1 << 36).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+        wtr.write_utf8_coded_u64((1 << 36) - 1).await.unwrap();
+        assert_eq!(wtr.len(), 7);
+    }
+}
+
+mod mavlink {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{
+        mavlink_crc, AsyncReadBytesExt, AsyncWriteBytesExt, MavlinkFrame, MavlinkVersion,
+    };
+
+    fn crc_extra(msgid: u32) -> Option<u8> {
+        match msgid {
+            0 => Some(50),
+            300 => Some(217),
+            _ => None,
+        }
+    }
+
+    fn frame(version: MavlinkVersion, msgid: u32) -> MavlinkFrame {
+        MavlinkFrame {
+            version,
+            incompat_flags: 0,
+            compat_flags: 0,
+            seq: 42,
+            sysid: 255,
+            compid: 190,
+            msgid,
+            payload: vec![1, 2, 3, 4, 5],
+            signature: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let mut signed = frame(MavlinkVersion::V2, 300);
+        signed.incompat_flags = 0x01;
+        signed.compat_flags = 0x80;
+        signed.signature = Some([0xaa; 13]);
+        let frames = [
+            frame(MavlinkVersion::V1, 0),
+            frame(MavlinkVersion::V2, 0),
+            frame(MavlinkVersion::V2, 300),
+            signed,
+        ];
+        for frame in &frames {
+            let mut wtr = Vec::new();
+            let extra = crc_extra(frame.msgid).unwrap();
+            wtr.write_mavlink_frame(frame, extra).await.unwrap();
+            let header_len = match frame.version {
+                MavlinkVersion::V1 => 6,
+                MavlinkVersion::V2 => 10,
+            };
+            let end = header_len + frame.payload.len();
+            let crc = mavlink_crc(&wtr[1..end], extra);
+            assert_eq!(wtr[end..end + 2], crc.to_le_bytes());
+
+            let mut rdr = tokio::io::BufReader::with_capacity(1, &wtr[..]);
+            assert_eq!(&rdr.read_mavlink_frame(crc_extra).await.unwrap(), frame);
+        }
+    }
+
+    #[tokio::test]
+    async fn v1_layout() {
+        let mut wtr = Vec::new();
+        wtr.write_mavlink_frame(&frame(MavlinkVersion::V1, 0), 50)
+            .await
+            .unwrap();
+        assert_eq!(wtr[..6], [0xfe, 5, 42, 255, 190, 0]);
+        assert_eq!(wtr[6..11], [1, 2, 3, 4, 5]);
+        assert_eq!(wtr.len(), 13);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut wtr = Vec::new();
+        wtr.write_mavlink_frame(&frame(MavlinkVersion::V2, 300), 217)
+            .await
+            .unwrap();
+
+        let mut rdr = &wtr[..];
+        let err = rdr.read_mavlink_frame(|_| Some(0)).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &wtr[..];
+        let err = rdr.read_mavlink_frame(|_| None).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut bad = wtr.clone();
+        bad[0] = 0x55;
+        let mut rdr = &bad[..];
+        let err = rdr.read_mavlink_frame(crc_extra).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut bad = wtr.clone();
+        bad[2] = 0x02;
+        let mut rdr = &bad[..];
+        let err = rdr.read_mavlink_frame(crc_extra).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &wtr[..wtr.len() - 1];
+        let err = rdr.read_mavlink_frame(crc_extra).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn invalid_frames() {
+        let mut long = frame(MavlinkVersion::V2, 0);
+        long.payload = vec![0; 256];
+        let mut v1_flags = frame(MavlinkVersion::V1, 0);
+        v1_flags.compat_flags = 0x01;
+        let mut v1_signed = frame(MavlinkVersion::V1, 0);
+        v1_signed.signature = Some([0; 13]);
+        let mut unflagged = frame(MavlinkVersion::V2, 0);
+        unflagged.signature = Some([0; 13]);
+        let mut unsigned = frame(MavlinkVersion::V2, 0);
+        unsigned.incompat_flags = 0x01;
+        let frames = [
+            long,
+            frame(MavlinkVersion::V1, 300),
+            v1_flags,
+            v1_signed,
+            frame(MavlinkVersion::V2, 1 << 24),
+            unflagged,
+            unsigned,
+        ];
+        for frame in &frames {
+            let mut wtr = Vec::new();
+            let err = wtr.write_mavlink_frame(frame, 50).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{:?}", frame);
+            assert!(wtr.is_empty());
+        }
+    }
+}
+
+mod stun {
+    use std::io::ErrorKind;
+    use std::net::SocketAddr;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, StunAttribute, StunHeader};
+
+    const TRANSACTION_ID: [u8; 12] = [
+        0xb7, 0xe7, 0xa7, 0x01, 0xbc, 0x34, 0xd6, 0x86, 0xfa, 0x87, 0xdf, 0xae,
+    ];
+
+    #[tokio::test]
+    async fn message() {
+        let header = StunHeader {
+            message_type: 0x0101,
+            length: 12 + 8,
+            transaction_id: TRANSACTION_ID,
+        };
+        let mut wtr = Vec::new();
+        wtr.write_stun_header(&header).await.unwrap();
+        let addr: SocketAddr = "192.0.2.1:32853".parse().unwrap();
+        let attr =
+            StunAttribute::xor_address(StunAttribute::XOR_MAPPED_ADDRESS, addr, &TRANSACTION_ID);
+        wtr.write_stun_attribute(attr.attr_type, &attr.value)
+            .await
+            .unwrap();
+        wtr.write_stun_attribute(0x8022, b"test").await.unwrap();
+        assert_eq!(wtr.len(), 20 + usize::from(header.length));
+
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &wtr[..]);
+        assert_eq!(rdr.read_stun_header().await.unwrap(), header);
+        let read = rdr.read_stun_attribute(64).await.unwrap();
+        assert_eq!(read, attr);
+        assert_eq!(read.decode_xor_address(&TRANSACTION_ID).unwrap(), addr);
+        let read = rdr.read_stun_attribute(64).await.unwrap();
+        assert_eq!(read.value, b"test");
+    }
+
+    #[tokio::test]
+    async fn padding() {
+        for len in 0..9 {
+            let value = vec![0xab; len];
+            let mut wtr = Vec::new();
+            wtr.write_stun_attribute(0x0006, &value).await.unwrap();
+            assert_eq!(wtr.len(), 4 + len.div_ceil(4) * 4);
+            wtr.write_u8(0xff).await.unwrap();
+            let mut rdr = &wtr[..];
+            assert_eq!(rdr.read_stun_attribute(64).await.unwrap().value, value);
+            assert_eq!(rdr, [0xff]);
+        }
+    }
+
+    #[tokio::test]
+    async fn attribute_too_long() {
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_stun_attribute(0x0006, &[0; 65536])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+        wtr.write_stun_attribute(0x0006, &[0; 65535]).await.unwrap();
+        assert_eq!(wtr.len(), 4 + 65536);
+    }
+
+    #[tokio::test]
+    async fn message_type_too_large() {
+        let header = StunHeader {
+            message_type: 0x4001,
+            length: 0,
+            transaction_id: TRANSACTION_ID,
+        };
+        let mut wtr = Vec::new();
+        let err = wtr.write_stun_header(&header).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+
+    #[test]
+    fn xor_ipv6() {
+        // the XOR-MAPPED-ADDRESS of RFC 5769 §2.3
+        let addr: SocketAddr = "[2001:db8:1234:5678:11:2233:4455:6677]:32853"
+            .parse()
+            .unwrap();
+        let attr =
+            StunAttribute::xor_address(StunAttribute::XOR_MAPPED_ADDRESS, addr, &TRANSACTION_ID);
+        assert_eq!(
+            attr.value,
+            [
+                0x00, 0x02, 0xa1, 0x47, 0x01, 0x13, 0xa9, 0xfa, 0xa5, 0xd3, 0xf1, 0x79, 0xbc, 0x25,
+                0xf4, 0xb5, 0xbe, 0xd2, 0xb9, 0xd9,
+            ]
+        );
+        assert_eq!(attr.decode_xor_address(&TRANSACTION_ID).unwrap(), addr);
+
+        let bad = StunAttribute {
+            attr_type: StunAttribute::XOR_MAPPED_ADDRESS,
+            value: attr.value[..8].to_vec(),
+        };
+        let err = bad.decode_xor_address(&TRANSACTION_ID).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let header = StunHeader {
+            message_type: 0x0001,
+            length: 0,
+            transaction_id: TRANSACTION_ID,
+        };
+        let mut wtr = Vec::new();
+        wtr.write_stun_header(&header).await.unwrap();
+        for &(i, b) in &[(0, 0x40), (4, 0x22), (3, 0x02)] {
+            let mut bad = wtr.clone();
+            bad[i] = b;
+            let mut rdr = &bad[..];
+            let err = rdr.read_stun_header().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        let mut rdr = &[0x80, 0x22, 0x00, 0x41][..];
+        let err = rdr.read_stun_attribute(64).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x80, 0x22, 0x00, 0x03, b'a', b'b', b'c'][..];
+        let err = rdr.read_stun_attribute(64).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod rtp {
+    use std::io::ErrorKind;
+    use tokio::io::AsyncBufReadExt;
+    use tokio_byteorder::{AsyncReadBytesExt, RtpHeader};
+
+    const PACKET: [u8; 22] = [
+        0xb2, 0x8b, 0xff, 0xff, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x03, 0x61, 0x62,
+    ];
+
+    fn expected() -> RtpHeader {
+        RtpHeader {
+            padding: true,
+            extension: true,
+            marker: true,
+            payload_type: 11,
+            sequence_number: 0xffff,
+            timestamp: 0xdead_beef,
+            ssrc: 1,
+            csrcs: vec![2, 3],
+        }
+    }
+
+    #[tokio::test]
+    async fn stream() {
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &PACKET[..]);
+        assert_eq!(rdr.read_rtp_header().await.unwrap(), expected());
+        assert_eq!(
+            rdr.read_u16::<tokio_byteorder::BigEndian>().await.unwrap(),
+            0x6162
+        );
+    }
+
+    #[tokio::test]
+    async fn peeked() {
+        let mut rdr = tokio::io::BufReader::new(&PACKET[..]);
+        let buf = rdr.fill_buf().await.unwrap();
+        let (header, payload) = RtpHeader::from_slice(buf).unwrap();
+        assert_eq!(header, expected());
+        assert_eq!(payload, b"ab");
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut bad = PACKET;
+        bad[0] = 0x42;
+        let err = RtpHeader::from_slice(&bad).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let mut rdr = &bad[..];
+        let err = rdr.read_rtp_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        for len in [0, 11, 19] {
+            let err = RtpHeader::from_slice(&PACKET[..len]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        }
+        let mut rdr = &PACKET[..19];
+        let err = rdr.read_rtp_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn rtsp_interleaved() {
+        let mut frame = vec![b'$', 0x00, 0x00, 0x16];
+        frame.extend_from_slice(&PACKET);
+        frame.extend_from_slice(b"RTSP/1.0 200 OK\r\n");
+
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &frame[..]);
+        let (channel, len) = rdr.read_rtsp_interleaved_header().await.unwrap();
+        assert_eq!((channel, len), (0, 22));
+        assert_eq!(rdr.read_rtp_header().await.unwrap(), expected());
+        assert_eq!(
+            rdr.read_u16::<tokio_byteorder::BigEndian>().await.unwrap(),
+            0x6162
+        );
+        let err = rdr.read_rtsp_interleaved_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[b'$', 0x03, 0xff][..];
+        let err = rdr.read_rtsp_interleaved_header().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod ninep {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, Qid};
+
+    #[tokio::test]
+    async fn rversion() {
+        // an Rversion: size[4] type[1] tag[2] msize[4] version[s]
+        let mut wtr = Vec::new();
+        wtr.write_9p_header(19, 101, 0xffff).await.unwrap();
+        wtr.write_u32::<tokio_byteorder::LittleEndian>(8192)
+            .await
+            .unwrap();
+        wtr.write_9p_string("9P2000").await.unwrap();
+        assert_eq!(wtr.len(), 19);
+
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &wtr[..]);
+        assert_eq!(rdr.read_9p_header().await.unwrap(), (19, 101, 0xffff));
+        let msize = rdr
+            .read_u32::<tokio_byteorder::LittleEndian>()
+            .await
+            .unwrap();
+        assert_eq!(msize, 8192);
+        assert_eq!(rdr.read_9p_string(64).await.unwrap(), "9P2000");
+    }
+
+    #[tokio::test]
+    async fn qids() {
+        let qid = Qid {
+            qid_type: 0x80,
+            version: 0xdead_beef,
+            path: u64::MAX - 1,
+        };
+        let mut wtr = Vec::new();
+        wtr.write_9p_qid(&qid).await.unwrap();
+        assert_eq!(wtr.len(), 13);
+        let mut rdr = tokio::io::BufReader::with_capacity(1, &wtr[..]);
+        assert_eq!(rdr.read_9p_qid().await.unwrap(), qid);
+    }
+
+    #[tokio::test]
+    async fn strings() {
+        let mut wtr = Vec::new();
+        wtr.write_9p_string("").await.unwrap();
+        assert_eq!(wtr, [0, 0]);
+        let mut rdr = &wtr[..];
+        assert_eq!(rdr.read_9p_string(0).await.unwrap(), "");
+
+        let long = "x".repeat(70_000);
+        let mut wtr = Vec::new();
+        let err = wtr.write_9p_string(&long).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+
+        let mut rdr = &b"\x06\x009P2000"[..];
+        let err = rdr.read_9p_string(5).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x02\x00\xff\xfe"[..];
+        let err = rdr.read_9p_string(5).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x06\x009P2"[..];
+        let err = rdr.read_9p_string(8).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod postgres {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian};
+
+    #[tokio::test]
+    async fn parameter_status() {
+        let mut wtr = Vec::new();
+        wtr.write_pg_message_header(b'S', 20).await.unwrap();
+        wtr.write_pg_string("server_version").await.unwrap();
+        wtr.write_pg_string("16.2").await.unwrap();
+        wtr.write_pg_message_header(b'D', 10).await.unwrap();
+        wtr.write_i16::<BigEndian>(1).await.unwrap();
+        wtr.write_i32::<BigEndian>(-1).await.unwrap();
+        wtr.write_i32::<BigEndian>(0).await.unwrap();
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        assert_eq!(rdr.read_pg_message_header().await.unwrap(), (b'S', 20));
+        assert_eq!(rdr.read_pg_string(64).await.unwrap(), "server_version");
+        assert_eq!(rdr.read_pg_string(64).await.unwrap(), "16.2");
+        assert_eq!(rdr.read_pg_message_header().await.unwrap(), (b'D', 10));
+        assert_eq!(rdr.read_i16::<BigEndian>().await.unwrap(), 1);
+        assert_eq!(rdr.read_i32::<BigEndian>().await.unwrap(), -1);
+        assert_eq!(rdr.read_i32::<BigEndian>().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        for len in [3, -1] {
+            let mut msg = vec![b'Z'];
+            msg.extend_from_slice(&i32::to_be_bytes(len));
+            let mut rdr = &msg[..];
+            let err = rdr.read_pg_message_header().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        let mut rdr = tokio::io::BufReader::with_capacity(2, &b"application_name\0"[..]);
+        let err = rdr.read_pg_string(8).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\xff\xfe\0"[..];
+        let err = rdr.read_pg_string(8).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"user"[..];
+        let err = rdr.read_pg_string(8).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut wtr = Vec::new();
+        let err = wtr.write_pg_string("a\0b").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+
+        for &body_len in &[i32::MAX as usize - 3, usize::MAX] {
+            let err = wtr
+                .write_pg_message_header(b'D', body_len)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            assert!(wtr.is_empty());
+        }
+        wtr.write_pg_message_header(b'D', i32::MAX as usize - 4)
+            .await
+            .unwrap();
+        assert_eq!(wtr, [b'D', 0x7f, 0xff, 0xff, 0xff]);
+    }
+}
+
+mod mysql {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn lenenc_ints() {
+        let ints = [
+            (0, &[0x00][..]),
+            (250, &[0xfa]),
+            (251, &[0xfc, 0xfb, 0x00]),
+            (0xffff, &[0xfc, 0xff, 0xff]),
+            (0x1_0000, &[0xfd, 0x00, 0x00, 0x01]),
+            (0xff_ffff, &[0xfd, 0xff, 0xff, 0xff]),
+            (
+                0x100_0000,
+                &[0xfe, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00],
+            ),
+            (
+                u64::MAX,
+                &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+        ];
+        for &(n, bytes) in &ints {
+            let mut wtr = Vec::new();
+            wtr.write_mysql_lenenc_int(n).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", n);
+            let mut rdr = tokio::io::BufReader::with_capacity(1, bytes);
+            assert_eq!(rdr.read_mysql_lenenc_int().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn packet() {
+        let mut payload = Vec::new();
+        payload.write_mysql_lenenc_int(2).await.unwrap();
+        payload.write_mysql_lenenc_str(b"").await.unwrap();
+        payload.write_mysql_lenenc_str(&[b'x'; 300]).await.unwrap();
+        payload.write_u8(0xfb).await.unwrap();
+        let mut wtr = Vec::new();
+        wtr.write_mysql_packet_header(payload.len() as u32, 3)
+            .await
+            .unwrap();
+        wtr.extend_from_slice(&payload);
+
+        let mut rdr = tokio::io::BufReader::with_capacity(7, &wtr[..]);
+        let (len, seq) = rdr.read_mysql_packet_header().await.unwrap();
+        assert_eq!((len as usize, seq), (payload.len(), 3));
+        assert_eq!(rdr.read_mysql_lenenc_int().await.unwrap(), 2);
+        assert_eq!(rdr.read_mysql_lenenc_str(0).await.unwrap().unwrap(), b"");
+        let s = rdr.read_mysql_lenenc_str(300).await.unwrap().unwrap();
+        assert_eq!(s, [b'x'; 300]);
+        assert_eq!(rdr.read_mysql_lenenc_str(0).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        for &b in &[0xfb, 0xff] {
+            let mut rdr = &[b][..];
+            let err = rdr.read_mysql_lenenc_int().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        let mut rdr = &b"\x04abcd"[..];
+        let err = rdr.read_mysql_lenenc_str(3).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0xfd, 0x00][..];
+        let err = rdr.read_mysql_lenenc_int().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn packet_too_long() {
+        let mut wtr = Vec::new();
+        let err = wtr.write_mysql_packet_header(1 << 24, 0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+        wtr.write_mysql_packet_header((1 << 24) - 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(wtr, [0xff, 0xff, 0xff, 0x00]);
+    }
+}
+
+mod amqp {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AmqpValue, AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn strings() {
+        let mut wtr = Vec::new();
+        wtr.write_amqp_short_string("").await.unwrap();
+        wtr.write_amqp_short_string("amq.topic").await.unwrap();
+        wtr.write_amqp_long_string(&[0xff; 300]).await.unwrap();
+        assert_eq!(&wtr[..11], b"\x00\x09amq.topic");
+        assert_eq!(&wtr[11..15], [0, 0, 0x01, 0x2c]);
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        assert_eq!(rdr.read_amqp_short_string().await.unwrap(), "");
+        assert_eq!(rdr.read_amqp_short_string().await.unwrap(), "amq.topic");
+        let s = rdr.read_amqp_long_string(300).await.unwrap();
+        assert_eq!(s, &[0xff; 300][..]);
+
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_amqp_short_string(&"x".repeat(256))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+
+    #[tokio::test]
+    async fn field_table() {
+        let table = vec![
+            ("bool".to_string(), AmqpValue::Bool(false)),
+            ("i8".to_string(), AmqpValue::I8(-1)),
+            ("u8".to_string(), AmqpValue::U8(200)),
+            ("i16".to_string(), AmqpValue::I16(-300)),
+            ("u16".to_string(), AmqpValue::U16(60000)),
+            ("i32".to_string(), AmqpValue::I32(-70000)),
+            ("u32".to_string(), AmqpValue::U32(4_000_000_000)),
+            ("i64".to_string(), AmqpValue::I64(i64::MIN)),
+            ("f32".to_string(), AmqpValue::F32(1.5)),
+            ("f64".to_string(), AmqpValue::F64(-0.25)),
+            (
+                "decimal".to_string(),
+                AmqpValue::Decimal {
+                    scale: 2,
+                    value: 12345,
+                },
+            ),
+            (
+                "string".to_string(),
+                AmqpValue::LongString(b"hello".to_vec()),
+            ),
+            (
+                "array".to_string(),
+                AmqpValue::Array(vec![AmqpValue::U8(1), AmqpValue::Void]),
+            ),
+            ("timestamp".to_string(), AmqpValue::Timestamp(1_700_000_000)),
+            (
+                "table".to_string(),
+                AmqpValue::Table(vec![(
+                    "nested".to_string(),
+                    AmqpValue::Table(vec![("x".to_string(), AmqpValue::Void)]),
+                )]),
+            ),
+            ("void".to_string(), AmqpValue::Void),
+            ("bytes".to_string(), AmqpValue::Bytes(vec![0, 1, 2])),
+            ("empty".to_string(), AmqpValue::Table(Vec::new())),
+        ];
+        let mut wtr = Vec::new();
+        wtr.write_amqp_field_table(&table).await.unwrap();
+        assert_eq!(&wtr[4..10], b"\x04boolt");
+        assert_eq!(
+            wtr.len(),
+            4 + u32::from_be_bytes([wtr[0], wtr[1], wtr[2], wtr[3]]) as usize
+        );
+
+        let mut rdr = tokio::io::BufReader::with_capacity(5, &wtr[..]);
+        assert_eq!(rdr.read_amqp_field_table(wtr.len()).await.unwrap(), table);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &b"\x00\x00\x00\x05\x01a?xx"[..];
+        let err = rdr.read_amqp_field_table(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // the nested table claims more bytes than its parent holds
+        let mut rdr = &b"\x00\x00\x00\x07\x01aF\x00\x00\x00\x01"[..];
+        let err = rdr.read_amqp_field_table(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x00\x00\x00\x11"[..];
+        let err = rdr.read_amqp_field_table(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x00\x00\x00\x04\x01at"[..];
+        let err = rdr.read_amqp_field_table(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut rdr = &b"\x02\xc3\x28"[..];
+        let err = rdr.read_amqp_short_string().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // arrays nested 33 deep
+        let mut value = AmqpValue::Void;
+        for _ in 0..33 {
+            value = AmqpValue::Array(vec![value]);
+        }
+        let mut wtr = Vec::new();
+        wtr.write_amqp_field_table(&[("a".to_string(), value)])
+            .await
+            .unwrap();
+        let err = (&wtr[..])
+            .read_amqp_field_table(wtr.len())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut wtr = Vec::new();
+        let table = [("x".repeat(256), AmqpValue::Void)];
+        let err = wtr.write_amqp_field_table(&table).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+}
+
+mod kafka {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn varints() {
+        let mut wtr = Vec::new();
+        for &n in &[0, -1, 1, i32::MIN, i32::MAX] {
+            wtr.write_kafka_varint(n).await.unwrap();
+        }
+        wtr.write_kafka_varlong(i64::MIN).await.unwrap();
+        wtr.write_kafka_unsigned_varint(u32::MAX).await.unwrap();
+        assert_eq!(&wtr[..3], [0x00, 0x01, 0x02]);
+
+        let mut rdr = &wtr[..];
+        for &n in &[0, -1, 1, i32::MIN, i32::MAX] {
+            assert_eq!(rdr.read_kafka_varint().await.unwrap(), n);
+        }
+        assert_eq!(rdr.read_kafka_varlong().await.unwrap(), i64::MIN);
+        assert_eq!(rdr.read_kafka_unsigned_varint().await.unwrap(), u32::MAX);
+        assert!(rdr.is_empty());
+
+        let mut rdr = &[0x80, 0x80, 0x80, 0x80, 0x10][..];
+        let err = rdr.read_kafka_varint().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let mut rdr = &[0x80, 0x80, 0x80, 0x80, 0x10][..];
+        let err = rdr.read_kafka_unsigned_varint().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn strings() {
+        let long = "x".repeat(200);
+        let mut wtr = Vec::new();
+        wtr.write_kafka_nullable_string(Some("")).await.unwrap();
+        wtr.write_kafka_nullable_string(None).await.unwrap();
+        wtr.write_kafka_compact_string(Some(&long)).await.unwrap();
+        wtr.write_kafka_compact_string(Some("")).await.unwrap();
+        wtr.write_kafka_compact_bytes(None).await.unwrap();
+        wtr.write_kafka_compact_bytes(Some(&[0xff, 0x00]))
+            .await
+            .unwrap();
+        assert_eq!(&wtr[..6], [0x00, 0x00, 0xff, 0xff, 0xc9, 0x01]);
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        let s = rdr.read_kafka_nullable_string(0).await.unwrap();
+        assert_eq!(s.as_deref(), Some(""));
+        assert_eq!(rdr.read_kafka_nullable_string(0).await.unwrap(), None);
+        let s = rdr.read_kafka_compact_string(200).await.unwrap();
+        assert_eq!(s, Some(long));
+        let s = rdr.read_kafka_compact_string(0).await.unwrap();
+        assert_eq!(s.as_deref(), Some(""));
+        assert_eq!(rdr.read_kafka_compact_bytes(0).await.unwrap(), None);
+        let b = rdr.read_kafka_compact_bytes(2).await.unwrap();
+        assert_eq!(b, Some(vec![0xff, 0x00]));
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &b"\xff\xfe"[..];
+        let err = rdr.read_kafka_nullable_string(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x00\x05hello"[..];
+        let err = rdr.read_kafka_nullable_string(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x06hello"[..];
+        let err = rdr.read_kafka_compact_bytes(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x03\xc3\x28"[..];
+        let err = rdr.read_kafka_compact_string(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\x06hel"[..];
+        let err = rdr.read_kafka_compact_string(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut wtr = Vec::new();
+        let long = "x".repeat(1 << 15);
+        let err = wtr
+            .write_kafka_nullable_string(Some(&long))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+}
+
+mod nbt {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, NbtTag};
+
+    #[tokio::test]
+    async fn compound() {
+        // `{"hello world": {"name": "Bananrama"}}`, from the NBT specification's test file
+        let data = b"\x0a\x00\x0bhello world\x08\x00\x04name\x00\x09Bananrama\x00";
+        let mut rdr = &data[..];
+        assert_eq!(rdr.read_nbt_tag().await.unwrap(), NbtTag::Compound);
+        assert_eq!(rdr.read_nbt_string(64).await.unwrap(), "hello world");
+        assert_eq!(rdr.read_nbt_tag().await.unwrap(), NbtTag::String);
+        assert_eq!(rdr.read_nbt_string(64).await.unwrap(), "name");
+        assert_eq!(rdr.read_nbt_string(64).await.unwrap(), "Bananrama");
+        assert_eq!(rdr.read_nbt_tag().await.unwrap(), NbtTag::End);
+        assert!(rdr.is_empty());
+
+        let mut wtr = Vec::new();
+        wtr.write_nbt_tag(NbtTag::Compound).await.unwrap();
+        wtr.write_nbt_string("hello world").await.unwrap();
+        wtr.write_nbt_tag(NbtTag::String).await.unwrap();
+        wtr.write_nbt_string("name").await.unwrap();
+        wtr.write_nbt_string("Bananrama").await.unwrap();
+        wtr.write_nbt_tag(NbtTag::End).await.unwrap();
+        assert_eq!(wtr, &data[..]);
+    }
+
+    #[tokio::test]
+    async fn modified_utf8() {
+        let s = "\0é€😀";
+        let mut wtr = Vec::new();
+        wtr.write_nbt_string(s).await.unwrap();
+        assert_eq!(
+            wtr,
+            [
+                0x00, 0x0d, 0xc0, 0x80, 0xc3, 0xa9, 0xe2, 0x82, 0xac, 0xed, 0xa0, 0xbd, 0xed, 0xb8,
+                0x80,
+            ]
+        );
+        let mut rdr = &wtr[..];
+        assert_eq!(rdr.read_nbt_string(13).await.unwrap(), s);
+
+        // plain utf-8 reads too
+        let mut rdr = &b"\x00\x04\xf0\x9f\x98\x80"[..];
+        assert_eq!(rdr.read_nbt_string(4).await.unwrap(), "😀");
+
+        // a lone surrogate
+        let mut rdr = &b"\x00\x03\xed\xa0\xbd"[..];
+        let err = rdr.read_nbt_string(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn arrays() {
+        let mut wtr = Vec::new();
+        wtr.write_nbt_byte_array(&[-128, 0, 127]).await.unwrap();
+        wtr.write_nbt_int_array(&[]).await.unwrap();
+        wtr.write_nbt_long_array(&[i64::MIN, 1]).await.unwrap();
+        wtr.write_i16::<BigEndian>(-2).await.unwrap();
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        let bytes = rdr.read_nbt_byte_array(3).await.unwrap();
+        assert_eq!(bytes, [-128, 0, 127]);
+        assert!(rdr.read_nbt_int_array(0).await.unwrap().is_empty());
+        let longs = rdr.read_nbt_long_array(2).await.unwrap();
+        assert_eq!(longs, [i64::MIN, 1]);
+        assert_eq!(rdr.read_i16::<BigEndian>().await.unwrap(), -2);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &[13][..];
+        let err = rdr.read_nbt_tag().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0xff, 0xff, 0xff, 0xff][..];
+        let err = rdr.read_nbt_int_array(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x00, 0x00, 0x00, 0x11][..];
+        let err = rdr.read_nbt_long_array(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &[0x00, 0x00, 0x00, 0x01, 0x00, 0x00][..];
+        let err = rdr.read_nbt_int_array(16).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut rdr = &b"\x00\x05hello"[..];
+        let err = rdr.read_nbt_string(4).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_nbt_string(&"\0".repeat(1 << 15))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+}
+
+mod minecraft {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn varints() {
+        // the samples from the protocol documentation
+        let ints = [
+            (0, &[0x00][..]),
+            (1, &[0x01]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (255, &[0xff, 0x01]),
+            (25565, &[0xdd, 0xc7, 0x01]),
+            (2097151, &[0xff, 0xff, 0x7f]),
+            (i32::MAX, &[0xff, 0xff, 0xff, 0xff, 0x07]),
+            (-1, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+            (i32::MIN, &[0x80, 0x80, 0x80, 0x80, 0x08]),
+        ];
+        for &(n, bytes) in &ints {
+            let mut wtr = Vec::new();
+            wtr.write_minecraft_varint(n).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", n);
+            let mut rdr = bytes;
+            assert_eq!(rdr.read_minecraft_varint().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn varlongs() {
+        let longs = [
+            (0, &[0x00][..]),
+            (2147483647, &[0xff, 0xff, 0xff, 0xff, 0x07]),
+            (
+                i64::MAX,
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+            ),
+            (
+                -1,
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            ),
+            (
+                i64::MIN,
+                &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01],
+            ),
+        ];
+        for &(n, bytes) in &longs {
+            let mut wtr = Vec::new();
+            wtr.write_minecraft_varlong(n).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", n);
+            let mut rdr = bytes;
+            assert_eq!(rdr.read_minecraft_varlong().await.unwrap(), n);
+        }
+    }
+
+    #[tokio::test]
+    async fn vanilla_quirks() {
+        // over-long encodings are accepted, and bits past the top are dropped
+        let mut rdr = &[0x80, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0x7f][..];
+        assert_eq!(rdr.read_minecraft_varint().await.unwrap(), 0);
+        assert_eq!(rdr.read_minecraft_varint().await.unwrap(), -1);
+
+        let mut rdr = &[0x80, 0x80, 0x80, 0x80, 0x80, 0x00][..];
+        let err = rdr.read_minecraft_varint().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // nothing past the fifth byte is read
+        assert_eq!(rdr, [0x00]);
+
+        let mut rdr = &[0x80; 11][..];
+        let err = rdr.read_minecraft_varlong().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(rdr, [0x80]);
+
+        let mut rdr = &[0x80, 0x80][..];
+        let err = rdr.read_minecraft_varint().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+mod simple_packing {
+    use tokio_byteorder::{BitReader, BitWriter, SimplePacking};
+
+    #[tokio::test]
+    async fn grib2() {
+        // R = 1.0, E = -2, D = 1, so x stands for (1 + x / 4) / 10
+        let packing = SimplePacking::grib2(5, 1.0, -2, 1);
+        let mut wtr = BitWriter::msb_first(Vec::new());
+        for &x in &[0, 3, 31, 8] {
+            wtr.write_bits(x, 5).await.unwrap();
+        }
+        wtr.align().await.unwrap();
+        let buf = wtr.into_inner();
+
+        let mut rdr = BitReader::msb_first(&buf[..]);
+        let mut values = [0.0; 4];
+        rdr.read_packed_into(&packing, &mut values).await.unwrap();
+        assert_eq!(values, [0.1, 0.175, 0.875, 0.3]);
+        // all ones is not special in grib2
+        assert!(!packing.is_missing(31));
+    }
+
+    #[tokio::test]
+    async fn grib2_constant_field() {
+        let packing = SimplePacking::grib2(0, 273.15, 0, 0);
+        let mut rdr = BitReader::msb_first(&[][..]);
+        let mut values = [0.0; 3];
+        rdr.read_packed_into(&packing, &mut values).await.unwrap();
+        assert_eq!(values, [273.15f32 as f64; 3]);
+    }
+
+    #[tokio::test]
+    async fn bufr() {
+        // latitude (0 05 001), temperature (0 12 001), and pressure (0 10 004)
+        let latitude = SimplePacking::bufr(25, -9_000_000, 5);
+        let temperature = SimplePacking::bufr(12, 0, 1);
+        let pressure = SimplePacking::bufr(14, 0, -1);
+
+        let mut wtr = BitWriter::msb_first(Vec::new());
+        wtr.write_bits(9_000_000 + 5_150_000, 25).await.unwrap();
+        wtr.write_bits(2_883, 12).await.unwrap();
+        wtr.write_bits(0x3fff, 14).await.unwrap();
+        wtr.align().await.unwrap();
+        let buf = wtr.into_inner();
+
+        let mut rdr = BitReader::msb_first(&buf[..]);
+        assert_eq!(rdr.read_packed(&latitude).await.unwrap(), 51.5);
+        assert_eq!(rdr.read_packed(&temperature).await.unwrap(), 288.3);
+        assert!(rdr.read_packed(&pressure).await.unwrap().is_nan());
+        assert_eq!(pressure.unpack(10_132), 101_320.0);
+    }
+}
+
+mod dicom {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, DicomElementHeader, DicomVrMode, RuntimeEndian,
+    };
+
+    fn header(
+        group: u16,
+        element: u16,
+        vr: Option<&[u8; 2]>,
+        length: Option<u32>,
+    ) -> DicomElementHeader {
+        DicomElementHeader {
+            group,
+            element,
+            vr: vr.copied(),
+            length,
+        }
+    }
+
+    #[tokio::test]
+    async fn explicit() {
+        let cases = [
+            (
+                header(0x0008, 0x0060, Some(b"CS"), Some(2)),
+                RuntimeEndian::Little,
+                &b"\x08\x00\x60\x00CS\x02\x00"[..],
+            ),
+            (
+                header(0x0008, 0x0060, Some(b"CS"), Some(2)),
+                RuntimeEndian::Big,
+                &b"\x00\x08\x00\x60CS\x00\x02"[..],
+            ),
+            (
+                header(0x7fe0, 0x0010, Some(b"OW"), Some(0x0001_0000)),
+                RuntimeEndian::Little,
+                &b"\xe0\x7f\x10\x00OW\x00\x00\x00\x00\x01\x00"[..],
+            ),
+            (
+                header(0x0008, 0x1115, Some(b"SQ"), None),
+                RuntimeEndian::Little,
+                &b"\x08\x00\x15\x11SQ\x00\x00\xff\xff\xff\xff"[..],
+            ),
+            // VRs that are not known to be short are long
+            (
+                header(0x0009, 0x0010, Some(b"ZZ"), Some(1)),
+                RuntimeEndian::Little,
+                &b"\x09\x00\x10\x00ZZ\x00\x00\x01\x00\x00\x00"[..],
+            ),
+            // an item carries no VR, even in explicit mode
+            (
+                header(0xfffe, 0xe000, None, None),
+                RuntimeEndian::Little,
+                &b"\xfe\xff\x00\xe0\xff\xff\xff\xff"[..],
+            ),
+            (
+                header(0xfffe, 0xe0dd, None, Some(0)),
+                RuntimeEndian::Little,
+                &b"\xfe\xff\xdd\xe0\x00\x00\x00\x00"[..],
+            ),
+        ];
+        for (h, endian, bytes) in &cases {
+            let mut wtr = Vec::new();
+            wtr.write_dicom_element_header(h, *endian, DicomVrMode::Explicit)
+                .await
+                .unwrap();
+            assert_eq!(wtr, *bytes, "{:?}", h);
+            let mut rdr = tokio::io::BufReader::with_capacity(3, *bytes);
+            let read = rdr
+                .read_dicom_element_header(*endian, DicomVrMode::Explicit)
+                .await
+                .unwrap();
+            assert_eq!(read, *h);
+        }
+    }
+
+    #[tokio::test]
+    async fn implicit() {
+        let bytes = b"\x10\x00\x20\x00\x06\x00\x00\x00";
+        let mut rdr = &bytes[..];
+        let h = rdr
+            .read_dicom_element_header(RuntimeEndian::Little, DicomVrMode::Implicit)
+            .await
+            .unwrap();
+        assert_eq!(h, header(0x0010, 0x0020, None, Some(6)));
+
+        // the vr is left out
+        let mut wtr = Vec::new();
+        let with_vr = header(0x0010, 0x0020, Some(b"LO"), Some(6));
+        wtr.write_dicom_element_header(&with_vr, RuntimeEndian::Little, DicomVrMode::Implicit)
+            .await
+            .unwrap();
+        assert_eq!(wtr, bytes);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let mut rdr = &b"\x08\x00\x60\x00cs\x02\x00"[..];
+        let err = rdr
+            .read_dicom_element_header(RuntimeEndian::Little, DicomVrMode::Explicit)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut rdr = &b"\xe0\x7f\x10\x00OB\x00\x00\xff\xff"[..];
+        let err = rdr
+            .read_dicom_element_header(RuntimeEndian::Little, DicomVrMode::Explicit)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn unwritable() {
+        let headers = [
+            header(0x0010, 0x0010, Some(b"PN"), Some(0x1_0000)),
+            header(0x0010, 0x0010, Some(b"PN"), None),
+            header(0x0010, 0x0010, None, Some(8)),
+        ];
+        for h in &headers {
+            let mut wtr = Vec::new();
+            let err = wtr
+                .write_dicom_element_header(h, RuntimeEndian::Little, DicomVrMode::Explicit)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{:?}", h);
+            assert!(wtr.is_empty());
+        }
+    }
+}
+
+mod mpeg_ts {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+
+    #[tokio::test]
+    async fn pes_timestamps() {
+        let cases = [
+            (0b0011, 0, [0x31, 0x00, 0x01, 0x00, 0x01]),
+            (0b0001, 900_000 - 3003, [0x11, 0x00, 0x37, 0x5f, 0xcb]),
+            (0b0011, (1 << 33) - 1, [0x3f, 0xff, 0xff, 0xff, 0xff]),
+        ];
+        for &(prefix, ts, bytes) in &cases {
+            let mut wtr = Vec::new();
+            wtr.write_mpeg_pes_timestamp(prefix, ts).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", ts);
+            let mut rdr = tokio::io::BufReader::with_capacity(2, &bytes[..]);
+            assert_eq!(rdr.read_mpeg_pes_timestamp().await.unwrap(), (prefix, ts));
+        }
+
+        for &i in &[0, 2, 4] {
+            let mut bytes = [0x21, 0x00, 0x37, 0x77, 0x41];
+            bytes[i] &= !1;
+            let err = (&bytes[..]).read_mpeg_pes_timestamp().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+    }
+
+    #[tokio::test]
+    async fn pcrs() {
+        let cases = [
+            (0, [0x00, 0x00, 0x00, 0x00, 0x7e, 0x00]),
+            (27_000_000, [0x00, 0x00, 0xaf, 0xc8, 0x7e, 0x00]),
+            (
+                ((1 << 33) - 1) * 300 + 299,
+                [0xff, 0xff, 0xff, 0xff, 0xff, 0x2b],
+            ),
+        ];
+        for &(pcr, bytes) in &cases {
+            let mut wtr = Vec::new();
+            wtr.write_mpeg_pcr(pcr).await.unwrap();
+            assert_eq!(wtr, bytes, "{}", pcr);
+            let mut rdr = tokio::io::BufReader::with_capacity(4, &bytes[..]);
+            assert_eq!(rdr.read_mpeg_pcr().await.unwrap(), pcr);
+        }
+
+        // the reserved bits are not checked, but the extension is
+        let mut rdr = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x01][..];
+        assert_eq!(rdr.read_mpeg_pcr().await.unwrap(), 1);
+        let mut rdr = &[0x00, 0x00, 0x00, 0x00, 0x7f, 0x2c][..];
+        let err = rdr.read_mpeg_pcr().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_mpeg_pes_timestamp(0b0010, 1 << 33)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = wtr.write_mpeg_pes_timestamp(0x10, 0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = wtr
+            .write_mpeg_pcr(((1 << 33) - 1) * 300 + 300)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+}
+
+mod ble {
+    use std::io::ErrorKind;
+    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BleAdStructure, BluetoothUuid};
+
+    #[test]
+    fn uuid_short_forms() {
+        let uuid = BluetoothUuid::from_u16(0x2a37);
+        assert_eq!(uuid.to_string(), "00002a37-0000-1000-8000-00805f9b34fb");
+        assert_eq!(uuid.to_u16(), Some(0x2a37));
+        assert_eq!(uuid.to_u32(), Some(0x2a37));
+
+        let uuid = BluetoothUuid::from_u32(0x1234_5678);
+        assert_eq!(uuid.to_u16(), None);
+        assert_eq!(uuid.to_u32(), Some(0x1234_5678));
+
+        let uuid = BluetoothUuid(0x6e40_0001_b5a3_f393_e0a9_e50e_24dc_ca9e);
+        assert_eq!(uuid.to_string(), "6e400001-b5a3-f393-e0a9-e50e24dcca9e");
+        assert_eq!(uuid.to_u32(), None);
+        assert_eq!(BluetoothUuid::BASE.to_u16(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn uuids() {
+        let heart_rate = BluetoothUuid::from_u16(0x180d);
+        let mut wtr = Vec::new();
+        wtr.write_ble_uuid16(heart_rate).await.unwrap();
+        wtr.write_ble_uuid32(heart_rate).await.unwrap();
+        wtr.write_ble_uuid128(heart_rate).await.unwrap();
+        assert_eq!(wtr.len(), 22);
+        assert_eq!(wtr[..6], [0x0d, 0x18, 0x0d, 0x18, 0x00, 0x00]);
+        assert_eq!(wtr[18..], [0x0d, 0x18, 0x00, 0x00]);
+
+        let mut rdr = tokio::io::BufReader::with_capacity(3, &wtr[..]);
+        assert_eq!(rdr.read_ble_uuid16().await.unwrap(), heart_rate);
+        assert_eq!(rdr.read_ble_uuid32().await.unwrap(), heart_rate);
+        assert_eq!(rdr.read_ble_uuid128().await.unwrap(), heart_rate);
+    }
+
+    #[tokio::test]
+    async fn without_short_form() {
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_ble_uuid16(BluetoothUuid::from_u32(0x10000))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = wtr.write_ble_uuid32(BluetoothUuid(1)).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ad_structures() {
+        let mut wtr = Vec::new();
+        wtr.write_ble_ad_structure(BleAdStructure::FLAGS, &[0x06])
+            .await
+            .unwrap();
+        wtr.write_ble_ad_structure(BleAdStructure::MANUFACTURER_SPECIFIC_DATA, &[0x4c, 0x00])
+            .await
+            .unwrap();
+        wtr.write_ble_ad_structure(0x7f, &[]).await.unwrap();
+        wtr.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            wtr,
+            [0x02, 0x01, 0x06, 0x03, 0xff, 0x4c, 0x00, 0x01, 0x7f, 0x00, 0x00]
+        );
+
+        let mut rdr = tokio::io::BufReader::with_capacity(2, &wtr[..]);
+        let expected = [
+            (BleAdStructure::FLAGS, &[0x06][..]),
+            (
+                BleAdStructure::MANUFACTURER_SPECIFIC_DATA,
+                &[0x4c, 0x00][..],
+            ),
+            (0x7f, &[][..]),
+        ];
+        for &(ad_type, data) in &expected {
+            let ad = rdr.read_ble_ad_structure().await.unwrap().unwrap();
+            assert_eq!(ad.ad_type, ad_type);
+            assert_eq!(ad.data, data);
+        }
+        assert_eq!(rdr.read_ble_ad_structure().await.unwrap(), None);
+        assert_eq!(rdr.read_ble_ad_structure().await.unwrap(), None);
+        let err = rdr.read_ble_ad_structure().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut rdr = &[0x05, 0x09, b'b', b'e'][..];
+        let err = rdr.read_ble_ad_structure().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut wtr = Vec::new();
+        let err = wtr
+            .write_ble_ad_structure(BleAdStructure::COMPLETE_LOCAL_NAME, &[b'a'; 255])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(wtr.is_empty());
+        wtr.write_ble_ad_structure(BleAdStructure::COMPLETE_LOCAL_NAME, &[b'a'; 254])
+            .await
+            .unwrap();
+        assert_eq!(wtr.len(), 256);
+    }
+}
+
+mod inet_checksum {
+    use tokio_byteorder::InetChecksum;
+
+    #[test]
+    fn sums() {
+        // the example of RFC 1071 §3, whose sum is 0xddf2
+        let data = [0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7];
+        let mut c = InetChecksum::new();
+        c.update(&data);
+        assert_eq!(c.value(), !0xddf2);
+
+        // any split gives the same result, odd ones included
+        for split in 0..=data.len() {
+            for split2 in split..=data.len() {
+                let mut c = InetChecksum::new();
+                c.update(&data[..split]);
+                c.update(&data[split..split2]);
+                c.update(&data[split2..]);
+                assert_eq!(c.value(), !0xddf2, "{} {}", split, split2);
+            }
+        }
+
+        // an odd byte at the end is padded with a zero
+        let mut c = InetChecksum::new();
+        c.update(&[0x12, 0x34, 0x56]);
+        assert_eq!(c.value(), !(0x1234 + 0x5600));
+
+        // a checksum folded back into the data makes the sum zero
+        let mut c = InetChecksum::new();
+        c.update(&data);
+        let sum = c.value();
+        c.update(&sum.to_be_bytes());
+        assert_eq!(c.value(), 0);
+
+        // many carries
+        let mut c = InetChecksum::new();
+        for _ in 0..100_000 {
+            c.update(&[0xff, 0xff, 0xff]);
+        }
+        assert_eq!(c.value(), 0);
+        c.reset();
+        assert_eq!(c.value(), 0xffff);
+    }
+}
+
+#[cfg(all(feature = "digest", feature = "test_util"))]
+mod hashing {
+    use sha2::{Digest, Sha256};
+    use std::io::ErrorKind;
+    use tokio_byteorder::test_util::{ChunkedReader, ChunkedWriter, Fault};
+    use tokio_byteorder::{
+        AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, HashingReader, HashingWriter,
+    };
+
+    #[tokio::test]
+    async fn partial_reads() {
+        let data: Vec<u8> = (0..16).collect();
+        let inner = ChunkedReader::new(&data[..], 3).pending_between(true);
+        let mut rdr = HashingReader::new(inner, Sha256::new());
+        assert_eq!(rdr.read_u32::<BigEndian>().await.unwrap(), 0x0001_0203);
+        assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 0x0405);
+        assert_eq!(rdr.finalize_reset(), Sha256::digest(&data[..6]));
+
+        // a read that comes back short hashes only what it got
+        let mut buf = [0; 8];
+        assert_eq!(
+            tokio::io::AsyncReadExt::read(&mut rdr, &mut buf)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(rdr.finalize_reset(), Sha256::digest(&data[6..9]));
+        assert_eq!(rdr.get_ref().get_ref(), &&data[9..]);
+    }
+
+    #[tokio::test]
+    async fn short_writes() {
+        let inner = ChunkedWriter::new(Vec::new(), 3).pending_between(true);
+        let mut wtr = HashingWriter::new(inner, Sha256::new());
+        wtr.write_u64::<BigEndian>(0x0102_0304_0506_0708)
+            .await
+            .unwrap();
+        assert_eq!(wtr.get_ref().writes(), 3);
+        assert_eq!(
+            wtr.finalize_reset(),
+            Sha256::digest([1, 2, 3, 4, 5, 6, 7, 8])
+        );
+
+        // bytes that the writer never took are not hashed
+        let inner =
+            ChunkedWriter::new(Vec::new(), 3).fail_at(5, Fault::Error(ErrorKind::BrokenPipe));
+        let mut wtr = HashingWriter::new(inner, Sha256::new());
+        let err = wtr
+            .write_u64::<BigEndian>(0x0102_0304_0506_0708)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+        let (inner, hasher) = wtr.into_parts();
+        assert_eq!(inner.get_ref(), &[1, 2, 3, 4, 5]);
+        assert_eq!(hasher.finalize(), Sha256::digest([1, 2, 3, 4, 5]));
+    }
+
+    #[tokio::test]
+    async fn frames() {
+        let mut wtr = HashingWriter::new(Vec::new(), Sha256::new());
+        wtr.write_u16::<BigEndian>(0x0102).await.unwrap();
+        let first = wtr.finalize_reset();
+        wtr.write_u16::<BigEndian>(0x0304).await.unwrap();
+        let second = wtr.finalize_reset();
+        assert_eq!(first, Sha256::digest([1, 2]));
+        assert_eq!(second, Sha256::digest([3, 4]));
+
+        let mut rdr = HashingReader::new(&wtr.get_ref()[..], Sha256::new());
+        rdr.read_u16::<BigEndian>().await.unwrap();
+        assert_eq!(rdr.finalize_reset(), first);
+        rdr.read_u16::<BigEndian>().await.unwrap();
+        assert_eq!(rdr.finalize_reset(), second);
+        // and nothing carries over into the next frame
+        assert_eq!(rdr.finalize_reset(), Sha256::digest([]));
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(all(feature = "monoio", unix))]
+
+use monoio::net::UnixStream;
+use tokio_byteorder::{BigEndian, LittleEndian, RentReader, RentWriter};
+
+#[monoio::test(driver = "legacy")]
+async fn round_trip() {
+    let (tx, rx) = UnixStream::pair().unwrap();
+    let mut w = RentWriter::new(tx);
+    w.write_u8(1).await.unwrap();
+    w.write_i24::<BigEndian>(-34253).await.unwrap();
+    w.write_u128::<LittleEndian>(u128::MAX - 1).await.unwrap();
+    w.write_f64::<BigEndian>(-0.5).await.unwrap();
+    w.flush().await.unwrap();
+    drop(w);
+
+    let mut r = RentReader::new(rx);
+    assert_eq!(r.read_u8().await.unwrap(), 1);
+    assert_eq!(r.read_i24::<BigEndian>().await.unwrap(), -34253);
+    assert_eq!(r.read_u128::<LittleEndian>().await.unwrap(), u128::MAX - 1);
+    assert_eq!(r.read_f64::<BigEndian>().await.unwrap(), -0.5);
+    let err = r.read_u16::<BigEndian>().await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,238 @@
+#![cfg(feature = "test_util")]
+
+//! Round trips every fixed-width read and write pair, in every byte order, through streams that
+//! split the bytes at arbitrary points and suspend between chunks.
+//!
+//! Each value is written and read back through the tokio traits and, with the `futures-traits`
+//! feature, through the `futures-io` traits as well, and both must produce the same bytes.
+
+use futures_lite::future::block_on;
+use proptest::prelude::*;
+use tokio_byteorder::test_util::{ChunkedReader, ChunkedWriter};
+use tokio_byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian, RuntimeEndian};
+
+/// How the bytes of a value travel through the stream.
+#[derive(Debug, Clone, Copy)]
+struct Io {
+    chunk: usize,
+    pending: bool,
+}
+
+impl Io {
+    fn writer(self) -> ChunkedWriter<Vec<u8>> {
+        ChunkedWriter::new(Vec::new(), self.chunk).pending_between(self.pending)
+    }
+
+    fn reader(self, bytes: &[u8]) -> ChunkedReader<&[u8]> {
+        ChunkedReader::new(bytes, self.chunk).pending_between(self.pending)
+    }
+
+    /// Every way of splitting up a value that is `width` bytes long.
+    fn all(width: usize) -> impl Iterator<Item = Io> {
+        (1..=width + 1).flat_map(|chunk| {
+            [false, true]
+                .iter()
+                .map(move |&pending| Io { chunk, pending })
+        })
+    }
+}
+
+fn io() -> impl Strategy<Value = Io> {
+    (1..=17usize, any::<bool>()).prop_map(|(chunk, pending)| Io { chunk, pending })
+}
+
+/// Equality that tells apart floats with different bit patterns, including NaNs.
+trait Same {
+    fn same(&self, other: &Self) -> bool;
+}
+
+macro_rules! same_eq {
+    ($($ty:ty),*) => {
+        $(impl Same for $ty {
+            fn same(&self, other: &Self) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+same_eq!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
+impl Same for f32 {
+    fn same(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl Same for f64 {
+    fn same(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+macro_rules! round_trip {
+    // single bytes, which have no byte order
+    ($name:ident: $ty:ty, $strategy:expr, [$($edge:expr),*], $write:ident, $read:ident) => {
+        mod $name {
+            use super::*;
+
+            fn check(v: $ty, io: Io) {
+                let bytes = block_on(async {
+                    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+                    let mut wtr = io.writer();
+                    wtr.$write(v).await.unwrap();
+                    let bytes = wtr.into_inner();
+                    let back = io.reader(&bytes).$read().await.unwrap();
+                    assert!(back.same(&v), "{:?} came back as {:?}", v, back);
+                    bytes
+                });
+                assert_eq!(bytes.len(), 1);
+
+                #[cfg(feature = "futures-traits")]
+                block_on(async {
+                    use tokio_byteorder::futures::{AsyncReadBytesExt, AsyncWriteBytesExt};
+                    let mut wtr = io.writer();
+                    wtr.$write(v).await.unwrap();
+                    assert_eq!(wtr.get_ref(), &bytes);
+                    let back = io.reader(&bytes).$read().await.unwrap();
+                    assert!(back.same(&v), "{:?} came back as {:?}", v, back);
+                });
+            }
+
+            #[test]
+            fn edges() {
+                for v in [$($edge),*] {
+                    for io in Io::all(1) {
+                        check(v, io);
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn values(v in $strategy, io in io()) {
+                    check(v, io);
+                }
+            }
+        }
+    };
+    ($name:ident: $ty:ty, $width:expr, $strategy:expr, [$($edge:expr),*],
+     $write:ident, $read:ident, $write_rt:ident, $read_rt:ident) => {
+        mod $name {
+            use super::*;
+
+            fn check<T: ByteOrder>(v: $ty, io: Io, rt: RuntimeEndian) {
+                let bytes = block_on(async {
+                    use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt};
+                    let mut wtr = io.writer();
+                    wtr.$write::<T>(v).await.unwrap();
+                    let bytes = wtr.into_inner();
+                    let back = io.reader(&bytes).$read::<T>().await.unwrap();
+                    assert!(back.same(&v), "{:?} came back as {:?}", v, back);
+
+                    let mut wtr = io.writer();
+                    wtr.$write_rt(rt, v).await.unwrap();
+                    assert_eq!(wtr.get_ref(), &bytes);
+                    let back = io.reader(&bytes).$read_rt(rt).await.unwrap();
+                    assert!(back.same(&v), "{:?} came back as {:?}", v, back);
+                    bytes
+                });
+                assert_eq!(bytes.len(), $width);
+
+                #[cfg(feature = "futures-traits")]
+                block_on(async {
+                    use tokio_byteorder::futures::{AsyncReadBytesExt, AsyncWriteBytesExt};
+                    let mut wtr = io.writer();
+                    wtr.$write::<T>(v).await.unwrap();
+                    assert_eq!(wtr.get_ref(), &bytes);
+                    let back = io.reader(&bytes).$read::<T>().await.unwrap();
+                    assert!(back.same(&v), "{:?} came back as {:?}", v, back);
+                });
+            }
+
+            fn check_all(v: $ty, io: Io) {
+                check::<BigEndian>(v, io, RuntimeEndian::Big);
+                check::<LittleEndian>(v, io, RuntimeEndian::Little);
+                check::<NativeEndian>(v, io, RuntimeEndian::Native);
+            }
+
+            #[test]
+            fn edges() {
+                for v in [$($edge),*] {
+                    for io in Io::all($width) {
+                        check_all(v, io);
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn values(v in $strategy, io in io()) {
+                    check_all(v, io);
+                }
+            }
+        }
+    };
+}
+
+round_trip!(u8: u8, any::<u8>(), [0, u8::MAX], write_u8, read_u8);
+round_trip!(i8: i8, any::<i8>(), [0, i8::MIN, i8::MAX], write_i8, read_i8);
+round_trip!(
+    u16: u16, 2, any::<u16>(), [0, u16::MAX],
+    write_u16, read_u16, write_u16_rt, read_u16_rt
+);
+round_trip!(
+    i16: i16, 2, any::<i16>(), [0, i16::MIN, i16::MAX],
+    write_i16, read_i16, write_i16_rt, read_i16_rt
+);
+round_trip!(
+    u24: u32, 3, 0..=0xff_ffffu32, [0, 0xff_ffff],
+    write_u24, read_u24, write_u24_rt, read_u24_rt
+);
+round_trip!(
+    i24: i32, 3, -0x80_0000..=0x7f_ffffi32, [0, -1, -0x80_0000, 0x7f_ffff],
+    write_i24, read_i24, write_i24_rt, read_i24_rt
+);
+round_trip!(
+    u32: u32, 4, any::<u32>(), [0, u32::MAX],
+    write_u32, read_u32, write_u32_rt, read_u32_rt
+);
+round_trip!(
+    i32: i32, 4, any::<i32>(), [0, i32::MIN, i32::MAX],
+    write_i32, read_i32, write_i32_rt, read_i32_rt
+);
+round_trip!(
+    u48: u64, 6, 0..=0xffff_ffff_ffffu64, [0, 0xffff_ffff_ffff],
+    write_u48, read_u48, write_u48_rt, read_u48_rt
+);
+round_trip!(
+    i48: i64, 6, -0x8000_0000_0000..=0x7fff_ffff_ffffi64,
+    [0, -1, -0x8000_0000_0000, 0x7fff_ffff_ffff],
+    write_i48, read_i48, write_i48_rt, read_i48_rt
+);
+round_trip!(
+    u64: u64, 8, any::<u64>(), [0, u64::MAX],
+    write_u64, read_u64, write_u64_rt, read_u64_rt
+);
+round_trip!(
+    i64: i64, 8, any::<i64>(), [0, i64::MIN, i64::MAX],
+    write_i64, read_i64, write_i64_rt, read_i64_rt
+);
+round_trip!(
+    u128: u128, 16, any::<u128>(), [0, u128::MAX],
+    write_u128, read_u128, write_u128_rt, read_u128_rt
+);
+round_trip!(
+    i128: i128, 16, any::<i128>(), [0, i128::MIN, i128::MAX],
+    write_i128, read_i128, write_i128_rt, read_i128_rt
+);
+round_trip!(
+    f32: f32, 4, any::<u32>().prop_map(f32::from_bits),
+    [0.0, -0.0, f32::MIN, f32::MAX, f32::INFINITY, f32::NAN],
+    write_f32, read_f32, write_f32_rt, read_f32_rt
+);
+round_trip!(
+    f64: f64, 8, any::<u64>().prop_map(f64::from_bits),
+    [0.0, -0.0, f64::MIN, f64::MAX, f64::INFINITY, f64::NAN],
+    write_f64, read_f64, write_f64_rt, read_f64_rt
+);
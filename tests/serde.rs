@@ -0,0 +1,102 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio_byteorder::serde::{from_reader, from_slice, to_vec, to_writer, Config, Error, LenWidth};
+use tokio_byteorder::RuntimeEndian;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle(f32),
+    Rect { w: u16, h: u16 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Scene {
+    id: u32,
+    name: String,
+    visible: bool,
+    tag: Option<char>,
+    shapes: Vec<Shape>,
+    attrs: BTreeMap<u8, i64>,
+    origin: (i16, i16),
+}
+
+fn scene() -> Scene {
+    Scene {
+        id: 7,
+        name: "demo".into(),
+        visible: true,
+        tag: Some('x'),
+        shapes: vec![Shape::Point, Shape::Circle(1.5), Shape::Rect { w: 3, h: 4 }],
+        attrs: vec![(1, -1), (2, 1 << 40)].into_iter().collect(),
+        origin: (-2, 9),
+    }
+}
+
+#[test]
+fn layout() {
+    let config = Config::new().len_width(LenWidth::U16);
+    let mut buf = Vec::new();
+    to_vec(
+        &mut buf,
+        &(0x0102u16, "ab", Some(3u8), Shape::Rect { w: 5, h: 6 }),
+        config,
+    )
+    .unwrap();
+    assert_eq!(buf, [1, 2, 0, 2, b'a', b'b', 1, 3, 0, 0, 0, 2, 0, 5, 0, 6]);
+
+    buf.clear();
+    let config = config.endian(RuntimeEndian::Little);
+    to_vec(&mut buf, &(0x0102u16, vec![9u8]), config).unwrap();
+    assert_eq!(buf, [2, 1, 1, 0, 9]);
+}
+
+#[test]
+fn roundtrip_slice() {
+    let config = Config::new().endian(RuntimeEndian::Little);
+    let mut buf = Vec::new();
+    to_vec(&mut buf, &scene(), config).unwrap();
+    buf.push(0xff);
+    let (back, rest) = from_slice::<Scene>(&buf, config).unwrap();
+    assert_eq!(back, scene());
+    assert_eq!(rest, [0xff]);
+}
+
+#[test]
+fn errors() {
+    let config = Config::new().len_width(LenWidth::U8);
+    let mut buf = Vec::new();
+    assert_eq!(
+        to_vec(&mut buf, &vec![0u8; 256], config),
+        Err(Error::LengthOverflow)
+    );
+    assert_eq!(from_slice::<u32>(&[0, 0], config), Err(Error::Eof));
+    assert_eq!(from_slice::<bool>(&[2], config), Err(Error::InvalidTag(2)));
+    assert_eq!(
+        from_slice::<String>(&[1, 0xff], config),
+        Err(Error::InvalidUtf8)
+    );
+}
+
+#[tokio::test]
+async fn roundtrip_stream() {
+    let config = Config::new();
+    let mut buf = Vec::new();
+    to_writer(&mut buf, &scene(), config).await.unwrap();
+    to_writer(&mut buf, &42u16, config).await.unwrap();
+
+    // a tiny buffer forces values to straddle many fill_buf calls
+    let mut rdr = BufReader::with_capacity(3, &buf[..]);
+    let back: Scene = from_reader(&mut rdr, config).await.unwrap();
+    assert_eq!(back, scene());
+    let n: u16 = from_reader(&mut rdr, config).await.unwrap();
+    assert_eq!(n, 42);
+    assert!(rdr.fill_buf().await.unwrap().is_empty());
+
+    let err = from_reader::<_, Scene>(&mut rdr, config).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
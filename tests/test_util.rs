@@ -0,0 +1,302 @@
+#![cfg(feature = "test_util")]
+
+use tokio_byteorder::test_util::ChunkedReader;
+use tokio_byteorder::{AsyncBufReadBytesExt, AsyncReadBytesExt, BigEndian, LittleEndian};
+
+#[tokio::test]
+async fn chunked_reader() {
+    let data = [1, 2, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 4, 5];
+    for chunk in 1..=4 {
+        for pending in [false, true] {
+            let mut rdr = ChunkedReader::new(&data[..], chunk).pending_between(pending);
+            assert_eq!(rdr.read_u8().await.unwrap(), 1);
+            assert_eq!(rdr.read_u16::<LittleEndian>().await.unwrap(), 2);
+            assert_eq!(rdr.read_u32::<BigEndian>().await.unwrap(), 3);
+            assert_eq!(rdr.read_u64::<BigEndian>().await.unwrap(), 0x0405);
+            assert_eq!(
+                rdr.read_u8().await.unwrap_err().kind(),
+                std::io::ErrorKind::UnexpectedEof
+            );
+            let reads = 1 + [2usize, 4, 8]
+                .iter()
+                .map(|n| n.div_ceil(chunk))
+                .sum::<usize>();
+            assert_eq!(rdr.reads(), reads as u64);
+        }
+    }
+}
+
+#[tokio::test]
+async fn chunked_reader_limits_each_read() {
+    let mut rdr = ChunkedReader::new(&[1, 2, 3, 4, 5][..], 2);
+    let mut buf = [0; 8];
+    assert_eq!(
+        tokio::io::AsyncReadExt::read(&mut rdr, &mut buf)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        tokio::io::AsyncReadExt::read(&mut rdr, &mut buf)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        tokio::io::AsyncReadExt::read(&mut rdr, &mut buf)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        tokio::io::AsyncReadExt::read(&mut rdr, &mut buf)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(rdr.reads(), 3);
+}
+
+#[tokio::test]
+async fn chunked_reader_pending() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let mut rdr = ChunkedReader::new(&[0, 1][..], 1).pending_between(true);
+    let waker = Arc::new(Noop).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut read = pin!(rdr.read_u16::<BigEndian>());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+    assert!(matches!(read.as_mut().poll(&mut cx), Poll::Ready(Ok(1))));
+}
+
+#[tokio::test]
+async fn chunked_buf_reader() {
+    let mut rdr = ChunkedReader::new(&[0, 1, 0, 2][..], 1);
+    // only a single byte is ever buffered, so a u16 cannot be peeked
+    assert_eq!(rdr.peek_u16::<BigEndian>().await.unwrap(), None);
+    assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 1);
+    let mut rdr = ChunkedReader::new(&[0, 2][..], 2);
+    assert_eq!(rdr.peek_u16::<BigEndian>().await.unwrap(), Some(2));
+}
+
+#[tokio::test]
+async fn chunked_writer() {
+    use tokio_byteorder::test_util::ChunkedWriter;
+    use tokio_byteorder::AsyncWriteBytesExt;
+
+    for chunk in 1..=4 {
+        for pending in [false, true] {
+            let mut wtr = ChunkedWriter::new(Vec::new(), chunk).pending_between(pending);
+            wtr.write_u8(1).await.unwrap();
+            wtr.write_u32::<BigEndian>(0x0203_0405).await.unwrap();
+            wtr.write_u16::<LittleEndian>(0x0706).await.unwrap();
+            assert_eq!(wtr.position(), 7);
+            let writes = 1 + [4usize, 2].iter().map(|n| n.div_ceil(chunk)).sum::<usize>();
+            assert_eq!(wtr.writes(), writes as u64);
+            assert_eq!(wtr.into_inner(), [1, 2, 3, 4, 5, 6, 7]);
+        }
+    }
+}
+
+#[tokio::test]
+async fn chunked_writer_faults() {
+    use std::io::ErrorKind;
+    use tokio_byteorder::test_util::{ChunkedWriter, Fault};
+    use tokio_byteorder::AsyncWriteBytesExt;
+
+    // writes are retried after interruptions and spurious wakeups
+    let mut wtr = ChunkedWriter::new(Vec::new(), 8)
+        .fail_at(0, Fault::Pending)
+        .fail_at(2, Fault::Interrupted)
+        .fail_at(2, Fault::Pending)
+        .fail_at(3, Fault::Interrupted);
+    wtr.write_u32::<BigEndian>(0x0102_0304).await.unwrap();
+    assert_eq!(wtr.writes(), 3);
+    assert_eq!(wtr.get_ref(), &[1, 2, 3, 4]);
+
+    // but would-block is not retried, and can be resumed from
+    let mut wtr = ChunkedWriter::new(Vec::new(), 8).fail_at(1, Fault::WouldBlock);
+    let err = wtr.write_u16::<BigEndian>(0x0102).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    assert_eq!(wtr.position(), 1);
+    tokio::io::AsyncWriteExt::write_all(&mut wtr, &[2])
+        .await
+        .unwrap();
+    assert_eq!(wtr.get_ref(), &[1, 2]);
+
+    // fatal errors stick
+    let mut wtr =
+        ChunkedWriter::new(Vec::new(), 8).fail_at(3, Fault::Error(ErrorKind::ConnectionReset));
+    let err = wtr.write_u32::<BigEndian>(0).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ConnectionReset);
+    let err = wtr.write_u8(0).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ConnectionReset);
+    assert_eq!(wtr.position(), 3);
+    tokio::io::AsyncWriteExt::flush(&mut wtr).await.unwrap();
+}
+
+/// Polls `fut` to completion, and returns its output and how often it yielded.
+fn drive<F: std::future::Future>(fut: F) -> (F::Output, usize) {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Arc::new(Noop).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = std::pin::pin!(fut);
+    let mut yields = 0;
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(v) => return (v, yields),
+            Poll::Pending => yields += 1,
+        }
+    }
+}
+
+#[test]
+fn bulk_operations_yield() {
+    use tokio_byteorder::test_util::ChunkedWriter;
+    use tokio_byteorder::{AsyncWriteBytesExt, I24Scaling};
+
+    let samples: Vec<i32> = (0..100_000).map(|i| i - 50_000).collect();
+
+    // a stream that takes or hands out everything at once never needs to yield
+    let mut bytes = Vec::new();
+    let (res, yields) =
+        drive(bytes.write_i24_from::<LittleEndian>(&samples, I24Scaling::SignExtend));
+    res.unwrap();
+    assert_eq!(yields, 0);
+    let mut back = vec![0; samples.len()];
+    let (res, yields) = drive(
+        (&bytes[..]).read_i24_into_unpacked::<LittleEndian>(&mut back, I24Scaling::SignExtend),
+    );
+    res.unwrap();
+    assert_eq!(yields, 0);
+
+    // but many reads or writes that are all ready right away do
+    let mut wtr = ChunkedWriter::new(Vec::new(), 4096);
+    let (res, yields) = drive(wtr.write_i24_from::<LittleEndian>(&samples, I24Scaling::SignExtend));
+    res.unwrap();
+    assert!(yields >= 4, "yielded {} times", yields);
+    assert_eq!(wtr.get_ref(), &bytes);
+
+    let mut rdr = ChunkedReader::new(&bytes[..], 4096);
+    let mut back = vec![0; samples.len()];
+    let (res, yields) =
+        drive(rdr.read_i24_into_unpacked::<LittleEndian>(&mut back, I24Scaling::SignExtend));
+    res.unwrap();
+    assert!(yields >= 4, "yielded {} times", yields);
+    assert_eq!(back, samples);
+}
+
+#[tokio::test]
+async fn read_tuple_reads_once() {
+    let data = [1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 4, 0xff];
+    let mut rdr = ChunkedReader::new(&data[..], 64);
+    let fields = rdr
+        .read_tuple::<(u8, u16, u32, u64), LittleEndian>()
+        .await
+        .unwrap();
+    assert_eq!(fields, (1, 2 << 8, 3 << 24, 4 << 56));
+    assert_eq!(rdr.reads(), 1);
+
+    // short reads are topped up
+    for chunk in 1..=4 {
+        let mut rdr = ChunkedReader::new(&data[..], chunk).pending_between(true);
+        let fields = rdr.read_tuple::<(u8, i16, f32), BigEndian>().await.unwrap();
+        assert_eq!(fields, (1, 2, f32::from_bits(3)));
+        assert_eq!(rdr.reads() as usize, 7usize.div_ceil(chunk));
+    }
+
+    let mut rdr = ChunkedReader::new(&data[..], 4);
+    let err = rdr
+        .read_tuple::<(u64, u64, u8), BigEndian>()
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[tokio::test]
+async fn resync_across_buffers() {
+    use std::io::ErrorKind;
+
+    let data = b"xxaaxaaab\x01ababac\x02";
+    for chunk in 1..=data.len() {
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        // a partial match that fails has to fall back to the match within it
+        assert_eq!(rdr.resync_to(b"aab", 64).await.unwrap(), 6);
+        assert_eq!(rdr.read_u8().await.unwrap(), 1);
+        assert_eq!(rdr.resync_to(b"abac", 64).await.unwrap(), 2);
+        assert_eq!(rdr.read_u8().await.unwrap(), 2);
+        let err = rdr.resync_to(b"a", 64).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        // the pattern has to start within the first `max_scan` bytes
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        assert_eq!(rdr.resync_to(b"aab", 6).await.unwrap(), 6);
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        let err = rdr.resync_to(b"aab", 5).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // without searching further than the pattern could reach
+        assert!(rdr.get_ref().len() >= data.len() - 8);
+    }
+
+    let mut rdr = ChunkedReader::new(&data[..], 4);
+    assert_eq!(rdr.resync_to(b"", 0).await.unwrap(), 0);
+    assert_eq!(rdr.read_u8().await.unwrap(), b'x');
+
+    // no limit at all
+    let mut rdr = ChunkedReader::new(&data[..], 3);
+    assert_eq!(rdr.resync_to(b"aab", usize::MAX).await.unwrap(), 6);
+    assert_eq!(rdr.read_u8().await.unwrap(), 1);
+    let err = rdr.resync_to(b"AB", usize::MAX).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[tokio::test]
+async fn resync_to_byte_keeps_sync() {
+    use std::io::ErrorKind;
+
+    let data = b"xx\x47ab\x47cd";
+    for chunk in 1..=data.len() {
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        assert_eq!(rdr.resync_to_byte(0x47, 64).await.unwrap(), 2);
+        // already in step, so nothing is skipped
+        assert_eq!(rdr.resync_to_byte(0x47, 64).await.unwrap(), 0);
+        assert_eq!(rdr.read_u8().await.unwrap(), 0x47);
+        assert_eq!(rdr.resync_to_byte(0x47, 64).await.unwrap(), 2);
+        assert_eq!(rdr.read_u16::<BigEndian>().await.unwrap(), 0x4763);
+        let err = rdr.resync_to_byte(0x47, 64).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        // the sync byte has to be within the first `max_scan + 1` bytes
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        assert_eq!(rdr.resync_to_byte(0x47, 2).await.unwrap(), 2);
+        assert_eq!(rdr.read_u8().await.unwrap(), 0x47);
+        let mut rdr = ChunkedReader::new(&data[..], chunk);
+        let err = rdr.resync_to_byte(0x47, 1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        // without searching further than the sync byte could be
+        assert_eq!(rdr.get_ref().len(), data.len() - 2);
+    }
+
+    // no limit at all
+    let mut rdr = ChunkedReader::new(&data[..], 3);
+    assert_eq!(rdr.resync_to_byte(b'c', usize::MAX).await.unwrap(), 6);
+    assert_eq!(rdr.read_u8().await.unwrap(), b'c');
+    let err = rdr.resync_to_byte(0x00, usize::MAX).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
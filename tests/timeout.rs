@@ -0,0 +1,40 @@
+#![cfg(feature = "time")]
+
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio_byteorder::{ByteReader, LittleEndian};
+
+const TIMEOUT: Duration = Duration::from_millis(20);
+
+#[tokio::test]
+async fn partial_value_survives_timeout() {
+    let (mut tx, rx) = tokio::io::duplex(64);
+    let mut r = ByteReader::with_capacity(16, rx);
+
+    let err = r.read_u8_timeout(TIMEOUT).await.unwrap_err();
+    assert_eq!(err.kind(), tokio::io::ErrorKind::TimedOut);
+
+    tx.write_all(&[1, 2, 3, 4, 5]).await.unwrap();
+    let err = r
+        .read_u64_timeout::<LittleEndian>(TIMEOUT)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), tokio::io::ErrorKind::TimedOut);
+    assert_eq!(r.position(), 0);
+    assert_eq!(r.buffer(), [1, 2, 3, 4, 5]);
+
+    tx.write_all(&[6, 7, 8, 9]).await.unwrap();
+    assert_eq!(
+        r.read_u64_timeout::<LittleEndian>(TIMEOUT).await.unwrap(),
+        0x08070605_04030201
+    );
+    assert_eq!(r.read_i8_timeout(TIMEOUT).await.unwrap(), 9);
+    assert_eq!(r.position(), 9);
+
+    drop(tx);
+    let err = r
+        .read_u16_timeout::<LittleEndian>(TIMEOUT)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), tokio::io::ErrorKind::UnexpectedEof);
+}
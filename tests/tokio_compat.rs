@@ -0,0 +1,65 @@
+#![cfg(any(feature = "tokio02", feature = "tokio03", feature = "tokio1"))]
+
+use futures_lite::future::block_on;
+
+#[cfg(feature = "tokio02")]
+#[test]
+fn tokio02_round_trip() {
+    use tokio_byteorder::tokio02::{AsyncReadBytesExt, AsyncWriteBytesExt};
+    use tokio_byteorder::{BigEndian, LittleEndian};
+
+    block_on(async {
+        let mut w = Vec::new();
+        w.write_u8(7).await.unwrap();
+        w.write_i24::<BigEndian>(-70000).await.unwrap();
+        w.write_f64::<LittleEndian>(2.5).await.unwrap();
+        assert_eq!(w.len(), 12);
+
+        let mut r = &w[..];
+        assert_eq!(r.read_u8().await.unwrap(), 7);
+        assert_eq!(r.read_i24::<BigEndian>().await.unwrap(), -70000);
+        assert_eq!(r.read_f64::<LittleEndian>().await.unwrap(), 2.5);
+        let err = r.read_u16::<BigEndian>().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    });
+}
+
+#[cfg(feature = "tokio03")]
+#[test]
+fn tokio03_round_trip() {
+    use tokio_byteorder::tokio03::{AsyncReadBytesExt, AsyncWriteBytesExt};
+    use tokio_byteorder::{BigEndian, LittleEndian};
+
+    block_on(async {
+        let mut w = Vec::new();
+        w.write_i8(-5).await.unwrap();
+        w.write_u64::<LittleEndian>(0x0102_0304_0506_0708)
+            .await
+            .unwrap();
+        w.write_i16::<BigEndian>(-2).await.unwrap();
+        assert_eq!(w.len(), 11);
+
+        let mut r = &w[..];
+        assert_eq!(r.read_i8().await.unwrap(), -5);
+        assert_eq!(
+            r.read_u64::<LittleEndian>().await.unwrap(),
+            0x0102_0304_0506_0708
+        );
+        assert_eq!(r.read_i16::<BigEndian>().await.unwrap(), -2);
+        assert!(r.is_empty());
+    });
+}
+
+#[cfg(feature = "tokio1")]
+#[test]
+fn tokio1_round_trip() {
+    use tokio_byteorder::tokio1::{AsyncReadBytesExt, AsyncWriteBytesExt};
+    use tokio_byteorder::BigEndian;
+
+    block_on(async {
+        let mut w = Vec::new();
+        w.write_u32::<BigEndian>(267).await.unwrap();
+        let mut r = &w[..];
+        assert_eq!(r.read_u32::<BigEndian>().await.unwrap(), 267);
+    });
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use tokio_byteorder::{AsyncReadBytesExt, AsyncWriteBytesExt, BigEndian, ByteReader, LittleEndian};
+
+/// Records the fields of every event as `name=value` pairs.
+#[derive(Clone, Default)]
+struct Collect(Arc<Mutex<Vec<String>>>);
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+}
+
+impl Subscriber for Collect {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "tokio_byteorder"
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(Vec::new());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0.join(" "));
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+fn traced<F: std::future::Future>(f: F) -> Vec<String> {
+    let collect = Collect::default();
+    tracing::subscriber::with_default(collect.clone(), || futures_lite::future::block_on(f));
+    let events = collect.0.lock().unwrap().clone();
+    events
+}
+
+#[test]
+fn reads_and_writes() {
+    let events = traced(async {
+        let mut rdr = &[0x01, 0x02, 0x03, 0xff][..];
+        assert_eq!(rdr.read_u24::<BigEndian>().await.unwrap(), 0x010203);
+        rdr.read_i8().await.unwrap();
+        assert!(rdr.read_u8().await.is_err());
+
+        let mut wtr = Vec::new();
+        wtr.write_u16::<LittleEndian>(0xbeef).await.unwrap();
+    });
+    assert_eq!(
+        events,
+        [
+            "message=read ty=u24 endian=BigEndian value=66051 bytes=01 02 03",
+            "message=read ty=i8 value=-1 bytes=ff",
+            "message=write ty=u16 endian=LittleEndian value=48879 bytes=ef be",
+        ]
+    );
+}
+
+#[test]
+fn byte_reader_offsets() {
+    let events = traced(async {
+        let mut rdr = ByteReader::new(&[0x00, 0x00, 0x2a, 0x80, 0x00][..]);
+        rdr.read_u16::<BigEndian>().await.unwrap();
+        rdr.read_u8().await.unwrap();
+        rdr.read_i16::<LittleEndian>().await.unwrap();
+    });
+    assert_eq!(
+        events,
+        [
+            "message=read ty=u16 endian=BigEndian value=0 offset=0 bytes=00 00",
+            "message=read ty=u8 value=42 offset=2 bytes=2a",
+            "message=read ty=i16 endian=LittleEndian value=128 offset=3 bytes=80 00",
+        ]
+    );
+}